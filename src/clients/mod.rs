@@ -0,0 +1,315 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+/// Default maximum number of connected clients, same as in Redis.
+pub const DEFAULT_MAXCLIENTS: usize = 10000;
+
+/// Default number of commands remembered per connection, for CLIENT INFO. Only the last
+/// command is kept.
+pub const DEFAULT_CLIENT_HISTORY_LEN: usize = 1;
+
+/// Keeps track of the clients connected to the server.
+#[derive(Debug)]
+pub struct Clients {
+    /// Number of connected clients.
+    connected: AtomicUsize,
+    /// Number of clients blocked on a command, for e.g. WAIT.
+    blocked: AtomicUsize,
+    /// Maximum number of connected clients. New connections are rejected beyond it.
+    maxclients: AtomicUsize,
+    /// Number of connections rejected because of the `maxclients` limit.
+    rejected: AtomicU64,
+    /// Id to be assigned to the next connection.
+    next_client_id: AtomicU64,
+    /// Number of commands remembered per connection.
+    history_len: AtomicUsize,
+}
+
+/// The state of a single client connection, which changes how its commands are executed.
+#[derive(Debug, Clone, Default)]
+pub struct ClientState {
+    /// Set by `CLIENT NO-EVICT`. Protects the client from being evicted to free memory.
+    no_evict: bool,
+    /// Set by `CLIENT NO-TOUCH`. The keys accessed by the client don't get their
+    /// last access time updated.
+    no_touch: bool,
+    /// Set by `CLIENT SETNAME`. `None` if the connection has no name.
+    name: Option<String>,
+    /// Set by `CLIENT TRACKING`. The client is sent invalidation messages for the keys it read.
+    tracking: bool,
+}
+
+/// The identity of a single client connection, and the commands it issued last, as
+/// reported by `CLIENT INFO`.
+///
+/// It's kept apart from `ClientState`, which is cloned to run commands with a timeout.
+#[derive(Debug)]
+pub struct ClientInfo {
+    /// Id of the connection. Ids are assigned in the order the connections are accepted.
+    id: u64,
+    /// Address of the client.
+    addr: String,
+    /// Time at which the connection was accepted.
+    connected_at: Instant,
+    /// Names of the last commands issued on the connection, oldest first.
+    history: VecDeque<String>,
+    /// Maximum number of commands kept in `history`. Always at least `1`.
+    history_len: usize,
+}
+
+/// Represents a connected client. The client is counted as disconnected when it's dropped.
+#[derive(Debug)]
+pub struct ClientGuard {
+    clients: Arc<Clients>,
+}
+
+/// Represents a client blocked on a command. The client is counted as unblocked when it's dropped.
+#[derive(Debug)]
+pub struct BlockedGuard<'a> {
+    clients: &'a Clients,
+}
+
+impl Clients {
+    /// Create a new instance of `Clients` with no connected clients.
+    pub fn new() -> Clients {
+        Clients {
+            connected: AtomicUsize::new(0),
+            blocked: AtomicUsize::new(0),
+            maxclients: AtomicUsize::new(DEFAULT_MAXCLIENTS),
+            rejected: AtomicU64::new(0),
+            next_client_id: AtomicU64::new(1),
+            history_len: AtomicUsize::new(DEFAULT_CLIENT_HISTORY_LEN),
+        }
+    }
+
+    /// Creates the `ClientInfo` of a new connection, with a new id and no commands.
+    pub fn info(&self, addr: String) -> ClientInfo {
+        let history_len = self.history_len.load(Ordering::Relaxed).max(1);
+
+        ClientInfo {
+            id: self.next_client_id.fetch_add(1, Ordering::Relaxed),
+            addr,
+            connected_at: Instant::now(),
+            history: VecDeque::with_capacity(history_len),
+            history_len,
+        }
+    }
+
+    /// Sets the number of commands remembered per connection. It applies to the connections
+    /// accepted afterwards. Values below `1` are treated as `1`, since the last command is
+    /// always reported.
+    pub fn set_history_len(&self, history_len: usize) {
+        self.history_len.store(history_len, Ordering::Relaxed);
+    }
+
+    /// Registers a new connection.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ClientGuard)` - If the connection is accepted. The guard should be held
+    ///   for as long as the connection is open.
+    /// * `None` - If the `maxclients` limit has been reached.
+    pub fn connect(self: &Arc<Self>) -> Option<ClientGuard> {
+        let maxclients = self.maxclients();
+        let res = self
+            .connected
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |connected| {
+                (connected < maxclients).then_some(connected + 1)
+            });
+
+        match res {
+            Ok(_) => Some(ClientGuard {
+                clients: self.clone(),
+            }),
+            Err(_) => {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Marks a client as blocked on a command, until the returned guard is dropped.
+    pub fn block(&self) -> BlockedGuard<'_> {
+        self.blocked.fetch_add(1, Ordering::SeqCst);
+        BlockedGuard { clients: self }
+    }
+
+    /// Returns the number of connected clients.
+    pub fn connected(&self) -> usize {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of clients blocked on a command.
+    pub fn blocked(&self) -> usize {
+        self.blocked.load(Ordering::SeqCst)
+    }
+
+    /// Returns the maximum number of connected clients.
+    pub fn maxclients(&self) -> usize {
+        self.maxclients.load(Ordering::Relaxed)
+    }
+
+    /// Sets the maximum number of connected clients. Clients which are already
+    /// connected are not disconnected.
+    pub fn set_maxclients(&self, maxclients: usize) {
+        self.maxclients.store(maxclients, Ordering::Relaxed);
+    }
+
+    /// Returns the number of connections rejected because of the `maxclients` limit.
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+}
+
+impl ClientState {
+    /// Create a new `ClientState` with all the flags turned off, and no name.
+    pub fn new() -> ClientState {
+        ClientState::default()
+    }
+
+    /// Checks if the client is protected from client eviction.
+    ///
+    /// Nimblecache doesn't evict clients yet, so this is only reported back to the client.
+    #[allow(dead_code)]
+    pub fn no_evict(&self) -> bool {
+        self.no_evict
+    }
+
+    /// Protects the client from client eviction, or removes the protection.
+    pub fn set_no_evict(&mut self, no_evict: bool) {
+        self.no_evict = no_evict;
+    }
+
+    /// Checks if the keys accessed by the client should be left untouched.
+    pub fn no_touch(&self) -> bool {
+        self.no_touch
+    }
+
+    /// Sets whether the keys accessed by the client should be left untouched.
+    pub fn set_no_touch(&mut self, no_touch: bool) {
+        self.no_touch = no_touch;
+    }
+
+    /// Returns the name of the connection, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets the name of the connection. An empty name removes the name.
+    pub fn set_name(&mut self, name: String) {
+        self.name = if name.is_empty() { None } else { Some(name) };
+    }
+
+    /// Checks if the client is sent invalidation messages for the keys it read.
+    pub fn tracking(&self) -> bool {
+        self.tracking
+    }
+
+    /// Turns the invalidation messages for the keys read by the client on or off.
+    pub fn set_tracking(&mut self, tracking: bool) {
+        self.tracking = tracking;
+    }
+}
+
+impl ClientInfo {
+    /// Remembers a command issued on the connection, forgetting the oldest one if the
+    /// history is full.
+    pub fn record(&mut self, name: &str) {
+        if self.history.len() == self.history_len {
+            self.history.pop_front();
+        }
+        self.history.push_back(name.to_ascii_lowercase());
+    }
+
+    /// Describes the connection on a single line, in the form
+    /// `id=<id> addr=<addr> name=<name> age=<seconds> cmd=<last command> history=<commands>`.
+    ///
+    /// The commands in the history are comma separated, oldest first.
+    pub fn line(&self, client: &ClientState) -> String {
+        let history: Vec<&str> = self.history.iter().map(|cmd| cmd.as_str()).collect();
+        format!(
+            "id={} addr={} name={} age={} cmd={} history={}",
+            self.id,
+            self.addr,
+            client.name().unwrap_or_default(),
+            self.connected_at.elapsed().as_secs(),
+            self.history.back().map(|cmd| cmd.as_str()).unwrap_or("NULL"),
+            history.join(","),
+        )
+    }
+}
+
+impl Drop for ClientGuard {
+    fn drop(&mut self) {
+        self.clients.connected.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Drop for BlockedGuard<'_> {
+    fn drop(&mut self) {
+        self.clients.blocked.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field<'a>(line: &'a str, name: &str) -> &'a str {
+        line.split(' ')
+            .find_map(|field| field.strip_prefix(&format!("{}=", name)))
+            .unwrap_or_else(|| panic!("no {} in {}", name, line))
+    }
+
+    #[test]
+    fn history_keeps_the_last_commands() {
+        let clients = Clients::new();
+        clients.set_history_len(2);
+        let mut info = clients.info(String::from("127.0.0.1:1234"));
+        let client = ClientState::new();
+
+        let line = info.line(&client);
+        assert_eq!(field(&line, "cmd"), "NULL");
+        assert_eq!(field(&line, "history"), "");
+
+        for name in ["SET", "Get", "lrange"] {
+            info.record(name);
+        }
+        let line = info.line(&client);
+        assert_eq!(field(&line, "cmd"), "lrange");
+        assert_eq!(field(&line, "history"), "get,lrange");
+    }
+
+    #[test]
+    fn history_always_keeps_the_last_command() {
+        let clients = Clients::new();
+        clients.set_history_len(0);
+        let mut info = clients.info(String::from("127.0.0.1:1234"));
+        info.record("set");
+        info.record("get");
+
+        let line = info.line(&ClientState::new());
+        assert_eq!(field(&line, "history"), "get");
+    }
+
+    #[test]
+    fn connections_get_increasing_ids() {
+        let clients = Clients::new();
+        let mut client = ClientState::new();
+        client.set_name(String::from("worker"));
+
+        let first = clients.info(String::from("127.0.0.1:1"));
+        let second = clients.info(String::from("127.0.0.1:2"));
+        let line = second.line(&client);
+        assert_eq!(field(&first.line(&client), "id"), "1");
+        assert_eq!(field(&line, "id"), "2");
+        assert_eq!(field(&line, "addr"), "127.0.0.1:2");
+        assert_eq!(field(&line, "name"), "worker");
+    }
+}
@@ -0,0 +1,76 @@
+// src/command/append.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the APPEND command in Nimblecache.
+///
+/// The `Append` struct is used to append a value to the string stored against a key.
+#[derive(Debug, Clone)]
+pub struct Append {
+    key: String,
+    value: String,
+}
+
+impl Append {
+    /// Creates a new `Append` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the APPEND command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Append)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Append, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'APPEND' command",
+            )));
+        }
+
+        match (&args[0], &args[1]) {
+            (RespType::BulkString(key), RespType::BulkString(value)) => Ok(Append {
+                key: key.to_string(),
+                value: value.to_string(),
+            }),
+            _ => Err(CommandError::Other(String::from(
+                "Invalid argument. Arguments must be bulk strings",
+            ))),
+        }
+    }
+
+    /// Executes the APPEND command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer` - The length of the string after the append.
+    /// - `SimpleError` - If an error is encountered.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.append(&self.key, &self.value) {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(String::from("APPEND")),
+            RespType::BulkString(self.key.clone()),
+            RespType::BulkString(self.value.clone()),
+        ])
+    }
+}
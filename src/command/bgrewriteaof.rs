@@ -0,0 +1,47 @@
+// src/command/bgrewriteaof.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Represents the BGREWRITEAOF command in Nimblecache.
+///
+/// In Redis, BGREWRITEAOF rewrites the append-only file in the background. Nimblecache
+/// doesn't log the commands to an append-only file, so there's nothing to rewrite. The
+/// command replies like a started rewrite, so that the clients and tools issuing it keep
+/// working.
+#[derive(Debug, Clone)]
+pub struct BgRewriteAof;
+
+impl BgRewriteAof {
+    /// Creates a new `BgRewriteAof` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the BGREWRITEAOF command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BgRewriteAof)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<BgRewriteAof, CommandError> {
+        if !args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'BGREWRITEAOF' command",
+            )));
+        }
+
+        Ok(BgRewriteAof)
+    }
+
+    /// Executes the BGREWRITEAOF command.
+    ///
+    /// # Returns
+    ///
+    /// The `SimpleString` Redis replies with once the rewrite is started.
+    pub fn apply(&self) -> RespType {
+        RespType::SimpleString(String::from(
+            "Background append only file rewriting started",
+        ))
+    }
+}
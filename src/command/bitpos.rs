@@ -0,0 +1,143 @@
+// src/command/bitpos.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the BITPOS command in Nimblecache.
+///
+/// The `BitPos` struct is used to find the first bit set to 0 or 1 in the string
+/// stored at a key, optionally within a range of bytes.
+#[derive(Debug, Clone)]
+pub struct BitPos {
+    key: String,
+    /// The bit to look for.
+    bit: bool,
+    /// Index of the first byte in the range. Negative values count from the end of the string.
+    start: i64,
+    /// Index of the last byte in the range (inclusive), if given. Negative values count
+    /// from the end of the string.
+    end: Option<i64>,
+}
+
+impl BitPos {
+    /// Creates a new `BitPos` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key bit [start [end]]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the BITPOS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BitPos)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<BitPos, CommandError> {
+        if args.len() < 2 || args.len() > 4 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'BITPOS' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse bit
+        let bit = match &args[1] {
+            RespType::BulkString(b) if b == "0" => false,
+            RespType::BulkString(b) if b == "1" => true,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "The bit argument must be 1 or 0.",
+                )));
+            }
+        };
+
+        // parse range
+        let mut range: Vec<i64> = vec![];
+        for arg in args[2..].iter() {
+            match arg {
+                RespType::BulkString(n) => match n.parse::<i64>() {
+                    Ok(n) => range.push(n),
+                    Err(_) => {
+                        return Err(CommandError::Other(String::from(
+                            "value is not an integer or out of range",
+                        )))
+                    }
+                },
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Value must be an integer in bulk string format",
+                    )));
+                }
+            }
+        }
+
+        Ok(BitPos {
+            key: key.to_string(),
+            bit,
+            start: range.first().copied().unwrap_or(0),
+            end: range.get(1).copied(),
+        })
+    }
+
+    /// Executes the BITPOS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The position of the first matching bit as an `Integer`, counting from the most
+    /// significant bit of the first byte of the string. `-1` is returned if no bit matches.
+    ///
+    /// When looking for a clear bit without an explicit end, the string is treated as if
+    /// it's padded with zeros on the right. So the position right after the range is
+    /// returned if all the bits in the range are set, and `0` is returned if the key
+    /// doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let value = match db.get(&self.key) {
+            Ok(Some(value)) => value,
+            Ok(None) => return RespType::Integer(if self.bit { -1 } else { 0 }),
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let bytes = value.as_bytes();
+        let len = bytes.len() as i64;
+        let normalize = |i: i64| if i < 0 { (len + i).max(0) } else { i };
+        let start = normalize(self.start);
+        let end = normalize(self.end.unwrap_or(-1)).min(len - 1);
+
+        if start > end {
+            return RespType::Integer(-1);
+        }
+
+        for (i, byte) in bytes[start as usize..=end as usize].iter().enumerate() {
+            // Flip the byte when looking for a clear bit, so that the bit to find is always 1.
+            let byte = if self.bit { *byte } else { !*byte };
+            if byte != 0 {
+                let pos = (start + i as i64) * 8 + byte.leading_zeros() as i64;
+                return RespType::Integer(pos);
+            }
+        }
+
+        match (self.bit, self.end) {
+            (false, None) => RespType::Integer((end + 1) * 8),
+            _ => RespType::Integer(-1),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
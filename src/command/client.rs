@@ -0,0 +1,186 @@
+// src/command/client.rs
+
+use crate::{
+    clients::{ClientInfo, ClientState},
+    resp::types::{Protocol, RespType},
+};
+
+use super::{help, CommandError};
+
+/// Represents the CLIENT command in Nimblecache.
+///
+/// CLIENT changes the state of the connection it's issued on, so this command is
+/// handled by `FrameHandler.handle`.
+#[derive(Debug, Clone)]
+pub struct ClientCommand {
+    subcommand: ClientSubcommand,
+}
+
+/// The supported CLIENT subcommands.
+#[derive(Debug, Clone)]
+enum ClientSubcommand {
+    /// CLIENT NO-EVICT ON|OFF
+    NoEvict(bool),
+    /// CLIENT NO-TOUCH ON|OFF
+    NoTouch(bool),
+    /// CLIENT TRACKING ON|OFF
+    Tracking(bool),
+    /// CLIENT SETNAME connection-name
+    SetName(String),
+    /// CLIENT GETNAME
+    GetName,
+    /// CLIENT INFO
+    Info,
+    /// CLIENT HELP
+    Help,
+}
+
+impl ClientCommand {
+    /// Creates a new `ClientCommand` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CLIENT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ClientCommand)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ClientCommand, CommandError> {
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let subcommand = match parsed.as_slice() {
+            [subcommand, mode]
+                if subcommand.eq_ignore_ascii_case("no-evict")
+                    || subcommand.eq_ignore_ascii_case("no-touch")
+                    || subcommand.eq_ignore_ascii_case("tracking") =>
+            {
+                let on = match mode.to_lowercase().as_str() {
+                    "on" => true,
+                    "off" => false,
+                    _ => return Err(CommandError::Other(String::from("syntax error"))),
+                };
+
+                if subcommand.eq_ignore_ascii_case("no-evict") {
+                    ClientSubcommand::NoEvict(on)
+                } else if subcommand.eq_ignore_ascii_case("no-touch") {
+                    ClientSubcommand::NoTouch(on)
+                } else {
+                    ClientSubcommand::Tracking(on)
+                }
+            }
+            [subcommand, name] if subcommand.eq_ignore_ascii_case("setname") => {
+                // The names are listed space separated, so they can't contain spaces.
+                if name.chars().any(|c| !('!'..='~').contains(&c)) {
+                    return Err(CommandError::Other(String::from(
+                        "Client names cannot contain spaces, newlines or special characters.",
+                    )));
+                }
+                ClientSubcommand::SetName(name.to_string())
+            }
+            [subcommand] if subcommand.eq_ignore_ascii_case("getname") => ClientSubcommand::GetName,
+            [subcommand] if subcommand.eq_ignore_ascii_case("info") => ClientSubcommand::Info,
+            [subcommand] if subcommand.eq_ignore_ascii_case("help") => ClientSubcommand::Help,
+            [] => {
+                return Err(CommandError::Other(String::from(
+                    "Wrong number of arguments specified for 'CLIENT' command",
+                )));
+            }
+            [subcommand, ..] => {
+                let known = [
+                    "no-evict", "no-touch", "tracking", "setname", "getname", "info", "help",
+                ];
+                if known.iter().any(|k| subcommand.eq_ignore_ascii_case(k)) {
+                    return Err(CommandError::Other(format!(
+                        "Wrong number of arguments specified for 'CLIENT {}' command",
+                        subcommand.to_uppercase()
+                    )));
+                }
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'CLIENT' command",
+                    subcommand
+                )));
+            }
+        };
+
+        Ok(ClientCommand { subcommand })
+    }
+
+    /// Executes the CLIENT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The state of the connection the command was issued on.
+    ///
+    /// * `info` - The identity of the connection, and the commands it issued last.
+    ///
+    /// * `protocol` - The protocol version used by the connection.
+    ///
+    /// # Returns
+    ///
+    /// - `CLIENT GETNAME` - The name of the connection as a `BulkString`, or a `NullBulkString`
+    ///   if it has no name.
+    /// - `CLIENT INFO` - A single line describing the connection, as a `BulkString`.
+    /// - `CLIENT HELP` - The usage of the subcommands as an `Array`.
+    /// - `CLIENT TRACKING ON` - A `SimpleError` if the connection uses RESP2, since the
+    ///   invalidation messages are pushed to the client.
+    /// - An "OK" response as a `SimpleString` for the other subcommands.
+    pub fn apply(
+        &self,
+        client: &mut ClientState,
+        info: &ClientInfo,
+        protocol: Protocol,
+    ) -> RespType {
+        match &self.subcommand {
+            ClientSubcommand::NoEvict(on) => client.set_no_evict(*on),
+            ClientSubcommand::NoTouch(on) => client.set_no_touch(*on),
+            ClientSubcommand::Tracking(true) if protocol == Protocol::Resp2 => {
+                return RespType::SimpleError(String::from(
+                    "Client tracking requires RESP3, switch to it with HELLO 3",
+                ));
+            }
+            ClientSubcommand::Tracking(on) => client.set_tracking(*on),
+            ClientSubcommand::SetName(name) => client.set_name(name.to_string()),
+            ClientSubcommand::GetName => {
+                return match client.name() {
+                    Some(name) => RespType::BulkString(name.to_string()),
+                    None => RespType::NullBulkString,
+                };
+            }
+            ClientSubcommand::Info => return RespType::BulkString(info.line(client)),
+            ClientSubcommand::Help => {
+                return help::reply(
+                    "CLIENT",
+                    &[
+                        "GETNAME",
+                        "    Return the name of the current connection.",
+                        "INFO",
+                        "    Return information about the current client connection.",
+                        "SETNAME <name>",
+                        "    Assign the name <name> to the current connection. An empty name",
+                        "    removes the name.",
+                        "NO-EVICT (ON|OFF)",
+                        "    Protect the current client connection from eviction.",
+                        "NO-TOUCH (ON|OFF)",
+                        "    Will not touch LRU/LFU stats when this mode is on.",
+                        "TRACKING (ON|OFF)",
+                        "    Control server assisted client side caching. Once on, the client",
+                        "    is sent an invalidation message when a key it read is modified.",
+                    ],
+                );
+            }
+        }
+
+        RespType::SimpleString(String::from("OK"))
+    }
+}
@@ -0,0 +1,105 @@
+// src/command/cluster.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Node id reported by `CLUSTER MYID`. Nimblecache always runs as a standalone
+/// instance, so the id never changes.
+const NODE_ID: &str = "0000000000000000000000000000000000000000";
+
+/// Represents the CLUSTER command in Nimblecache.
+///
+/// Nimblecache doesn't support clustering. This command only returns the replies a
+/// standalone instance would report, so that cluster-aware clients which probe the
+/// server on connect can fall back to standalone mode.
+#[derive(Debug, Clone)]
+pub struct Cluster {
+    subcommand: ClusterSubcommand,
+}
+
+/// The supported CLUSTER subcommands.
+#[derive(Debug, Clone)]
+enum ClusterSubcommand {
+    /// CLUSTER INFO
+    Info,
+    /// CLUSTER SLOTS
+    Slots,
+    /// CLUSTER MYID
+    MyId,
+}
+
+impl Cluster {
+    /// Creates a new `Cluster` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CLUSTER command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Cluster)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Cluster, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'CLUSTER' command",
+            )));
+        }
+
+        let subcommand = match &args[0] {
+            RespType::BulkString(s) => s,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Subcommand must be a bulk string",
+                )));
+            }
+        };
+
+        let subcommand = match subcommand.to_lowercase().as_str() {
+            "info" => ClusterSubcommand::Info,
+            "slots" => ClusterSubcommand::Slots,
+            "myid" => ClusterSubcommand::MyId,
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'CLUSTER' command",
+                    subcommand
+                )));
+            }
+        };
+
+        Ok(Cluster { subcommand })
+    }
+
+    /// Executes the CLUSTER command.
+    ///
+    /// # Returns
+    ///
+    /// - `CLUSTER INFO` - The cluster state of a standalone instance as a `BulkString`.
+    /// - `CLUSTER SLOTS` - An empty `Array`, since no slots are served by a cluster.
+    /// - `CLUSTER MYID` - The fixed node id of this instance as a `BulkString`.
+    pub fn apply(&self) -> RespType {
+        match self.subcommand {
+            ClusterSubcommand::Info => {
+                let info = [
+                    "cluster_enabled:0",
+                    "cluster_state:ok",
+                    "cluster_slots_assigned:0",
+                    "cluster_slots_ok:0",
+                    "cluster_slots_pfail:0",
+                    "cluster_slots_fail:0",
+                    "cluster_known_nodes:1",
+                    "cluster_size:0",
+                    "cluster_current_epoch:0",
+                    "cluster_my_epoch:0",
+                ];
+                let mut info = info.join("\r\n");
+                info.push_str("\r\n");
+
+                RespType::BulkString(info)
+            }
+            ClusterSubcommand::Slots => RespType::Array(vec![]),
+            ClusterSubcommand::MyId => RespType::BulkString(String::from(NODE_ID)),
+        }
+    }
+}
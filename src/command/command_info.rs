@@ -0,0 +1,307 @@
+// src/command/command_info.rs
+
+use crate::resp::types::RespType;
+
+use super::{help, CommandError};
+
+/// Represents the COMMAND command in Nimblecache.
+///
+/// It's not named after the command, as it would clash with the `Command` enum.
+#[derive(Debug, Clone)]
+pub struct CommandInfo {
+    subcommand: CommandSubcommand,
+}
+
+/// The supported COMMAND subcommands.
+#[derive(Debug, Clone)]
+enum CommandSubcommand {
+    /// COMMAND GETKEYS command [arg ...]
+    GetKeys(Vec<String>),
+    /// COMMAND HELP
+    Help,
+}
+
+/// Where the keys are found in the arguments of a command, similar to the key specs of Redis.
+/// Positions are indices in the full command, so the first argument after the name is `1`.
+#[derive(Debug, Clone, Copy)]
+enum KeySpec {
+    /// The keys are found from `first` to `last`, every `step` arguments. A negative `last`
+    /// counts from the end, so `-1` is the last argument.
+    Range { first: usize, last: i64, step: usize },
+    /// The argument at `numkeys` gives the number of keys, which follow it.
+    KeyNum { numkeys: usize },
+    /// The keys follow the first `keyword` argument, and take up the first half of the
+    /// arguments after it. The other half holds a value for each key.
+    Keyword { keyword: &'static str },
+}
+
+/// A single key, right after the command name.
+const FIRST_ARG: KeySpec = KeySpec::Range {
+    first: 1,
+    last: 1,
+    step: 1,
+};
+
+/// Every argument is a key.
+const ALL_ARGS: KeySpec = KeySpec::Range {
+    first: 1,
+    last: -1,
+    step: 1,
+};
+
+/// A single key, right after the subcommand.
+const SUBCOMMAND_ARG: KeySpec = KeySpec::Range {
+    first: 2,
+    last: 2,
+    step: 1,
+};
+
+/// The key specs of the commands which take keys. Commands which aren't listed take no keys.
+const KEY_SPECS: [(&str, KeySpec); 61] = [
+    ("set", FIRST_ARG),
+    ("get", FIRST_ARG),
+    ("mget", ALL_ARGS),
+    (
+        "mset",
+        KeySpec::Range {
+            first: 1,
+            last: -1,
+            step: 2,
+        },
+    ),
+    ("getdel", FIRST_ARG),
+    ("getex", FIRST_ARG),
+    ("append", FIRST_ARG),
+    ("incr", FIRST_ARG),
+    ("setrange", FIRST_ARG),
+    ("bitpos", FIRST_ARG),
+    ("del", ALL_ARGS),
+    ("unlink", ALL_ARGS),
+    ("lpush", FIRST_ARG),
+    ("rpush", FIRST_ARG),
+    ("lrange", FIRST_ARG),
+    ("lpos", FIRST_ARG),
+    ("sadd", FIRST_ARG),
+    ("srem", FIRST_ARG),
+    ("spop", FIRST_ARG),
+    ("srandmember", FIRST_ARG),
+    ("smembers", FIRST_ARG),
+    ("sscan", FIRST_ARG),
+    ("sunion", ALL_ARGS),
+    ("sinterstore", ALL_ARGS),
+    ("sunionstore", ALL_ARGS),
+    ("sdiffstore", ALL_ARGS),
+    ("sintercard", KeySpec::KeyNum { numkeys: 1 }),
+    (
+        "smove",
+        KeySpec::Range {
+            first: 1,
+            last: 2,
+            step: 1,
+        },
+    ),
+    (
+        "copy",
+        KeySpec::Range {
+            first: 1,
+            last: 2,
+            step: 1,
+        },
+    ),
+    ("hset", FIRST_ARG),
+    ("hgetall", FIRST_ARG),
+    ("hstrlen", FIRST_ARG),
+    ("hrandfield", FIRST_ARG),
+    ("hscan", FIRST_ARG),
+    ("pfadd", FIRST_ARG),
+    ("pfcount", ALL_ARGS),
+    ("zadd", FIRST_ARG),
+    ("zrem", FIRST_ARG),
+    ("zincrby", FIRST_ARG),
+    ("zpopmin", FIRST_ARG),
+    ("zpopmax", FIRST_ARG),
+    ("zcard", FIRST_ARG),
+    ("zcount", FIRST_ARG),
+    ("zscan", FIRST_ARG),
+    ("xadd", FIRST_ARG),
+    ("xlen", FIRST_ARG),
+    ("xrange", FIRST_ARG),
+    ("xread", KeySpec::Keyword { keyword: "streams" }),
+    ("geoadd", FIRST_ARG),
+    ("geosearch", FIRST_ARG),
+    ("sort", FIRST_ARG),
+    ("expire", FIRST_ARG),
+    ("expireat", FIRST_ARG),
+    ("pexpireat", FIRST_ARG),
+    ("persist", FIRST_ARG),
+    ("ttl", FIRST_ARG),
+    ("expiretime", FIRST_ARG),
+    ("pexpiretime", FIRST_ARG),
+    ("watch", ALL_ARGS),
+    ("object", SUBCOMMAND_ARG),
+    ("memory", SUBCOMMAND_ARG),
+];
+
+/// The commands which are known to take no keys.
+const KEYLESS_COMMANDS: [&str; 29] = [
+    "ping",
+    "dbsize",
+    "flushall",
+    "info",
+    "lolwut",
+    "bgrewriteaof",
+    "client",
+    "hello",
+    "cluster",
+    "config",
+    "command",
+    "replicaof",
+    "role",
+    "psync",
+    "replconf",
+    "wait",
+    "shutdown",
+    "subscribe",
+    "unsubscribe",
+    "psubscribe",
+    "punsubscribe",
+    "publish",
+    "pubsub",
+    "multi",
+    "exec",
+    "discard",
+    "unwatch",
+    "monitor",
+    "debug",
+];
+
+impl CommandInfo {
+    /// Creates a new `CommandInfo` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the COMMAND command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CommandInfo)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CommandInfo, CommandError> {
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let subcommand = match parsed.as_slice() {
+            [subcommand, command @ ..]
+                if subcommand.eq_ignore_ascii_case("getkeys") && !command.is_empty() =>
+            {
+                CommandSubcommand::GetKeys(command.iter().map(|arg| arg.to_string()).collect())
+            }
+            [subcommand] if subcommand.eq_ignore_ascii_case("help") => CommandSubcommand::Help,
+            [] => {
+                return Err(CommandError::Other(String::from(
+                    "Wrong number of arguments specified for 'COMMAND' command",
+                )));
+            }
+            [subcommand, ..] => {
+                if ["getkeys", "help"].iter().any(|k| subcommand.eq_ignore_ascii_case(k)) {
+                    return Err(CommandError::Other(format!(
+                        "Wrong number of arguments specified for 'COMMAND {}' command",
+                        subcommand.to_uppercase()
+                    )));
+                }
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'COMMAND' command",
+                    subcommand
+                )));
+            }
+        };
+
+        Ok(CommandInfo { subcommand })
+    }
+
+    /// Executes the COMMAND command.
+    ///
+    /// # Returns
+    ///
+    /// - `COMMAND GETKEYS` - The key arguments of the given command as an `Array` of
+    ///   `BulkString`s, or a `SimpleError` if the command is unknown, takes no keys, or
+    ///   its arguments don't hold the keys it expects.
+    /// - `COMMAND HELP` - The usage of the subcommands as an `Array`.
+    pub fn apply(&self) -> RespType {
+        match &self.subcommand {
+            CommandSubcommand::GetKeys(command) => match Self::get_keys(command) {
+                Ok(keys) => RespType::Array(
+                    keys.into_iter()
+                        .map(|key| RespType::BulkString(key.to_string()))
+                        .collect(),
+                ),
+                Err(e) => RespType::SimpleError(String::from(e)),
+            },
+            CommandSubcommand::Help => help::reply(
+                "COMMAND",
+                &[
+                    "GETKEYS <full-command>",
+                    "    Return the keys from a full Redis command.",
+                ],
+            ),
+        }
+    }
+
+    /// Extracts the key arguments of a full command, according to the key spec of the command.
+    fn get_keys(command: &[String]) -> Result<Vec<&String>, &'static str> {
+        let name = command[0].to_ascii_lowercase();
+        let spec = match KEY_SPECS.iter().find(|(command, _)| *command == name) {
+            Some((_, spec)) => *spec,
+            None if KEYLESS_COMMANDS.contains(&name.as_str()) => {
+                return Err("The command has no key arguments");
+            }
+            None => return Err("Invalid command specified"),
+        };
+
+        let invalid = "Invalid arguments specified for command";
+        match spec {
+            KeySpec::Range { first, last, step } => {
+                let last = if last < 0 {
+                    command.len() as i64 + last
+                } else {
+                    last
+                };
+                if first >= command.len() || last < first as i64 || last >= command.len() as i64 {
+                    return Err(invalid);
+                }
+
+                Ok(command[first..=last as usize].iter().step_by(step).collect())
+            }
+            KeySpec::KeyNum { numkeys } => {
+                let count = match command.get(numkeys).map(|n| n.parse::<usize>()) {
+                    Some(Ok(count)) if count > 0 => count,
+                    _ => return Err(invalid),
+                };
+                match command.get(numkeys + 1..numkeys + 1 + count) {
+                    Some(keys) => Ok(keys.iter().collect()),
+                    None => Err(invalid),
+                }
+            }
+            KeySpec::Keyword { keyword } => {
+                let rest = match command.iter().position(|arg| arg.eq_ignore_ascii_case(keyword)) {
+                    Some(pos) => &command[pos + 1..],
+                    None => return Err(invalid),
+                };
+                if rest.is_empty() || !rest.len().is_multiple_of(2) {
+                    return Err(invalid);
+                }
+
+                Ok(rest[..rest.len() / 2].iter().collect())
+            }
+        }
+    }
+}
@@ -0,0 +1,318 @@
+// src/command/config.rs
+
+use crate::{
+    config::{self, AppendFsync},
+    resp::types::RespType,
+    storage::{db::Storage, eviction::MaxMemoryPolicy},
+};
+
+use super::{glob, help, CommandError};
+
+/// Represents the CONFIG command in Nimblecache.
+///
+/// CONFIG is used to inspect and change the configuration of the server at runtime.
+#[derive(Debug, Clone)]
+pub struct ConfigCommand {
+    subcommand: ConfigSubcommand,
+}
+
+/// The supported CONFIG subcommands.
+#[derive(Debug, Clone)]
+enum ConfigSubcommand {
+    /// CONFIG GET pattern [pattern ...]
+    Get(Vec<String>),
+    /// CONFIG SET parameter value [parameter value ...]
+    Set(Vec<MutableParam>),
+    /// CONFIG REWRITE
+    Rewrite,
+    /// CONFIG HELP
+    Help,
+}
+
+/// The parameters which can be changed at runtime with CONFIG SET, along with their new values.
+#[derive(Debug, Clone)]
+enum MutableParam {
+    MaxClients(usize),
+    ReadOnly(bool),
+    MaxMemory(u64),
+    MaxMemoryPolicy(MaxMemoryPolicy),
+    DefaultTtl(u64),
+    ListMaxSize(u64),
+    MaxValueSize(u64),
+    SetMaxIntsetEntries(usize),
+    AppendFsync(AppendFsync),
+}
+
+/// The parameters which can only be set when the server starts.
+const IMMUTABLE_PARAMS: [&str; 11] = [
+    "port",
+    "bind",
+    "appendonly",
+    "pidfile",
+    "client-output-buffer-limit",
+    "command-timeout",
+    "commands-per-yield",
+    "client-history-len",
+    "logfile",
+    "io-threads",
+    "configfile",
+];
+
+impl ConfigCommand {
+    /// Creates a new `ConfigCommand` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CONFIG command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ConfigCommand)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ConfigCommand, CommandError> {
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        if parsed.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'CONFIG' command",
+            )));
+        }
+
+        let subcommand = match parsed[0].to_lowercase().as_str() {
+            "get" if parsed.len() > 1 => ConfigSubcommand::Get(
+                parsed[1..]
+                    .iter()
+                    .map(|pattern| pattern.to_lowercase())
+                    .collect(),
+            ),
+            "set" if parsed.len() > 1 && (parsed.len() - 1).is_multiple_of(2) => {
+                Self::parse_set(&parsed[1..])?
+            }
+            "rewrite" if parsed.len() == 1 => ConfigSubcommand::Rewrite,
+            "help" if parsed.len() == 1 => ConfigSubcommand::Help,
+            "get" | "set" | "rewrite" | "help" => {
+                return Err(CommandError::Other(format!(
+                    "Wrong number of arguments specified for 'CONFIG {}' command",
+                    parsed[0].to_uppercase()
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'CONFIG' command",
+                    parsed[0]
+                )));
+            }
+        };
+
+        Ok(ConfigCommand { subcommand })
+    }
+
+    /// Parses the parameter and value pairs of `CONFIG SET`, which follow the subcommand.
+    /// The values are validated here, so that either all the parameters are set or none.
+    fn parse_set(args: &[&String]) -> Result<ConfigSubcommand, CommandError> {
+        let mut params: Vec<MutableParam> = vec![];
+        for pair in args.chunks(2) {
+            let name = pair[0].to_lowercase();
+            if IMMUTABLE_PARAMS.contains(&name.as_str()) {
+                return Err(CommandError::Other(format!(
+                    "CONFIG SET failed (possibly related to argument '{}') - can't set immutable config",
+                    name
+                )));
+            }
+
+            let param = match name.as_str() {
+                "maxclients" => match pair[1].parse::<usize>() {
+                    Ok(maxclients) => MutableParam::MaxClients(maxclients),
+                    Err(_) => {
+                        return Err(CommandError::Other(format!(
+                            "CONFIG SET failed (possibly related to argument '{}') - argument couldn't be parsed into an integer",
+                            name
+                        )));
+                    }
+                },
+                "read-only" => match pair[1].to_lowercase().as_str() {
+                    "yes" => MutableParam::ReadOnly(true),
+                    "no" => MutableParam::ReadOnly(false),
+                    _ => {
+                        return Err(CommandError::Other(format!(
+                            "CONFIG SET failed (possibly related to argument '{}') - argument must be 'yes' or 'no'",
+                            name
+                        )));
+                    }
+                },
+                "maxmemory" => match config::parse_memory(pair[1]) {
+                    Ok(maxmemory) => MutableParam::MaxMemory(maxmemory),
+                    Err(e) => {
+                        return Err(CommandError::Other(format!(
+                            "CONFIG SET failed (possibly related to argument '{}') - {}",
+                            name, e
+                        )));
+                    }
+                },
+                "maxmemory-policy" => match pair[1].parse::<MaxMemoryPolicy>() {
+                    Ok(policy) => MutableParam::MaxMemoryPolicy(policy),
+                    Err(e) => {
+                        return Err(CommandError::Other(format!(
+                            "CONFIG SET failed (possibly related to argument '{}') - {}",
+                            name, e
+                        )));
+                    }
+                },
+                "default-ttl" => match pair[1].parse::<u64>() {
+                    Ok(default_ttl) => MutableParam::DefaultTtl(default_ttl),
+                    Err(_) => {
+                        return Err(CommandError::Other(format!(
+                            "CONFIG SET failed (possibly related to argument '{}') - argument couldn't be parsed into an integer",
+                            name
+                        )));
+                    }
+                },
+                "list-max-size" => match pair[1].parse::<u64>() {
+                    Ok(list_max_size) => MutableParam::ListMaxSize(list_max_size),
+                    Err(_) => {
+                        return Err(CommandError::Other(format!(
+                            "CONFIG SET failed (possibly related to argument '{}') - argument couldn't be parsed into an integer",
+                            name
+                        )));
+                    }
+                },
+                "max-value-size" => match pair[1].parse::<u64>() {
+                    Ok(max_value_size) => MutableParam::MaxValueSize(max_value_size),
+                    Err(_) => {
+                        return Err(CommandError::Other(format!(
+                            "CONFIG SET failed (possibly related to argument '{}') - argument couldn't be parsed into an integer",
+                            name
+                        )));
+                    }
+                },
+                "set-max-intset-entries" => match pair[1].parse::<usize>() {
+                    Ok(entries) => MutableParam::SetMaxIntsetEntries(entries),
+                    Err(_) => {
+                        return Err(CommandError::Other(format!(
+                            "CONFIG SET failed (possibly related to argument '{}') - argument couldn't be parsed into an integer",
+                            name
+                        )));
+                    }
+                },
+                "appendfsync" => match pair[1].parse::<AppendFsync>() {
+                    Ok(appendfsync) => MutableParam::AppendFsync(appendfsync),
+                    Err(e) => {
+                        return Err(CommandError::Other(format!(
+                            "CONFIG SET failed (possibly related to argument '{}') - {}",
+                            name, e
+                        )));
+                    }
+                },
+                _ => {
+                    return Err(CommandError::Other(format!(
+                        "Unknown option or number of arguments for CONFIG SET - '{}'",
+                        name
+                    )));
+                }
+            };
+
+            params.push(param);
+        }
+
+        Ok(ConfigSubcommand::Set(params))
+    }
+
+    /// Executes the CONFIG command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the configuration of the server.
+    ///
+    /// # Returns
+    ///
+    /// - `CONFIG GET` - A `Map` of the parameters whose names match any of the glob-style
+    ///   patterns, to their values.
+    /// - `CONFIG SET` - "OK" as a `SimpleString` once the parameters are set.
+    /// - `CONFIG REWRITE` - "OK" as a `SimpleString` once the parameters are written to the
+    ///   config file the server was started with, or a `SimpleError` if there's no such file.
+    /// - `CONFIG HELP` - The usage of the subcommands as an `Array`.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        match &self.subcommand {
+            ConfigSubcommand::Get(patterns) => {
+                let params = storage
+                    .config()
+                    .params()
+                    .into_iter()
+                    .filter(|(name, _)| patterns.iter().any(|pattern| glob::matches(pattern, name)))
+                    .map(|(name, value)| (RespType::BulkString(name), RespType::BulkString(value)))
+                    .collect();
+
+                RespType::Map(params)
+            }
+            ConfigSubcommand::Set(params) => {
+                for param in params.iter() {
+                    match param {
+                        MutableParam::MaxClients(maxclients) => {
+                            storage.clients().set_maxclients(*maxclients);
+                            storage.config().set("maxclients", maxclients.to_string());
+                        }
+                        MutableParam::ReadOnly(read_only) => {
+                            storage.set_read_only(*read_only);
+                            let value = if *read_only { "yes" } else { "no" };
+                            storage.config().set("read-only", String::from(value));
+                        }
+                        MutableParam::MaxMemory(maxmemory) => {
+                            storage.set_maxmemory(*maxmemory);
+                            storage.config().set("maxmemory", maxmemory.to_string());
+                        }
+                        MutableParam::MaxMemoryPolicy(policy) => {
+                            storage.set_maxmemory_policy(*policy);
+                            storage.config().set("maxmemory-policy", String::from(policy.name()));
+                        }
+                        MutableParam::DefaultTtl(default_ttl) => {
+                            storage.set_default_ttl(*default_ttl);
+                            storage.config().set("default-ttl", default_ttl.to_string());
+                        }
+                        MutableParam::ListMaxSize(list_max_size) => {
+                            storage.set_list_max_size(*list_max_size);
+                            storage.config().set("list-max-size", list_max_size.to_string());
+                        }
+                        MutableParam::MaxValueSize(max_value_size) => {
+                            storage.set_max_value_size(*max_value_size);
+                            storage.config().set("max-value-size", max_value_size.to_string());
+                        }
+                        MutableParam::SetMaxIntsetEntries(entries) => {
+                            storage.db().set_set_max_intset_entries(*entries);
+                            storage.config().set("set-max-intset-entries", entries.to_string());
+                        }
+                        MutableParam::AppendFsync(appendfsync) => {
+                            storage.config().set("appendfsync", String::from(appendfsync.name()));
+                        }
+                    }
+                }
+
+                RespType::SimpleString(String::from("OK"))
+            }
+            ConfigSubcommand::Rewrite => match storage.config().rewrite() {
+                Ok(_) => RespType::SimpleString(String::from("OK")),
+                Err(e) => RespType::SimpleError(format!("Rewriting config file: {}", e)),
+            },
+            ConfigSubcommand::Help => help::reply(
+                "CONFIG",
+                &[
+                    "GET <pattern> [<pattern> ...]",
+                    "    Return parameters matching the glob-like <pattern> and their values.",
+                    "SET <directive> <value> [<directive> <value> ...]",
+                    "    Set the configuration <directive> to <value>.",
+                    "REWRITE",
+                    "    Rewrite the configuration file.",
+                ],
+            ),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// src/command/copy.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the COPY command in Nimblecache.
+///
+/// The `CopyCommand` struct is used to copy the value stored at a key to another key,
+/// along with its expiry. It's not named after the command, as it would shadow the
+/// `Copy` trait.
+#[derive(Debug, Clone)]
+pub struct CopyCommand {
+    source: String,
+    destination: String,
+    /// Set by the `REPLACE` option. The destination key is replaced if it exists.
+    replace: bool,
+}
+
+impl CopyCommand {
+    /// Creates a new `CopyCommand` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `source destination [DB destination-db] [REPLACE]`.
+    /// There's a single database, so `DB` only accepts `0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the COPY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CopyCommand)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CopyCommand, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'COPY' command",
+            )));
+        }
+
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let mut copy = CopyCommand {
+            source: parsed[0].to_string(),
+            destination: parsed[1].to_string(),
+            replace: false,
+        };
+
+        // parse options
+        let mut options = parsed[2..].iter();
+        while let Some(option) = options.next() {
+            match option.to_lowercase().as_str() {
+                "replace" => copy.replace = true,
+                "db" => match options.next().map(|db| db.parse::<i64>()) {
+                    Some(Ok(0)) => {}
+                    Some(Ok(_)) => {
+                        return Err(CommandError::Other(String::from("DB index is out of range")));
+                    }
+                    Some(Err(_)) => {
+                        return Err(CommandError::Other(String::from(
+                            "value is not an integer or out of range",
+                        )));
+                    }
+                    None => return Err(CommandError::Other(String::from("syntax error"))),
+                },
+                _ => return Err(CommandError::Other(String::from("syntax error"))),
+            }
+        }
+
+        if copy.source == copy.destination {
+            return Err(CommandError::Other(String::from(
+                "source and destination objects are the same",
+            )));
+        }
+
+        Ok(copy)
+    }
+
+    /// Executes the COPY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// `1` if the value was copied, or `0` if the source doesn't exist or the destination
+    /// already exists without `REPLACE`, as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.copy(&self.source, &self.destination, self.replace) {
+            Ok(copied) => RespType::Integer(copied as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.source.clone(), self.destination.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args = vec![
+            RespType::BulkString(String::from("COPY")),
+            RespType::BulkString(self.source.clone()),
+            RespType::BulkString(self.destination.clone()),
+        ];
+        if self.replace {
+            args.push(RespType::BulkString(String::from("REPLACE")));
+        }
+
+        RespType::Array(args)
+    }
+}
@@ -0,0 +1,49 @@
+// src/command/dbsize.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the DBSIZE command in Nimblecache.
+///
+/// DBSIZE returns the number of keys in the database.
+#[derive(Debug, Clone)]
+pub struct DbSize;
+
+impl DbSize {
+    /// Creates a new `DbSize` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the DBSIZE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DbSize)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<DbSize, CommandError> {
+        if !args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'DBSIZE' command",
+            )));
+        }
+
+        Ok(DbSize)
+    }
+
+    /// Executes the DBSIZE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of keys in the database as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.len() {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,367 @@
+// src/command/debug.rs
+
+use std::time::Duration;
+
+use tokio_util::codec::Decoder;
+
+use crate::{
+    replication,
+    resp::{frame::RespCommandFrame, types::RespType},
+    storage::db::Storage,
+};
+
+use super::{glob, Command, CommandError};
+
+/// Represents the DEBUG command in Nimblecache.
+///
+/// DEBUG groups the subcommands which help with testing and inspecting the server.
+#[derive(Debug, Clone)]
+pub struct DebugCommand {
+    subcommand: DebugSubcommand,
+}
+
+/// The supported DEBUG subcommands.
+#[derive(Debug, Clone)]
+enum DebugSubcommand {
+    /// DEBUG RELOAD
+    Reload,
+    /// DEBUG POPULATE count [prefix]
+    Populate { count: u64, prefix: String },
+    /// DEBUG SLEEP seconds
+    Sleep(Duration),
+    /// DEBUG STRINGMATCH-LEN pattern string
+    StringMatchLen { pattern: String, string: String },
+    /// DEBUG CHANGE-REPL-ID
+    ChangeReplId,
+    /// DEBUG OBJECT key
+    Object(String),
+    /// DEBUG PROTOCOL type
+    Protocol(String),
+}
+
+/// The reply types which DEBUG PROTOCOL can send.
+const PROTOCOL_TYPES: [&str; 7] =
+    ["string", "integer", "null", "array", "set", "verbatim", "attrib"];
+
+impl DebugCommand {
+    /// Creates a new `DebugCommand` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the DEBUG command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DebugCommand)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<DebugCommand, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'DEBUG' command",
+            )));
+        }
+
+        let subcommand = match &args[0] {
+            RespType::BulkString(s) => s,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Subcommand must be a bulk string",
+                )));
+            }
+        };
+
+        let subcommand = match subcommand.to_lowercase().as_str() {
+            "reload" => {
+                if args.len() != 1 {
+                    return Err(CommandError::Other(String::from(
+                        "Wrong number of arguments specified for 'DEBUG RELOAD' command",
+                    )));
+                }
+                DebugSubcommand::Reload
+            }
+            "populate" => {
+                if args.len() < 2 || args.len() > 3 {
+                    return Err(CommandError::Other(String::from(
+                        "Wrong number of arguments specified for 'DEBUG POPULATE' command",
+                    )));
+                }
+
+                let count = match &args[1] {
+                    RespType::BulkString(c) => match c.parse::<u64>() {
+                        Ok(c) => c,
+                        Err(_) => {
+                            return Err(CommandError::Other(String::from(
+                                "Count should be a non-negative integer",
+                            )));
+                        }
+                    },
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "Invalid argument. Count must be an integer in bulk string format",
+                        )));
+                    }
+                };
+
+                let prefix = match args.get(2) {
+                    Some(RespType::BulkString(p)) => p.to_string(),
+                    Some(_) => {
+                        return Err(CommandError::Other(String::from(
+                            "Invalid argument. Prefix must be a bulk string",
+                        )));
+                    }
+                    None => String::from("key:"),
+                };
+
+                DebugSubcommand::Populate { count, prefix }
+            }
+            "sleep" => {
+                if args.len() != 2 {
+                    return Err(CommandError::Other(String::from(
+                        "Wrong number of arguments specified for 'DEBUG SLEEP' command",
+                    )));
+                }
+
+                let duration = match &args[1] {
+                    RespType::BulkString(s) => match s.parse::<f64>() {
+                        Ok(s) => Duration::try_from_secs_f64(s).ok(),
+                        Err(_) => None,
+                    },
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "Invalid argument. Seconds must be a number in bulk string format",
+                        )));
+                    }
+                };
+
+                match duration {
+                    Some(d) => DebugSubcommand::Sleep(d),
+                    None => {
+                        return Err(CommandError::Other(String::from(
+                            "Seconds should be a non-negative number",
+                        )));
+                    }
+                }
+            }
+            "change-repl-id" => {
+                if args.len() != 1 {
+                    return Err(CommandError::Other(String::from(
+                        "Wrong number of arguments specified for 'DEBUG CHANGE-REPL-ID' command",
+                    )));
+                }
+                DebugSubcommand::ChangeReplId
+            }
+            "object" => match (args.get(1), args.len()) {
+                (Some(RespType::BulkString(key)), 2) => DebugSubcommand::Object(key.to_string()),
+                (_, 2) => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Wrong number of arguments specified for 'DEBUG OBJECT' command",
+                    )));
+                }
+            },
+            "protocol" => match (args.get(1), args.len()) {
+                (Some(RespType::BulkString(name)), 2)
+                    if PROTOCOL_TYPES.contains(&name.to_lowercase().as_str()) =>
+                {
+                    DebugSubcommand::Protocol(name.to_lowercase())
+                }
+                (_, 2) => {
+                    return Err(CommandError::Other(format!(
+                        "Wrong protocol type name. Please use one of the following: {}",
+                        PROTOCOL_TYPES.join("|")
+                    )));
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Wrong number of arguments specified for 'DEBUG PROTOCOL' command",
+                    )));
+                }
+            },
+            "stringmatch-len" => match (args.get(1), args.get(2), args.len()) {
+                (Some(RespType::BulkString(pattern)), Some(RespType::BulkString(string)), 3) => {
+                    DebugSubcommand::StringMatchLen {
+                        pattern: pattern.to_string(),
+                        string: string.to_string(),
+                    }
+                }
+                (_, _, 3) => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Pattern and string must be bulk strings",
+                    )));
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Wrong number of arguments specified for 'DEBUG STRINGMATCH-LEN' command",
+                    )));
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'DEBUG' command",
+                    subcommand
+                )));
+            }
+        };
+
+        Ok(DebugCommand { subcommand })
+    }
+
+    /// Executes the DEBUG command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the database and the server state.
+    ///
+    /// # Returns
+    ///
+    /// - `DEBUG RELOAD` - "OK" as a `SimpleString` once the dataset is serialized into a
+    ///   dump and replaced by the one loaded back from the dump. The dump is the one sent to
+    ///   the replicas during a full resync, so this checks both ends of it agree.
+    /// - `DEBUG POPULATE` - "OK" as a `SimpleString` once the keys `<prefix>0` to
+    ///   `<prefix><count - 1>` are set to the values `value:0` to `value:<count - 1>`.
+    ///   Keys which already exist are left untouched.
+    /// - `DEBUG SLEEP` - "OK" as a `SimpleString` after blocking the current thread for
+    ///   the given duration, simulating a long-running command.
+    /// - `DEBUG STRINGMATCH-LEN` - `1` as an `Integer` if the glob-style pattern matches the
+    ///   whole string, `0` otherwise. It exposes the matcher used for key and parameter patterns.
+    /// - `DEBUG CHANGE-REPL-ID` - "OK" as a `SimpleString` once the run id reported by INFO is
+    ///   regenerated, so that clients see the server as restarted.
+    /// - `DEBUG OBJECT` - A `SimpleString` of space separated `field:value` pairs describing
+    ///   how the value of the key is stored, or a `SimpleError` if the key doesn't exist.
+    ///   Lists also report their quicklist layout, which is always a single node as they're
+    ///   stored in one contiguous buffer.
+    /// - `DEBUG PROTOCOL` - A sample reply of the given type, to test how clients decode it.
+    ///   The RESP3 types are sent as their RESP2 equivalent to RESP2 clients, and the
+    ///   attributes of an `attrib` reply are left out.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        match &self.subcommand {
+            DebugSubcommand::Reload => match reload(storage) {
+                Ok(_) => RespType::SimpleString(String::from("OK")),
+                Err(e) => RespType::SimpleError(e),
+            },
+            DebugSubcommand::Populate { count, prefix } => {
+                match storage.db().populate(*count, prefix) {
+                    Ok(_) => RespType::SimpleString(String::from("OK")),
+                    Err(e) => RespType::SimpleError(format!("{}", e)),
+                }
+            }
+            DebugSubcommand::Sleep(duration) => {
+                std::thread::sleep(*duration);
+                RespType::SimpleString(String::from("OK"))
+            }
+            DebugSubcommand::StringMatchLen { pattern, string } => {
+                RespType::Integer(glob::matches(pattern, string) as i64)
+            }
+            DebugSubcommand::ChangeReplId => {
+                storage.change_run_id();
+                RespType::SimpleString(String::from("OK"))
+            }
+            DebugSubcommand::Object(key) => match storage.db().object_info(key) {
+                Ok(Some(info)) => {
+                    let mut reply = format!(
+                        "refcount:1 encoding:{} serializedlength:{} lru_seconds_idle:{}",
+                        info.encoding, info.serialized_length, info.idle_seconds
+                    );
+                    if info.encoding == "quicklist" {
+                        reply.push_str(&format!(
+                            " ql_nodes:{} ql_avg_node:{:.2} ql_listpack_max:{} ql_compressed:0 \
+                             ql_uncompressed_size:{}",
+                            (info.len > 0) as usize,
+                            info.len as f64,
+                            info.len,
+                            info.serialized_length
+                        ));
+                    }
+                    RespType::SimpleString(reply)
+                }
+                Ok(None) => RespType::SimpleError(String::from("no such key")),
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            DebugSubcommand::Protocol(name) => protocol_reply(name),
+        }
+    }
+
+    /// Checks if the subcommand modifies the data stored in the DB, which only POPULATE does.
+    pub fn is_write(&self) -> bool {
+        matches!(self.subcommand, DebugSubcommand::Populate { .. })
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    ///
+    /// Only POPULATE modifies the DB. It's propagated as it is, since it only adds the keys
+    /// which don't exist yet, with values derived from their names, so the replicas end up
+    /// with the same keys as the master.
+    pub fn build_command(&self) -> Option<RespType> {
+        match &self.subcommand {
+            DebugSubcommand::Populate { count, prefix } => Some(RespType::Array(vec![
+                RespType::BulkString(String::from("DEBUG")),
+                RespType::BulkString(String::from("POPULATE")),
+                RespType::BulkString(count.to_string()),
+                RespType::BulkString(prefix.clone()),
+            ])),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the sample reply of DEBUG PROTOCOL for the given type, the same as Redis sends.
+fn protocol_reply(name: &str) -> RespType {
+    let integers = || (0..3).map(RespType::Integer).collect();
+    match name {
+        "string" => RespType::BulkString(String::from("Hello World")),
+        "integer" => RespType::Integer(12345),
+        "array" => RespType::Array(integers()),
+        "set" => RespType::Set(integers()),
+        "verbatim" => RespType::VerbatimString {
+            format: *b"txt",
+            data: String::from("This is a verbatim\nstring"),
+        },
+        "attrib" => RespType::Attribute {
+            attributes: vec![(
+                RespType::BulkString(String::from("key-popularity")),
+                RespType::Array(vec![
+                    RespType::BulkString(String::from("key:123")),
+                    RespType::Integer(90),
+                ]),
+            )],
+            reply: Box::new(RespType::BulkString(String::from(
+                "Some real reply following the attribute",
+            ))),
+        },
+        _ => RespType::NullBulkString,
+    }
+}
+
+/// Serializes the dataset into a dump, and replaces it with the dataset loaded back from
+/// the dump.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the dataset was reloaded.
+/// * `Err(String)` - The error message, if the dataset couldn't be dumped or loaded.
+fn reload(storage: &Storage) -> Result<(), String> {
+    // Writes are blocked meanwhile, as the ones applied after the dump would be lost.
+    let _guard = storage.replication().lock_writes();
+
+    let db = storage.db();
+    let mut dump = replication::dump(&db).map_err(|e| format!("{}", e))?;
+    db.flush().map_err(|e| format!("{}", e))?;
+
+    // The commands are applied directly, so that they aren't propagated to the replicas,
+    // which already have the same dataset.
+    let mut codec = RespCommandFrame::new();
+    while let Some(frame) = codec.decode(&mut dump).map_err(|e| format!("{}", e))? {
+        let frame = frame.map_err(|e| format!("{}", e))?;
+        let cmd = Command::from_resp_command_frame(frame).map_err(|e| format!("{}", e))?;
+        if let RespType::SimpleError(e) = cmd.apply(storage) {
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+// src/command/del.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the DEL command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Del {
+    keys: Vec<String>,
+}
+
+impl Del {
+    /// Creates a new `Del` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the DEL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Del)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Del, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'DEL' command",
+            )));
+        }
+
+        // parse keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(Del { keys })
+    }
+
+    /// Executes the DEL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of keys that were removed as an `Integer`. A collection counts as a single
+    /// key, however many elements it holds.
+    ///
+    /// The values are freed once the keys are removed from the DB, so that other connections
+    /// don't wait on it. Use UNLINK to free them in the background instead.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.remove(&self.keys) {
+            Ok(removed) => RespType::Integer(removed.len() as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.clone()
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args: Vec<RespType> = vec![RespType::BulkString(String::from("DEL"))];
+
+        for key in self.keys.iter() {
+            args.push(RespType::BulkString(key.to_string()));
+        }
+
+        RespType::Array(args)
+    }
+}
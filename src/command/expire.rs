@@ -0,0 +1,278 @@
+// src/command/expire.rs
+
+use std::sync::OnceLock;
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{current_time_millis, ExpireCondition, DB},
+};
+
+use super::CommandError;
+
+/// Represents the EXPIRE command in Nimblecache.
+///
+/// The `Expire` struct is used to set a timeout (in seconds) on a key, optionally only
+/// if the condition given by one of the NX, XX, GT or LT options holds.
+#[derive(Debug, Clone)]
+pub struct Expire {
+    key: String,
+    /// Timeout in seconds.
+    seconds: i64,
+    /// Conditions which must all hold for the timeout to be set.
+    conditions: Vec<ExpireCondition>,
+    /// The expiry time computed when the command is applied, so that the same time is
+    /// propagated to the replicas.
+    expires_at: OnceLock<Option<u64>>,
+}
+
+impl Expire {
+    /// Creates a new `Expire` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the EXPIRE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Expire)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Expire, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'EXPIRE' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse timeout
+        let seconds = match &args[1] {
+            RespType::BulkString(s) => match s.parse::<i64>() {
+                Ok(s) => s,
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "Timeout should be an integer",
+                    )));
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Timeout must be an integer in bulk string format",
+                )));
+            }
+        };
+
+        // parse options
+        let conditions = parse_conditions(&args[2..])?;
+
+        Ok(Expire {
+            key,
+            seconds,
+            conditions,
+            expires_at: OnceLock::new(),
+        })
+    }
+
+    /// Executes the EXPIRE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` - If the timeout was set.
+    /// - `Integer(0)` - If the key doesn't exist, or the condition given by the options doesn't hold.
+    /// - `SimpleError` - If the expiry time overflows, or an error is encountered.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let expires_at = match self.expires_at() {
+            Some(expires_at) => expires_at,
+            None => {
+                return RespType::SimpleError(String::from(
+                    "invalid expire time in 'expire' command",
+                ));
+            }
+        };
+
+        match db.expire(&self.key, expires_at, &self.conditions) {
+            Ok(true) => RespType::Integer(1),
+            Ok(false) => RespType::Integer(0),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Returns the time at which the key expires, as a Unix timestamp in milliseconds, or
+    /// `None` if it overflows. An expiry time in the past deletes the key.
+    ///
+    /// It's computed from the current time the first time it's called, and the same time is
+    /// returned afterwards.
+    fn expires_at(&self) -> Option<u64> {
+        *self.expires_at.get_or_init(|| {
+            self.seconds
+                .checked_mul(1000)
+                .and_then(|ms| ms.checked_add(current_time_millis() as i64))
+                .map(|expires_at| expires_at.max(0) as u64)
+        })
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    ///
+    /// The timeout is relative to the time the command is applied, so it's propagated as
+    /// PEXPIREAT with the absolute expiry time instead. Otherwise the key would expire later
+    /// on the replicas, by however long the command took to reach them. The expiry time is
+    /// the one computed when the command was applied.
+    pub fn build_command(&self) -> RespType {
+        let expires_at = self.expires_at().unwrap_or(0);
+        let mut args = vec![
+            RespType::BulkString(String::from("PEXPIREAT")),
+            RespType::BulkString(self.key.clone()),
+            RespType::BulkString(expires_at.to_string()),
+        ];
+
+        args.extend(condition_args(&self.conditions));
+
+        RespType::Array(args)
+    }
+}
+
+/// Parses the NX, XX, GT and LT options of the EXPIRE family of commands.
+///
+/// # Arguments
+///
+/// * `args` - The arguments following the key and the expiry time.
+///
+/// # Returns
+///
+/// * `Ok(Vec<ExpireCondition>)` - The conditions which must all hold for the expiry to be set.
+/// * `Err(CommandError)` - If an option is unknown, or the options aren't compatible.
+pub(super) fn parse_conditions(args: &[RespType]) -> Result<Vec<ExpireCondition>, CommandError> {
+    let mut conditions: Vec<ExpireCondition> = vec![];
+    for arg in args.iter() {
+        let option = match arg {
+            RespType::BulkString(o) => o,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Option must be a bulk string",
+                )));
+            }
+        };
+
+        let condition = match option.to_lowercase().as_str() {
+            "nx" => ExpireCondition::Nx,
+            "xx" => ExpireCondition::Xx,
+            "gt" => ExpireCondition::Gt,
+            "lt" => ExpireCondition::Lt,
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unsupported option {}",
+                    option
+                )));
+            }
+        };
+        if !conditions.contains(&condition) {
+            conditions.push(condition);
+        }
+    }
+
+    let nx = conditions.contains(&ExpireCondition::Nx);
+    let gt = conditions.contains(&ExpireCondition::Gt);
+    let lt = conditions.contains(&ExpireCondition::Lt);
+    if nx && conditions.len() > 1 {
+        return Err(CommandError::Other(String::from(
+            "NX and XX, GT or LT options at the same time are not compatible",
+        )));
+    }
+    if gt && lt {
+        return Err(CommandError::Other(String::from(
+            "GT and LT options at the same time are not compatible",
+        )));
+    }
+
+    Ok(conditions)
+}
+
+/// Builds the options of the EXPIRE family of commands from the given conditions.
+pub(super) fn condition_args(conditions: &[ExpireCondition]) -> Vec<RespType> {
+    conditions
+        .iter()
+        .map(|condition| {
+            let option = match condition {
+                ExpireCondition::Nx => "NX",
+                ExpireCondition::Xx => "XX",
+                ExpireCondition::Gt => "GT",
+                ExpireCondition::Lt => "LT",
+            };
+            RespType::BulkString(String::from(option))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::db::{freeze_time, Value};
+
+    use super::*;
+
+    fn expire(args: &[&str]) -> Expire {
+        let args = args.iter().map(|arg| RespType::BulkString(arg.to_string())).collect();
+        Expire::with_args(args).unwrap()
+    }
+
+    /// Returns the arguments of the command built for the replicas.
+    fn propagated(expire: &Expire) -> Vec<String> {
+        match expire.build_command() {
+            RespType::Array(args) => args
+                .into_iter()
+                .map(|arg| match arg {
+                    RespType::BulkString(arg) => arg,
+                    arg => panic!("unexpected argument: {:?}", arg),
+                })
+                .collect(),
+            cmd => panic!("unexpected command: {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn expire_is_propagated_as_pexpireat_with_the_applied_expiry_time() {
+        let db = DB::new();
+        db.set(String::from("k"), Value::String(String::from("v")), None, false, None)
+            .unwrap();
+
+        let expire = expire(&["k", "100", "NX"]);
+        let expires_at = {
+            let _frozen = freeze_time();
+            assert!(matches!(expire.apply(&db), RespType::Integer(1)));
+            current_time_millis() + 100_000
+        };
+        assert_eq!(db.expiry("k").unwrap(), Some(Some(expires_at)));
+
+        // The expiry time doesn't move with the time the command is propagated at.
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert_eq!(
+            propagated(&expire),
+            ["PEXPIREAT", "k", &expires_at.to_string(), "NX"]
+        );
+    }
+
+    #[test]
+    fn expiry_times_in_the_past_are_propagated_as_is() {
+        let expire = expire(&["k", "-10"]);
+        let _frozen = freeze_time();
+        let expires_at = current_time_millis() - 10_000;
+        assert_eq!(propagated(&expire), ["PEXPIREAT", "k", &expires_at.to_string()]);
+    }
+}
@@ -0,0 +1,128 @@
+// src/command/expireat.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ExpireCondition, DB},
+};
+
+use super::{
+    expire::{condition_args, parse_conditions},
+    CommandError,
+};
+
+/// Represents the EXPIREAT command in Nimblecache.
+///
+/// The `ExpireAt` struct is used to set the absolute time (as a Unix timestamp in seconds)
+/// at which a key expires, optionally only if the condition given by one of the NX, XX,
+/// GT or LT options holds.
+#[derive(Debug, Clone)]
+pub struct ExpireAt {
+    key: String,
+    /// Expiry time as a Unix timestamp in seconds.
+    timestamp: i64,
+    /// Conditions which must all hold for the expiry to be set.
+    conditions: Vec<ExpireCondition>,
+}
+
+impl ExpireAt {
+    /// Creates a new `ExpireAt` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the EXPIREAT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExpireAt)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ExpireAt, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'EXPIREAT' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse timestamp
+        let timestamp = match &args[1] {
+            RespType::BulkString(t) => match t.parse::<i64>() {
+                Ok(t) => t,
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "Timestamp should be an integer",
+                    )));
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Timestamp must be an integer in bulk string format",
+                )));
+            }
+        };
+
+        // parse options
+        let conditions = parse_conditions(&args[2..])?;
+
+        Ok(ExpireAt {
+            key,
+            timestamp,
+            conditions,
+        })
+    }
+
+    /// Executes the EXPIREAT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` - If the expiry was set.
+    /// - `Integer(0)` - If the key doesn't exist, or the condition given by the options doesn't hold.
+    /// - `SimpleError` - If the expiry time overflows, or an error is encountered.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let expires_at = match self.timestamp.checked_mul(1000) {
+            // An expiry time in the past deletes the key.
+            Some(expires_at) => expires_at.max(0) as u64,
+            None => {
+                return RespType::SimpleError(String::from(
+                    "invalid expire time in 'expireat' command",
+                ));
+            }
+        };
+
+        match db.expire(&self.key, expires_at, &self.conditions) {
+            Ok(true) => RespType::Integer(1),
+            Ok(false) => RespType::Integer(0),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args = vec![
+            RespType::BulkString(String::from("EXPIREAT")),
+            RespType::BulkString(self.key.clone()),
+            RespType::BulkString(self.timestamp.to_string()),
+        ];
+        args.extend(condition_args(&self.conditions));
+
+        RespType::Array(args)
+    }
+}
@@ -0,0 +1,71 @@
+// src/command/expiretime.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the EXPIRETIME command in Nimblecache.
+///
+/// The `ExpireTime` struct is used to retrieve the absolute time at which a key expires,
+/// as a Unix timestamp in seconds.
+#[derive(Debug, Clone)]
+pub struct ExpireTime {
+    key: String,
+}
+
+impl ExpireTime {
+    /// Creates a new `ExpireTime` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the EXPIRETIME command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExpireTime)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ExpireTime, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'EXPIRETIME' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(ExpireTime { key })
+    }
+
+    /// Executes the EXPIRETIME command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(-2)` - If the key doesn't exist.
+    /// - `Integer(-1)` - If the key exists, but has no expiry.
+    /// - The expiry time as a Unix timestamp in seconds as an `Integer`, otherwise.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.expiry(&self.key) {
+            Ok(None) => RespType::Integer(-2),
+            Ok(Some(None)) => RespType::Integer(-1),
+            Ok(Some(Some(expires_at))) => RespType::Integer((expires_at / 1000) as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
@@ -0,0 +1,84 @@
+// src/command/flushall.rs
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::CommandError;
+
+/// Represents the FLUSHALL command in Nimblecache.
+///
+/// FLUSHALL removes every key. Nimblecache has a single database, so it's the same as
+/// emptying that database.
+#[derive(Debug, Clone)]
+pub struct FlushAll {
+    /// Set by `ASYNC`. The removed values are then freed in the background.
+    lazy: bool,
+}
+
+impl FlushAll {
+    /// Creates a new `FlushAll` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `[ASYNC | SYNC]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the FLUSHALL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FlushAll)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<FlushAll, CommandError> {
+        match args.as_slice() {
+            [] => Ok(FlushAll { lazy: false }),
+            [RespType::BulkString(mode)] if mode.eq_ignore_ascii_case("async") => {
+                Ok(FlushAll { lazy: true })
+            }
+            [RespType::BulkString(mode)] if mode.eq_ignore_ascii_case("sync") => {
+                Ok(FlushAll { lazy: false })
+            }
+            _ => Err(CommandError::Other(String::from("syntax error"))),
+        }
+    }
+
+    /// Executes the FLUSHALL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the database along with the client-side
+    ///   caching and WATCH state of the keys.
+    ///
+    /// # Returns
+    ///
+    /// "OK" as a `SimpleString` once every key is removed. With `ASYNC`, the values are freed
+    /// on a background thread, so that it doesn't hold up the connection.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        let removed = match storage.db().flush() {
+            Ok(removed) => removed,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        // The command has no keys of its own, so every cached or watched key is dropped here.
+        storage.tracking().invalidate_all();
+        storage.watches().touch_all();
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if self.lazy => {
+                handle.spawn_blocking(move || drop(removed));
+            }
+            _ => drop(removed),
+        }
+
+        RespType::SimpleString(String::from("OK"))
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args = vec![RespType::BulkString(String::from("FLUSHALL"))];
+        if self.lazy {
+            args.push(RespType::BulkString(String::from("ASYNC")));
+        }
+
+        RespType::Array(args)
+    }
+}
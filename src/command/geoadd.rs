@@ -0,0 +1,124 @@
+// src/command/geoadd.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{geohash, CommandError};
+
+/// Represents the GEOADD command in Nimblecache.
+///
+/// The `GeoAdd` struct is used to add members with their coordinates to a sorted set.
+/// The coordinates are stored as the score of the member, encoded into a geohash.
+#[derive(Debug, Clone)]
+pub struct GeoAdd {
+    key: String,
+    /// The members, along with their longitude and latitude.
+    members: Vec<(String, f64, f64)>,
+}
+
+impl GeoAdd {
+    /// Creates a new `GeoAdd` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key longitude latitude member [longitude latitude member ...]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GEOADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GeoAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GeoAdd, CommandError> {
+        if args.len() < 4 || !(args.len() - 1).is_multiple_of(3) {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'GEOADD' command",
+            )));
+        }
+
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        // parse longitude, latitude and member triples
+        let mut members: Vec<(String, f64, f64)> = vec![];
+        for triple in parsed[1..].chunks(3) {
+            let (lon, lat) = match (triple[0].parse::<f64>(), triple[1].parse::<f64>()) {
+                (Ok(lon), Ok(lat)) => (lon, lat),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "value is not a valid float",
+                    )))
+                }
+            };
+
+            if !(geohash::LON_MIN..=geohash::LON_MAX).contains(&lon)
+                || !(geohash::LAT_MIN..=geohash::LAT_MAX).contains(&lat)
+            {
+                return Err(CommandError::Other(format!(
+                    "invalid longitude,latitude pair {:.6},{:.6}",
+                    lon, lat
+                )));
+            }
+
+            members.push((triple[2].to_string(), lon, lat));
+        }
+
+        Ok(GeoAdd {
+            key: parsed[0].to_string(),
+            members,
+        })
+    }
+
+    /// Executes the GEOADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of members that were added, not including the members whose
+    /// coordinates were updated.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let members = self
+            .members
+            .iter()
+            .map(|(member, lon, lat)| (member.to_string(), geohash::encode(*lon, *lat) as f64))
+            .collect();
+
+        match db.zadd(&self.key, members, &[]) {
+            Ok((added, _)) => RespType::Integer(added as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args: Vec<RespType> = vec![
+            RespType::BulkString(String::from("GEOADD")),
+            RespType::BulkString(self.key.clone()),
+        ];
+
+        for (member, lon, lat) in self.members.iter() {
+            args.push(RespType::BulkString(lon.to_string()));
+            args.push(RespType::BulkString(lat.to_string()));
+            args.push(RespType::BulkString(member.to_string()));
+        }
+
+        RespType::Array(args)
+    }
+}
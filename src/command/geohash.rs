@@ -0,0 +1,74 @@
+// src/command/geohash.rs
+
+/// Minimum longitude which can be indexed.
+pub const LON_MIN: f64 = -180.0;
+/// Maximum longitude which can be indexed.
+pub const LON_MAX: f64 = 180.0;
+/// Minimum latitude which can be indexed. Areas closer to the poles are left out, same as
+/// the Web Mercator projection.
+pub const LAT_MIN: f64 = -85.05112878;
+/// Maximum latitude which can be indexed.
+pub const LAT_MAX: f64 = 85.05112878;
+
+/// Number of bits used for each of longitude and latitude. The resulting 52 bit hash
+/// fits in the mantissa of the score, so that it's stored without loss.
+const STEP: u32 = 26;
+
+/// Radius of the earth in meters, as used by Redis for distance calculations.
+const EARTH_RADIUS: f64 = 6372797.560856;
+
+/// Encodes the coordinates into a geohash, by interleaving the bits of the latitude
+/// (at even positions) and the longitude (at odd positions).
+///
+/// Refer <https://en.wikipedia.org/wiki/Geohash>
+pub fn encode(lon: f64, lat: f64) -> u64 {
+    let scale = (1u64 << STEP) as f64;
+    let lat_offset = ((lat - LAT_MIN) / (LAT_MAX - LAT_MIN) * scale) as u64;
+    let lon_offset = ((lon - LON_MIN) / (LON_MAX - LON_MIN) * scale) as u64;
+
+    // The maximum coordinates would overflow the available bits.
+    let max_offset = (1 << STEP) - 1;
+    spread(lat_offset.min(max_offset)) | (spread(lon_offset.min(max_offset)) << 1)
+}
+
+/// Decodes a geohash into the coordinates at the center of the area it covers,
+/// as `(longitude, latitude)`.
+pub fn decode(hash: u64) -> (f64, f64) {
+    let scale = (1u64 << STEP) as f64;
+    let lat_offset = squash(hash) as f64;
+    let lon_offset = squash(hash >> 1) as f64;
+
+    let lat = LAT_MIN + (lat_offset + 0.5) / scale * (LAT_MAX - LAT_MIN);
+    let lon = LON_MIN + (lon_offset + 0.5) / scale * (LON_MAX - LON_MIN);
+
+    (lon.clamp(LON_MIN, LON_MAX), lat.clamp(LAT_MIN, LAT_MAX))
+}
+
+/// Returns the distance in meters between two points on the earth, using the haversine formula.
+pub fn distance(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let u = ((lat2 - lat1) / 2.0).sin();
+    let v = ((lon2 - lon1).to_radians() / 2.0).sin();
+
+    2.0 * EARTH_RADIUS * (u * u + lat1.cos() * lat2.cos() * v * v).sqrt().asin()
+}
+
+/// Spreads the lower 32 bits of the value to the even bit positions.
+fn spread(v: u64) -> u64 {
+    let mut v = v & 0xFFFFFFFF;
+    v = (v | (v << 16)) & 0x0000FFFF0000FFFF;
+    v = (v | (v << 8)) & 0x00FF00FF00FF00FF;
+    v = (v | (v << 4)) & 0x0F0F0F0F0F0F0F0F;
+    v = (v | (v << 2)) & 0x3333333333333333;
+    (v | (v << 1)) & 0x5555555555555555
+}
+
+/// Collects the bits at the even positions of the value. It's the inverse of `spread`.
+fn squash(v: u64) -> u64 {
+    let mut v = v & 0x5555555555555555;
+    v = (v | (v >> 1)) & 0x3333333333333333;
+    v = (v | (v >> 2)) & 0x0F0F0F0F0F0F0F0F;
+    v = (v | (v >> 4)) & 0x00FF00FF00FF00FF;
+    v = (v | (v >> 8)) & 0x0000FFFF0000FFFF;
+    (v | (v >> 16)) & 0xFFFFFFFF
+}
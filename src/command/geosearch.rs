@@ -0,0 +1,196 @@
+// src/command/geosearch.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{geohash, CommandError};
+
+/// Represents the GEOSEARCH command in Nimblecache.
+///
+/// The `GeoSearch` struct is used to find the members of a sorted set populated by
+/// GEOADD, which are within a radius of the given coordinates.
+#[derive(Debug, Clone)]
+pub struct GeoSearch {
+    key: String,
+    lon: f64,
+    lat: f64,
+    /// Radius in meters.
+    radius: f64,
+    /// Order of the results by distance from the center. `None` if they are not sorted.
+    order: Option<Order>,
+}
+
+/// The order in which the results are sorted.
+#[derive(Debug, Clone, Copy)]
+enum Order {
+    Asc,
+    Desc,
+}
+
+impl GeoSearch {
+    /// Creates a new `GeoSearch` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key FROMLONLAT longitude latitude BYRADIUS radius M|KM|FT|MI [ASC|DESC]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GEOSEARCH command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GeoSearch)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GeoSearch, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'GEOSEARCH' command",
+            )));
+        }
+
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let mut center: Option<(f64, f64)> = None;
+        let mut radius: Option<f64> = None;
+        let mut order: Option<Order> = None;
+
+        let mut i = 1;
+        while i < parsed.len() {
+            match parsed[i].to_lowercase().as_str() {
+                "fromlonlat" if i + 2 < parsed.len() => {
+                    let lon = Self::parse_float(parsed[i + 1])?;
+                    let lat = Self::parse_float(parsed[i + 2])?;
+                    if !(geohash::LON_MIN..=geohash::LON_MAX).contains(&lon)
+                        || !(geohash::LAT_MIN..=geohash::LAT_MAX).contains(&lat)
+                    {
+                        return Err(CommandError::Other(format!(
+                            "invalid longitude,latitude pair {:.6},{:.6}",
+                            lon, lat
+                        )));
+                    }
+                    center = Some((lon, lat));
+                    i += 3;
+                }
+                "byradius" if i + 2 < parsed.len() => {
+                    let r = Self::parse_float(parsed[i + 1])?;
+                    if r < 0.0 {
+                        return Err(CommandError::Other(String::from(
+                            "radius cannot be negative",
+                        )));
+                    }
+                    radius = Some(r * Self::parse_unit(parsed[i + 2])?);
+                    i += 3;
+                }
+                "asc" => {
+                    order = Some(Order::Asc);
+                    i += 1;
+                }
+                "desc" => {
+                    order = Some(Order::Desc);
+                    i += 1;
+                }
+                _ => return Err(CommandError::Other(String::from("syntax error"))),
+            }
+        }
+
+        let (lon, lat) = match center {
+            Some(center) => center,
+            None => {
+                return Err(CommandError::Other(String::from(
+                    "exactly one of FROMMEMBER or FROMLONLAT can be specified for GEOSEARCH",
+                )))
+            }
+        };
+
+        let radius = match radius {
+            Some(radius) => radius,
+            None => {
+                return Err(CommandError::Other(String::from(
+                    "exactly one of BYRADIUS and BYBOX can be specified for GEOSEARCH",
+                )))
+            }
+        };
+
+        Ok(GeoSearch {
+            key: parsed[0].to_string(),
+            lon,
+            lat,
+            radius,
+            order,
+        })
+    }
+
+    /// Parses a coordinate or a radius.
+    fn parse_float(s: &str) -> Result<f64, CommandError> {
+        match s.parse::<f64>() {
+            Ok(f) if f.is_finite() => Ok(f),
+            _ => Err(CommandError::Other(String::from(
+                "value is not a valid float",
+            ))),
+        }
+    }
+
+    /// Parses a unit of distance, and returns the number of meters in it.
+    fn parse_unit(unit: &str) -> Result<f64, CommandError> {
+        match unit.to_lowercase().as_str() {
+            "m" => Ok(1.0),
+            "km" => Ok(1000.0),
+            "ft" => Ok(0.3048),
+            "mi" => Ok(1609.34),
+            _ => Err(CommandError::Other(String::from(
+                "unsupported unit provided. please use M, KM, FT, MI",
+            ))),
+        }
+    }
+
+    /// Executes the GEOSEARCH command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` of the members within the radius. It's empty if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let members = match db.zmembers(&self.key) {
+            Ok(members) => members,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let mut matches: Vec<(String, f64)> = members
+            .into_iter()
+            .filter_map(|(member, score)| {
+                let (lon, lat) = geohash::decode(score as u64);
+                let dist = geohash::distance(self.lon, self.lat, lon, lat);
+                (dist <= self.radius).then_some((member, dist))
+            })
+            .collect();
+
+        match self.order {
+            Some(Order::Asc) => matches.sort_by(|a, b| a.1.total_cmp(&b.1)),
+            Some(Order::Desc) => matches.sort_by(|a, b| b.1.total_cmp(&a.1)),
+            None => {}
+        }
+
+        RespType::Array(
+            matches
+                .into_iter()
+                .map(|(member, _)| RespType::BulkString(member))
+                .collect(),
+        )
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
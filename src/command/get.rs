@@ -29,7 +29,7 @@ impl Get {
     /// * `Ok(Get)` - If parsing succeeds and the key is valid.
     /// * `Err(CommandError)` - if parsing fails due to validation errors.
     pub fn with_args(args: Vec<RespType>) -> Result<Get, CommandError> {
-        if args.len() < 1 {
+        if args.is_empty() {
             return Err(CommandError::Other(String::from(
                 "Wrong number of arguments specified for 'GET' command",
             )));
@@ -69,4 +69,9 @@ impl Get {
             Err(e) => RespType::SimpleError(format!("{}", e)),
         }
     }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
 }
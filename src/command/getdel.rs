@@ -0,0 +1,85 @@
+// src/command/getdel.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the GETDEL command in Nimblecache.
+///
+/// The `GetDel` struct is used to retrieve the value associated with a specified key,
+/// and delete the key.
+#[derive(Debug, Clone)]
+pub struct GetDel {
+    key: String,
+}
+
+impl GetDel {
+    /// Creates a new `GetDel` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GETDEL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GetDel)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GetDel, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'GETDEL' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(GetDel { key })
+    }
+
+    /// Executes the GETDEL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - If key is present in DB - Value of the key as a `BulkString`, once the key is deleted
+    /// - If key is not found in DB - A `NullBulkString`
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.getdel(&self.key) {
+            Ok(Some(s)) => RespType::BulkString(s),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    ///
+    /// Replicas don't need the value, so the command is propagated as DEL, and only if the
+    /// key was deleted.
+    pub fn build_command(&self, res: &RespType) -> Option<RespType> {
+        match res {
+            RespType::BulkString(_) => Some(RespType::Array(vec![
+                RespType::BulkString(String::from("DEL")),
+                RespType::BulkString(self.key.clone()),
+            ])),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+// src/command/getex.rs
+
+use std::sync::OnceLock;
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{current_time_millis, DB},
+};
+
+use super::CommandError;
+
+/// Represents the GETEX command in Nimblecache.
+///
+/// The `GetEx` struct is used to retrieve the value associated with a specified key,
+/// optionally updating the expiry of the key.
+#[derive(Debug, Clone)]
+pub struct GetEx {
+    key: String,
+    /// The update to the expiry of the key. The expiry is left as it is if `None`.
+    expiry: Option<GetExExpiry>,
+    /// The expiry time computed when the command is applied, so that the same time is
+    /// propagated to the replicas.
+    expires_at: OnceLock<Option<u64>>,
+}
+
+/// The expiry options of the GETEX command.
+#[derive(Debug, Clone, Copy)]
+enum GetExExpiry {
+    /// EX seconds or PX milliseconds, relative to the time the command is applied.
+    In(i64),
+    /// EXAT timestamp or PXAT timestamp, as a Unix timestamp in milliseconds.
+    At(i64),
+    /// PERSIST
+    Persist,
+}
+
+impl GetEx {
+    /// Creates a new `GetEx` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form
+    /// `key [EX seconds | PX milliseconds | EXAT timestamp | PXAT timestamp | PERSIST]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GETEX command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GetEx)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GetEx, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'GETEX' command",
+            )));
+        }
+
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let expiry = match &parsed[1..] {
+            [] => None,
+            [option] if option.eq_ignore_ascii_case("persist") => Some(GetExExpiry::Persist),
+            [option, time] => {
+                let time = match time.parse::<i64>() {
+                    Ok(time) => time,
+                    Err(_) => {
+                        return Err(CommandError::Other(String::from(
+                            "value is not an integer or out of range",
+                        )));
+                    }
+                };
+
+                let expiry = match option.to_lowercase().as_str() {
+                    "ex" => time.checked_mul(1000).map(GetExExpiry::In),
+                    "px" => Some(GetExExpiry::In(time)),
+                    "exat" => time.checked_mul(1000).map(GetExExpiry::At),
+                    "pxat" => Some(GetExExpiry::At(time)),
+                    _ => return Err(CommandError::Other(String::from("syntax error"))),
+                };
+
+                match expiry {
+                    Some(expiry) if time > 0 => Some(expiry),
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "invalid expire time in 'getex' command",
+                        )));
+                    }
+                }
+            }
+            _ => return Err(CommandError::Other(String::from("syntax error"))),
+        };
+
+        Ok(GetEx {
+            key: parsed[0].to_string(),
+            expiry,
+            expires_at: OnceLock::new(),
+        })
+    }
+
+    /// Checks if the command changes the expiry of the key. Without options, GETEX only
+    /// reads the value like GET.
+    pub fn updates_expiry(&self) -> bool {
+        self.expiry.is_some()
+    }
+
+    /// Returns the new expiry time of the key as a Unix timestamp in milliseconds, or `None`
+    /// if the expiry is removed or it overflows. It's computed the first time it's called,
+    /// and the same time is returned afterwards.
+    fn expires_at(&self) -> Option<u64> {
+        *self.expires_at.get_or_init(|| match self.expiry {
+            Some(GetExExpiry::In(ms)) => ms
+                .checked_add(current_time_millis() as i64)
+                .map(|expires_at| expires_at as u64),
+            Some(GetExExpiry::At(ms)) => Some(ms as u64),
+            Some(GetExExpiry::Persist) | None => None,
+        })
+    }
+
+    /// Executes the GETEX command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - If key is present in DB - Value of the key as a `BulkString`, once its expiry is updated
+    /// - If key is not found in DB - A `NullBulkString`
+    /// - If an error is encountered - A `SimpleError` with an error message
+    pub fn apply(&self, db: &DB) -> RespType {
+        let res = match self.expiry {
+            None => db.get(&self.key),
+            Some(GetExExpiry::Persist) => db.getex(&self.key, None),
+            Some(_) => match self.expires_at() {
+                Some(expires_at) => db.getex(&self.key, Some(expires_at)),
+                None => {
+                    return RespType::SimpleError(String::from(
+                        "invalid expire time in 'getex' command",
+                    ));
+                }
+            },
+        };
+
+        match res {
+            Ok(Some(s)) => RespType::BulkString(s),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    ///
+    /// Replicas don't need the value, so only the change to the expiry is propagated, and
+    /// only if the key exists. It's propagated as PERSIST, or as PEXPIREAT with the absolute
+    /// expiry time so that the key doesn't expire later on the replicas.
+    pub fn build_command(&self, res: &RespType) -> Option<RespType> {
+        if !matches!(res, RespType::BulkString(_)) {
+            return None;
+        }
+
+        let cmd = match self.expiry? {
+            GetExExpiry::Persist => vec![
+                RespType::BulkString(String::from("PERSIST")),
+                RespType::BulkString(self.key.clone()),
+            ],
+            _ => vec![
+                RespType::BulkString(String::from("PEXPIREAT")),
+                RespType::BulkString(self.key.clone()),
+                RespType::BulkString(self.expires_at().unwrap_or(0).to_string()),
+            ],
+        };
+
+        Some(RespType::Array(cmd))
+    }
+}
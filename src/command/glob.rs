@@ -0,0 +1,203 @@
+// src/command/glob.rs
+
+/// Checks if the whole string matches the glob-style pattern, as used by Redis for
+/// key and parameter patterns.
+///
+/// - `?` matches any single character.
+/// - `*` matches any number of characters, including none.
+/// - `[abc]` matches one of the given characters, `[a-c]` a range of characters, and
+///   `[^abc]` any character except the given ones.
+/// - `\` escapes the character following it, so that it's matched literally.
+pub fn matches(pattern: &str, s: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+
+    matches_chars(&pattern, &s)
+}
+
+fn matches_chars(pattern: &[char], s: &[char]) -> bool {
+    let (mut p, mut i) = (0, 0);
+
+    while p < pattern.len() {
+        match pattern[p] {
+            '*' => {
+                // Consecutive stars are the same as a single one.
+                while p + 1 < pattern.len() && pattern[p + 1] == '*' {
+                    p += 1;
+                }
+                if p + 1 == pattern.len() {
+                    return true;
+                }
+
+                return (i..=s.len()).any(|start| matches_chars(&pattern[p + 1..], &s[start..]));
+            }
+            '?' => {
+                if i >= s.len() {
+                    return false;
+                }
+                i += 1;
+            }
+            '[' => {
+                if i >= s.len() {
+                    return false;
+                }
+
+                let (matched, class_end) = match_class(pattern, p + 1, s[i]);
+                if !matched {
+                    return false;
+                }
+                p = class_end;
+                i += 1;
+            }
+            c => {
+                // An escaped character is matched literally.
+                let c = if c == '\\' && p + 1 < pattern.len() {
+                    p += 1;
+                    pattern[p]
+                } else {
+                    c
+                };
+
+                if i >= s.len() || s[i] != c {
+                    return false;
+                }
+                i += 1;
+            }
+        }
+
+        p += 1;
+    }
+
+    i == s.len()
+}
+
+/// Matches a character against the class which starts at `start`, right after the `[`.
+///
+/// # Returns
+///
+/// Whether the character matched, and the index of the `]` which ends the class. If the
+/// class is not terminated, it extends till the end of the pattern.
+fn match_class(pattern: &[char], start: usize, c: char) -> (bool, usize) {
+    let mut p = start;
+    let negate = p < pattern.len() && pattern[p] == '^';
+    if negate {
+        p += 1;
+    }
+
+    let mut matched = false;
+    while p < pattern.len() && pattern[p] != ']' {
+        if pattern[p] == '\\' && p + 1 < pattern.len() {
+            p += 1;
+            matched |= pattern[p] == c;
+        } else if p + 2 < pattern.len() && pattern[p + 1] == '-' && pattern[p + 2] != ']' {
+            let (low, high) = if pattern[p] <= pattern[p + 2] {
+                (pattern[p], pattern[p + 2])
+            } else {
+                (pattern[p + 2], pattern[p])
+            };
+            matched |= low <= c && c <= high;
+            p += 2;
+        } else {
+            matched |= pattern[p] == c;
+        }
+        p += 1;
+    }
+
+    let class_end = p.min(pattern.len() - 1);
+    (matched != negate, class_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches;
+
+    #[test]
+    fn literal_patterns_match_the_whole_string() {
+        assert!(matches("key", "key"));
+        assert!(matches("", ""));
+        assert!(!matches("key", "keys"));
+        assert!(!matches("keys", "key"));
+        assert!(!matches("key", "KEY"));
+    }
+
+    #[test]
+    fn star_matches_any_number_of_characters() {
+        assert!(matches("*", ""));
+        assert!(matches("*", "anything"));
+        assert!(matches("user:*", "user:"));
+        assert!(matches("user:*", "user:42"));
+        assert!(matches("*:42", "user:42"));
+        assert!(matches("u*r*2", "user:42"));
+        assert!(!matches("user:*", "users"));
+        assert!(!matches("*:42", "user:43"));
+    }
+
+    #[test]
+    fn consecutive_stars_are_collapsed() {
+        assert!(matches("**", ""));
+        assert!(matches("a***b", "ab"));
+        assert!(matches("a***b", "axxb"));
+        assert!(!matches("a***b", "axxc"));
+    }
+
+    #[test]
+    fn question_mark_matches_a_single_character() {
+        assert!(matches("h?llo", "hello"));
+        assert!(matches("h?llo", "hallo"));
+        assert!(!matches("h?llo", "hllo"));
+        assert!(!matches("h?llo", "heello"));
+        assert!(matches("???", "abc"));
+        assert!(!matches("???", "ab"));
+    }
+
+    #[test]
+    fn classes_match_one_of_their_characters() {
+        assert!(matches("h[ae]llo", "hello"));
+        assert!(matches("h[ae]llo", "hallo"));
+        assert!(!matches("h[ae]llo", "hillo"));
+        assert!(!matches("h[ae]llo", "hllo"));
+    }
+
+    #[test]
+    fn negated_classes_match_the_other_characters() {
+        assert!(matches("h[^e]llo", "hallo"));
+        assert!(!matches("h[^e]llo", "hello"));
+        assert!(!matches("h[^e]llo", "hllo"));
+    }
+
+    #[test]
+    fn ranges_match_the_characters_between_their_bounds() {
+        assert!(matches("[a-z]", "a"));
+        assert!(matches("[a-z]", "m"));
+        assert!(matches("[a-z]", "z"));
+        assert!(!matches("[a-z]", "A"));
+        assert!(matches("key[0-9]", "key7"));
+        assert!(!matches("key[0-9]", "keyx"));
+        // Reversed bounds are swapped.
+        assert!(matches("[z-a]", "m"));
+        // Ranges and single characters can be mixed.
+        assert!(matches("[a-cx]", "x"));
+        assert!(matches("[a-cx]", "b"));
+        assert!(!matches("[a-cx]", "d"));
+        // A dash right before the end of the class is literal.
+        assert!(matches("[a-]", "-"));
+    }
+
+    #[test]
+    fn escaped_characters_are_matched_literally() {
+        assert!(matches("\\*", "*"));
+        assert!(!matches("\\*", "a"));
+        assert!(matches("a\\?", "a?"));
+        assert!(!matches("a\\?", "ab"));
+        assert!(matches("\\[a\\]", "[a]"));
+        assert!(matches("[\\]]", "]"));
+        assert!(matches("[\\-a]", "-"));
+        assert!(!matches("[\\-a]", "b"));
+    }
+
+    #[test]
+    fn unterminated_classes_extend_to_the_end_of_the_pattern() {
+        assert!(matches("[abc", "a"));
+        assert!(!matches("[abc", "d"));
+    }
+}
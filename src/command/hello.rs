@@ -0,0 +1,106 @@
+// src/command/hello.rs
+
+use crate::{
+    resp::types::{Protocol, RespType},
+    storage::db::Storage,
+};
+
+use super::CommandError;
+
+/// Represents the HELLO command in Nimblecache.
+///
+/// HELLO switches the connection to the given protocol version, and replies with
+/// details about the server. Since the protocol version is part of the state of the
+/// connection, this command is handled by `FrameHandler.handle`.
+#[derive(Debug, Clone)]
+pub struct Hello {
+    /// The protocol version to switch to. `None` keeps the current version.
+    protocol: Option<Protocol>,
+}
+
+impl Hello {
+    /// Creates a new `Hello` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HELLO command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Hello)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Hello, CommandError> {
+        let protover = match args.as_slice() {
+            [] => return Ok(Hello { protocol: None }),
+            [RespType::BulkString(protover)] => protover,
+            [_] => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Protocol version must be a bulk string",
+                )));
+            }
+            // AUTH and SETNAME options are not supported.
+            _ => return Err(CommandError::Other(String::from("syntax error"))),
+        };
+
+        let protocol = match protover.parse::<i64>() {
+            Ok(2) => Protocol::Resp2,
+            Ok(3) => Protocol::Resp3,
+            Ok(_) => {
+                return Err(CommandError::Other(String::from(
+                    "NOPROTO unsupported protocol version",
+                )));
+            }
+            Err(_) => {
+                return Err(CommandError::Other(String::from(
+                    "Protocol version is not an integer or out of range",
+                )));
+            }
+        };
+
+        Ok(Hello {
+            protocol: Some(protocol),
+        })
+    }
+
+    /// Returns the protocol version the connection should switch to, if one was given.
+    pub fn protocol(&self) -> Option<Protocol> {
+        self.protocol
+    }
+
+    /// Executes the HELLO command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the replication state.
+    ///
+    /// * `protocol` - The protocol version of the connection, after the switch.
+    ///
+    /// # Returns
+    ///
+    /// A `Map` of field names to values describing the server. It's encoded as a flat `Array`
+    /// of alternating field names and values in RESP2.
+    pub fn apply(&self, storage: &Storage, protocol: Protocol) -> RespType {
+        let proto = match protocol {
+            Protocol::Resp2 => 2,
+            Protocol::Resp3 => 3,
+        };
+        let role = if storage.replication().is_replica() {
+            "replica"
+        } else {
+            "master"
+        };
+
+        let field = |name: &str| RespType::BulkString(String::from(name));
+        RespType::Map(vec![
+            (field("server"), RespType::BulkString(String::from("nimblecache"))),
+            (
+                field("version"),
+                RespType::BulkString(String::from(env!("CARGO_PKG_VERSION"))),
+            ),
+            (field("proto"), RespType::Integer(proto)),
+            (field("mode"), RespType::BulkString(String::from("standalone"))),
+            (field("role"), RespType::BulkString(String::from(role))),
+            (field("modules"), RespType::Array(vec![])),
+        ])
+    }
+}
@@ -0,0 +1,28 @@
+// src/command/help.rs
+
+use crate::resp::types::RespType;
+
+/// Builds the reply of the HELP subcommand of a command, which lists the subcommands
+/// of the command along with their usage.
+///
+/// # Arguments
+///
+/// * `command` - Name of the command, in upper case.
+/// * `lines` - Lines describing the subcommands. Each subcommand is given with its arguments
+///   on one line, followed by its description on lines indented with four spaces.
+///
+/// # Returns
+///
+/// An `Array` of `SimpleString`s, one per line, ending with the usage of HELP itself.
+pub fn reply(command: &str, lines: &[&str]) -> RespType {
+    let mut reply = vec![RespType::SimpleString(format!(
+        "{} <subcommand> [<arg> [value] [opt] ...]. Subcommands are:",
+        command
+    ))];
+
+    reply.extend(lines.iter().map(|line| RespType::SimpleString(line.to_string())));
+    reply.push(RespType::SimpleString(String::from("HELP")));
+    reply.push(RespType::SimpleString(String::from("    Print this help.")));
+
+    RespType::Array(reply)
+}
@@ -0,0 +1,71 @@
+// src/command/hgetall.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HGETALL command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct HGetAll {
+    key: String,
+}
+
+impl HGetAll {
+    /// Creates a new `HGetAll` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HGETALL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HGetAll)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HGetAll, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'HGETALL' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(HGetAll {
+            key: key.to_string(),
+        })
+    }
+
+    /// Executes the HGETALL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// A `Map` of the fields of the hash to their values. It's empty if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hgetall(&self.key) {
+            Ok(fields) => RespType::Map(
+                fields
+                    .into_iter()
+                    .map(|(field, value)| (RespType::BulkString(field), RespType::BulkString(value)))
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
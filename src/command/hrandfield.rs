@@ -0,0 +1,122 @@
+// src/command/hrandfield.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HRANDFIELD command in Nimblecache.
+///
+/// The `HRandField` struct is used to get random fields of a hash, optionally along
+/// with their values.
+#[derive(Debug, Clone)]
+pub struct HRandField {
+    key: String,
+    /// If positive, up to `count` distinct fields are returned. If negative, `-count`
+    /// fields are returned, possibly with repeats. `None` returns a single field.
+    count: Option<i64>,
+    /// Return the values along with the fields.
+    with_values: bool,
+}
+
+impl HRandField {
+    /// Creates a new `HRandField` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key [count [WITHVALUES]]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HRANDFIELD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HRandField)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HRandField, CommandError> {
+        if args.is_empty() || args.len() > 3 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'HRANDFIELD' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse count
+        let count = match args.get(1) {
+            Some(RespType::BulkString(c)) => match c.parse::<i64>() {
+                Ok(c) => Some(c),
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "value is not an integer or out of range",
+                    )))
+                }
+            },
+            Some(_) => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+            None => None,
+        };
+
+        // parse WITHVALUES option
+        let with_values = match args.get(2) {
+            Some(RespType::BulkString(o)) if o.eq_ignore_ascii_case("withvalues") => true,
+            Some(_) => return Err(CommandError::Other(String::from("syntax error"))),
+            None => false,
+        };
+
+        Ok(HRandField {
+            key: key.to_string(),
+            count,
+            with_values,
+        })
+    }
+
+    /// Executes the HRANDFIELD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// Without a count, a random field as a `BulkString`, or a `NullBulkString` if the
+    /// key doesn't exist. With a count, an `Array` of random fields, each followed by its
+    /// value if `WITHVALUES` is given.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let fields = match db.hrandfield(&self.key, self.count.unwrap_or(1)) {
+            Ok(fields) => fields,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        if self.count.is_none() {
+            return match fields.into_iter().next() {
+                Some((field, _)) => RespType::BulkString(field),
+                None => RespType::NullBulkString,
+            };
+        }
+
+        let mut reply: Vec<RespType> = vec![];
+        for (field, value) in fields {
+            reply.push(RespType::BulkString(field));
+            if self.with_values {
+                reply.push(RespType::BulkString(value));
+            }
+        }
+
+        RespType::Array(reply)
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
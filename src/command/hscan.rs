@@ -0,0 +1,85 @@
+// src/command/hscan.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{scan::{self, ScanArgs}, CommandError};
+
+/// Represents the HSCAN command in Nimblecache.
+///
+/// The `HScan` struct is used to iterate over the fields of the hash stored at a key in batches,
+/// using a cursor returned by the previous call.
+#[derive(Debug, Clone)]
+pub struct HScan {
+    key: String,
+    args: ScanArgs,
+}
+
+impl HScan {
+    /// Creates a new `HScan` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key cursor [MATCH pattern] [COUNT count]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HSCAN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HScan)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HScan, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'HSCAN' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(HScan {
+            key: key.to_string(),
+            args: ScanArgs::parse(&args[1..])?,
+        })
+    }
+
+    /// Executes the HSCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` of the cursor to continue the iteration with, which is `0` once the
+    /// iteration is complete, and an `Array` of the fields in the batch, each followed by its value. The `MATCH` pattern
+    /// is applied after the batch is retrieved, so the batch can be empty before the end of
+    /// the iteration.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hscan(&self.key, self.args.cursor, self.args.count) {
+            Ok((cursor, batch)) => {
+                let elements = batch
+                    .into_iter()
+                    .filter(|(field, _)| self.args.matches(field))
+                    .flat_map(|(field, value)| {
+                        [RespType::BulkString(field), RespType::BulkString(value)]
+                    })
+                    .collect();
+                scan::reply(cursor, elements)
+            }
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
@@ -0,0 +1,103 @@
+// src/command/hset.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HSET command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct HSet {
+    key: String,
+    fields: Vec<(String, String)>,
+}
+
+impl HSet {
+    /// Creates a new `HSet` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key field value [field value ...]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HSET command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HSet)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HSet, CommandError> {
+        if args.len() < 3 || args.len().is_multiple_of(2) {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'HSET' command",
+            )));
+        }
+
+        // parse key
+        let key = &args[0];
+        let key = match key {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse field value pairs
+        let mut fields: Vec<(String, String)> = vec![];
+        for pair in args[1..].chunks(2) {
+            match pair {
+                [RespType::BulkString(f), RespType::BulkString(v)] => {
+                    fields.push((f.to_string(), v.to_string()))
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Field and value must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        Ok(HSet {
+            key: key.to_string(),
+            fields,
+        })
+    }
+
+    /// Executes the HSET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of fields that were added to the hash, not including
+    /// the fields whose value was updated.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hset(self.key.clone(), self.fields.clone()) {
+            Ok(added) => RespType::Integer(added as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args: Vec<RespType> = vec![
+            RespType::BulkString(String::from("HSET")),
+            RespType::BulkString(self.key.clone()),
+        ];
+
+        for (field, value) in self.fields.iter() {
+            args.push(RespType::BulkString(field.to_string()));
+            args.push(RespType::BulkString(value.to_string()));
+        }
+
+        RespType::Array(args)
+    }
+}
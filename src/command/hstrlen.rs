@@ -0,0 +1,63 @@
+// src/command/hstrlen.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the HSTRLEN command in Nimblecache.
+///
+/// The `HStrLen` struct is used to get the length of the value of a field in a hash.
+#[derive(Debug, Clone)]
+pub struct HStrLen {
+    key: String,
+    field: String,
+}
+
+impl HStrLen {
+    /// Creates a new `HStrLen` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HSTRLEN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HStrLen)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HStrLen, CommandError> {
+        match args.as_slice() {
+            [RespType::BulkString(key), RespType::BulkString(field)] => Ok(HStrLen {
+                key: key.to_string(),
+                field: field.to_string(),
+            }),
+            [_, _] => Err(CommandError::Other(String::from(
+                "Invalid argument. Key and field must be bulk strings",
+            ))),
+            _ => Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'HSTRLEN' command",
+            ))),
+        }
+    }
+
+    /// Executes the HSTRLEN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The length in bytes of the value of the field as an `Integer`, or `0` if the field
+    /// or the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hstrlen(&self.key, &self.field) {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
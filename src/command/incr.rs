@@ -0,0 +1,73 @@
+// src/command/incr.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the INCR command in Nimblecache.
+///
+/// The `Incr` struct is used to increment the integer stored as a string against a key.
+#[derive(Debug, Clone)]
+pub struct Incr {
+    key: String,
+}
+
+impl Incr {
+    /// Creates a new `Incr` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the INCR command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Incr)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Incr, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'INCR' command",
+            )));
+        }
+
+        match &args[0] {
+            RespType::BulkString(key) => Ok(Incr {
+                key: key.to_string(),
+            }),
+            _ => Err(CommandError::Other(String::from(
+                "Invalid argument. Key must be a bulk string",
+            ))),
+        }
+    }
+
+    /// Executes the INCR command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer` - The value of the key after the increment. A missing key is treated as `0`.
+    /// - `SimpleError` - If the value isn't an integer, or the increment would overflow.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.incr_by(&self.key, 1) {
+            Ok(value) => RespType::Integer(value),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(String::from("INCR")),
+            RespType::BulkString(self.key.clone()),
+        ])
+    }
+}
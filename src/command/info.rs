@@ -0,0 +1,107 @@
+// src/command/info.rs
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::CommandError;
+
+/// Represents the INFO command in Nimblecache.
+///
+/// INFO returns information and statistics about the server, grouped in sections.
+#[derive(Debug, Clone)]
+pub struct Info {
+    /// The requested section. All sections are returned if `None`.
+    section: Option<String>,
+}
+
+impl Info {
+    /// Creates a new `Info` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the INFO command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Info)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Info, CommandError> {
+        let section = match args.as_slice() {
+            [] => None,
+            [RespType::BulkString(s)] => Some(s.to_lowercase()),
+            [_] => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Section must be a bulk string",
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Wrong number of arguments specified for 'INFO' command",
+                )));
+            }
+        };
+
+        Ok(Info { section })
+    }
+
+    /// Executes the INFO command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the server state.
+    ///
+    /// # Returns
+    ///
+    /// The requested sections as a plain text `VerbatimString`. Each section starts with
+    /// a `# Name` header, followed by one `field:value` line per field. An unknown section
+    /// results in an empty string.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        let sections = [
+            ("server", Self::server(storage)),
+            ("clients", Self::clients(storage)),
+            ("stats", Self::stats(storage)),
+        ];
+
+        let info: Vec<String> = sections
+            .into_iter()
+            .filter(|(name, _)| match self.section.as_deref() {
+                None | Some("all") | Some("default") | Some("everything") => true,
+                Some(section) => section == *name,
+            })
+            .map(|(_, section)| section)
+            .collect();
+
+        RespType::VerbatimString {
+            format: *b"txt",
+            data: info.join("\r\n"),
+        }
+    }
+
+    /// Builds the server section.
+    fn server(storage: &Storage) -> String {
+        format!(
+            "# Server\r\nnimblecache_version:{}\r\nprocess_id:{}\r\nrun_id:{}\r\n",
+            env!("CARGO_PKG_VERSION"),
+            std::process::id(),
+            storage.run_id()
+        )
+    }
+
+    /// Builds the clients section.
+    fn clients(storage: &Storage) -> String {
+        let clients = storage.clients();
+        format!(
+            "# Clients\r\nconnected_clients:{}\r\nblocked_clients:{}\r\nmaxclients:{}\r\n",
+            clients.connected(),
+            clients.blocked(),
+            clients.maxclients()
+        )
+    }
+
+    /// Builds the stats section.
+    fn stats(storage: &Storage) -> String {
+        format!(
+            "# Stats\r\nrejected_connections:{}\r\n",
+            storage.clients().rejected()
+        )
+    }
+}
@@ -0,0 +1,56 @@
+// src/command/lolwut.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Represents the LOLWUT command in Nimblecache.
+///
+/// In Redis, LOLWUT draws a piece of generative art followed by the server version.
+/// Nimblecache skips the art, and only reports the version.
+#[derive(Debug, Clone)]
+pub struct Lolwut;
+
+impl Lolwut {
+    /// Creates a new `Lolwut` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `[VERSION version]`. The version selects
+    /// the art in Redis, so it's only validated here.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the LOLWUT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Lolwut)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Lolwut, CommandError> {
+        match args.as_slice() {
+            [] => Ok(Lolwut),
+            [RespType::BulkString(option), RespType::BulkString(version)]
+                if option.eq_ignore_ascii_case("version") =>
+            {
+                match version.parse::<i64>() {
+                    Ok(_) => Ok(Lolwut),
+                    Err(_) => Err(CommandError::Other(String::from(
+                        "value is not an integer or out of range",
+                    ))),
+                }
+            }
+            _ => Err(CommandError::Other(String::from("syntax error"))),
+        }
+    }
+
+    /// Executes the LOLWUT command.
+    ///
+    /// # Returns
+    ///
+    /// The server version as a plain text `VerbatimString`.
+    pub fn apply(&self) -> RespType {
+        RespType::VerbatimString {
+            format: *b"txt",
+            data: format!("Nimblecache ver. {}\n", env!("CARGO_PKG_VERSION")),
+        }
+    }
+}
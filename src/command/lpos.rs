@@ -0,0 +1,150 @@
+// src/command/lpos.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the LPOS command in Nimblecache.
+///
+/// The `LPos` struct is used to find the indices of the elements of a list which are
+/// equal to a given element.
+#[derive(Debug, Clone)]
+pub struct LPos {
+    key: String,
+    element: String,
+    /// The match to start from. Negative values scan the list from the tail.
+    rank: i64,
+    /// The maximum number of indices to return, if given. `0` returns all the matches.
+    count: Option<usize>,
+    /// The maximum number of elements to compare. `0` scans the whole list.
+    maxlen: usize,
+}
+
+impl LPos {
+    /// Creates a new `LPos` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form
+    /// `key element [RANK rank] [COUNT count] [MAXLEN len]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the LPOS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(LPos)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<LPos, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'LPOS' command",
+            )));
+        }
+
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let mut lpos = LPos {
+            key: parsed[0].to_string(),
+            element: parsed[1].to_string(),
+            rank: 1,
+            count: None,
+            maxlen: 0,
+        };
+
+        // parse options
+        for option in parsed[2..].chunks(2) {
+            let (name, value) = match option {
+                [name, value] => (name.to_lowercase(), Self::parse_integer(value)?),
+                _ => return Err(CommandError::Other(String::from("syntax error"))),
+            };
+
+            match name.as_str() {
+                "rank" => {
+                    // The magnitude of i64::MIN doesn't fit in an i64.
+                    if value == 0 || value == i64::MIN {
+                        return Err(CommandError::Other(String::from(
+                            "RANK can't be zero: use 1 to start from the first match, 2 from the second ... or use negative to start from the end of the list",
+                        )));
+                    }
+                    lpos.rank = value;
+                }
+                "count" => match usize::try_from(value) {
+                    Ok(count) => lpos.count = Some(count),
+                    Err(_) => {
+                        return Err(CommandError::Other(String::from(
+                            "COUNT can't be negative",
+                        )))
+                    }
+                },
+                "maxlen" => match usize::try_from(value) {
+                    Ok(maxlen) => lpos.maxlen = maxlen,
+                    Err(_) => {
+                        return Err(CommandError::Other(String::from(
+                            "MAXLEN can't be negative",
+                        )))
+                    }
+                },
+                _ => return Err(CommandError::Other(String::from("syntax error"))),
+            }
+        }
+
+        Ok(lpos)
+    }
+
+    /// Parses the value of an option as an integer.
+    fn parse_integer(value: &str) -> Result<i64, CommandError> {
+        match value.parse::<i64>() {
+            Ok(n) => Ok(n),
+            Err(_) => Err(CommandError::Other(String::from(
+                "value is not an integer or out of range",
+            ))),
+        }
+    }
+
+    /// Executes the LPOS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// Without `COUNT`, the index of the first match as an `Integer`, or a `NullBulkString`
+    /// if there's no match. With `COUNT`, an `Array` of the indices of the matches, which is
+    /// empty if there's no match. Indices are always counted from the head of the list.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let count = self.count.unwrap_or(1);
+        let indices = match db.lpos(&self.key, &self.element, self.rank, count, self.maxlen) {
+            Ok(indices) => indices,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        match self.count {
+            Some(_) => RespType::Array(
+                indices
+                    .into_iter()
+                    .map(|i| RespType::Integer(i as i64))
+                    .collect(),
+            ),
+            None => match indices.first() {
+                Some(i) => RespType::Integer(*i as i64),
+                None => RespType::NullBulkString,
+            },
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
@@ -99,17 +99,21 @@ impl LRange {
     /// # Returns
     ///
     /// It returns the specified number of elements in the list stored at key, based on start and stop indices.
+    ///
+    /// The reply is encoded straight from the list, so that a large range isn't copied into
+    /// a `RespType` per element first.
     pub fn apply(&self, db: &DB) -> RespType {
-        match db.lrange(self.key.clone(), self.start_idx, self.end_idx) {
-            Ok(elems) => {
-                let sub_list = elems
-                    .iter()
-                    .cloned()
-                    .map(|e| RespType::BulkString(e))
-                    .collect();
-                RespType::Array(sub_list)
-            }
+        let reply = db.lrange_with(&self.key, self.start_idx, self.end_idx, |elems| {
+            RespType::bulk_string_array(elems)
+        });
+        match reply {
+            Ok(reply) => reply,
             Err(e) => RespType::SimpleError(format!("{}", e)),
         }
     }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
 }
@@ -0,0 +1,181 @@
+// src/command/memory.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{help, CommandError};
+
+/// The number of elements sampled to estimate the size of a collection, if `SAMPLES`
+/// option is not given.
+const DEFAULT_SAMPLES: usize = 5;
+
+/// Represents the MEMORY command in Nimblecache.
+///
+/// MEMORY is used to inspect the memory used by the dataset.
+#[derive(Debug, Clone)]
+pub struct Memory {
+    subcommand: MemorySubcommand,
+}
+
+/// The supported MEMORY subcommands.
+#[derive(Debug, Clone)]
+enum MemorySubcommand {
+    /// MEMORY USAGE key [SAMPLES count]
+    Usage { key: String, samples: usize },
+    /// MEMORY STATS
+    Stats,
+    /// MEMORY DOCTOR
+    Doctor,
+    /// MEMORY HELP
+    Help,
+}
+
+impl Memory {
+    /// Creates a new `Memory` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the MEMORY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Memory)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Memory, CommandError> {
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        if parsed.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'MEMORY' command",
+            )));
+        }
+
+        let subcommand = match parsed[0].to_lowercase().as_str() {
+            "usage" => Self::parse_usage(&parsed[1..])?,
+            "stats" if parsed.len() == 1 => MemorySubcommand::Stats,
+            "doctor" if parsed.len() == 1 => MemorySubcommand::Doctor,
+            "help" if parsed.len() == 1 => MemorySubcommand::Help,
+            "stats" | "doctor" | "help" => {
+                return Err(CommandError::Other(format!(
+                    "Wrong number of arguments specified for 'MEMORY {}' command",
+                    parsed[0].to_uppercase()
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'MEMORY' command",
+                    parsed[0]
+                )));
+            }
+        };
+
+        Ok(Memory { subcommand })
+    }
+
+    /// Parses the arguments of `MEMORY USAGE`, which follow the subcommand.
+    fn parse_usage(args: &[&String]) -> Result<MemorySubcommand, CommandError> {
+        match args {
+            [key] => Ok(MemorySubcommand::Usage {
+                key: key.to_string(),
+                samples: DEFAULT_SAMPLES,
+            }),
+            [key, option, samples] if option.eq_ignore_ascii_case("samples") => {
+                match samples.parse::<usize>() {
+                    Ok(samples) => Ok(MemorySubcommand::Usage {
+                        key: key.to_string(),
+                        samples,
+                    }),
+                    Err(_) => Err(CommandError::Other(String::from(
+                        "value is not an integer or out of range",
+                    ))),
+                }
+            }
+            [_, _, _] => Err(CommandError::Other(String::from("syntax error"))),
+            _ => Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'MEMORY USAGE' command",
+            ))),
+        }
+    }
+
+    /// Executes the MEMORY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `MEMORY USAGE` - The estimated number of bytes used by the key and its value as an
+    ///   `Integer`, or a `NullBulkString` if the key doesn't exist. The estimate for collections
+    ///   is based on `samples` of their elements, or all of them if `samples` is `0`.
+    /// - `MEMORY STATS` - An `Array` of alternating stat names and values.
+    /// - `MEMORY DOCTOR` - A report on the memory usage as a `BulkString`.
+    /// - `MEMORY HELP` - The usage of the subcommands as an `Array`.
+    ///
+    /// Nimblecache doesn't track its allocations, so the stats only cover the estimated
+    /// size of the dataset.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match &self.subcommand {
+            MemorySubcommand::Usage { key, samples } => match db.memory_usage(key, *samples) {
+                Ok(Some(bytes)) => RespType::Integer(bytes as i64),
+                Ok(None) => RespType::NullBulkString,
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            MemorySubcommand::Stats => match db.dataset_usage(DEFAULT_SAMPLES) {
+                Ok((keys, bytes)) => {
+                    let bytes_per_key = bytes.checked_div(keys).unwrap_or(0);
+                    RespType::Array(vec![
+                        RespType::BulkString(String::from("keys.count")),
+                        RespType::Integer(keys as i64),
+                        RespType::BulkString(String::from("keys.bytes-per-key")),
+                        RespType::Integer(bytes_per_key as i64),
+                        RespType::BulkString(String::from("dataset.bytes")),
+                        RespType::Integer(bytes as i64),
+                    ])
+                }
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            MemorySubcommand::Doctor => match db.dataset_usage(DEFAULT_SAMPLES) {
+                Ok((0, _)) => RespType::BulkString(String::from(
+                    "The instance is empty, so there's no memory usage to report on.",
+                )),
+                Ok((keys, bytes)) => RespType::BulkString(format!(
+                    "No memory issues detected. The dataset holds {} keys, using about {} bytes.",
+                    keys, bytes
+                )),
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            MemorySubcommand::Help => help::reply(
+                "MEMORY",
+                &[
+                    "DOCTOR",
+                    "    Return memory problems reports.",
+                    "STATS",
+                    "    Return information about the memory usage of the server.",
+                    "USAGE <key> [SAMPLES <count>]",
+                    "    Return memory in bytes used by <key> and its value. Nested values are",
+                    "    sampled up to <count> times (default: 5, 0 means sample all).",
+                ],
+            ),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        match &self.subcommand {
+            MemorySubcommand::Usage { key, .. } => vec![key.clone()],
+            MemorySubcommand::Stats | MemorySubcommand::Doctor | MemorySubcommand::Help => {
+                vec![]
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+// src/command/mget.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the MGET command in Nimblecache.
+///
+/// The `MGet` struct is used to retrieve the values associated with multiple keys
+/// from the database.
+#[derive(Debug, Clone)]
+pub struct MGet {
+    keys: Vec<String>,
+}
+
+impl MGet {
+    /// Creates a new `MGet` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the MGET command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MGet)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<MGet, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'MGET' command",
+            )));
+        }
+
+        // parse keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(MGet { keys })
+    }
+
+    /// Executes the MGET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` with the value of each key as a `BulkString`, in the order of the keys.
+    /// Keys which are not found, have expired, or don't hold a string are `NullBulkString`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.mget(&self.keys) {
+            Ok(values) => RespType::Array(
+                values
+                    .into_iter()
+                    .map(|v| match v {
+                        Some(s) => RespType::BulkString(s),
+                        None => RespType::NullBulkString,
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.clone()
+    }
+}
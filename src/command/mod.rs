@@ -1,21 +1,181 @@
 use core::fmt;
 
+use append::Append;
+use bgrewriteaof::BgRewriteAof;
+use bitpos::BitPos;
+use client::ClientCommand;
+use cluster::Cluster;
+use command_info::CommandInfo;
+use copy::CopyCommand;
+use config::ConfigCommand;
+use dbsize::DbSize;
+use debug::DebugCommand;
+use del::Del;
+use expire::Expire;
+use expireat::ExpireAt;
+use expiretime::ExpireTime;
+use flushall::FlushAll;
+use geoadd::GeoAdd;
+use geosearch::GeoSearch;
 use get::Get;
+use getdel::GetDel;
+use getex::GetEx;
+use hello::Hello;
+use hgetall::HGetAll;
+use hrandfield::HRandField;
+use hscan::HScan;
+use hset::HSet;
+use incr::Incr;
+use hstrlen::HStrLen;
+use info::Info;
+use lolwut::Lolwut;
+use lpos::LPos;
 use lpush::LPush;
 use lrange::LRange;
+use memory::Memory;
+use mget::MGet;
+use object::Object;
+use persist::Persist;
+use pexpireat::PExpireAt;
+use pexpiretime::PExpireTime;
+use pfadd::PfAdd;
+use pfcount::PfCount;
 use ping::Ping;
+use psync::PSync;
+use psubscribe::PSubscribe;
+use publish::Publish;
+use pubsub::PubSubCommand;
+use punsubscribe::PUnsubscribe;
+use replconf::ReplConf;
+use replicaof::ReplicaOf;
+use role::RoleCommand;
 use rpush::RPush;
+use sadd::SAdd;
+use sdiffstore::SDiffStore;
 use set::Set;
+use setrange::SetRange;
+use shutdown::Shutdown;
+use sintercard::SInterCard;
+use sinterstore::SInterStore;
+use smembers::SMembers;
+use smove::SMove;
+use srandmember::SRandMember;
+use srem::SRem;
+use sort::Sort;
+use spop::SPop;
+use sscan::SScan;
+use subscribe::Subscribe;
+use sunion::SUnion;
+use sunionstore::SUnionStore;
+use ttl::Ttl;
+use unlink::Unlink;
+use unsubscribe::Unsubscribe;
+use wait::Wait;
+use watch::Watch;
+use xadd::XAdd;
+use xlen::XLen;
+use xrange::XRange;
+use xread::XRead;
+use zadd::ZAdd;
+use zcard::ZCard;
+use zcount::ZCount;
+use zincrby::ZIncrBy;
+use zpopmax::ZPopMax;
+use zpopmin::ZPopMin;
+use zrem::ZRem;
+use zscan::ZScan;
 
-use crate::{resp::types::RespType, storage::db::DB};
+use crate::{clients::ClientState, resp::types::RespType, storage::db::Storage};
 
+mod append;
+mod bgrewriteaof;
+mod bitpos;
+mod client;
+mod cluster;
+mod command_info;
+mod config;
+mod copy;
+mod dbsize;
+mod debug;
+mod del;
+mod expire;
+mod expireat;
+mod expiretime;
+mod flushall;
+mod geoadd;
+mod geohash;
+mod geosearch;
 mod get;
+mod getdel;
+mod getex;
+pub mod glob;
+mod hello;
+mod help;
+mod hgetall;
+mod hrandfield;
+mod hscan;
+mod hset;
+mod incr;
+mod hstrlen;
+mod info;
+mod lolwut;
+mod lpos;
 mod lpush;
 mod lrange;
+mod memory;
+mod mget;
+mod object;
+mod persist;
+mod pexpireat;
+mod pexpiretime;
+mod pfadd;
+mod pfcount;
 pub mod ping;
+mod psync;
+mod psubscribe;
+mod publish;
+mod pubsub;
+mod punsubscribe;
+pub mod replconf;
+mod replicaof;
+mod role;
 mod rpush;
+mod sadd;
+mod scan;
+mod sdiffstore;
 mod set;
+mod setrange;
+mod shutdown;
+mod sintercard;
+mod sinterstore;
+mod smembers;
+mod smove;
+mod srandmember;
+mod srem;
+mod sort;
+mod spop;
+mod sscan;
+mod subscribe;
+mod sunion;
+mod sunionstore;
 pub mod transactions;
+mod ttl;
+mod unlink;
+mod unsubscribe;
+mod wait;
+mod watch;
+mod xadd;
+mod xlen;
+mod xrange;
+mod xread;
+mod zadd;
+mod zcard;
+mod zcount;
+mod zincrby;
+mod zpopmax;
+mod zpopmin;
+mod zrem;
+mod zscan;
 
 /// Represents the supported Nimblecache commands.
 #[derive(Debug, Clone)]
@@ -24,20 +184,180 @@ pub enum Command {
   Ping(Ping),
   /// The SET command
   Set(Set),
+  /// The DEL command.
+  Del(Del),
+  /// The UNLINK command.
+  Unlink(Unlink),
+  /// The FLUSHALL command.
+  FlushAll(FlushAll),
   /// The GET command
   Get(Get),
+  /// The MGET command.
+  MGet(MGet),
+  /// The GETDEL command.
+  GetDel(GetDel),
+  /// The GETEX command.
+  GetEx(GetEx),
+  /// The BITPOS command.
+  BitPos(BitPos),
   /// The LPUSH command
   LPush(LPush),
   /// The RPUSH command,
   RPush(RPush),
   /// The LRange command,
   LRange(LRange),
+  /// The LPOS command.
+  LPos(LPos),
+  /// The SADD command.
+  SAdd(SAdd),
+  /// The PFADD command.
+  PfAdd(PfAdd),
+  /// The PFCOUNT command.
+  PfCount(PfCount),
+  /// The ZADD command.
+  ZAdd(ZAdd),
+  /// The ZREM command.
+  ZRem(ZRem),
+  /// The ZINCRBY command.
+  ZIncrBy(ZIncrBy),
+  /// The ZPOPMIN command.
+  ZPopMin(ZPopMin),
+  /// The ZPOPMAX command.
+  ZPopMax(ZPopMax),
+  /// The ZSCAN command.
+  ZScan(ZScan),
+  /// The ZCARD command.
+  ZCard(ZCard),
+  /// The ZCOUNT command.
+  ZCount(ZCount),
+  /// The XADD command.
+  XAdd(XAdd),
+  /// The XLEN command.
+  XLen(XLen),
+  /// The XRANGE command.
+  XRange(XRange),
+  /// The XREAD command.
+  XRead(XRead),
+  /// The GEOADD command.
+  GeoAdd(GeoAdd),
+  /// The GEOSEARCH command.
+  GeoSearch(GeoSearch),
+  /// The SINTERCARD command.
+  SInterCard(SInterCard),
+  /// The SMEMBERS command.
+  SMembers(SMembers),
+  /// The SUNION command.
+  SUnion(SUnion),
+  /// The SSCAN command.
+  SScan(SScan),
+  /// The SRANDMEMBER command.
+  SRandMember(SRandMember),
+  /// The SREM command.
+  SRem(SRem),
+  /// The SINTERSTORE command.
+  SInterStore(SInterStore),
+  /// The SUNIONSTORE command.
+  SUnionStore(SUnionStore),
+  /// The SDIFFSTORE command.
+  SDiffStore(SDiffStore),
+  /// The SPOP command.
+  SPop(SPop),
+  /// The SMOVE command.
+  SMove(SMove),
+  /// The COPY command.
+  Copy(CopyCommand),
+  /// The HSET command.
+  HSet(HSet),
+  /// The HRANDFIELD command.
+  HRandField(HRandField),
+  /// The HGETALL command.
+  HGetAll(HGetAll),
+  /// The HSTRLEN command.
+  HStrLen(HStrLen),
+  /// The HSCAN command.
+  HScan(HScan),
+  /// The SORT command.
+  Sort(Sort),
+  /// The EXPIRE command.
+  Expire(Expire),
+  /// The EXPIREAT command.
+  ExpireAt(ExpireAt),
+  /// The PEXPIREAT command.
+  PExpireAt(PExpireAt),
+  /// The PERSIST command.
+  Persist(Persist),
+  /// The APPEND command.
+  Append(Append),
+  /// The INCR command.
+  Incr(Incr),
+  /// The SETRANGE command.
+  SetRange(SetRange),
+  /// The TTL command.
+  Ttl(Ttl),
+  /// The EXPIRETIME command.
+  ExpireTime(ExpireTime),
+  /// The PEXPIRETIME command.
+  PExpireTime(PExpireTime),
+  /// The DBSIZE command.
+  DbSize(DbSize),
+  /// The INFO command.
+  Info(Info),
+  /// The LOLWUT command.
+  Lolwut(Lolwut),
+  /// The BGREWRITEAOF command.
+  BgRewriteAof(BgRewriteAof),
+  /// The OBJECT command.
+  Object(Object),
+  /// The MEMORY command.
+  Memory(Memory),
+  /// The CLIENT command.
+  Client(ClientCommand),
+  /// The COMMAND command.
+  CommandInfo(CommandInfo),
+  /// The HELLO command.
+  Hello(Hello),
+  /// The CLUSTER command.
+  Cluster(Cluster),
+  /// The CONFIG command.
+  Config(ConfigCommand),
+  /// The REPLICAOF command (and its alias SLAVEOF).
+  ReplicaOf(ReplicaOf),
+  /// The ROLE command.
+  Role(RoleCommand),
+  /// The PSYNC command.
+  PSync(PSync),
+  /// The REPLCONF command.
+  ReplConf(ReplConf),
+  /// The WAIT command.
+  Wait(Wait),
+  /// The SHUTDOWN command.
+  Shutdown(Shutdown),
+  /// The DEBUG command.
+  Debug(DebugCommand),
+  /// The SUBSCRIBE command.
+  Subscribe(Subscribe),
+  /// The UNSUBSCRIBE command.
+  Unsubscribe(Unsubscribe),
+  /// The PSUBSCRIBE command.
+  PSubscribe(PSubscribe),
+  /// The PUNSUBSCRIBE command.
+  PUnsubscribe(PUnsubscribe),
+  /// The PUBLISH command.
+  Publish(Publish),
+  /// The PUBSUB command.
+  PubSub(PubSubCommand),
   /// The MULTI command.
   Multi,
   /// The EXEC command.
   Exec,
   /// The DISCARD command.
-  Discard
+  Discard,
+  /// The WATCH command.
+  Watch(Watch),
+  /// The UNWATCH command.
+  Unwatch,
+  /// The MONITOR command.
+  Monitor,
 }
 
 impl Command {
@@ -46,20 +366,27 @@ impl Command {
   /// # Arguments
   ///
   /// * `frame` - A vector of `RespType` representing the command and its arguments.
-  /// The first item is always the command name, and the rest are its arguments.
+  ///   The first item is always the command name, and the rest are its arguments.
   ///
   /// # Returns
   ///
   /// * `Ok(Command)` if parsing succeeds.
   /// * `Err(CommandError)` if parsing fails.
   pub fn from_resp_command_frame(frame: Vec<RespType>) -> Result<Command, CommandError> {
+    // The codec never yields empty frames, but guard against them anyway.
+    if frame.is_empty() {
+      return Err(CommandError::InvalidFormat);
+    }
+
     let (cmd_name, args) = frame.split_at(1);
     let cmd_name = match &cmd_name[0] {
       RespType::BulkString(s) => s.clone(),
       _ => return Err(CommandError::InvalidFormat),
     };
 
-    let cmd = match cmd_name.to_lowercase().as_str() {
+    // Only ASCII letters are folded. Unicode case folding would map some non-ASCII
+    // characters to ASCII ones, e.g. the Kelvin sign to `k`, turning them into known commands.
+    let cmd = match cmd_name.to_ascii_lowercase().as_str() {
         "ping" => Command::Ping(Ping::with_args(Vec::from(args))?),
         "set" => {
             let cmd = Set::with_args(Vec::from(args));
@@ -68,6 +395,27 @@ impl Command {
                 Err(e) => return Err(e),
             }
         }
+        "del" => {
+            let cmd = Del::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Del(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "unlink" => {
+            let cmd = Unlink::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Unlink(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "flushall" => {
+            let cmd = FlushAll::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::FlushAll(cmd),
+                Err(e) => return Err(e),
+            }
+        }
         "get" => {
             let cmd = Get::with_args(Vec::from(args));
             match cmd {
@@ -75,6 +423,34 @@ impl Command {
                 Err(e) => return Err(e),
             }
         }
+        "mget" => {
+            let cmd = MGet::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::MGet(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "getdel" => {
+            let cmd = GetDel::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::GetDel(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "getex" => {
+            let cmd = GetEx::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::GetEx(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "bitpos" => {
+            let cmd = BitPos::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::BitPos(cmd),
+                Err(e) => return Err(e),
+            }
+        }
         "lpush" => {
             let cmd = LPush::with_args(Vec::from(args));
             match cmd {
@@ -96,13 +472,510 @@ impl Command {
                 Err(e) => return Err(e),
             }
         }
+        "lpos" => {
+            let cmd = LPos::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::LPos(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "sadd" => {
+            let cmd = SAdd::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SAdd(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "smembers" => {
+            let cmd = SMembers::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SMembers(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "sunion" => {
+            let cmd = SUnion::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SUnion(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "sscan" => {
+            let cmd = SScan::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SScan(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "pfadd" => {
+            let cmd = PfAdd::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::PfAdd(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "pfcount" => {
+            let cmd = PfCount::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::PfCount(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "zadd" => {
+            let cmd = ZAdd::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ZAdd(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "zrem" => {
+            let cmd = ZRem::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ZRem(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "zincrby" => {
+            let cmd = ZIncrBy::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ZIncrBy(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "zpopmin" => {
+            let cmd = ZPopMin::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ZPopMin(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "zpopmax" => {
+            let cmd = ZPopMax::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ZPopMax(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "zscan" => {
+            let cmd = ZScan::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ZScan(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "zcard" => {
+            let cmd = ZCard::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ZCard(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "zcount" => {
+            let cmd = ZCount::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ZCount(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "xadd" => {
+            let cmd = XAdd::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::XAdd(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "xlen" => {
+            let cmd = XLen::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::XLen(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "xrange" => {
+            let cmd = XRange::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::XRange(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "xread" => {
+            let cmd = XRead::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::XRead(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "geoadd" => {
+            let cmd = GeoAdd::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::GeoAdd(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "geosearch" => {
+            let cmd = GeoSearch::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::GeoSearch(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "sintercard" => {
+            let cmd = SInterCard::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SInterCard(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "srandmember" => {
+            let cmd = SRandMember::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SRandMember(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "srem" => {
+            let cmd = SRem::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SRem(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "sinterstore" => {
+            let cmd = SInterStore::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SInterStore(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "sunionstore" => {
+            let cmd = SUnionStore::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SUnionStore(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "sdiffstore" => {
+            let cmd = SDiffStore::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SDiffStore(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "spop" => {
+            let cmd = SPop::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SPop(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "smove" => {
+            let cmd = SMove::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SMove(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "copy" => {
+            let cmd = CopyCommand::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Copy(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "hset" => {
+            let cmd = HSet::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::HSet(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "hrandfield" => {
+            let cmd = HRandField::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::HRandField(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "hgetall" => {
+            let cmd = HGetAll::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::HGetAll(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "hstrlen" => {
+            let cmd = HStrLen::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::HStrLen(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "hscan" => {
+            let cmd = HScan::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::HScan(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "sort" => {
+            let cmd = Sort::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Sort(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "expire" => {
+            let cmd = Expire::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Expire(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "expireat" => {
+            let cmd = ExpireAt::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ExpireAt(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "pexpireat" => {
+            let cmd = PExpireAt::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::PExpireAt(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "persist" => {
+            let cmd = Persist::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Persist(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "append" => {
+            let cmd = Append::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Append(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "incr" => {
+            let cmd = Incr::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Incr(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "setrange" => {
+            let cmd = SetRange::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::SetRange(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "ttl" => {
+            let cmd = Ttl::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Ttl(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "expiretime" => {
+            let cmd = ExpireTime::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ExpireTime(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "pexpiretime" => {
+            let cmd = PExpireTime::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::PExpireTime(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "dbsize" => {
+            let cmd = DbSize::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::DbSize(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "info" => {
+            let cmd = Info::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Info(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "lolwut" => {
+            let cmd = Lolwut::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Lolwut(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "bgrewriteaof" => {
+            let cmd = BgRewriteAof::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::BgRewriteAof(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "object" => {
+            let cmd = Object::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Object(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "memory" => {
+            let cmd = Memory::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Memory(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "client" => {
+            let cmd = ClientCommand::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Client(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "command" => {
+            let cmd = CommandInfo::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::CommandInfo(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "hello" => {
+            let cmd = Hello::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Hello(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "cluster" => {
+            let cmd = Cluster::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Cluster(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "config" => {
+            let cmd = ConfigCommand::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Config(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "replicaof" | "slaveof" => {
+            let cmd = ReplicaOf::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ReplicaOf(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "role" => {
+            let cmd = RoleCommand::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Role(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "psync" => {
+            let cmd = PSync::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::PSync(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "replconf" => {
+            let cmd = ReplConf::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::ReplConf(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "wait" => {
+            let cmd = Wait::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Wait(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "shutdown" => {
+            let cmd = Shutdown::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Shutdown(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "debug" => {
+            let cmd = DebugCommand::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Debug(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "subscribe" => {
+            let cmd = Subscribe::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Subscribe(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "unsubscribe" => {
+            let cmd = Unsubscribe::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Unsubscribe(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "psubscribe" => {
+            let cmd = PSubscribe::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::PSubscribe(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "punsubscribe" => {
+            let cmd = PUnsubscribe::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::PUnsubscribe(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "publish" => {
+            let cmd = Publish::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Publish(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "pubsub" => {
+            let cmd = PubSubCommand::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::PubSub(cmd),
+                Err(e) => return Err(e),
+            }
+        }
         "multi" => Command::Multi,
         "exec" => Command::Exec,
         "discard" => Command::Discard,
+        "watch" => {
+            let cmd = Watch::with_args(Vec::from(args));
+            match cmd {
+                Ok(cmd) => Command::Watch(cmd),
+                Err(e) => return Err(e),
+            }
+        }
+        "unwatch" => Command::Unwatch,
+        "monitor" => Command::Monitor,
         _ => {
-            return Err(CommandError::UnknownCommand(ErrUnknownCommand {
-                cmd: cmd_name,
-            }));
+            return Err(CommandError::UnknownCommand(ErrUnknownCommand::new(cmd_name, args)));
         }
     };
 
@@ -111,25 +984,482 @@ impl Command {
 
   /// Executes the Redis-clone command.
   ///
+  /// Write commands which succeed are propagated to the connected replicas. The last access
+  /// time of the keys accessed by the command is updated, unless the client turned on `NO-TOUCH`.
+  ///
+  /// # Arguments
+  ///
+  /// * `storage` - The shared storage, which holds the database and the server state.
+  ///
+  /// * `client` - The state of the connection the command was issued on.
+  ///
   /// # Returns
   ///
   /// The result of the command execution as a `RespType`.
-  pub fn execute(&self, db: &DB) -> RespType {
+  pub fn execute(&self, storage: &Storage, client: &ClientState) -> RespType {
+    let _guard = storage.lock_command();
+    self.execute_locked(storage, client)
+  }
+
+  /// Executes the command like `execute`, except that the caller is expected to hold the
+  /// transaction lock already, either in shared mode or in exclusive mode for a transaction.
+  fn execute_locked(&self, storage: &Storage, client: &ClientState) -> RespType {
+    let res = if self.is_write() {
+      // Writes are applied and propagated while holding the same lock, so that the
+      // replicas receive them in the order in which they were applied.
+      let _guard = storage.replication().lock_writes();
+
+      if self.may_use_memory() {
+        if let Err(e) = storage.free_memory() {
+          return RespType::SimpleError(format!("{}", e));
+        }
+      }
+
+      let res = self.apply(storage);
+      if !matches!(res, RespType::SimpleError(_)) {
+        if let Some(cmd) = self.build_command(&res, storage) {
+          storage.replication().propagate(cmd);
+        }
+        // The clients caching the modified keys are told to drop them, and the transactions
+        // watching them are aborted.
+        storage.tracking().invalidate(&self.keys());
+        storage.watches().touch(&self.keys());
+      }
+
+      res
+    } else {
+      self.apply(storage)
+    };
+
+    if !client.no_touch() && self.touches_keys() {
+      if let Err(e) = storage.db().touch(&self.keys()) {
+        return RespType::SimpleError(format!("{}", e));
+      }
+    }
+
+    res
+  }
+
+  /// Applies the command on the shared storage and returns the result.
+  fn apply(&self, storage: &Storage) -> RespType {
+    let db = storage.db();
+    let db = db.as_ref();
+
     match self {
       Command::Ping(ping) => ping.apply(),
-      Command::Set(set) => set.apply(db),
+      Command::Set(set) => set.apply(storage),
+      Command::Del(del) => del.apply(db),
+      Command::Unlink(unlink) => unlink.apply(db),
+      Command::FlushAll(flushall) => flushall.apply(storage),
       Command::Get(get) => get.apply(db),
-      Command::LPush(lpush) => lpush.apply(db),
-      Command::RPush(rpush) => rpush.apply(db),
+      Command::MGet(mget) => mget.apply(db),
+      Command::GetDel(getdel) => getdel.apply(db),
+      Command::GetEx(getex) => getex.apply(db),
+      Command::BitPos(bitpos) => bitpos.apply(db),
+      Command::LPush(lpush) => lpush.apply(storage),
+      Command::RPush(rpush) => rpush.apply(storage),
       Command::LRange(lrange) => lrange.apply(db),
+      Command::LPos(lpos) => lpos.apply(db),
+      Command::SAdd(sadd) => sadd.apply(db),
+      Command::SRandMember(srandmember) => srandmember.apply(db),
+      Command::SRem(srem) => srem.apply(db),
+      Command::SInterStore(sinterstore) => sinterstore.apply(db),
+      Command::SUnionStore(sunionstore) => sunionstore.apply(db),
+      Command::SDiffStore(sdiffstore) => sdiffstore.apply(db),
+      Command::SPop(spop) => spop.apply(db),
+      Command::SMove(smove) => smove.apply(db),
+      Command::Copy(copy) => copy.apply(db),
+      Command::HSet(hset) => hset.apply(db),
+      Command::HRandField(hrandfield) => hrandfield.apply(db),
+      Command::HGetAll(hgetall) => hgetall.apply(db),
+      Command::HStrLen(hstrlen) => hstrlen.apply(db),
+      Command::HScan(hscan) => hscan.apply(db),
+      Command::ZAdd(zadd) => zadd.apply(db),
+      Command::ZRem(zrem) => zrem.apply(db),
+      Command::ZIncrBy(zincrby) => zincrby.apply(db),
+      Command::ZPopMin(zpopmin) => zpopmin.apply(db),
+      Command::ZPopMax(zpopmax) => zpopmax.apply(db),
+      Command::ZScan(zscan) => zscan.apply(db),
+      Command::ZCard(zcard) => zcard.apply(db),
+      Command::ZCount(zcount) => zcount.apply(db),
+      Command::XAdd(xadd) => xadd.apply(storage),
+      Command::XLen(xlen) => xlen.apply(db),
+      Command::XRange(xrange) => xrange.apply(db),
+      Command::XRead(xread) => xread.apply(db),
+      Command::GeoAdd(geoadd) => geoadd.apply(db),
+      Command::GeoSearch(geosearch) => geosearch.apply(db),
+      Command::SInterCard(sintercard) => sintercard.apply(db),
+      Command::PfAdd(pfadd) => pfadd.apply(db),
+      Command::PfCount(pfcount) => pfcount.apply(db),
+      Command::SMembers(smembers) => smembers.apply(db),
+      Command::SUnion(sunion) => sunion.apply(db),
+      Command::SScan(sscan) => sscan.apply(db),
+      Command::Sort(sort) => sort.apply(db),
+      Command::Expire(expire) => expire.apply(db),
+      Command::ExpireAt(expireat) => expireat.apply(db),
+      Command::PExpireAt(pexpireat) => pexpireat.apply(db),
+      Command::Persist(persist) => persist.apply(db),
+      Command::Append(append) => append.apply(db),
+      Command::Incr(incr) => incr.apply(db),
+      Command::SetRange(setrange) => setrange.apply(db),
+      Command::Ttl(ttl) => ttl.apply(db),
+      Command::ExpireTime(expiretime) => expiretime.apply(db),
+      Command::PExpireTime(pexpiretime) => pexpiretime.apply(db),
+      Command::DbSize(dbsize) => dbsize.apply(db),
+      Command::Info(info) => info.apply(storage),
+      Command::Lolwut(lolwut) => lolwut.apply(),
+      Command::BgRewriteAof(bgrewriteaof) => bgrewriteaof.apply(),
+      Command::Object(object) => object.apply(db),
+      Command::Memory(memory) => memory.apply(db),
+      // CLIENT calls are handled inside FrameHandler.handle since they change the state of the connection.
+      Command::Client(_) => {
+        RespType::SimpleError(String::from("CLIENT is not allowed inside a transaction"))
+      }
+      Command::CommandInfo(command_info) => command_info.apply(),
+      // HELLO calls are handled inside FrameHandler.handle since they change the protocol of the connection.
+      Command::Hello(_) => {
+        RespType::SimpleError(String::from("HELLO is not allowed inside a transaction"))
+      }
+      Command::Cluster(cluster) => cluster.apply(),
+      Command::Config(config) => config.apply(storage),
+      Command::ReplicaOf(replicaof) => replicaof.apply(storage),
+      Command::Role(role) => role.apply(storage),
+      // PSYNC calls are handled inside FrameHandler.handle since the dataset is streamed over the connection.
+      Command::PSync(_) => {
+        RespType::SimpleError(String::from("PSYNC is not allowed inside a transaction"))
+      }
+      Command::ReplConf(replconf) => replconf.apply(),
+      // WAIT calls are handled inside FrameHandler.handle since they block the connection.
+      // Inside a transaction, they return without blocking.
+      Command::Wait(wait) => wait.apply_nonblocking(storage),
+      // SHUTDOWN calls are handled inside FrameHandler.handle since they close the connection.
+      Command::Shutdown(_) => {
+        RespType::SimpleError(String::from("SHUTDOWN is not allowed inside a transaction"))
+      }
+      Command::Debug(debug) => debug.apply(storage),
+      // SUBSCRIBE, UNSUBSCRIBE, PSUBSCRIBE and PUNSUBSCRIBE calls are handled inside
+      // FrameHandler.handle since they change the state of the connection.
+      Command::Subscribe(_) => {
+        RespType::SimpleError(String::from("SUBSCRIBE is not allowed inside a transaction"))
+      }
+      Command::Unsubscribe(_) => {
+        RespType::SimpleError(String::from("UNSUBSCRIBE is not allowed inside a transaction"))
+      }
+      Command::PSubscribe(_) => {
+        RespType::SimpleError(String::from("PSUBSCRIBE is not allowed inside a transaction"))
+      }
+      Command::PUnsubscribe(_) => {
+        RespType::SimpleError(String::from("PUNSUBSCRIBE is not allowed inside a transaction"))
+      }
+      Command::Publish(publish) => publish.apply(storage),
+      Command::PubSub(pubsub) => pubsub.apply(storage),
       // MULTI calls are handled inside FrameHandler.handle since it involves command queueing.
       Command::Multi => RespType::SimpleString(String::from("OK")),
       // EXEC calls are handled inside FrameHandler.handle too, since it involves executing queued commands.
       Command::Exec => RespType::NullBulkString,
       // DISCARD calls are handled inside FrameHandler.handle too, since it involves discarding queued commands.
       Command::Discard => RespType::SimpleString(String::from("OK")),
+      // WATCH calls are handled inside FrameHandler.handle too, since the keys are watched by the
+      // transaction state of the connection.
+      Command::Watch(_) => {
+        RespType::SimpleError(String::from("WATCH inside MULTI is not allowed"))
+      }
+      // UNWATCH is queued inside a transaction, and EXEC clears the watched keys anyway.
+      Command::Unwatch => RespType::SimpleString(String::from("OK")),
+      // MONITOR calls are handled inside FrameHandler.handle since they change the state of the
+      // connection.
+      Command::Monitor => {
+        RespType::SimpleError(String::from("MONITOR is not allowed inside a transaction"))
+      }
     }
   }
+
+  /// Builds the RESP command which performs the same operation as this command.
+  ///
+  /// # Arguments
+  ///
+  /// * `res` - The result of the command. It's used by the commands whose effect isn't
+  ///   determined by their arguments alone, like SPOP which removes random members.
+  ///
+  /// * `storage` - The shared storage. It's used by the commands which depend on the
+  ///   configuration of the server, like SET which applies the default TTL.
+  ///
+  /// # Returns
+  ///
+  /// * `Some(RespType)` - The command as an array of bulk strings, if it's a write command.
+  /// * `None` - If the command doesn't modify the data, and hence isn't propagated.
+  pub fn build_command(&self, res: &RespType, storage: &Storage) -> Option<RespType> {
+    match self {
+      Command::Set(set) => Some(set.build_command(storage)),
+      Command::Del(del) => Some(del.build_command()),
+      Command::Unlink(unlink) => Some(unlink.build_command()),
+      Command::FlushAll(flushall) => Some(flushall.build_command()),
+      Command::LPush(lpush) => Some(lpush.build_command()),
+      Command::RPush(rpush) => Some(rpush.build_command()),
+      Command::SAdd(sadd) => Some(sadd.build_command()),
+      Command::SRem(srem) => Some(srem.build_command()),
+      Command::SInterStore(sinterstore) => Some(sinterstore.build_command()),
+      Command::SUnionStore(sunionstore) => Some(sunionstore.build_command()),
+      Command::SDiffStore(sdiffstore) => Some(sdiffstore.build_command()),
+      Command::SPop(spop) => spop.build_command(res),
+      Command::XAdd(xadd) => xadd.build_command(res),
+      Command::ZPopMin(zpopmin) => zpopmin.build_command(res),
+      Command::ZPopMax(zpopmax) => zpopmax.build_command(res),
+      Command::GetDel(getdel) => getdel.build_command(res),
+      Command::GetEx(getex) => getex.build_command(res),
+      Command::SMove(smove) => Some(smove.build_command()),
+      Command::Copy(copy) => Some(copy.build_command()),
+      Command::HSet(hset) => Some(hset.build_command()),
+      Command::PfAdd(pfadd) => Some(pfadd.build_command()),
+      Command::ZAdd(zadd) => Some(zadd.build_command()),
+      Command::ZRem(zrem) => Some(zrem.build_command()),
+      Command::ZIncrBy(zincrby) => Some(zincrby.build_command()),
+      Command::GeoAdd(geoadd) => Some(geoadd.build_command()),
+      Command::Expire(expire) => Some(expire.build_command()),
+      Command::ExpireAt(expireat) => Some(expireat.build_command()),
+      Command::PExpireAt(pexpireat) => Some(pexpireat.build_command()),
+      Command::Persist(persist) => Some(persist.build_command()),
+      Command::Append(append) => Some(append.build_command()),
+      Command::Incr(incr) => Some(incr.build_command()),
+      Command::SetRange(setrange) => Some(setrange.build_command()),
+      Command::Debug(debug) => debug.build_command(),
+      _ => None,
+    }
+  }
+
+  /// Returns the keys accessed by the command.
+  pub fn keys(&self) -> Vec<String> {
+    match self {
+      Command::Set(set) => set.keys(),
+      Command::Del(del) => del.keys(),
+      Command::Unlink(unlink) => unlink.keys(),
+      Command::Watch(watch) => watch.keys(),
+      Command::Get(get) => get.keys(),
+      Command::MGet(mget) => mget.keys(),
+      Command::GetDel(getdel) => getdel.keys(),
+      Command::GetEx(getex) => getex.keys(),
+      Command::BitPos(bitpos) => bitpos.keys(),
+      Command::LPush(lpush) => lpush.keys(),
+      Command::RPush(rpush) => rpush.keys(),
+      Command::LRange(lrange) => lrange.keys(),
+      Command::LPos(lpos) => lpos.keys(),
+      Command::SAdd(sadd) => sadd.keys(),
+      Command::SRandMember(srandmember) => srandmember.keys(),
+      Command::SRem(srem) => srem.keys(),
+      Command::SInterStore(sinterstore) => sinterstore.keys(),
+      Command::SUnionStore(sunionstore) => sunionstore.keys(),
+      Command::SDiffStore(sdiffstore) => sdiffstore.keys(),
+      Command::SPop(spop) => spop.keys(),
+      Command::SMove(smove) => smove.keys(),
+      Command::Copy(copy) => copy.keys(),
+      Command::HSet(hset) => hset.keys(),
+      Command::HRandField(hrandfield) => hrandfield.keys(),
+      Command::HGetAll(hgetall) => hgetall.keys(),
+      Command::HStrLen(hstrlen) => hstrlen.keys(),
+      Command::HScan(hscan) => hscan.keys(),
+      Command::ZAdd(zadd) => zadd.keys(),
+      Command::ZRem(zrem) => zrem.keys(),
+      Command::ZIncrBy(zincrby) => zincrby.keys(),
+      Command::ZPopMin(zpopmin) => zpopmin.keys(),
+      Command::ZPopMax(zpopmax) => zpopmax.keys(),
+      Command::ZScan(zscan) => zscan.keys(),
+      Command::ZCard(zcard) => zcard.keys(),
+      Command::ZCount(zcount) => zcount.keys(),
+      Command::XAdd(xadd) => xadd.keys(),
+      Command::XLen(xlen) => xlen.keys(),
+      Command::XRange(xrange) => xrange.keys(),
+      Command::XRead(xread) => xread.keys(),
+      Command::GeoAdd(geoadd) => geoadd.keys(),
+      Command::GeoSearch(geosearch) => geosearch.keys(),
+      Command::SInterCard(sintercard) => sintercard.keys(),
+      Command::PfAdd(pfadd) => pfadd.keys(),
+      Command::PfCount(pfcount) => pfcount.keys(),
+      Command::SMembers(smembers) => smembers.keys(),
+      Command::SUnion(sunion) => sunion.keys(),
+      Command::SScan(sscan) => sscan.keys(),
+      Command::Sort(sort) => sort.keys(),
+      Command::Expire(expire) => expire.keys(),
+      Command::ExpireAt(expireat) => expireat.keys(),
+      Command::PExpireAt(pexpireat) => pexpireat.keys(),
+      Command::Persist(persist) => persist.keys(),
+      Command::Append(append) => append.keys(),
+      Command::Incr(incr) => incr.keys(),
+      Command::SetRange(setrange) => setrange.keys(),
+      Command::Ttl(ttl) => ttl.keys(),
+      Command::ExpireTime(expiretime) => expiretime.keys(),
+      Command::PExpireTime(pexpiretime) => pexpiretime.keys(),
+      Command::Object(object) => object.keys(),
+      Command::Memory(memory) => memory.keys(),
+      _ => vec![],
+    }
+  }
+
+  /// Checks if the command could use more memory, in which case it's rejected when the
+  /// dataset is over `maxmemory` and no key can be evicted.
+  ///
+  /// Commands which only remove data, or change the expiry of keys, are always allowed, so
+  /// that memory can still be freed.
+  fn may_use_memory(&self) -> bool {
+    self.is_write()
+      && !matches!(
+        self,
+        Command::Del(_)
+          | Command::Unlink(_)
+          | Command::FlushAll(_)
+          | Command::GetDel(_)
+          | Command::GetEx(_)
+          | Command::SRem(_)
+          | Command::SPop(_)
+          | Command::ZRem(_)
+          | Command::ZPopMin(_)
+          | Command::ZPopMax(_)
+          | Command::Expire(_)
+          | Command::ExpireAt(_)
+          | Command::PExpireAt(_)
+          | Command::Persist(_)
+      )
+  }
+
+  /// Checks if executing the command updates the last access time of its keys.
+  ///
+  /// Commands which only inspect the metadata of keys leave them untouched.
+  fn touches_keys(&self) -> bool {
+    !matches!(
+      self,
+      Command::Ttl(_)
+        | Command::ExpireTime(_)
+        | Command::PExpireTime(_)
+        | Command::Object(_)
+        | Command::Memory(_)
+    )
+  }
+
+  /// Classifies the command by how it accesses the data stored in the DB.
+  ///
+  /// The match is exhaustive on purpose, so that every new command has to be classified.
+  pub fn kind(&self) -> CommandKind {
+    match self {
+      // GETEX only modifies the key when it's given an option to update its expiry.
+      Command::GetEx(getex) if getex.updates_expiry() => CommandKind::Write,
+      // DEBUG POPULATE writes keys, while the other DEBUG subcommands don't touch the DB.
+      Command::Debug(debug) if debug.is_write() => CommandKind::Write,
+      Command::Set(_)
+      | Command::Append(_)
+      | Command::Incr(_)
+      | Command::SetRange(_)
+      | Command::GetDel(_)
+      | Command::Del(_)
+      | Command::Unlink(_)
+      | Command::FlushAll(_)
+      | Command::LPush(_)
+      | Command::RPush(_)
+      | Command::SAdd(_)
+      | Command::SRem(_)
+      | Command::SInterStore(_)
+      | Command::SUnionStore(_)
+      | Command::SDiffStore(_)
+      | Command::SPop(_)
+      | Command::SMove(_)
+      | Command::Copy(_)
+      | Command::HSet(_)
+      | Command::PfAdd(_)
+      | Command::ZAdd(_)
+      | Command::ZRem(_)
+      | Command::ZIncrBy(_)
+      | Command::ZPopMin(_)
+      | Command::ZPopMax(_)
+      | Command::XAdd(_)
+      | Command::GeoAdd(_)
+      | Command::Expire(_)
+      | Command::ExpireAt(_)
+      | Command::Persist(_)
+      | Command::PExpireAt(_) => CommandKind::Write,
+      Command::Get(_)
+      | Command::MGet(_)
+      | Command::GetEx(_)
+      | Command::BitPos(_)
+      | Command::LRange(_)
+      | Command::LPos(_)
+      | Command::PfCount(_)
+      | Command::ZScan(_)
+      | Command::ZCard(_)
+      | Command::ZCount(_)
+      | Command::XLen(_)
+      | Command::XRange(_)
+      | Command::XRead(_)
+      | Command::GeoSearch(_)
+      | Command::SInterCard(_)
+      | Command::SMembers(_)
+      | Command::SUnion(_)
+      | Command::SScan(_)
+      | Command::SRandMember(_)
+      | Command::HRandField(_)
+      | Command::HGetAll(_)
+      | Command::HStrLen(_)
+      | Command::HScan(_)
+      | Command::Sort(_)
+      | Command::Ttl(_)
+      | Command::ExpireTime(_)
+      | Command::PExpireTime(_)
+      | Command::DbSize(_)
+      | Command::Object(_)
+      | Command::Memory(_) => CommandKind::ReadOnly,
+      Command::Ping(_)
+      | Command::Info(_)
+      | Command::Lolwut(_)
+      | Command::BgRewriteAof(_)
+      | Command::Client(_)
+      | Command::CommandInfo(_)
+      | Command::Hello(_)
+      | Command::Cluster(_)
+      | Command::Config(_)
+      | Command::ReplicaOf(_)
+      | Command::Role(_)
+      | Command::PSync(_)
+      | Command::ReplConf(_)
+      | Command::Wait(_)
+      | Command::Shutdown(_)
+      | Command::Debug(_)
+      | Command::Subscribe(_)
+      | Command::Unsubscribe(_)
+      | Command::PSubscribe(_)
+      | Command::PUnsubscribe(_)
+      | Command::Publish(_)
+      | Command::PubSub(_)
+      | Command::Multi
+      | Command::Exec
+      | Command::Discard
+      | Command::Watch(_)
+      | Command::Unwatch
+      | Command::Monitor => CommandKind::Other,
+    }
+  }
+
+  /// Checks if the command modifies the data stored in the DB.
+  ///
+  /// A replica rejects such commands from its clients, since its dataset
+  /// is only modified by its master. The same goes for a server in read-only mode.
+  /// These are also the commands which are propagated to the replicas.
+  pub fn is_write(&self) -> bool {
+    self.kind() == CommandKind::Write
+  }
+
+  /// Checks if the command reads the data stored in the DB, without modifying it.
+  pub fn is_readonly(&self) -> bool {
+    self.kind() == CommandKind::ReadOnly
+  }
+}
+
+/// The classification of commands by how they access the data stored in the DB.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommandKind {
+  /// Commands which modify the data.
+  Write,
+  /// Commands which only read the data, or the metadata of keys.
+  ReadOnly,
+  /// Commands which don't access the data, like the connection, pub/sub, replication and
+  /// server management commands.
+  Other,
 }
 
 /// Represents all possible errors that can occur during command parsing and execution.
@@ -148,6 +1478,50 @@ pub enum CommandError {
 pub struct ErrUnknownCommand {
     /// The name of the unknown command.
     pub cmd: String,
+    /// The first arguments of the command, up to `UNKNOWN_COMMAND_ARGS_LEN` characters
+    /// in total, as shown in the error.
+    pub args: Vec<String>,
+}
+
+/// The number of characters of the name and of the arguments of an unknown command shown
+/// in the error, same as Redis.
+const UNKNOWN_COMMAND_ARGS_LEN: usize = 128;
+
+impl ErrUnknownCommand {
+    /// Creates the error for the given command name and arguments. Only the beginning of
+    /// the arguments is kept.
+    fn new(cmd: String, args: &[RespType]) -> ErrUnknownCommand {
+        let mut shown = vec![];
+        let mut len = 0;
+        for arg in args.iter() {
+            if len >= UNKNOWN_COMMAND_ARGS_LEN {
+                break;
+            }
+
+            let arg = match arg {
+                RespType::BulkString(arg) => arg,
+                _ => continue,
+            };
+            let arg: String = arg.chars().take(UNKNOWN_COMMAND_ARGS_LEN - len).collect();
+            // Each argument is shown quoted and followed by a space.
+            len += arg.chars().count() + 3;
+            shown.push(arg);
+        }
+
+        ErrUnknownCommand { cmd, args: shown }
+    }
+}
+
+impl fmt::Display for ErrUnknownCommand {
+    /// Formats the error like Redis, e.g.
+    /// `unknown command 'FOO', with args beginning with: 'bar' 'baz' `. Line breaks are
+    /// replaced with spaces, since the error is sent as a simple error.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cmd: String = self.cmd.chars().take(UNKNOWN_COMMAND_ARGS_LEN).collect();
+        let args: String = self.args.iter().map(|arg| format!("'{}' ", arg)).collect();
+        let msg = format!("unknown command '{}', with args beginning with: {}", cmd, args);
+        msg.replace(['\r', '\n'], " ").fmt(f)
+    }
 }
 
 impl std::error::Error for CommandError {}
@@ -156,8 +1530,144 @@ impl fmt::Display for CommandError {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     match self {
       CommandError::InvalidFormat => "Invalid command format".fmt(f),
-      CommandError::UnknownCommand(e) => write!(f, "Unknown command: {}", e.cmd),
+      CommandError::UnknownCommand(e) => e.fmt(f),
       CommandError::Other(msg) => msg.as_str().fmt(f)
     }
   }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Parses a command given as its name and arguments.
+  fn parse(args: &[&str]) -> Command {
+    let frame = args.iter().map(|arg| RespType::BulkString(arg.to_string())).collect();
+    Command::from_resp_command_frame(frame).unwrap()
+  }
+
+  #[test]
+  fn empty_frames_are_rejected() {
+    assert!(matches!(
+      Command::from_resp_command_frame(vec![]),
+      Err(CommandError::InvalidFormat)
+    ));
+  }
+
+  #[test]
+  fn command_names_are_case_insensitive() {
+    assert!(matches!(parse(&["pInG"]), Command::Ping(_)));
+    assert!(matches!(parse(&["unlink", "k"]), Command::Unlink(_)));
+  }
+
+  #[test]
+  fn only_ascii_letters_are_folded_in_command_names() {
+    // The Kelvin sign is lowercased to an ASCII 'k' by Unicode case folding.
+    assert_eq!("UNLIN\u{212A}".to_lowercase(), "unlink");
+
+    let frame = vec![
+      RespType::BulkString(String::from("UNLIN\u{212A}")),
+      RespType::BulkString(String::from("k")),
+    ];
+    match Command::from_resp_command_frame(frame) {
+      Err(CommandError::UnknownCommand(e)) => assert_eq!(e.cmd, "UNLIN\u{212A}"),
+      res => panic!("unexpected result: {:?}", res),
+    }
+  }
+
+  /// Returns the error of an unknown command given as its name and arguments.
+  fn unknown_command_error(args: &[&str]) -> String {
+    let frame = args.iter().map(|arg| RespType::BulkString(arg.to_string())).collect();
+    match Command::from_resp_command_frame(frame) {
+      Err(e @ CommandError::UnknownCommand(_)) => e.to_string(),
+      res => panic!("unexpected result: {:?}", res),
+    }
+  }
+
+  #[test]
+  fn unknown_commands_are_shown_with_their_first_arguments() {
+    assert_eq!(
+      unknown_command_error(&["FOO", "bar", "baz"]),
+      "unknown command 'FOO', with args beginning with: 'bar' 'baz' "
+    );
+    assert_eq!(
+      unknown_command_error(&["FOO"]),
+      "unknown command 'FOO', with args beginning with: "
+    );
+    assert_eq!(
+      unknown_command_error(&["FOO", "a\r\nb"]),
+      "unknown command 'FOO', with args beginning with: 'a  b' "
+    );
+  }
+
+  #[test]
+  fn only_the_beginning_of_long_arguments_is_shown() {
+    let long = "x".repeat(200);
+    let error = unknown_command_error(&["FOO", &long, "next"]);
+    let expected = format!(
+      "unknown command 'FOO', with args beginning with: '{}' ",
+      "x".repeat(UNKNOWN_COMMAND_ARGS_LEN)
+    );
+    assert_eq!(error, expected);
+
+    // Arguments are shown until the limit is reached.
+    let args: Vec<String> = (0..100).map(|i| format!("{:02}", i)).collect();
+    let mut frame = vec!["FOO"];
+    frame.extend(args.iter().map(|arg| arg.as_str()));
+    let error = unknown_command_error(&frame);
+    let shown: String = args[..26].iter().map(|arg| format!("'{}' ", arg)).collect();
+    assert_eq!(error, format!("unknown command 'FOO', with args beginning with: {}", shown));
+  }
+
+  #[test]
+  fn commands_modifying_the_data_are_writes() {
+    for args in [
+      &["SET", "k", "v"][..],
+      &["LPUSH", "l", "a"],
+      &["RPUSH", "l", "a"],
+      &["DEL", "k"],
+      &["EXPIRE", "k", "10"],
+      &["GETEX", "k", "PERSIST"],
+      &["DEBUG", "POPULATE", "10"],
+    ] {
+      let cmd = parse(args);
+      assert_eq!(cmd.kind(), CommandKind::Write, "{:?}", args);
+      assert!(cmd.is_write(), "{:?}", args);
+      assert!(!cmd.is_readonly(), "{:?}", args);
+    }
+  }
+
+  #[test]
+  fn commands_reading_the_data_are_read_only() {
+    for args in [
+      &["GET", "k"][..],
+      &["LRANGE", "l", "0", "10"],
+      &["GETEX", "k"],
+      &["TTL", "k"],
+      &["DBSIZE"],
+    ] {
+      let cmd = parse(args);
+      assert_eq!(cmd.kind(), CommandKind::ReadOnly, "{:?}", args);
+      assert!(!cmd.is_write(), "{:?}", args);
+      assert!(cmd.is_readonly(), "{:?}", args);
+    }
+  }
+
+  #[test]
+  fn commands_not_accessing_the_data_are_neither() {
+    for args in [
+      &["PING"][..],
+      &["INFO"],
+      &["MULTI"],
+      &["PUBLISH", "ch", "hi"],
+      &["DEBUG", "SLEEP", "0"],
+      &["DEBUG", "STRINGMATCH-LEN", "*", "k"],
+      &["BGREWRITEAOF"],
+    ] {
+      let cmd = parse(args);
+      assert_eq!(cmd.kind(), CommandKind::Other, "{:?}", args);
+      assert!(!cmd.is_write(), "{:?}", args);
+      assert!(!cmd.is_readonly(), "{:?}", args);
+    }
+  }
+}
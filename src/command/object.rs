@@ -0,0 +1,116 @@
+// src/command/object.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{help, CommandError};
+
+/// Represents the OBJECT command in Nimblecache.
+///
+/// OBJECT is used to inspect the internals of the value stored against a key.
+/// Inspecting a key doesn't update its last access time.
+#[derive(Debug, Clone)]
+pub struct Object {
+    subcommand: ObjectSubcommand,
+}
+
+/// The supported OBJECT subcommands.
+#[derive(Debug, Clone)]
+enum ObjectSubcommand {
+    /// OBJECT ENCODING key
+    Encoding(String),
+    /// OBJECT IDLETIME key
+    IdleTime(String),
+    /// OBJECT HELP
+    Help,
+}
+
+impl Object {
+    /// Creates a new `Object` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the OBJECT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Object)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Object, CommandError> {
+        let subcommand = match args.as_slice() {
+            [RespType::BulkString(subcommand)] if subcommand.eq_ignore_ascii_case("help") => {
+                ObjectSubcommand::Help
+            }
+            [RespType::BulkString(subcommand), RespType::BulkString(key)] => {
+                match subcommand.to_lowercase().as_str() {
+                    "encoding" => ObjectSubcommand::Encoding(key.to_string()),
+                    "idletime" => ObjectSubcommand::IdleTime(key.to_string()),
+                    _ => {
+                        return Err(CommandError::Other(format!(
+                            "Unknown subcommand '{}' for 'OBJECT' command",
+                            subcommand
+                        )));
+                    }
+                }
+            }
+            [_, _] => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Subcommand and key must be bulk strings",
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Wrong number of arguments specified for 'OBJECT' command",
+                )));
+            }
+        };
+
+        Ok(Object { subcommand })
+    }
+
+    /// Executes the OBJECT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `OBJECT ENCODING` - The name of the encoding Redis would use for the value as a
+    ///   `BulkString`, or a `NullBulkString` if the key doesn't exist.
+    /// - `OBJECT IDLETIME` - The number of seconds since the key was last accessed as an `Integer`,
+    ///   or a `NullBulkString` if the key doesn't exist.
+    /// - `OBJECT HELP` - The usage of the subcommands as an `Array`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match &self.subcommand {
+            ObjectSubcommand::Encoding(key) => match db.encoding(key) {
+                Ok(Some(encoding)) => RespType::BulkString(String::from(encoding)),
+                Ok(None) => RespType::NullBulkString,
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            ObjectSubcommand::IdleTime(key) => match db.idle_time(key) {
+                Ok(Some(idle_time)) => RespType::Integer(idle_time as i64),
+                Ok(None) => RespType::NullBulkString,
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            },
+            ObjectSubcommand::Help => help::reply(
+                "OBJECT",
+                &[
+                    "ENCODING <key>",
+                    "    Return the kind of internal representation used in order to store the",
+                    "    value associated with a <key>.",
+                    "IDLETIME <key>",
+                    "    Return the idle time of the key, that is the approximated number of",
+                    "    seconds elapsed since the last access to the key.",
+                ],
+            ),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        match &self.subcommand {
+            ObjectSubcommand::Encoding(key) | ObjectSubcommand::IdleTime(key) => vec![key.clone()],
+            ObjectSubcommand::Help => vec![],
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// src/command/persist.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the PERSIST command in Nimblecache.
+///
+/// The `Persist` struct is used to remove the expiry of a key, so that it never expires.
+#[derive(Debug, Clone)]
+pub struct Persist {
+    key: String,
+}
+
+impl Persist {
+    /// Creates a new `Persist` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PERSIST command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Persist)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Persist, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'PERSIST' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(Persist { key })
+    }
+
+    /// Executes the PERSIST command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` - If the expiry was removed.
+    /// - `Integer(0)` - If the key doesn't exist, or has no expiry.
+    /// - `SimpleError` - If an error is encountered.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.persist(&self.key) {
+            Ok(true) => RespType::Integer(1),
+            Ok(false) => RespType::Integer(0),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(String::from("PERSIST")),
+            RespType::BulkString(self.key.clone()),
+        ])
+    }
+}
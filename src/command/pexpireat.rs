@@ -0,0 +1,121 @@
+// src/command/pexpireat.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ExpireCondition, DB},
+};
+
+use super::{
+    expire::{condition_args, parse_conditions},
+    CommandError,
+};
+
+/// Represents the PEXPIREAT command in Nimblecache.
+///
+/// The `PExpireAt` struct is used to set the absolute time (as a Unix timestamp in
+/// milliseconds) at which a key expires, optionally only if the condition given by one of
+/// the NX, XX, GT or LT options holds.
+#[derive(Debug, Clone)]
+pub struct PExpireAt {
+    key: String,
+    /// Expiry time as a Unix timestamp in milliseconds.
+    timestamp: i64,
+    /// Conditions which must all hold for the expiry to be set.
+    conditions: Vec<ExpireCondition>,
+}
+
+impl PExpireAt {
+    /// Creates a new `PExpireAt` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PEXPIREAT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PExpireAt)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PExpireAt, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'PEXPIREAT' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse timestamp
+        let timestamp = match &args[1] {
+            RespType::BulkString(t) => match t.parse::<i64>() {
+                Ok(t) => t,
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "Timestamp should be an integer",
+                    )));
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Timestamp must be an integer in bulk string format",
+                )));
+            }
+        };
+
+        // parse options
+        let conditions = parse_conditions(&args[2..])?;
+
+        Ok(PExpireAt {
+            key,
+            timestamp,
+            conditions,
+        })
+    }
+
+    /// Executes the PEXPIREAT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(1)` - If the expiry was set.
+    /// - `Integer(0)` - If the key doesn't exist, or the condition given by the options doesn't hold.
+    /// - `SimpleError` - If an error is encountered.
+    pub fn apply(&self, db: &DB) -> RespType {
+        // An expiry time in the past deletes the key.
+        let expires_at = self.timestamp.max(0) as u64;
+
+        match db.expire(&self.key, expires_at, &self.conditions) {
+            Ok(true) => RespType::Integer(1),
+            Ok(false) => RespType::Integer(0),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args = vec![
+            RespType::BulkString(String::from("PEXPIREAT")),
+            RespType::BulkString(self.key.clone()),
+            RespType::BulkString(self.timestamp.to_string()),
+        ];
+        args.extend(condition_args(&self.conditions));
+
+        RespType::Array(args)
+    }
+}
@@ -0,0 +1,71 @@
+// src/command/pexpiretime.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the PEXPIRETIME command in Nimblecache.
+///
+/// The `PExpireTime` struct is used to retrieve the absolute time at which a key expires,
+/// as a Unix timestamp in milliseconds.
+#[derive(Debug, Clone)]
+pub struct PExpireTime {
+    key: String,
+}
+
+impl PExpireTime {
+    /// Creates a new `PExpireTime` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PEXPIRETIME command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PExpireTime)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PExpireTime, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'PEXPIRETIME' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(PExpireTime { key })
+    }
+
+    /// Executes the PEXPIRETIME command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(-2)` - If the key doesn't exist.
+    /// - `Integer(-1)` - If the key exists, but has no expiry.
+    /// - The expiry time as a Unix timestamp in milliseconds as an `Integer`, otherwise.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.expiry(&self.key) {
+            Ok(None) => RespType::Integer(-2),
+            Ok(Some(None)) => RespType::Integer(-1),
+            Ok(Some(Some(expires_at))) => RespType::Integer(expires_at as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
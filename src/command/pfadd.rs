@@ -0,0 +1,99 @@
+// src/command/pfadd.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the PFADD command in Nimblecache.
+///
+/// The `PfAdd` struct is used to add elements to a HyperLogLog, which estimates the
+/// number of distinct elements added to it.
+#[derive(Debug, Clone)]
+pub struct PfAdd {
+    key: String,
+    elements: Vec<String>,
+}
+
+impl PfAdd {
+    /// Creates a new `PfAdd` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PFADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PfAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PfAdd, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'PFADD' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse elements
+        let mut elements: Vec<String> = vec![];
+        for arg in args[1..].iter() {
+            match arg {
+                RespType::BulkString(e) => elements.push(e.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Element must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(PfAdd {
+            key: key.to_string(),
+            elements,
+        })
+    }
+
+    /// Executes the PFADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// `1` if the HyperLogLog was created or its estimated cardinality changed, `0` otherwise.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.pfadd(&self.key, &self.elements) {
+            Ok(changed) => RespType::Integer(changed as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args: Vec<RespType> = vec![
+            RespType::BulkString(String::from("PFADD")),
+            RespType::BulkString(self.key.clone()),
+        ];
+
+        for element in self.elements.iter() {
+            args.push(RespType::BulkString(element.to_string()));
+        }
+
+        RespType::Array(args)
+    }
+}
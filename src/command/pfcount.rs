@@ -0,0 +1,71 @@
+// src/command/pfcount.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the PFCOUNT command in Nimblecache.
+///
+/// The `PfCount` struct is used to estimate the number of distinct elements added to
+/// one or more HyperLogLogs.
+#[derive(Debug, Clone)]
+pub struct PfCount {
+    keys: Vec<String>,
+}
+
+impl PfCount {
+    /// Creates a new `PfCount` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PFCOUNT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PfCount)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PfCount, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'PFCOUNT' command",
+            )));
+        }
+
+        // parse keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(PfCount { keys })
+    }
+
+    /// Executes the PFCOUNT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The estimated cardinality of the union of the HyperLogLogs as an `Integer`. Keys
+    /// which don't exist are treated as empty HyperLogLogs.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.pfcount(&self.keys) {
+            Ok(count) => RespType::Integer(count as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.clone()
+    }
+}
@@ -20,7 +20,7 @@ impl Ping {
   ///
   /// * `Ok(Ping)` if parsing succeeds.
   pub fn with_args(args: Vec<RespType>) -> Result<Ping, CommandError> {
-    if args.len() == 0 {
+    if args.is_empty() {
       return Ok(Ping { msg: None })
     }
 
@@ -46,4 +46,17 @@ impl Ping {
       RespType::SimpleString(String::from("PONG"))
     }
   }
+
+  /// Executes the PING command on a connection which is subscribed to channels.
+  ///
+  /// # Returns
+  ///
+  /// An array of the form `["pong", message]`, where message is an empty `BulkString`
+  /// if no message was provided.
+  pub fn apply_subscribed(&self) -> RespType {
+    RespType::Array(vec![
+      RespType::BulkString(String::from("pong")),
+      RespType::BulkString(self.msg.clone().unwrap_or_default()),
+    ])
+  }
 }
\ No newline at end of file
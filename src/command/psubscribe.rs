@@ -0,0 +1,75 @@
+// src/command/psubscribe.rs
+
+use crate::{pubsub::Subscriber, resp::types::RespType};
+
+use super::CommandError;
+
+/// Represents the PSUBSCRIBE command in Nimblecache.
+///
+/// PSUBSCRIBE subscribes the connection to the channels matching the given glob-style
+/// patterns. Since it changes the state of the connection, this command is handled by
+/// `FrameHandler.handle`.
+#[derive(Debug, Clone)]
+pub struct PSubscribe {
+    patterns: Vec<String>,
+}
+
+impl PSubscribe {
+    /// Creates a new `PSubscribe` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PSUBSCRIBE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PSubscribe)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PSubscribe, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'PSUBSCRIBE' command",
+            )));
+        }
+
+        let mut patterns: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(p) => patterns.push(p.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Pattern must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(PSubscribe { patterns })
+    }
+
+    /// Executes the PSUBSCRIBE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscriber` - The pub/sub state of the connection.
+    ///
+    /// # Returns
+    ///
+    /// One reply per pattern, in the order in which the patterns were given. Each reply
+    /// is a push of the form `["psubscribe", pattern, count]`, where count is the number
+    /// of channels and patterns the connection is subscribed to after subscribing to that
+    /// pattern.
+    pub fn apply(&self, subscriber: &mut Subscriber) -> Vec<RespType> {
+        self.patterns
+            .iter()
+            .map(|pattern| {
+                let count = subscriber.psubscribe(pattern);
+                RespType::Push(vec![
+                    RespType::BulkString(String::from("psubscribe")),
+                    RespType::BulkString(pattern.to_string()),
+                    RespType::Integer(count as i64),
+                ])
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,46 @@
+// src/command/psync.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Represents the PSYNC command in Nimblecache.
+///
+/// PSYNC is sent by a replica to start replicating from this server. Nimblecache always
+/// performs a full resync, so the replication id and offset sent by the replica are
+/// only validated, but not used.
+///
+/// The full resync involves streaming the dataset over the connection, so this command
+/// is handled by `FrameHandler.handle`.
+#[derive(Debug, Clone)]
+pub struct PSync;
+
+impl PSync {
+    /// Creates a new `PSync` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PSYNC command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PSync)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PSync, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'PSYNC' command",
+            )));
+        }
+
+        for arg in args.iter() {
+            if !matches!(arg, RespType::BulkString(_)) {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Replication id and offset must be bulk strings",
+                )));
+            }
+        }
+
+        Ok(PSync)
+    }
+}
@@ -0,0 +1,62 @@
+// src/command/publish.rs
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::CommandError;
+
+/// Represents the PUBLISH command in Nimblecache.
+///
+/// PUBLISH sends a message to all the connections subscribed to a channel.
+#[derive(Debug, Clone)]
+pub struct Publish {
+    channel: String,
+    message: String,
+}
+
+impl Publish {
+    /// Creates a new `Publish` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PUBLISH command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Publish)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Publish, CommandError> {
+        let (channel, message) = match args.as_slice() {
+            [RespType::BulkString(channel), RespType::BulkString(message)] => (channel, message),
+            [_, _] => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Channel and message must be bulk strings",
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Wrong number of arguments specified for 'PUBLISH' command",
+                )));
+            }
+        };
+
+        Ok(Publish {
+            channel: channel.to_string(),
+            message: message.to_string(),
+        })
+    }
+
+    /// Executes the PUBLISH command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the pub/sub state.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscribers which received the message as an `Integer`.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        let received = storage.pubsub().publish(&self.channel, &self.message);
+
+        RespType::Integer(received as i64)
+    }
+}
@@ -0,0 +1,135 @@
+// src/command/pubsub.rs
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::{help, CommandError};
+
+/// Represents the PUBSUB command in Nimblecache.
+///
+/// PUBSUB is used to inspect the state of the pub/sub subscriptions.
+#[derive(Debug, Clone)]
+pub struct PubSubCommand {
+    subcommand: PubSubSubcommand,
+}
+
+/// The supported PUBSUB subcommands.
+#[derive(Debug, Clone)]
+enum PubSubSubcommand {
+    /// PUBSUB CHANNELS [pattern]
+    Channels(Option<String>),
+    /// PUBSUB NUMSUB [channel ...]
+    NumSub(Vec<String>),
+    /// PUBSUB NUMPAT
+    NumPat,
+    /// PUBSUB HELP
+    Help,
+}
+
+impl PubSubCommand {
+    /// Creates a new `PubSubCommand` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PUBSUB command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PubSubCommand)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PubSubCommand, CommandError> {
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        if parsed.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'PUBSUB' command",
+            )));
+        }
+
+        let subcommand = match (parsed[0].to_lowercase().as_str(), &parsed[1..]) {
+            ("channels", []) => PubSubSubcommand::Channels(None),
+            ("channels", [pattern]) => PubSubSubcommand::Channels(Some(pattern.to_string())),
+            ("numsub", channels) => {
+                PubSubSubcommand::NumSub(channels.iter().map(|c| c.to_string()).collect())
+            }
+            ("numpat", []) => PubSubSubcommand::NumPat,
+            ("help", []) => PubSubSubcommand::Help,
+            ("channels" | "numpat" | "help", _) => {
+                return Err(CommandError::Other(format!(
+                    "Wrong number of arguments specified for 'PUBSUB {}' command",
+                    parsed[0].to_uppercase()
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Unknown subcommand '{}' for 'PUBSUB' command",
+                    parsed[0]
+                )));
+            }
+        };
+
+        Ok(PubSubCommand { subcommand })
+    }
+
+    /// Executes the PUBSUB command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the pub/sub state.
+    ///
+    /// # Returns
+    ///
+    /// - `PUBSUB CHANNELS` - The channels with at least one subscriber as an `Array`,
+    ///   optionally only the ones matching the pattern.
+    /// - `PUBSUB NUMSUB` - An `Array` of alternating channels and their number of subscribers.
+    /// - `PUBSUB NUMPAT` - The number of patterns subscribed to as an `Integer`.
+    /// - `PUBSUB HELP` - The usage of the subcommands as an `Array`.
+    ///
+    /// Only subscriptions to channels are counted by CHANNELS and NUMSUB, not the ones to
+    /// patterns, like in Redis.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        match &self.subcommand {
+            PubSubSubcommand::Channels(pattern) => RespType::Array(
+                storage
+                    .pubsub()
+                    .active_channels(pattern.as_deref())
+                    .into_iter()
+                    .map(RespType::BulkString)
+                    .collect(),
+            ),
+            PubSubSubcommand::NumSub(channels) => RespType::Array(
+                storage
+                    .pubsub()
+                    .subscriber_counts(channels)
+                    .into_iter()
+                    .flat_map(|(channel, count)| {
+                        [RespType::BulkString(channel), RespType::Integer(count as i64)]
+                    })
+                    .collect(),
+            ),
+            PubSubSubcommand::NumPat => RespType::Integer(storage.pubsub().pattern_count() as i64),
+            PubSubSubcommand::Help => help::reply(
+                "PUBSUB",
+                &[
+                    "CHANNELS [<pattern>]",
+                    "    Return the currently active channels matching a <pattern>",
+                    "    (default: '*').",
+                    "NUMPAT",
+                    "    Return number of subscriptions to patterns.",
+                    "NUMSUB [<channel> ...]",
+                    "    Return the number of subscribers for the specified channels, excluding",
+                    "    pattern subscriptions (default: no channels).",
+                ],
+            ),
+        }
+    }
+}
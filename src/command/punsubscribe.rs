@@ -0,0 +1,83 @@
+// src/command/punsubscribe.rs
+
+use crate::{pubsub::Subscriber, resp::types::RespType};
+
+use super::CommandError;
+
+/// Represents the PUNSUBSCRIBE command in Nimblecache.
+///
+/// PUNSUBSCRIBE unsubscribes the connection from the given patterns, or from all of
+/// them if no pattern is given. Since it changes the state of the connection, this
+/// command is handled by `FrameHandler.handle`.
+#[derive(Debug, Clone)]
+pub struct PUnsubscribe {
+    patterns: Vec<String>,
+}
+
+impl PUnsubscribe {
+    /// Creates a new `PUnsubscribe` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PUNSUBSCRIBE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PUnsubscribe)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PUnsubscribe, CommandError> {
+        let mut patterns: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(p) => patterns.push(p.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Pattern must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(PUnsubscribe { patterns })
+    }
+
+    /// Executes the PUNSUBSCRIBE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscriber` - The pub/sub state of the connection.
+    ///
+    /// # Returns
+    ///
+    /// One reply per pattern, of the form `["punsubscribe", pattern, count]`, where count is
+    /// the number of channels and patterns the connection is still subscribed to. If no
+    /// pattern was given and the connection isn't subscribed to any, a single reply with
+    /// a null pattern is returned.
+    pub fn apply(&self, subscriber: &mut Subscriber) -> Vec<RespType> {
+        let patterns = if self.patterns.is_empty() {
+            subscriber.patterns()
+        } else {
+            self.patterns.clone()
+        };
+
+        if patterns.is_empty() {
+            return vec![RespType::Push(vec![
+                RespType::BulkString(String::from("punsubscribe")),
+                RespType::NullBulkString,
+                RespType::Integer(subscriber.subscription_count() as i64),
+            ])];
+        }
+
+        patterns
+            .iter()
+            .map(|pattern| {
+                let count = subscriber.punsubscribe(pattern);
+                RespType::Push(vec![
+                    RespType::BulkString(String::from("punsubscribe")),
+                    RespType::BulkString(pattern.to_string()),
+                    RespType::Integer(count as i64),
+                ])
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,90 @@
+// src/command/replconf.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Represents the REPLCONF command in Nimblecache.
+///
+/// REPLCONF is exchanged between a master and its replicas to track how far each
+/// replica has processed the replication stream.
+#[derive(Debug, Clone)]
+pub struct ReplConf {
+    subcommand: ReplConfSubcommand,
+}
+
+/// The supported REPLCONF subcommands.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplConfSubcommand {
+    /// `REPLCONF ACK <offset>`, sent by a replica to acknowledge the replication offset
+    /// it has processed.
+    Ack(u64),
+    /// `REPLCONF GETACK *`, sent by a master to ask the replica for an acknowledgement.
+    GetAck,
+    /// Any other subcommand. These are only accepted, for compatibility.
+    Other,
+}
+
+impl ReplConf {
+    /// Creates a new `ReplConf` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the REPLCONF command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ReplConf)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ReplConf, CommandError> {
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        if parsed.is_empty() || !parsed.len().is_multiple_of(2) {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'REPLCONF' command",
+            )));
+        }
+
+        let subcommand = match parsed[0].to_lowercase().as_str() {
+            "ack" => match parsed[1].parse::<u64>() {
+                Ok(offset) => ReplConfSubcommand::Ack(offset),
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "Replication offset should be a non-negative integer",
+                    )));
+                }
+            },
+            "getack" => ReplConfSubcommand::GetAck,
+            _ => ReplConfSubcommand::Other,
+        };
+
+        Ok(ReplConf { subcommand })
+    }
+
+    /// Returns the subcommand of the REPLCONF command.
+    pub fn subcommand(&self) -> &ReplConfSubcommand {
+        &self.subcommand
+    }
+
+    /// Executes the REPLCONF command.
+    ///
+    /// Acknowledgements are handled by the replication links themselves, so here the
+    /// command is only accepted.
+    ///
+    /// # Returns
+    ///
+    /// An "OK" response as a `SimpleString`.
+    pub fn apply(&self) -> RespType {
+        RespType::SimpleString(String::from("OK"))
+    }
+}
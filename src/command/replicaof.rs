@@ -0,0 +1,83 @@
+// src/command/replicaof.rs
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::CommandError;
+
+/// Represents the REPLICAOF command (and its alias SLAVEOF) in Nimblecache.
+///
+/// `REPLICAOF host port` turns the server into a replica of the given master, while
+/// `REPLICAOF NO ONE` turns it back into a master.
+#[derive(Debug, Clone)]
+pub struct ReplicaOf {
+    /// Address of the new master. `None` if the server should stop replicating.
+    master: Option<(String, u16)>,
+}
+
+impl ReplicaOf {
+    /// Creates a new `ReplicaOf` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the REPLICAOF command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ReplicaOf)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ReplicaOf, CommandError> {
+        let (host, port) = match args.as_slice() {
+            [RespType::BulkString(host), RespType::BulkString(port)] => (host, port),
+            [_, _] => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Host and port must be bulk strings",
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Wrong number of arguments specified for 'REPLICAOF' command",
+                )));
+            }
+        };
+
+        if host.eq_ignore_ascii_case("no") && port.eq_ignore_ascii_case("one") {
+            return Ok(ReplicaOf { master: None });
+        }
+
+        let port = match port.parse::<u16>() {
+            Ok(p) => p,
+            Err(_) => {
+                return Err(CommandError::Other(String::from("Invalid master port")));
+            }
+        };
+
+        Ok(ReplicaOf {
+            master: Some((host.to_string(), port)),
+        })
+    }
+
+    /// Executes the REPLICAOF command.
+    ///
+    /// The link to the master is established in the background, so this command
+    /// returns immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the replication state.
+    ///
+    /// # Returns
+    ///
+    /// An "OK" response as a `SimpleString`.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        match &self.master {
+            Some((host, port)) => {
+                storage
+                    .replication()
+                    .replicate_from(host.clone(), *port, storage.clone());
+            }
+            None => storage.replication().promote(),
+        }
+
+        RespType::SimpleString(String::from("OK"))
+    }
+}
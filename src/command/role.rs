@@ -0,0 +1,95 @@
+// src/command/role.rs
+
+use crate::{
+    replication::{LinkState, Role},
+    resp::types::RespType,
+    storage::db::Storage,
+};
+
+use super::CommandError;
+
+/// Represents the ROLE command in Nimblecache.
+///
+/// ROLE reports the replication role of the server, along with the state of the
+/// replication from the point of view of that role.
+#[derive(Debug, Clone)]
+pub struct RoleCommand;
+
+impl RoleCommand {
+    /// Creates a new `RoleCommand` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ROLE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RoleCommand)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<RoleCommand, CommandError> {
+        if !args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'ROLE' command",
+            )));
+        }
+
+        Ok(RoleCommand)
+    }
+
+    /// Executes the ROLE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the replication state.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` which is either
+    ///
+    /// - `["master", <offset>, [[<ip>, <port>, <offset>], ...]]` on a master, listing the
+    ///   address each replica connected from and the offset it acknowledged, or
+    /// - `["slave", <master host>, <master port>, <link state>, <offset>]` on a replica.
+    ///   The offset is `-1` until the full resync with the master is done.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        let replication = storage.replication();
+
+        match replication.role() {
+            Role::Master => {
+                let replicas = replication
+                    .replicas()
+                    .into_iter()
+                    .map(|(addr, offset)| {
+                        RespType::Array(vec![
+                            RespType::BulkString(addr.ip().to_string()),
+                            RespType::BulkString(addr.port().to_string()),
+                            RespType::BulkString(offset.to_string()),
+                        ])
+                    })
+                    .collect();
+
+                RespType::Array(vec![
+                    RespType::BulkString(String::from("master")),
+                    RespType::Integer(replication.offset() as i64),
+                    RespType::Array(replicas),
+                ])
+            }
+            Role::Replica { host, port } => {
+                let link_state = replication.link_state();
+                let (state, offset) = match link_state {
+                    LinkState::Connect => ("connect", -1),
+                    LinkState::Connecting => ("connecting", -1),
+                    LinkState::Sync => ("sync", -1),
+                    LinkState::Connected => ("connected", replication.offset() as i64),
+                };
+
+                RespType::Array(vec![
+                    RespType::BulkString(String::from("slave")),
+                    RespType::BulkString(host),
+                    RespType::Integer(port as i64),
+                    RespType::BulkString(String::from(state)),
+                    RespType::Integer(offset),
+                ])
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 // src/command/rpush.rs
 
-use crate::{resp::types::RespType, storage::db::DB};
+use crate::{resp::types::RespType, storage::db::Storage};
 
 use super::CommandError;
 
@@ -63,18 +63,33 @@ impl RPush {
     ///
     /// # Arguments
     ///
-    /// * `db` - The database where the key and values are stored.
+    /// * `storage` - The shared storage, which holds the database and the maximum sizes of lists
+    ///   and values.
     ///
     /// # Returns
     ///
-    /// It returns the length of the list if value is successfully written.
-    pub fn apply(&self, db: &DB) -> RespType {
-        match db.rpush(self.key.clone(), self.values.clone()) {
+    /// It returns the length of the list if value is successfully written, or an error if
+    /// any of the values is over the configured maximum value size, or the list would grow
+    /// over the configured maximum size.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        if let Err(e) = storage.check_value_size(&self.values) {
+            return RespType::SimpleError(format!("{}", e));
+        }
+
+        let max_len = storage.list_max_size();
+        match storage.db().rpush(self.key.clone(), self.values.clone(), max_len) {
             Ok(len) => RespType::Integer(len as i64),
             Err(e) => RespType::SimpleError(format!("{}", e)),
         }
     }
 
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
     pub fn build_command(&self) -> RespType {
         let mut args: Vec<RespType> = vec![
             RespType::BulkString(String::from("RPUSH")),
@@ -0,0 +1,98 @@
+// src/command/sadd.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SADD command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct SAdd {
+    key: String,
+    members: Vec<String>,
+}
+
+impl SAdd {
+    /// Creates a new `SAdd` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SAdd, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SADD' command",
+            )));
+        }
+
+        // parse key
+        let key = &args[0];
+        let key = match key {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse members
+        let mut members: Vec<String> = vec![];
+        for arg in args[1..].iter() {
+            match arg {
+                RespType::BulkString(m) => members.push(m.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Member must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(SAdd {
+            key: key.to_string(),
+            members,
+        })
+    }
+
+    /// Executes the SADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of members that were added to the set, not including
+    /// the members already present in the set.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.sadd(self.key.clone(), self.members.clone()) {
+            Ok(added) => RespType::Integer(added as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args: Vec<RespType> = vec![
+            RespType::BulkString(String::from("SADD")),
+            RespType::BulkString(self.key.clone()),
+        ];
+
+        for member in self.members.iter() {
+            args.push(RespType::BulkString(member.to_string()));
+        }
+
+        RespType::Array(args)
+    }
+}
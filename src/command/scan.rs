@@ -0,0 +1,97 @@
+// src/command/scan.rs
+
+use crate::resp::types::RespType;
+
+use super::{glob, CommandError};
+
+/// The number of elements returned by each call of the SCAN family of commands, if
+/// `COUNT` option is not given.
+const DEFAULT_COUNT: usize = 10;
+
+/// The arguments shared by the SCAN family of commands, which follow the key.
+#[derive(Debug, Clone)]
+pub struct ScanArgs {
+    /// The cursor returned by the previous call, or `0` to start a new iteration.
+    pub cursor: u64,
+    /// Only the elements matching this glob-style pattern are returned, if given.
+    pattern: Option<String>,
+    /// The number of elements to look at in this call.
+    pub count: usize,
+}
+
+impl ScanArgs {
+    /// Parses the arguments in the form `cursor [MATCH pattern] [COUNT count]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The arguments following the key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ScanArgs)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn parse(args: &[RespType]) -> Result<ScanArgs, CommandError> {
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let cursor = match parsed[0].parse::<u64>() {
+            Ok(cursor) => cursor,
+            Err(_) => return Err(CommandError::Other(String::from("invalid cursor"))),
+        };
+
+        let mut scan_args = ScanArgs {
+            cursor,
+            pattern: None,
+            count: DEFAULT_COUNT,
+        };
+
+        // parse options
+        for option in parsed[1..].chunks(2) {
+            match option {
+                [name, pattern] if name.eq_ignore_ascii_case("match") => {
+                    scan_args.pattern = Some(pattern.to_string());
+                }
+                [name, count] if name.eq_ignore_ascii_case("count") => {
+                    match count.parse::<usize>() {
+                        Ok(count) if count > 0 => scan_args.count = count,
+                        Ok(_) => return Err(CommandError::Other(String::from("syntax error"))),
+                        Err(_) => {
+                            return Err(CommandError::Other(String::from(
+                                "value is not an integer or out of range",
+                            )))
+                        }
+                    }
+                }
+                _ => return Err(CommandError::Other(String::from("syntax error"))),
+            }
+        }
+
+        Ok(scan_args)
+    }
+
+    /// Checks if an element should be returned, according to the `MATCH` pattern.
+    pub fn matches(&self, element: &str) -> bool {
+        match &self.pattern {
+            Some(pattern) => glob::matches(pattern, element),
+            None => true,
+        }
+    }
+}
+
+/// Builds the reply of the SCAN family of commands, which is an `Array` of the cursor to
+/// continue the iteration with and an `Array` of the elements.
+pub fn reply(cursor: u64, elements: Vec<RespType>) -> RespType {
+    RespType::Array(vec![
+        RespType::BulkString(cursor.to_string()),
+        RespType::Array(elements),
+    ])
+}
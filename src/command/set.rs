@@ -1,8 +1,10 @@
 // src/command/set.rs
 
+use std::sync::OnceLock;
+
 use crate::{
   resp::types::RespType,
-  storage::db::{Value, DB},
+  storage::db::{current_time_millis, SetCondition, Storage, Value},
 };
 
 use super::CommandError;
@@ -15,13 +17,35 @@ use super::CommandError;
 pub struct Set {
   key: String,
   value: String,
+  /// The expiry given with the command. The default TTL of the server applies if `None`.
+  expiry: Option<SetExpiry>,
+  /// NX or XX, the condition under which the value is set.
+  condition: Option<SetCondition>,
+  /// Set by `GET`. The previous value of the key is then returned instead of "OK".
+  get: bool,
+  /// The expiry time computed when the command is applied, so that the same time is
+  /// propagated to the replicas.
+  expires_at: OnceLock<Option<u64>>,
+}
+
+/// The expiry options of the SET command.
+#[derive(Debug, Clone, Copy)]
+enum SetExpiry {
+  /// EX seconds or PX milliseconds, relative to the time the command is applied.
+  In(i64),
+  /// EXAT timestamp or PXAT timestamp, as a Unix timestamp in milliseconds.
+  At(i64),
+  /// KEEPTTL, which keeps the expiry the key had before, if any.
+  Keep,
 }
 
 impl Set {
   /// Creates a new `Set` instance from the given arguments.
   ///
   /// This function parses the arguments provided in the form of a `RespType` vector.
-  /// It validates and extracts the key and value for the SET command.
+  /// It validates and extracts the key and value for the SET command, followed by
+  /// the options in the form
+  /// `[NX | XX] [GET] [EX seconds | PX milliseconds | EXAT timestamp | PXAT timestamp | KEEPTTL]`.
   ///
   /// # Arguments
   ///
@@ -60,30 +84,194 @@ impl Set {
           }
       };
 
+      // parse options
+      let mut expiry: Option<SetExpiry> = None;
+      let mut condition: Option<SetCondition> = None;
+      let mut get = false;
+      let mut idx = 2;
+      while idx < args.len() {
+          let option = match &args[idx] {
+              RespType::BulkString(o) => o.to_lowercase(),
+              _ => return Err(CommandError::Other(String::from("syntax error"))),
+          };
+
+          let parsed_condition = match option.as_str() {
+              "nx" => Some(SetCondition::Nx),
+              "xx" => Some(SetCondition::Xx),
+              _ => None,
+          };
+          if let Some(parsed_condition) = parsed_condition {
+              // NX and XX can't be given together.
+              if condition.is_some_and(|c| c != parsed_condition) {
+                  return Err(CommandError::Other(String::from("syntax error")));
+              }
+              condition = Some(parsed_condition);
+              idx += 1;
+              continue;
+          }
+          if option == "get" {
+              get = true;
+              idx += 1;
+              continue;
+          }
+
+          // Only one of the expiry options may be given.
+          if expiry.is_some() {
+              return Err(CommandError::Other(String::from("syntax error")));
+          }
+
+          if option == "keepttl" {
+              expiry = Some(SetExpiry::Keep);
+              idx += 1;
+              continue;
+          }
+
+          let time = match args.get(idx + 1) {
+              Some(RespType::BulkString(time)) => time,
+              _ => return Err(CommandError::Other(String::from("syntax error"))),
+          };
+          let time = match time.parse::<i64>() {
+              Ok(time) => time,
+              Err(_) => {
+                  return Err(CommandError::Other(String::from(
+                      "value is not an integer or out of range",
+                  )));
+              }
+          };
+
+          let parsed = match option.as_str() {
+              "ex" => time.checked_mul(1000).map(SetExpiry::In),
+              "px" => Some(SetExpiry::In(time)),
+              "exat" => time.checked_mul(1000).map(SetExpiry::At),
+              "pxat" => Some(SetExpiry::At(time)),
+              _ => return Err(CommandError::Other(String::from("syntax error"))),
+          };
+
+          expiry = match parsed {
+              Some(parsed) if time > 0 => Some(parsed),
+              _ => {
+                  return Err(CommandError::Other(String::from(
+                      "invalid expire time in 'set' command",
+                  )));
+              }
+          };
+          idx += 2;
+      }
+
       Ok(Set {
           key: key.to_string(),
           value,
+          expiry,
+          condition,
+          get,
+          expires_at: OnceLock::new(),
+      })
+  }
+
+  /// Returns the time at which the key expires, as a Unix timestamp in milliseconds, or
+  /// `None` if the key doesn't expire or, with `KEEPTTL`, keeps its previous expiry.
+  ///
+  /// Keys written without an expiry get the default TTL of the server, if any. Replicas
+  /// don't apply it, since the master propagates the expiry along with the command.
+  ///
+  /// It's computed the first time it's called, and the same time is returned afterwards.
+  fn expires_at(&self, storage: &Storage) -> Option<u64> {
+      *self.expires_at.get_or_init(|| {
+          let ttl = match self.expiry {
+              Some(SetExpiry::At(ms)) => return Some(ms as u64),
+              Some(SetExpiry::Keep) => return None,
+              Some(SetExpiry::In(ms)) => ms,
+              None if storage.replication().is_replica() => return None,
+              None => match storage.default_ttl() {
+                  0 => return None,
+                  seconds => seconds.saturating_mul(1000).min(i64::MAX as u64) as i64,
+              },
+          };
+
+          ttl.checked_add(current_time_millis() as i64)
+              .map(|expires_at| expires_at as u64)
       })
   }
 
   /// Executes the SET command.
   ///
-  /// This method writes the string value to the database under the specified key.
+  /// This method writes the string value to the database under the specified key,
+  /// along with its expiry.
   /// If the operation is successful, it returns an "OK" response as a `BulkString`.
   /// If the operation fails, it returns an error response.
   ///
   /// # Arguments
   ///
-  /// * `db` - A reference to the `DB` instance where the key-value pair should be stored.
+  /// * `storage` - The shared storage, which holds the database, the default TTL and the
+  ///   maximum size of values.
   ///
   /// # Returns
   ///
   /// * `BulkString("OK")` - If the value is successfully written.
-  /// * `SimpleError` - If the operation fails due to some error.
-  pub fn apply(&self, db: &DB) -> RespType {
-      match db.set(self.key.clone(), Value::String(self.value.clone())) {
-          Ok(_) => RespType::BulkString("OK".to_string()),
+  /// * `NullBulkString` - If the value isn't written because the NX or XX condition
+  ///   doesn't hold.
+  /// * `BulkString` or `NullBulkString` - With `GET`, the previous value of the key, whether
+  ///   the value is written or not.
+  /// * `SimpleError` - If the value is over the configured maximum value size, or the
+  ///   operation fails due to some error.
+  pub fn apply(&self, storage: &Storage) -> RespType {
+      if let Err(e) = storage.check_value_size(std::slice::from_ref(&self.value)) {
+          return RespType::SimpleError(format!("{}", e));
+      }
+
+      let keep_ttl = matches!(self.expiry, Some(SetExpiry::Keep));
+      let expires_at = match self.expires_at(storage) {
+          Some(expires_at) => Some(expires_at),
+          None if self.expiry.is_some() && !keep_ttl => {
+              return RespType::SimpleError(String::from("invalid expire time in 'set' command"));
+          }
+          None => None,
+      };
+
+      let value = Value::String(self.value.clone());
+      match storage
+          .db()
+          .set(self.key.clone(), value, expires_at, keep_ttl, self.condition)
+      {
+          Ok((_, Some(previous))) if self.get => RespType::BulkString(previous),
+          Ok((_, None)) if self.get => RespType::NullBulkString,
+          Ok((true, _)) => RespType::BulkString("OK".to_string()),
+          Ok((false, _)) => RespType::NullBulkString,
           Err(e) => RespType::SimpleError(format!("{}", e)),
       }
   }
+
+  /// Returns the keys accessed by the command.
+  pub fn keys(&self) -> Vec<String> {
+      vec![self.key.clone()]
+  }
+
+  /// Builds the RESP command which performs the same operation, to be propagated
+  /// to the replicas.
+  ///
+  /// The expiry, including the default TTL, is propagated as PXAT with the absolute
+  /// expiry time computed when the command was applied, so that the key doesn't expire later
+  /// on the replicas. `GET` only changes the reply, so it's left out.
+  pub fn build_command(&self, storage: &Storage) -> RespType {
+      let mut cmd = vec![
+          RespType::BulkString(String::from("SET")),
+          RespType::BulkString(self.key.clone()),
+          RespType::BulkString(self.value.clone()),
+      ];
+
+      match self.condition {
+          Some(SetCondition::Nx) => cmd.push(RespType::BulkString(String::from("NX"))),
+          Some(SetCondition::Xx) => cmd.push(RespType::BulkString(String::from("XX"))),
+          None => {}
+      }
+
+      if let Some(expires_at) = self.expires_at(storage) {
+          cmd.push(RespType::BulkString(String::from("PXAT")));
+          cmd.push(RespType::BulkString(expires_at.to_string()));
+      } else if let Some(SetExpiry::Keep) = self.expiry {
+          cmd.push(RespType::BulkString(String::from("KEEPTTL")));
+      }
+
+      RespType::Array(cmd)
+  }
 }
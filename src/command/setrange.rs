@@ -0,0 +1,100 @@
+// src/command/setrange.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SETRANGE command in Nimblecache.
+///
+/// The `SetRange` struct is used to overwrite part of the string stored against a key,
+/// starting at a byte offset.
+#[derive(Debug, Clone)]
+pub struct SetRange {
+    key: String,
+    offset: usize,
+    value: String,
+}
+
+impl SetRange {
+    /// Creates a new `SetRange` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key offset value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SETRANGE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SetRange)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SetRange, CommandError> {
+        if args.len() != 3 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SETRANGE' command",
+            )));
+        }
+
+        let (key, offset, value) = match (&args[0], &args[1], &args[2]) {
+            (
+                RespType::BulkString(key),
+                RespType::BulkString(offset),
+                RespType::BulkString(value),
+            ) => (key, offset, value),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Arguments must be bulk strings",
+                )));
+            }
+        };
+
+        let offset = match offset.parse::<i64>() {
+            Ok(offset) if offset >= 0 => offset as usize,
+            Ok(_) => return Err(CommandError::Other(String::from("offset is out of range"))),
+            Err(_) => {
+                return Err(CommandError::Other(String::from(
+                    "value is not an integer or out of range",
+                )));
+            }
+        };
+
+        Ok(SetRange {
+            key: key.to_string(),
+            offset,
+            value: value.to_string(),
+        })
+    }
+
+    /// Executes the SETRANGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer` - The length of the string after it's modified.
+    /// - `SimpleError` - If an error is encountered.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.setrange(&self.key, self.offset, &self.value) {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(String::from("SETRANGE")),
+            RespType::BulkString(self.key.clone()),
+            RespType::BulkString(self.offset.to_string()),
+            RespType::BulkString(self.value.clone()),
+        ])
+    }
+}
@@ -0,0 +1,68 @@
+// src/command/shutdown.rs
+
+use log::info;
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::CommandError;
+
+/// Represents the SHUTDOWN command in Nimblecache.
+///
+/// SHUTDOWN stops the server from accepting new connections and exits the process.
+/// Since the dataset isn't persisted yet, `SAVE`, `NOSAVE` and the default behave the same.
+///
+/// On success no response is sent, the connection is closed instead. Hence this command
+/// is handled by `FrameHandler.handle`.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    /// The save modifier passed to the command, if any.
+    save: Option<bool>,
+}
+
+impl Shutdown {
+    /// Creates a new `Shutdown` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SHUTDOWN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Shutdown)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Shutdown, CommandError> {
+        let save = match args.as_slice() {
+            [] => None,
+            [RespType::BulkString(modifier)] => match modifier.to_lowercase().as_str() {
+                "save" => Some(true),
+                "nosave" => Some(false),
+                _ => return Err(CommandError::Other(String::from("syntax error"))),
+            },
+            [_] => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Modifier must be a bulk string",
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Wrong number of arguments specified for 'SHUTDOWN' command",
+                )));
+            }
+        };
+
+        Ok(Shutdown { save })
+    }
+
+    /// Executes the SHUTDOWN command, by signalling the server to shut down.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the shutdown signal of the server.
+    pub fn apply(&self, storage: &Storage) {
+        info!("User requested shutdown");
+        if self.save == Some(true) {
+            info!("Persistence is not configured, skipping the save");
+        }
+        storage.shutdown();
+    }
+}
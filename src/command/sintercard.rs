@@ -0,0 +1,128 @@
+// src/command/sintercard.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SINTERCARD command in Nimblecache.
+///
+/// The `SInterCard` struct is used to count the members in the intersection of
+/// multiple sets, without sending the intersection itself back to the client.
+#[derive(Debug, Clone)]
+pub struct SInterCard {
+    keys: Vec<String>,
+    /// Counting stops once this value is reached. `0` means no limit.
+    limit: usize,
+}
+
+impl SInterCard {
+    /// Creates a new `SInterCard` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `numkeys key [key ...] [LIMIT limit]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SINTERCARD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SInterCard)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SInterCard, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SINTERCARD' command",
+            )));
+        }
+
+        // parse number of keys
+        let numkeys = match &args[0] {
+            RespType::BulkString(n) => match n.parse::<i64>() {
+                Ok(n) => n,
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "Number of keys should be an integer",
+                    )))
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+        };
+
+        if numkeys <= 0 {
+            return Err(CommandError::Other(String::from(
+                "Number of keys should be greater than 0",
+            )));
+        }
+
+        let numkeys = numkeys as usize;
+        if numkeys > args.len() - 1 {
+            return Err(CommandError::Other(String::from(
+                "Number of keys can't be greater than number of args",
+            )));
+        }
+
+        // parse keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args[1..=numkeys].iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        // parse the optional LIMIT
+        let options = &args[numkeys + 1..];
+        let limit = match options {
+            [] => 0,
+            [RespType::BulkString(opt), RespType::BulkString(limit)]
+                if opt.eq_ignore_ascii_case("limit") =>
+            {
+                match limit.parse::<usize>() {
+                    Ok(l) => l,
+                    Err(_) => {
+                        return Err(CommandError::Other(String::from(
+                            "LIMIT should be a non-negative integer",
+                        )))
+                    }
+                }
+            }
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Syntax error in 'SINTERCARD' command",
+                )));
+            }
+        };
+
+        Ok(SInterCard { keys, limit })
+    }
+
+    /// Executes the SINTERCARD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of members in the intersection of the sets, capped at the
+    /// given limit. If any of the keys doesn't exist, `0` is returned.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.sintercard(&self.keys, self.limit) {
+            Ok(card) => RespType::Integer(card as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.clone()
+    }
+}
@@ -0,0 +1,66 @@
+// src/command/smembers.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SMEMBERS command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct SMembers {
+    key: String,
+}
+
+impl SMembers {
+    /// Creates a new `SMembers` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SMEMBERS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SMembers)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SMembers, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SMEMBERS' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(SMembers {
+            key: key.to_string(),
+        })
+    }
+
+    /// Executes the SMEMBERS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// A `Set` of the members of the set. It's empty if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.smembers(&self.key) {
+            Ok(members) => RespType::Set(members.into_iter().map(RespType::BulkString).collect()),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
@@ -0,0 +1,86 @@
+// src/command/smove.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SMOVE command in Nimblecache.
+///
+/// The `SMove` struct is used to atomically move a member from one set to another.
+#[derive(Debug, Clone)]
+pub struct SMove {
+    source: String,
+    destination: String,
+    member: String,
+}
+
+impl SMove {
+    /// Creates a new `SMove` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SMOVE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SMove)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SMove, CommandError> {
+        let (source, destination, member) = match args.as_slice() {
+            [
+                RespType::BulkString(source),
+                RespType::BulkString(destination),
+                RespType::BulkString(member),
+            ] => (source, destination, member),
+            [_, _, _] => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Keys and member must be bulk strings",
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Wrong number of arguments specified for 'SMOVE' command",
+                )));
+            }
+        };
+
+        Ok(SMove {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            member: member.to_string(),
+        })
+    }
+
+    /// Executes the SMOVE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// `1` if the member was moved, or `0` if it wasn't a member of the source set,
+    /// as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.smove(&self.source, &self.destination, &self.member) {
+            Ok(moved) => RespType::Integer(moved as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.source.clone(), self.destination.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(String::from("SMOVE")),
+            RespType::BulkString(self.source.clone()),
+            RespType::BulkString(self.destination.clone()),
+            RespType::BulkString(self.member.clone()),
+        ])
+    }
+}
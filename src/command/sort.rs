@@ -0,0 +1,248 @@
+// src/command/sort.rs
+
+use std::cmp::Ordering;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SORT command in Nimblecache.
+///
+/// The `Sort` struct is used to retrieve the elements of a list (or set) in sorted order.
+/// Elements are compared as numbers by default, or lexicographically with the ALPHA option.
+#[derive(Debug, Clone)]
+pub struct Sort {
+    key: String,
+    /// Sort in descending order.
+    desc: bool,
+    /// Sort lexicographically instead of numerically.
+    alpha: bool,
+    /// Offset and count of the elements to be returned.
+    limit: Option<(usize, Option<usize>)>,
+}
+
+impl Sort {
+    /// Creates a new `Sort` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key [ASC|DESC] [ALPHA] [LIMIT offset count]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SORT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Sort)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Sort, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SORT' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse options
+        let mut desc = false;
+        let mut alpha = false;
+        let mut limit = None;
+        let mut options = args[1..].iter();
+        while let Some(option) = options.next() {
+            let option = match option {
+                RespType::BulkString(o) => o,
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Option must be a bulk string",
+                    )));
+                }
+            };
+
+            match option.to_lowercase().as_str() {
+                "asc" => desc = false,
+                "desc" => desc = true,
+                "alpha" => alpha = true,
+                "limit" => {
+                    let (offset, count) = match (options.next(), options.next()) {
+                        (Some(RespType::BulkString(o)), Some(RespType::BulkString(c))) => {
+                            match (o.parse::<i64>(), c.parse::<i64>()) {
+                                (Ok(o), Ok(c)) => (o, c),
+                                _ => {
+                                    return Err(CommandError::Other(String::from(
+                                        "LIMIT offset and count should be integers",
+                                    )));
+                                }
+                            }
+                        }
+                        _ => {
+                            return Err(CommandError::Other(String::from(
+                                "Syntax error in 'SORT' command",
+                            )));
+                        }
+                    };
+
+                    // A negative offset is treated as 0, and a negative count as no limit.
+                    let offset = offset.max(0) as usize;
+                    let count = if count < 0 { None } else { Some(count as usize) };
+                    limit = Some((offset, count));
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Syntax error in 'SORT' command",
+                    )));
+                }
+            }
+        }
+
+        Ok(Sort {
+            key,
+            desc,
+            alpha,
+            limit,
+        })
+    }
+
+    /// Executes the SORT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The sorted elements as an `Array`, sliced as per the LIMIT option. An empty `Array`
+    /// if the key doesn't exist. A `SimpleError` if an element can't be parsed as a number
+    /// while sorting numerically.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let elems = match db.elements(&self.key) {
+            Ok(elems) => elems,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let mut elems = if self.alpha {
+            let mut elems = elems;
+            elems.sort();
+            elems
+        } else {
+            let mut scored: Vec<(f64, String)> = vec![];
+            for elem in elems {
+                match elem.trim().parse::<f64>() {
+                    Ok(score) if !score.is_nan() => scored.push((score, elem)),
+                    _ => {
+                        return RespType::SimpleError(String::from(
+                            "One or more scores can't be converted into double",
+                        ));
+                    }
+                }
+            }
+            scored.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+            scored.into_iter().map(|(_, elem)| elem).collect()
+        };
+
+        if self.desc {
+            elems.reverse();
+        }
+
+        let elems: Vec<String> = match self.limit {
+            Some((offset, count)) => elems
+                .into_iter()
+                .skip(offset)
+                .take(count.unwrap_or(usize::MAX))
+                .collect(),
+            None => elems,
+        };
+
+        RespType::Array(elems.into_iter().map(RespType::BulkString).collect())
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a DB holding the given elements in the list `l`.
+    fn db_with_list(elems: &[&str]) -> DB {
+        let db = DB::new();
+        let elems = elems.iter().map(|elem| elem.to_string()).collect();
+        db.rpush(String::from("l"), elems, 0).unwrap();
+        db
+    }
+
+    /// Runs SORT with the given arguments against the DB, and returns the sorted elements
+    /// or the error message.
+    fn sort(db: &DB, args: &[&str]) -> Result<Vec<String>, String> {
+        let args = args.iter().map(|arg| RespType::BulkString(arg.to_string())).collect();
+        match Sort::with_args(args).unwrap().apply(db) {
+            RespType::Array(elems) => Ok(elems
+                .into_iter()
+                .map(|elem| match elem {
+                    RespType::BulkString(elem) => elem,
+                    elem => panic!("unexpected element: {:?}", elem),
+                })
+                .collect()),
+            RespType::SimpleError(e) => Err(e),
+            reply => panic!("unexpected reply: {:?}", reply),
+        }
+    }
+
+    fn sorted(elems: &[&str]) -> Result<Vec<String>, String> {
+        Ok(elems.iter().map(|elem| elem.to_string()).collect())
+    }
+
+    #[test]
+    fn elements_are_sorted_numerically_by_default() {
+        let db = db_with_list(&["10", "2", "-1.5", "3e1", "2"]);
+        assert_eq!(sort(&db, &["l"]), sorted(&["-1.5", "2", "2", "10", "3e1"]));
+        assert_eq!(sort(&db, &["l", "ASC"]), sorted(&["-1.5", "2", "2", "10", "3e1"]));
+    }
+
+    #[test]
+    fn non_numeric_elements_are_rejected_without_alpha() {
+        let db = db_with_list(&["1", "b", "2"]);
+        assert_eq!(
+            sort(&db, &["l"]),
+            Err(String::from("One or more scores can't be converted into double"))
+        );
+    }
+
+    #[test]
+    fn alpha_sorts_lexicographically() {
+        let db = db_with_list(&["banana", "10", "apple", "2"]);
+        assert_eq!(sort(&db, &["l", "ALPHA"]), sorted(&["10", "2", "apple", "banana"]));
+    }
+
+    #[test]
+    fn desc_reverses_the_order() {
+        let db = db_with_list(&["10", "2", "30"]);
+        assert_eq!(sort(&db, &["l", "DESC"]), sorted(&["30", "10", "2"]));
+        assert_eq!(sort(&db, &["l", "DESC", "ALPHA"]), sorted(&["30", "2", "10"]));
+    }
+
+    #[test]
+    fn limit_slices_the_sorted_elements() {
+        let db = db_with_list(&["5", "3", "1", "4", "2"]);
+        assert_eq!(sort(&db, &["l", "LIMIT", "1", "2"]), sorted(&["2", "3"]));
+        assert_eq!(sort(&db, &["l", "DESC", "LIMIT", "0", "3"]), sorted(&["5", "4", "3"]));
+        assert_eq!(sort(&db, &["l", "LIMIT", "3", "-1"]), sorted(&["4", "5"]));
+        assert_eq!(sort(&db, &["l", "LIMIT", "10", "2"]), sorted(&[]));
+    }
+
+    #[test]
+    fn missing_keys_sort_to_an_empty_array() {
+        let db = DB::new();
+        assert_eq!(sort(&db, &["missing"]), sorted(&[]));
+    }
+}
@@ -0,0 +1,127 @@
+// src/command/spop.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SPOP command in Nimblecache.
+///
+/// The `SPop` struct is used to remove random members from a set and return them.
+#[derive(Debug, Clone)]
+pub struct SPop {
+    key: String,
+    /// The maximum number of members to be removed. `None` removes a single member.
+    count: Option<usize>,
+}
+
+impl SPop {
+    /// Creates a new `SPop` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SPOP command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SPop)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SPop, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SPOP' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse count
+        let count = match args.get(1) {
+            Some(RespType::BulkString(c)) => match c.parse::<usize>() {
+                Ok(c) => Some(c),
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "value is out of range, must be positive",
+                    )))
+                }
+            },
+            Some(_) => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+            None => None,
+        };
+
+        Ok(SPop {
+            key: key.to_string(),
+            count,
+        })
+    }
+
+    /// Executes the SPOP command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// Without a count, the removed member as a `BulkString`, or a `NullBulkString` if the
+    /// key doesn't exist. With a count, an `Array` of the removed members.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let members = match db.spop(&self.key, self.count.unwrap_or(1)) {
+            Ok(members) => members,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        match self.count {
+            Some(_) => RespType::Array(members.into_iter().map(RespType::BulkString).collect()),
+            None => match members.into_iter().next() {
+                Some(member) => RespType::BulkString(member),
+                None => RespType::NullBulkString,
+            },
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    ///
+    /// The members are picked at random, so the command is propagated as an SREM of
+    /// the members which were removed, taken from the result of the command.
+    ///
+    /// # Returns
+    ///
+    /// The SREM command, or `None` if no member was removed.
+    pub fn build_command(&self, res: &RespType) -> Option<RespType> {
+        let members: Vec<RespType> = match res {
+            RespType::BulkString(member) => vec![RespType::BulkString(member.to_string())],
+            RespType::Array(members) => members.clone(),
+            _ => vec![],
+        };
+
+        if members.is_empty() {
+            return None;
+        }
+
+        let mut args: Vec<RespType> = vec![
+            RespType::BulkString(String::from("SREM")),
+            RespType::BulkString(self.key.clone()),
+        ];
+        args.extend(members);
+
+        Some(RespType::Array(args))
+    }
+}
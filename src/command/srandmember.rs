@@ -0,0 +1,99 @@
+// src/command/srandmember.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SRANDMEMBER command in Nimblecache.
+///
+/// The `SRandMember` struct is used to get random members of a set, without removing them.
+#[derive(Debug, Clone)]
+pub struct SRandMember {
+    key: String,
+    /// If positive, up to `count` distinct members are returned. If negative, `-count`
+    /// members are returned, possibly with repeats. `None` returns a single member.
+    count: Option<i64>,
+}
+
+impl SRandMember {
+    /// Creates a new `SRandMember` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SRANDMEMBER command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SRandMember)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SRandMember, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SRANDMEMBER' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse count
+        let count = match args.get(1) {
+            Some(RespType::BulkString(c)) => match c.parse::<i64>() {
+                Ok(c) => Some(c),
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "value is not an integer or out of range",
+                    )))
+                }
+            },
+            Some(_) => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+            None => None,
+        };
+
+        Ok(SRandMember {
+            key: key.to_string(),
+            count,
+        })
+    }
+
+    /// Executes the SRANDMEMBER command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// Without a count, a random member as a `BulkString`, or a `NullBulkString` if the
+    /// key doesn't exist. With a count, an `Array` of random members.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let members = match db.srandmember(&self.key, self.count.unwrap_or(1)) {
+            Ok(members) => members,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        match self.count {
+            Some(_) => RespType::Array(members.into_iter().map(RespType::BulkString).collect()),
+            None => match members.into_iter().next() {
+                Some(member) => RespType::BulkString(member),
+                None => RespType::NullBulkString,
+            },
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
@@ -0,0 +1,98 @@
+// src/command/srem.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SREM command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct SRem {
+    key: String,
+    members: Vec<String>,
+}
+
+impl SRem {
+    /// Creates a new `SRem` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SREM command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SRem)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SRem, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SREM' command",
+            )));
+        }
+
+        // parse key
+        let key = &args[0];
+        let key = match key {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse members
+        let mut members: Vec<String> = vec![];
+        for arg in args[1..].iter() {
+            match arg {
+                RespType::BulkString(m) => members.push(m.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Member must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(SRem {
+            key: key.to_string(),
+            members,
+        })
+    }
+
+    /// Executes the SREM command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of members that were removed from the set, not including
+    /// the members which weren't present in the set.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.srem(&self.key, &self.members) {
+            Ok(removed) => RespType::Integer(removed as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args: Vec<RespType> = vec![
+            RespType::BulkString(String::from("SREM")),
+            RespType::BulkString(self.key.clone()),
+        ];
+
+        for member in self.members.iter() {
+            args.push(RespType::BulkString(member.to_string()));
+        }
+
+        RespType::Array(args)
+    }
+}
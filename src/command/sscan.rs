@@ -0,0 +1,83 @@
+// src/command/sscan.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{scan::{self, ScanArgs}, CommandError};
+
+/// Represents the SSCAN command in Nimblecache.
+///
+/// The `SScan` struct is used to iterate over the members of the set stored at a key in batches,
+/// using a cursor returned by the previous call.
+#[derive(Debug, Clone)]
+pub struct SScan {
+    key: String,
+    args: ScanArgs,
+}
+
+impl SScan {
+    /// Creates a new `SScan` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key cursor [MATCH pattern] [COUNT count]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SSCAN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SScan)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SScan, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SSCAN' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(SScan {
+            key: key.to_string(),
+            args: ScanArgs::parse(&args[1..])?,
+        })
+    }
+
+    /// Executes the SSCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` of the cursor to continue the iteration with, which is `0` once the
+    /// iteration is complete, and an `Array` of the members in the batch. The `MATCH` pattern
+    /// is applied after the batch is retrieved, so the batch can be empty before the end of
+    /// the iteration.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.sscan(&self.key, self.args.cursor, self.args.count) {
+            Ok((cursor, batch)) => {
+                let elements = batch
+                    .into_iter()
+                    .filter(|member| self.args.matches(member))
+                    .map(RespType::BulkString)
+                    .collect();
+                scan::reply(cursor, elements)
+            }
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
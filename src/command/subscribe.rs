@@ -0,0 +1,74 @@
+// src/command/subscribe.rs
+
+use crate::{pubsub::Subscriber, resp::types::RespType};
+
+use super::CommandError;
+
+/// Represents the SUBSCRIBE command in Nimblecache.
+///
+/// SUBSCRIBE subscribes the connection to the given channels. Since it changes the
+/// state of the connection, this command is handled by `FrameHandler.handle`.
+#[derive(Debug, Clone)]
+pub struct Subscribe {
+    channels: Vec<String>,
+}
+
+impl Subscribe {
+    /// Creates a new `Subscribe` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SUBSCRIBE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Subscribe)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Subscribe, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SUBSCRIBE' command",
+            )));
+        }
+
+        let mut channels: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(c) => channels.push(c.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Channel must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(Subscribe { channels })
+    }
+
+    /// Executes the SUBSCRIBE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscriber` - The pub/sub state of the connection.
+    ///
+    /// # Returns
+    ///
+    /// One reply per channel, in the order in which the channels were given. Each reply
+    /// is a push of the form `["subscribe", channel, count]`, where count is the number
+    /// of channels and patterns the connection is subscribed to after subscribing to that
+    /// channel.
+    pub fn apply(&self, subscriber: &mut Subscriber) -> Vec<RespType> {
+        self.channels
+            .iter()
+            .map(|channel| {
+                let count = subscriber.subscribe(channel);
+                RespType::Push(vec![
+                    RespType::BulkString(String::from("subscribe")),
+                    RespType::BulkString(channel.to_string()),
+                    RespType::Integer(count as i64),
+                ])
+            })
+            .collect()
+    }
+}
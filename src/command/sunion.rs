@@ -0,0 +1,68 @@
+// src/command/sunion.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SUNION command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct SUnion {
+    keys: Vec<String>,
+}
+
+impl SUnion {
+    /// Creates a new `SUnion` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SUNION command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SUnion)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SUnion, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SUNION' command",
+            )));
+        }
+
+        // parse keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(SUnion { keys })
+    }
+
+    /// Executes the SUNION command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// A `Set` of the members of the union of the sets. Keys which don't exist are
+    /// treated as empty sets.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.sunion(&self.keys) {
+            Ok(members) => RespType::Set(members.into_iter().map(RespType::BulkString).collect()),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.clone()
+    }
+}
@@ -0,0 +1,85 @@
+// src/command/sunionstore.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the SUNIONSTORE command in Nimblecache.
+///
+/// The `SUnionStore` struct is used to store the union of sets at a destination key.
+#[derive(Debug, Clone)]
+pub struct SUnionStore {
+    dest: String,
+    keys: Vec<String>,
+}
+
+impl SUnionStore {
+    /// Creates a new `SUnionStore` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `destination key [key ...]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SUNIONSTORE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SUnionStore)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SUnionStore, CommandError> {
+        if args.len() < 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'SUNIONSTORE' command",
+            )));
+        }
+
+        // parse destination and keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+        let dest = keys.remove(0);
+
+        Ok(SUnionStore { dest, keys })
+    }
+
+    /// Executes the SUNIONSTORE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of members of the union as an `Integer`. The destination key is
+    /// deleted if the union is empty.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.sunionstore(&self.dest, &self.keys) {
+            Ok(card) => RespType::Integer(card as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command, the destination key first.
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys = vec![self.dest.clone()];
+        keys.extend(self.keys.iter().cloned());
+        keys
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args: Vec<RespType> = vec![RespType::BulkString(String::from("SUNIONSTORE"))];
+        args.extend(self.keys().into_iter().map(RespType::BulkString));
+
+        RespType::Array(args)
+    }
+}
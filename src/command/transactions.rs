@@ -1,6 +1,13 @@
 // src/command/transactions.rs
 
-use crate::{resp::types::RespType, storage::db::DB};
+use std::sync::atomic::Ordering;
+
+use crate::{
+    clients::ClientState,
+    resp::types::RespType,
+    storage::db::{freeze_time, Storage},
+    watch::Watcher,
+};
 
 use super::Command;
 
@@ -10,17 +17,40 @@ pub struct Transaction {
     commands: Vec<Command>,
     /// Indicates whether a transaction is currently active.
     is_active: bool,
+    /// Set if a command couldn't be queued, in which case EXEC fails without executing
+    /// any command.
+    failed: bool,
+    /// The keys watched with the WATCH command. The transaction fails if any of them is
+    /// modified before EXEC.
+    watcher: Watcher,
 }
 
 impl Transaction {
     /// Creates a new `Transaction` instance.
-    pub fn new() -> Transaction {
+    ///
+    /// # Arguments
+    ///
+    /// * `watcher` - The watch state of the connection the transaction belongs to.
+    pub fn new(watcher: Watcher) -> Transaction {
         Transaction {
             commands: vec![],
             is_active: false,
+            failed: false,
+            watcher,
         }
     }
 
+    /// Watches the keys, so that the next transaction fails if any of them is modified
+    /// before EXEC (WATCH command).
+    pub fn watch(&mut self, keys: &[String]) {
+        self.watcher.watch(keys);
+    }
+
+    /// Stops watching all the keys (UNWATCH command).
+    pub fn unwatch(&mut self) {
+        self.watcher.unwatch();
+    }
+
     /// Initializes a new transaction (MULTI command).
     ///
     /// # Returns
@@ -45,6 +75,14 @@ impl Transaction {
         self.commands.push(cmd);
     }
 
+    /// Marks the transaction as failed, because a command couldn't be parsed or decoded,
+    /// or isn't allowed inside a transaction.
+    /// It's still active, so the following commands are queued, but EXEC fails without
+    /// executing any of them.
+    pub fn fail(&mut self) {
+        self.failed = true;
+    }
+
     /// Checks if a transaction is currently active.
     pub fn is_active(&self) -> bool {
         self.is_active
@@ -56,35 +94,85 @@ impl Transaction {
     /// responses as a `RespType::Array`. After the execution, the transaction is
     /// automatically discarded.
     ///
+    /// If any of the watched keys was modified, no command is executed and a
+    /// `RespType::NullBulkString` is returned instead. If the transaction failed while
+    /// queueing the commands, none of them is executed either and an `EXECABORT` error
+    /// is returned.
+    ///
+    /// The commands are executed while holding the transaction lock exclusively, so
+    /// commands from other connections can't run in between them, nor observe the
+    /// dataset midway through the transaction. The time is frozen while they run, so
+    /// that a key doesn't expire between two of them.
+    ///
+    /// The lock is acquired on tokio's blocking thread pool rather than on the connection's
+    /// task. A command abandoned after the command timeout may still hold it in shared mode,
+    /// and waiting for it would otherwise block a worker thread, along with every connection
+    /// scheduled on it.
+    ///
     /// # Arguments
     ///
-    /// * `db` - The database where the key and values are stored.
+    /// * `storage` - The shared storage, which holds the database where the key and values are stored.
+    ///
+    /// * `client` - The state of the connection the transaction was issued on.
     ///
     /// # Returns
     ///
     /// A `RespType::Array` containing the responses for each command in the transaction.
-    pub async fn exec(&mut self, db: &DB) -> RespType {
-        let mut responses: Vec<RespType> = vec![];
-
-        for cmd in self.commands.iter() {
-            // execute the command
-            let res = cmd.execute(db);
-
-            responses.push(res);
+    pub async fn exec(&mut self, storage: &Storage, client: &ClientState) -> RespType {
+        if self.failed {
+            self.discard();
+            return RespType::SimpleError(String::from(
+                "EXECABORT Transaction discarded because of previous errors.",
+            ));
         }
 
+        let commands = std::mem::take(&mut self.commands);
+        let dirty = self.watcher.dirty_flag();
+        let task_storage = storage.clone();
+        let task_client = client.clone();
+
+        let task = tokio::task::spawn_blocking(move || {
+            let guard = task_storage.lock_transaction();
+            // The watched keys are checked under the lock, so that they can't be modified
+            // between the check and the execution.
+            if dirty.load(Ordering::Relaxed) {
+                return RespType::NullBulkString;
+            }
+
+            let mut responses: Vec<RespType> = vec![];
+            let frozen_time = freeze_time();
+            for cmd in commands.iter() {
+                // execute the command
+                let res = cmd.execute_locked(&task_storage, &task_client);
+
+                responses.push(res);
+            }
+            drop(frozen_time);
+            drop(guard);
+
+            RespType::Array(responses)
+        });
+
+        let res = match task.await {
+            Ok(res) => res,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        };
+
         // discard txn after executing all commands
         self.discard();
 
-        RespType::Array(responses)
+        res
     }
 
     /// Discards the current transaction.
     ///
-    /// This method clears the queue of commands and resets the `is_active` flag.
+    /// This method clears the queue of commands, resets the `is_active` and `failed` flags
+    /// and stops watching the keys, so that they don't affect the next transaction.
     pub fn discard(&mut self) {
         self.commands = vec![];
         self.is_active = false;
+        self.failed = false;
+        self.watcher.unwatch();
     }
 }
 
@@ -0,0 +1,73 @@
+// src/command/ttl.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the TTL command in Nimblecache.
+///
+/// The `Ttl` struct is used to retrieve the remaining time to live of a key, in seconds.
+#[derive(Debug, Clone)]
+pub struct Ttl {
+    key: String,
+}
+
+impl Ttl {
+    /// Creates a new `Ttl` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TTL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Ttl)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Ttl, CommandError> {
+        if args.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'TTL' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k.to_string(),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        Ok(Ttl { key })
+    }
+
+    /// Executes the TTL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Integer(-2)` - If the key doesn't exist.
+    /// - `Integer(-1)` - If the key exists, but has no expiry.
+    /// - The remaining time to live in seconds as an `Integer`, otherwise.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ttl(&self.key) {
+            Ok(None) => RespType::Integer(-2),
+            Ok(Some(None)) => RespType::Integer(-1),
+            Ok(Some(Some(ttl_ms))) => {
+                // round to the nearest second
+                RespType::Integer(((ttl_ms + 500) / 1000) as i64)
+            }
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
@@ -0,0 +1,93 @@
+// src/command/unlink.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the UNLINK command in Nimblecache.
+///
+/// UNLINK removes keys like DEL, except that their values are freed in the background.
+#[derive(Debug, Clone)]
+pub struct Unlink {
+    keys: Vec<String>,
+}
+
+impl Unlink {
+    /// Creates a new `Unlink` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the UNLINK command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Unlink)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Unlink, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'UNLINK' command",
+            )));
+        }
+
+        // parse keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(Unlink { keys })
+    }
+
+    /// Executes the UNLINK command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of keys that were removed as an `Integer`, the same as DEL. The values are
+    /// freed on a background thread, so that removing a large collection doesn't hold up the
+    /// connection.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let removed = match db.remove(&self.keys) {
+            Ok(removed) => removed,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let count = removed.len();
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(move || drop(removed));
+            }
+            Err(_) => drop(removed),
+        }
+
+        RespType::Integer(count as i64)
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.clone()
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        let mut args: Vec<RespType> = vec![RespType::BulkString(String::from("UNLINK"))];
+
+        for key in self.keys.iter() {
+            args.push(RespType::BulkString(key.to_string()));
+        }
+
+        RespType::Array(args)
+    }
+}
@@ -0,0 +1,83 @@
+// src/command/unsubscribe.rs
+
+use crate::{pubsub::Subscriber, resp::types::RespType};
+
+use super::CommandError;
+
+/// Represents the UNSUBSCRIBE command in Nimblecache.
+///
+/// UNSUBSCRIBE unsubscribes the connection from the given channels, or from all of
+/// them if no channel is given. Since it changes the state of the connection, this
+/// command is handled by `FrameHandler.handle`.
+#[derive(Debug, Clone)]
+pub struct Unsubscribe {
+    channels: Vec<String>,
+}
+
+impl Unsubscribe {
+    /// Creates a new `Unsubscribe` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the UNSUBSCRIBE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Unsubscribe)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Unsubscribe, CommandError> {
+        let mut channels: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(c) => channels.push(c.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Channel must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(Unsubscribe { channels })
+    }
+
+    /// Executes the UNSUBSCRIBE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscriber` - The pub/sub state of the connection.
+    ///
+    /// # Returns
+    ///
+    /// One reply per channel, of the form `["unsubscribe", channel, count]`, where count is
+    /// the number of channels and patterns the connection is still subscribed to. If no
+    /// channel was given and the connection isn't subscribed to any, a single reply with
+    /// a null channel is returned.
+    pub fn apply(&self, subscriber: &mut Subscriber) -> Vec<RespType> {
+        let channels = if self.channels.is_empty() {
+            subscriber.channels()
+        } else {
+            self.channels.clone()
+        };
+
+        if channels.is_empty() {
+            return vec![RespType::Push(vec![
+                RespType::BulkString(String::from("unsubscribe")),
+                RespType::NullBulkString,
+                RespType::Integer(subscriber.subscription_count() as i64),
+            ])];
+        }
+
+        channels
+            .iter()
+            .map(|channel| {
+                let count = subscriber.unsubscribe(channel);
+                RespType::Push(vec![
+                    RespType::BulkString(String::from("unsubscribe")),
+                    RespType::BulkString(channel.to_string()),
+                    RespType::Integer(count as i64),
+                ])
+            })
+            .collect()
+    }
+}
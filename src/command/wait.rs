@@ -0,0 +1,127 @@
+// src/command/wait.rs
+
+use std::time::Duration;
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::CommandError;
+
+/// Represents the WAIT command in Nimblecache.
+///
+/// The `Wait` struct is used to block the client until the write commands issued so far
+/// are acknowledged by the given number of replicas, or until the timeout elapses.
+#[derive(Debug, Clone)]
+pub struct Wait {
+    /// The number of replicas to wait for.
+    numreplicas: usize,
+    /// Timeout in milliseconds. `0` means wait forever.
+    timeout: u64,
+}
+
+impl Wait {
+    /// Creates a new `Wait` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the WAIT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Wait)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Wait, CommandError> {
+        if args.len() != 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'WAIT' command",
+            )));
+        }
+
+        // parse number of replicas
+        let numreplicas = match &args[0] {
+            RespType::BulkString(n) => match n.parse::<usize>() {
+                Ok(n) => n,
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "Number of replicas should be a non-negative integer",
+                    )))
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+        };
+
+        // parse timeout
+        let timeout = match &args[1] {
+            RespType::BulkString(t) => match t.parse::<u64>() {
+                Ok(t) => t,
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "Timeout should be a non-negative integer",
+                    )))
+                }
+            },
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+        };
+
+        Ok(Wait {
+            numreplicas,
+            timeout,
+        })
+    }
+
+    /// Executes the WAIT command, blocking until enough replicas acknowledge the write
+    /// commands issued so far, or until the timeout elapses.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the replication state.
+    ///
+    /// # Returns
+    ///
+    /// The number of replicas which acknowledged the write commands as an `Integer`,
+    /// or a `SimpleError` if the server is a replica.
+    pub async fn apply(&self, storage: &Storage) -> RespType {
+        let replication = storage.replication();
+        if replication.is_replica() {
+            return RespType::SimpleError(String::from(
+                "WAIT cannot be used with replica instances",
+            ));
+        }
+
+        let timeout = match self.timeout {
+            0 => None,
+            t => Some(Duration::from_millis(t)),
+        };
+
+        // The client is counted as blocked while waiting.
+        let _blocked = storage.clients().block();
+        let acked = replication.wait_for_acks(self.numreplicas, timeout).await;
+
+        RespType::Integer(acked as i64)
+    }
+
+    /// Executes the WAIT command without blocking. This is used inside transactions,
+    /// where the command can't block.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the replication state.
+    ///
+    /// # Returns
+    ///
+    /// The number of replicas which have already acknowledged the write commands
+    /// as an `Integer`.
+    pub fn apply_nonblocking(&self, storage: &Storage) -> RespType {
+        let replication = storage.replication();
+        let acked = replication.acked_replicas(replication.offset());
+
+        RespType::Integer(acked as i64)
+    }
+}
@@ -0,0 +1,55 @@
+// src/command/watch.rs
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Represents the WATCH command in Nimblecache.
+///
+/// WATCH marks keys to be checked by the next transaction of the connection, which fails
+/// if any of them is modified before EXEC. The keys are watched by the transaction state
+/// of the connection, so this command is handled by `FrameHandler.handle`.
+#[derive(Debug, Clone)]
+pub struct Watch {
+    keys: Vec<String>,
+}
+
+impl Watch {
+    /// Creates a new `Watch` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the WATCH command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Watch)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Watch, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'WATCH' command",
+            )));
+        }
+
+        // parse keys
+        let mut keys: Vec<String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(k) => keys.push(k.to_string()),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Key must be a bulk string",
+                    )));
+                }
+            }
+        }
+
+        Ok(Watch { keys })
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.clone()
+    }
+}
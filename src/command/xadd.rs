@@ -0,0 +1,126 @@
+// src/command/xadd.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::Storage, stream::StreamId},
+};
+
+use super::CommandError;
+
+/// Represents the XADD command in Nimblecache.
+///
+/// The `XAdd` struct is used to append an entry to a stream.
+#[derive(Debug, Clone)]
+pub struct XAdd {
+    key: String,
+    /// The ID of the entry. It's generated when the command is applied if `None`, i.e. if
+    /// the ID is given as `*`.
+    id: Option<StreamId>,
+    fields: Vec<(String, String)>,
+}
+
+impl XAdd {
+    /// Creates a new `XAdd` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key <* | id> field value [field value ...]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the XADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(XAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<XAdd, CommandError> {
+        if args.len() < 4 || !args.len().is_multiple_of(2) {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'XADD' command",
+            )));
+        }
+
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let id = match parsed[1].as_str() {
+            "*" => None,
+            id => match id.parse::<StreamId>() {
+                Ok(id) => Some(id),
+                Err(e) => return Err(CommandError::Other(e)),
+            },
+        };
+
+        let fields = parsed[2..]
+            .chunks(2)
+            .map(|pair| (pair[0].to_string(), pair[1].to_string()))
+            .collect();
+
+        Ok(XAdd {
+            key: parsed[0].to_string(),
+            id,
+            fields,
+        })
+    }
+
+    /// Executes the XADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The shared storage, which holds the database and the maximum size
+    ///   of values.
+    ///
+    /// # Returns
+    ///
+    /// The ID of the entry added as a `BulkString`, or an error if any of the values is over
+    /// the configured maximum value size, or the ID isn't greater than the ID of the last
+    /// entry of the stream.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        let values: Vec<String> = self.fields.iter().map(|(_, v)| v.clone()).collect();
+        if let Err(e) = storage.check_value_size(&values) {
+            return RespType::SimpleError(format!("{}", e));
+        }
+
+        match storage.db().xadd(&self.key, self.id, self.fields.clone()) {
+            Ok(id) => RespType::BulkString(id.to_string()),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    ///
+    /// It's only propagated if the entry was added, with the ID it was added with, so that
+    /// the replicas don't generate a different one.
+    pub fn build_command(&self, res: &RespType) -> Option<RespType> {
+        let id = match res {
+            RespType::BulkString(id) => id,
+            _ => return None,
+        };
+
+        let mut args = vec![
+            RespType::BulkString(String::from("XADD")),
+            RespType::BulkString(self.key.clone()),
+            RespType::BulkString(id.clone()),
+        ];
+        for (field, value) in self.fields.iter() {
+            args.push(RespType::BulkString(field.clone()));
+            args.push(RespType::BulkString(value.clone()));
+        }
+
+        Some(RespType::Array(args))
+    }
+}
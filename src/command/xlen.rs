@@ -0,0 +1,61 @@
+// src/command/xlen.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the XLEN command in Nimblecache.
+///
+/// The `XLen` struct is used to get the number of entries of a stream.
+#[derive(Debug, Clone)]
+pub struct XLen {
+    key: String,
+}
+
+impl XLen {
+    /// Creates a new `XLen` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the XLEN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(XLen)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<XLen, CommandError> {
+        match args.as_slice() {
+            [RespType::BulkString(key)] => Ok(XLen {
+                key: key.to_string(),
+            }),
+            [_] => Err(CommandError::Other(String::from(
+                "Invalid argument. Key must be a bulk string",
+            ))),
+            _ => Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'XLEN' command",
+            ))),
+        }
+    }
+
+    /// Executes the XLEN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries of the stream as an `Integer`, or `0` if the key doesn't
+    /// exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.xlen(&self.key) {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
@@ -0,0 +1,135 @@
+// src/command/xrange.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::DB,
+        stream::{StreamEntry, StreamId},
+    },
+};
+
+use super::CommandError;
+
+/// Represents the XRANGE command in Nimblecache.
+///
+/// The `XRange` struct is used to get the entries of a stream whose IDs are within a range.
+#[derive(Debug, Clone)]
+pub struct XRange {
+    key: String,
+    start: StreamId,
+    end: StreamId,
+    /// Set by `COUNT`. All the entries in the range are returned if `None`.
+    count: Option<usize>,
+}
+
+impl XRange {
+    /// Creates a new `XRange` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key start end [COUNT count]`, where `start`
+    /// can be `-` for the smallest ID and `end` can be `+` for the greatest ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the XRANGE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(XRange)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<XRange, CommandError> {
+        if args.len() < 3 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'XRANGE' command",
+            )));
+        }
+
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        let start = parse_range_bound(parsed[1], false)?;
+        let end = parse_range_bound(parsed[2], true)?;
+
+        let count = match &parsed[3..] {
+            [] => None,
+            [name, count] if name.eq_ignore_ascii_case("count") => Some(parse_count(count)?),
+            _ => return Err(CommandError::Other(String::from("syntax error"))),
+        };
+
+        Ok(XRange {
+            key: parsed[0].to_string(),
+            start,
+            end,
+            count,
+        })
+    }
+
+    /// Executes the XRANGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The entries in the range as an `Array`, in ascending order of ID. Each entry is an
+    /// `Array` of its ID and an `Array` of its fields and values. It's empty if the key
+    /// doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.xrange(&self.key, self.start, self.end, self.count) {
+            Ok(entries) => entries_reply(entries),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
+
+/// Parses a bound of a range of IDs, which is either `-`, `+`, or an ID where the sequence
+/// may be left out.
+fn parse_range_bound(bound: &str, upper: bool) -> Result<StreamId, CommandError> {
+    match bound {
+        "-" => Ok(StreamId::MIN),
+        "+" => Ok(StreamId::MAX),
+        _ => StreamId::parse_bound(bound, upper).map_err(CommandError::Other),
+    }
+}
+
+/// Parses the value of the `COUNT` option of the stream commands.
+pub(super) fn parse_count(count: &str) -> Result<usize, CommandError> {
+    count.parse::<usize>().map_err(|_| {
+        CommandError::Other(String::from("value is not an integer or out of range"))
+    })
+}
+
+/// Builds the reply holding stream entries, which is an `Array` with an `Array` of the ID
+/// and the flattened field-value pairs for each entry.
+pub(super) fn entries_reply(entries: Vec<StreamEntry>) -> RespType {
+    RespType::Array(
+        entries
+            .into_iter()
+            .map(|(id, fields)| {
+                RespType::Array(vec![
+                    RespType::BulkString(id.to_string()),
+                    RespType::Array(
+                        fields
+                            .into_iter()
+                            .flat_map(|(f, v)| [RespType::BulkString(f), RespType::BulkString(v)])
+                            .collect(),
+                    ),
+                ])
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,122 @@
+// src/command/xread.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, stream::StreamId},
+};
+
+use super::{
+    xrange::{entries_reply, parse_count},
+    CommandError,
+};
+
+/// Represents the XREAD command in Nimblecache.
+///
+/// The `XRead` struct is used to get the entries added to one or more streams after the
+/// given IDs. It never blocks, so only the entries already in the streams are returned.
+#[derive(Debug, Clone)]
+pub struct XRead {
+    /// The keys of the streams, each with the ID after which entries are returned.
+    streams: Vec<(String, StreamId)>,
+    /// Set by `COUNT`. All the entries are returned if `None`.
+    count: Option<usize>,
+}
+
+impl XRead {
+    /// Creates a new `XRead` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form
+    /// `[COUNT count] STREAMS key [key ...] id [id ...]`, with one ID per key.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the XREAD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(XRead)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<XRead, CommandError> {
+        if args.len() < 3 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'XREAD' command",
+            )));
+        }
+
+        let mut parsed: Vec<&String> = vec![];
+        for arg in args.iter() {
+            match arg {
+                RespType::BulkString(s) => parsed.push(s),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Arguments must be bulk strings",
+                    )));
+                }
+            }
+        }
+
+        // parse options, up to STREAMS
+        let mut count = None;
+        let mut rest = parsed.as_slice();
+        loop {
+            match rest {
+                [name, tail @ ..] if name.eq_ignore_ascii_case("streams") => {
+                    rest = tail;
+                    break;
+                }
+                [name, n, tail @ ..] if name.eq_ignore_ascii_case("count") => {
+                    // COUNT 0 means no limit, like when it's not given.
+                    count = Some(parse_count(n)?).filter(|n| *n > 0);
+                    rest = tail;
+                }
+                _ => return Err(CommandError::Other(String::from("syntax error"))),
+            }
+        }
+
+        if rest.is_empty() || !rest.len().is_multiple_of(2) {
+            return Err(CommandError::Other(String::from(
+                "Unbalanced 'xread' list of streams: for each stream key an ID must be specified",
+            )));
+        }
+
+        let (keys, ids) = rest.split_at(rest.len() / 2);
+        let mut streams = vec![];
+        for (key, id) in keys.iter().zip(ids.iter()) {
+            let id = StreamId::parse_bound(id, false).map_err(CommandError::Other)?;
+            streams.push((key.to_string(), id));
+        }
+
+        Ok(XRead { streams, count })
+    }
+
+    /// Executes the XREAD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` with an `Array` of the key and its entries for each stream which has
+    /// entries after the given ID, or a `NullBulkString` if none of them has. The entries
+    /// are formatted like in the reply of XRANGE.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.xread(&self.streams, self.count) {
+            Ok(streams) if streams.is_empty() => RespType::NullBulkString,
+            Ok(streams) => RespType::Array(
+                streams
+                    .into_iter()
+                    .map(|(key, entries)| {
+                        RespType::Array(vec![RespType::BulkString(key), entries_reply(entries)])
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        self.streams.iter().map(|(key, _)| key.clone()).collect()
+    }
+}
@@ -0,0 +1,213 @@
+// src/command/zadd.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{ZAddCondition, DB},
+};
+
+use super::CommandError;
+
+/// Represents the ZADD command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct ZAdd {
+    key: String,
+    members: Vec<(String, f64)>,
+    /// Conditions which must all hold for a member to be added or updated.
+    conditions: Vec<ZAddCondition>,
+    /// Set by `CH`. The reply then counts the updated members along with the added ones.
+    ch: bool,
+    /// Set by `INCR`. The score of the single member is then incremented, like ZINCRBY.
+    incr: bool,
+}
+
+impl ZAdd {
+    /// Creates a new `ZAdd` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form
+    /// `key [NX | XX] [GT | LT] [CH] [INCR] score member [score member ...]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZAdd, CommandError> {
+        if args.len() < 3 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'ZADD' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse options
+        let mut conditions: Vec<ZAddCondition> = vec![];
+        let mut ch = false;
+        let mut incr = false;
+        let mut idx = 1;
+        while let Some(RespType::BulkString(option)) = args.get(idx) {
+            let condition = match option.to_lowercase().as_str() {
+                "nx" => ZAddCondition::Nx,
+                "xx" => ZAddCondition::Xx,
+                "gt" => ZAddCondition::Gt,
+                "lt" => ZAddCondition::Lt,
+                "ch" => {
+                    ch = true;
+                    idx += 1;
+                    continue;
+                }
+                "incr" => {
+                    incr = true;
+                    idx += 1;
+                    continue;
+                }
+                _ => break,
+            };
+            if !conditions.contains(&condition) {
+                conditions.push(condition);
+            }
+            idx += 1;
+        }
+
+        let nx = conditions.contains(&ZAddCondition::Nx);
+        let xx = conditions.contains(&ZAddCondition::Xx);
+        let gt = conditions.contains(&ZAddCondition::Gt);
+        let lt = conditions.contains(&ZAddCondition::Lt);
+        if nx && xx {
+            return Err(CommandError::Other(String::from(
+                "XX and NX options at the same time are not compatible",
+            )));
+        }
+        if (gt && lt) || (nx && (gt || lt)) {
+            return Err(CommandError::Other(String::from(
+                "GT, LT, and/or NX options at the same time are not compatible",
+            )));
+        }
+
+        let pairs = &args[idx..];
+        if pairs.is_empty() || !pairs.len().is_multiple_of(2) {
+            return Err(CommandError::Other(String::from("syntax error")));
+        }
+        if incr && pairs.len() > 2 {
+            return Err(CommandError::Other(String::from(
+                "INCR option supports a single increment-element pair",
+            )));
+        }
+
+        // parse score and member pairs
+        let mut members: Vec<(String, f64)> = vec![];
+        for pair in pairs.chunks(2) {
+            let score = match &pair[0] {
+                RespType::BulkString(s) => match s.parse::<f64>() {
+                    Ok(score) if !score.is_nan() => score,
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "value is not a valid float",
+                        )))
+                    }
+                },
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Score must be a bulk string",
+                    )));
+                }
+            };
+
+            let member = match &pair[1] {
+                RespType::BulkString(m) => m,
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid argument. Member must be a bulk string",
+                    )));
+                }
+            };
+
+            members.push((member.to_string(), score));
+        }
+
+        Ok(ZAdd {
+            key: key.to_string(),
+            members,
+            conditions,
+            ch,
+            incr,
+        })
+    }
+
+    /// Executes the ZADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of members that were added to the sorted set, not including
+    /// the members whose scores were updated unless `CH` is given.
+    ///
+    /// With `INCR`, it returns the new score of the member as a `BulkString`, or a
+    /// `NullBulkString` if the increment was aborted because of a condition.
+    pub fn apply(&self, db: &DB) -> RespType {
+        if self.incr {
+            let (member, increment) = &self.members[0];
+            return match db.zincrby(&self.key, member, *increment, &self.conditions) {
+                Ok(Some(score)) => RespType::BulkString(score.to_string()),
+                Ok(None) => RespType::NullBulkString,
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            };
+        }
+
+        match db.zadd(&self.key, self.members.clone(), &self.conditions) {
+            Ok((added, updated)) if self.ch => RespType::Integer((added + updated) as i64),
+            Ok((added, _)) => RespType::Integer(added as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    ///
+    /// `CH` only changes the reply, so it's left out.
+    pub fn build_command(&self) -> RespType {
+        let mut args: Vec<RespType> = vec![
+            RespType::BulkString(String::from("ZADD")),
+            RespType::BulkString(self.key.clone()),
+        ];
+
+        for condition in self.conditions.iter() {
+            let option = match condition {
+                ZAddCondition::Nx => "NX",
+                ZAddCondition::Xx => "XX",
+                ZAddCondition::Gt => "GT",
+                ZAddCondition::Lt => "LT",
+            };
+            args.push(RespType::BulkString(String::from(option)));
+        }
+        if self.incr {
+            args.push(RespType::BulkString(String::from("INCR")));
+        }
+
+        for (member, score) in self.members.iter() {
+            args.push(RespType::BulkString(score.to_string()));
+            args.push(RespType::BulkString(member.to_string()));
+        }
+
+        RespType::Array(args)
+    }
+}
@@ -0,0 +1,61 @@
+// src/command/zcard.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the ZCARD command in Nimblecache.
+///
+/// The `ZCard` struct is used to get the number of members of a sorted set.
+#[derive(Debug, Clone)]
+pub struct ZCard {
+    key: String,
+}
+
+impl ZCard {
+    /// Creates a new `ZCard` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZCARD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZCard)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZCard, CommandError> {
+        match args.as_slice() {
+            [RespType::BulkString(key)] => Ok(ZCard {
+                key: key.to_string(),
+            }),
+            [_] => Err(CommandError::Other(String::from(
+                "Invalid argument. Key must be a bulk string",
+            ))),
+            _ => Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'ZCARD' command",
+            ))),
+        }
+    }
+
+    /// Executes the ZCARD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of members of the sorted set as an `Integer`, or `0` if the key doesn't
+    /// exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zcard(&self.key) {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
@@ -0,0 +1,102 @@
+// src/command/zcount.rs
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, sorted_set::ScoreRange},
+};
+
+use super::CommandError;
+
+/// Represents the ZCOUNT command in Nimblecache.
+///
+/// The `ZCount` struct is used to count the members of a sorted set whose scores are
+/// within a range.
+#[derive(Debug, Clone)]
+pub struct ZCount {
+    key: String,
+    range: ScoreRange,
+}
+
+impl ZCount {
+    /// Creates a new `ZCount` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key min max`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZCOUNT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZCount)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZCount, CommandError> {
+        match args.as_slice() {
+            [RespType::BulkString(key), RespType::BulkString(min), RespType::BulkString(max)] => {
+                Ok(ZCount {
+                    key: key.to_string(),
+                    range: parse_score_range(min, max)?,
+                })
+            }
+            [_, _, _] => Err(CommandError::Other(String::from(
+                "Invalid argument. Key, min and max must be bulk strings",
+            ))),
+            _ => Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'ZCOUNT' command",
+            ))),
+        }
+    }
+
+    /// Executes the ZCOUNT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The number of members whose scores are within the range as an `Integer`, or `0` if
+    /// the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zcount(&self.key, &self.range) {
+            Ok(count) => RespType::Integer(count as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+}
+
+/// Parses the `min` and `max` bounds of a range of scores. A bound is exclusive if it's
+/// prefixed with `(`, and `-inf` and `+inf` stand for the lowest and highest scores.
+///
+/// # Returns
+///
+/// * `Ok(ScoreRange)` - The range of scores.
+/// * `Err(CommandError)` - If either bound is not a float.
+pub(super) fn parse_score_range(min: &str, max: &str) -> Result<ScoreRange, CommandError> {
+    let parse_bound = |bound: &str| {
+        let (bound, exclusive) = match bound.strip_prefix('(') {
+            Some(bound) => (bound, true),
+            None => (bound, false),
+        };
+
+        match bound.parse::<f64>() {
+            Ok(bound) if !bound.is_nan() => Ok((bound, exclusive)),
+            _ => Err(CommandError::Other(String::from("min or max is not a float"))),
+        }
+    };
+
+    let (min, min_exclusive) = parse_bound(min)?;
+    let (max, max_exclusive) = parse_bound(max)?;
+
+    Ok(ScoreRange {
+        min,
+        min_exclusive,
+        max,
+        max_exclusive,
+    })
+}
@@ -0,0 +1,90 @@
+// src/command/zincrby.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the ZINCRBY command in Nimblecache.
+///
+/// The `ZIncrBy` struct is used to increment the score of a member of a sorted set.
+#[derive(Debug, Clone)]
+pub struct ZIncrBy {
+    key: String,
+    increment: f64,
+    member: String,
+}
+
+impl ZIncrBy {
+    /// Creates a new `ZIncrBy` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key increment member`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZINCRBY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZIncrBy)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZIncrBy, CommandError> {
+        let (key, increment, member) = match args.as_slice() {
+            [RespType::BulkString(k), RespType::BulkString(i), RespType::BulkString(m)] => (k, i, m),
+            [_, _, _] => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key, increment and member must be bulk strings",
+                )));
+            }
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Wrong number of arguments specified for 'ZINCRBY' command",
+                )));
+            }
+        };
+
+        let increment = match increment.parse::<f64>() {
+            Ok(increment) if !increment.is_nan() => increment,
+            _ => return Err(CommandError::Other(String::from("value is not a valid float"))),
+        };
+
+        Ok(ZIncrBy {
+            key: key.to_string(),
+            increment,
+            member: member.to_string(),
+        })
+    }
+
+    /// Executes the ZINCRBY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The new score of the member as a `BulkString`. A member which doesn't exist is added
+    /// with the increment as its score.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zincrby(&self.key, &self.member, self.increment, &[]) {
+            Ok(Some(score)) => RespType::BulkString(score.to_string()),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(String::from("ZINCRBY")),
+            RespType::BulkString(self.key.clone()),
+            RespType::BulkString(self.increment.to_string()),
+            RespType::BulkString(self.member.clone()),
+        ])
+    }
+}
@@ -0,0 +1,120 @@
+// src/command/zpopmax.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the ZPOPMAX command in Nimblecache.
+///
+/// The `ZPopMax` struct is used to remove the members with the highest scores from a sorted
+/// set and return them.
+#[derive(Debug, Clone)]
+pub struct ZPopMax {
+    key: String,
+    /// The maximum number of members to be removed.
+    count: usize,
+}
+
+impl ZPopMax {
+    /// Creates a new `ZPopMax` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key [count]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZPOPMAX command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZPopMax)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZPopMax, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'ZPOPMAX' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse count
+        let count = match args.get(1) {
+            Some(RespType::BulkString(c)) => match c.parse::<usize>() {
+                Ok(c) => c,
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "value is out of range, must be positive",
+                    )))
+                }
+            },
+            Some(_) => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+            None => 1,
+        };
+
+        Ok(ZPopMax {
+            key: key.to_string(),
+            count,
+        })
+    }
+
+    /// Executes the ZPOPMAX command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` of the removed members, highest score first, each followed by its score.
+    /// Empty if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zpopmax(&self.key, self.count) {
+            Ok(members) => RespType::Array(
+                members
+                    .into_iter()
+                    .flat_map(|(member, score)| {
+                        [
+                            RespType::BulkString(member),
+                            RespType::BulkString(score.to_string()),
+                        ]
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    ///
+    /// # Returns
+    ///
+    /// The ZPOPMAX command, or `None` if no member was removed.
+    pub fn build_command(&self, res: &RespType) -> Option<RespType> {
+        match res {
+            RespType::Array(members) if !members.is_empty() => Some(RespType::Array(vec![
+                RespType::BulkString(String::from("ZPOPMAX")),
+                RespType::BulkString(self.key.clone()),
+                RespType::BulkString(self.count.to_string()),
+            ])),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// src/command/zpopmin.rs
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::CommandError;
+
+/// Represents the ZPOPMIN command in Nimblecache.
+///
+/// The `ZPopMin` struct is used to remove the members with the lowest scores from a sorted
+/// set and return them.
+#[derive(Debug, Clone)]
+pub struct ZPopMin {
+    key: String,
+    /// The maximum number of members to be removed.
+    count: usize,
+}
+
+impl ZPopMin {
+    /// Creates a new `ZPopMin` instance from the given arguments.
+    ///
+    /// The arguments are expected in the form `key [count]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZPOPMIN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZPopMin)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZPopMin, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(CommandError::Other(String::from(
+                "Wrong number of arguments specified for 'ZPOPMIN' command",
+            )));
+        }
+
+        // parse key
+        let key = match &args[0] {
+            RespType::BulkString(k) => k,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Key must be a bulk string",
+                )));
+            }
+        };
+
+        // parse count
+        let count = match args.get(1) {
+            Some(RespType::BulkString(c)) => match c.parse::<usize>() {
+                Ok(c) => c,
+                Err(_) => {
+                    return Err(CommandError::Other(String::from(
+                        "value is out of range, must be positive",
+                    )))
+                }
+            },
+            Some(_) => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid argument. Value must be an integer in bulk string format",
+                )));
+            }
+            None => 1,
+        };
+
+        Ok(ZPopMin {
+            key: key.to_string(),
+            count,
+        })
+    }
+
+    /// Executes the ZPOPMIN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` of the removed members, lowest score first, each followed by its score.
+    /// Empty if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zpopmin(&self.key, self.count) {
+            Ok(members) => RespType::Array(
+                members
+                    .into_iter()
+                    .flat_map(|(member, score)| {
+                        [
+                            RespType::BulkString(member),
+                            RespType::BulkString(score.to_string()),
+                        ]
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns the keys accessed by the command.
+    pub fn keys(&self) -> Vec<String> {
+        vec![self.key.clone()]
+    }
+
+    /// Builds the RESP command which performs the same operation, to be propagated
+    /// to the replicas.
+    ///
+    /// # Returns
+    ///
+    /// The ZPOPMIN command, or `None` if no member was removed.
+    pub fn build_command(&self, res: &RespType) -> Option<RespType> {
+        match res {
+            RespType::Array(members) if !members.is_empty() => Some(RespType::Array(vec![
+                RespType::BulkString(String::from("ZPOPMIN")),
+                RespType::BulkString(self.key.clone()),
+                RespType::BulkString(self.count.to_string()),
+            ])),
+            _ => None,
+        }
+    }
+}
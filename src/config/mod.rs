@@ -0,0 +1,219 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{OnceLock, RwLock},
+};
+
+use anyhow::{anyhow, Result};
+
+/// Holds the configuration parameters of the server, which can be read at runtime
+/// with the CONFIG GET command.
+///
+/// The parameters are stored by name as strings, the same way they are exchanged
+/// with the clients. Parameters which weren't set are not reported.
+#[derive(Debug)]
+pub struct Config {
+    params: RwLock<BTreeMap<String, String>>,
+    /// The config file the server was started with, if any. CONFIG REWRITE writes
+    /// the parameters back to it.
+    file: OnceLock<PathBuf>,
+}
+
+impl Config {
+    /// Create a new `Config` with no parameters set.
+    pub fn new() -> Config {
+        Config {
+            params: RwLock::new(BTreeMap::new()),
+            file: OnceLock::new(),
+        }
+    }
+
+    /// Sets the value of a parameter. Parameter names are case-insensitive.
+    pub fn set(&self, name: &str, value: String) {
+        let mut params = self.params.write().unwrap_or_else(|e| e.into_inner());
+        params.insert(name.to_lowercase(), value);
+    }
+
+    /// Returns all the parameters and their values, ordered by name.
+    pub fn params(&self) -> Vec<(String, String)> {
+        let params = self.params.read().unwrap_or_else(|e| e.into_inner());
+        params
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Sets the config file the server was started with. It can only be set once.
+    pub fn set_file(&self, path: PathBuf) {
+        let _ = self.file.set(path);
+    }
+
+    /// Writes the current parameters back to the config file the server was started with.
+    ///
+    /// The lines of the file which set a parameter are updated in place, and the parameters
+    /// which aren't in the file yet are appended to it. Comments, blank lines and unknown
+    /// directives are left as they are.
+    ///
+    /// # Errors
+    /// Error will be returned if the server was started without a config file, or if the
+    /// file couldn't be read or written.
+    pub fn rewrite(&self) -> Result<()> {
+        let path = match self.file.get() {
+            Some(path) => path,
+            None => return Err(anyhow!("The server is running without a config file")),
+        };
+
+        let mut params: BTreeMap<String, String> = self.params().into_iter().collect();
+        let names: BTreeSet<String> = params.keys().cloned().collect();
+
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+        let mut lines: Vec<String> = vec![];
+        for line in contents.lines() {
+            let name = match parse_line(line) {
+                Some((name, _)) => name,
+                None => {
+                    lines.push(line.to_string());
+                    continue;
+                }
+            };
+
+            // The first occurrence of a parameter is updated, and any other is dropped.
+            match params.remove(&name) {
+                Some(value) => lines.push(format!("{} {}", name, value)),
+                None if names.contains(&name) => {}
+                None => lines.push(line.to_string()),
+            }
+        }
+
+        for (name, value) in params {
+            lines.push(format!("{} {}", name, value));
+        }
+
+        let mut contents = lines.join("\n");
+        contents.push('\n');
+        std::fs::write(path, contents)?;
+
+        Ok(())
+    }
+}
+
+/// How often the append-only file is synced to disk, as set by `appendfsync`.
+///
+/// The policy is validated and reported like in Redis, but it has no effect yet, since the
+/// append-only file isn't supported.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AppendFsync {
+    /// After every write.
+    Always,
+    /// Once per second.
+    #[default]
+    EverySec,
+    /// Whenever the OS flushes its buffers.
+    No,
+}
+
+impl AppendFsync {
+    /// Returns the name of the policy, as used in the config.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppendFsync::Always => "always",
+            AppendFsync::EverySec => "everysec",
+            AppendFsync::No => "no",
+        }
+    }
+}
+
+impl FromStr for AppendFsync {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "always" => Ok(AppendFsync::Always),
+            "everysec" => Ok(AppendFsync::EverySec),
+            "no" => Ok(AppendFsync::No),
+            _ => Err(String::from("argument must be one of 'always', 'everysec' or 'no'")),
+        }
+    }
+}
+
+/// Reads the directives from a config file, in the order they appear.
+///
+/// Each line holds a directive name followed by its value, for e.g. `port 6379`. Directive
+/// names are case-insensitive. Blank lines and lines starting with `#` are ignored.
+///
+/// # Errors
+/// Error will be returned if the file couldn't be read, or if a directive has no value.
+pub fn read_file(path: &Path) -> Result<Vec<(String, String)>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            return Err(anyhow!(
+                "Could not read the config file {}. Err: {}",
+                path.display(),
+                e
+            ))
+        }
+    };
+
+    let mut directives = vec![];
+    for (i, line) in contents.lines().enumerate() {
+        match parse_line(line) {
+            Some((name, value)) if !value.is_empty() => directives.push((name, value)),
+            Some((name, _)) => {
+                return Err(anyhow!(
+                    "Missing value for '{}' at line {} of the config file {}",
+                    name,
+                    i + 1,
+                    path.display()
+                ))
+            }
+            None => {}
+        }
+    }
+
+    Ok(directives)
+}
+
+/// Splits a line of a config file into the directive name and its value. Returns `None`
+/// for blank lines and comments.
+fn parse_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let (name, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let value = value.trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+
+    Some((name.to_lowercase(), value.to_string()))
+}
+
+/// Parses an amount of memory, given either as bytes or with a unit, for e.g. `100mb`.
+/// Units are case-insensitive, and follow Redis: `k`, `m` and `g` are powers of 1000, while
+/// `kb`, `mb` and `gb` are powers of 1024.
+pub fn parse_memory(value: &str) -> std::result::Result<u64, String> {
+    let value = value.to_lowercase();
+    let digits = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let multiplier: u64 = match &value[digits.len()..] {
+        "" | "b" => 1,
+        "k" => 1000,
+        "kb" => 1024,
+        "m" => 1000 * 1000,
+        "mb" => 1024 * 1024,
+        "g" => 1000 * 1000 * 1000,
+        "gb" => 1024 * 1024 * 1024,
+        unit => return Err(format!("unknown memory unit '{}'", unit)),
+    };
+
+    match digits.parse::<u64>() {
+        Ok(n) => n
+            .checked_mul(multiplier)
+            .ok_or(format!("{} is out of range", value)),
+        Err(_) => Err(format!("{} is not a valid amount of memory", value)),
+    }
+}
@@ -1,25 +1,67 @@
-use anyhow::Result;
+use std::{io::ErrorKind, time::Duration};
+
+use anyhow::{anyhow, Result};
 use futures::{SinkExt, StreamExt};
-use log::error;
-use tokio::net::TcpStream;
+use log::{debug, error, info, warn};
+use tokio::{
+  io::AsyncWriteExt,
+  net::TcpStream,
+  sync::mpsc::UnboundedReceiver,
+  time::{sleep, Instant},
+};
 use tokio_util::codec::Framed;
 
 use crate::{
-  command::{transactions::Transaction, Command},
-  resp::{frame::RespCommandFrame, types::RespType},
-  storage::db::DB,
+  clients::ClientState,
+  command::{replconf::ReplConfSubcommand, transactions::Transaction, Command},
+  replication::FullSync,
+  resp::{
+    frame::RespCommandFrame,
+    types::{Protocol, RespType},
+  },
+  storage::db::Storage,
 };
 
+/// Limits on the amount of response data buffered for a client which doesn't read
+/// its responses fast enough. A limit of `0` disables it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClientOutputBufferLimit {
+  /// The client is disconnected as soon as its output buffer exceeds this many bytes.
+  pub hard: usize,
+  /// The client is disconnected if its output buffer stays above this many bytes
+  /// for longer than `soft_seconds`.
+  pub soft: usize,
+  /// How long the output buffer may stay above the soft limit.
+  pub soft_seconds: u64,
+}
+
 /// Handles RESP command frames over a single TCP connection.
 pub struct FrameHandler {
   /// The framed connection using `RespCommandFrame` as the codec.
   conn: Framed<TcpStream, RespCommandFrame>,
+  /// Limits on the size of the output buffer of the connection.
+  output_buffer_limit: ClientOutputBufferLimit,
+  /// Maximum time a single command may run for. `None` if commands can run indefinitely.
+  command_timeout: Option<Duration>,
+  /// Number of commands run in a row before yielding to the other connections. `0` if the
+  /// connection never yields on its own.
+  commands_per_yield: usize,
 }
 
 impl FrameHandler {
   /// Create a new `FrameHandler` instance.
-  pub fn new(conn: Framed<TcpStream, RespCommandFrame>) -> FrameHandler {
-    FrameHandler { conn }
+  pub fn new(
+    conn: Framed<TcpStream, RespCommandFrame>,
+    output_buffer_limit: ClientOutputBufferLimit,
+    command_timeout: Option<Duration>,
+    commands_per_yield: usize,
+  ) -> FrameHandler {
+    FrameHandler {
+      conn,
+      output_buffer_limit,
+      command_timeout,
+      commands_per_yield,
+    }
   }
 
   /// Handles incoming RESP command frames.
@@ -42,9 +84,36 @@ impl FrameHandler {
   /// `EXEC` command is received. When `EXEC` is called, all the queued
   /// commands are executed, and the array of responses is sent back.
   ///
+  /// ## Backpressure
+  ///
+  /// No new commands are read until the response of the previous command has been
+  /// written to the TCP stream. If the client doesn't read its responses, and its output
+  /// buffer goes over the configured `ClientOutputBufferLimit`, the connection is closed.
+  ///
+  /// ## Pipelining
+  ///
+  /// When the client pipelines many commands, they could all be read and run without
+  /// ever waiting on the socket, keeping other connections on the same worker thread
+  /// from running. So the connection yields to the scheduler every `commands_per_yield`
+  /// commands. The commands are still run and replied to in order.
+  ///
+  /// ## Pub/Sub
+  ///
+  /// Once the connection subscribes to a channel or pattern, the messages published to it
+  /// are forwarded to the client, and only (P)SUBSCRIBE, (P)UNSUBSCRIBE and PING commands
+  /// are accepted until the connection unsubscribes from all channels and patterns.
+  ///
+  /// ## Replication
+  ///
+  /// If the server is a replica, commands which modify the dataset are rejected,
+  /// since the dataset is only modified by the master. When a replica sends a `PSYNC`
+  /// command, the whole dataset is streamed to it as a sequence of commands, after
+  /// which the connection only forwards the write commands to the replica.
+  ///
   /// # Arguments
   /// 
-  /// * `db` - Reference to the database where the key-value pairs are stored.
+  /// * `storage` - Reference to the shared storage, which holds the database where the
+  ///   key-value pairs are stored.
   /// 
   /// # Returns
   ///
@@ -54,78 +123,520 @@ impl FrameHandler {
   ///
   /// This method will return an error if there's an issue with reading
   /// from or writing to the connection.
-  pub async fn handle(mut self, db: &DB) -> Result<()> {
+  pub async fn handle(mut self, storage: &Storage) -> Result<()> {
     // commands are queued here if MULTI command was issued
-    let mut multicommand = Transaction::new();
+    let mut multicommand = Transaction::new(storage.watcher());
+    // channels and patterns subscribed by the connection
+    let mut subscriber = storage.subscriber();
+    // keys read by the connection, if it turned on client-side caching
+    let mut tracker = storage.tracker();
+    // commands received by the server, if the connection issued MONITOR
+    let mut monitor = storage.monitor();
+    // address of the client, as shown to the monitoring connections
+    let addr = match self.conn.get_ref().peer_addr() {
+      Ok(addr) => addr.to_string(),
+      Err(_) => String::new(),
+    };
+    // flags set on the connection with the CLIENT command
+    let mut client = ClientState::new();
+    // id of the connection and the commands it issued last, for CLIENT INFO
+    let mut client_info = storage.clients().info(addr.clone());
+    // number of commands run since the connection last yielded to the scheduler
+    let mut commands_since_yield: usize = 0;
+
+    loop {
+      let resp_cmd = tokio::select! {
+        // Check for a shutdown first, so that a long pipeline doesn't delay it.
+        biased;
+        // Close the connection once the server is shutting down, even if it's idle
+        // or waiting for messages on the subscribed channels.
+        _ = storage.wait_for_shutdown() => break,
+        resp_cmd = self.conn.next() => match resp_cmd {
+          Some(resp_cmd) => resp_cmd,
+          None => break,
+        },
+        // Forward the messages published to the subscribed channels.
+        Some(message) = subscriber.recv() => {
+          if let Err(e) = self.write_response(message).await {
+            log_write_error(&e);
+            break;
+          }
+          continue;
+        }
+        // Forward the invalidation messages for the keys read by the connection.
+        Some(message) = tracker.recv() => {
+          if let Err(e) = self.write_response(message).await {
+            log_write_error(&e);
+            break;
+          }
+          continue;
+        }
+        // Forward the commands received by the server, if the connection is monitoring it.
+        Some(line) = monitor.recv() => {
+          if let Err(e) = self.write_response(line).await {
+            log_write_error(&e);
+            break;
+          }
+          continue;
+        }
+      };
 
-    while let Some(resp_cmd) = self.conn.next().await {
       match resp_cmd {
-        Ok(cmd_frame) => {
+        Ok(Ok(cmd_frame)) => {
+          // Show the command to the monitoring connections before it's parsed, so that
+          // commands which fail are shown too.
+          storage.monitors().feed(&addr, &cmd_frame);
+          if let Some(RespType::BulkString(name)) = cmd_frame.first() {
+            client_info.record(name);
+          }
+
           // Read the command from the frame.
           let resp_cmd = Command::from_resp_command_frame(cmd_frame);
 
+          // Set if a full resync was requested by a replica.
+          let mut full_sync: Option<FullSync> = None;
+
           // If command is parsed successfully, execute it and get the RESP response,
           // otherwise set a SimpleError RESP value as the response.
           let response = match resp_cmd {
             Ok(cmd) => match cmd {
+              // Reply to PING with an array if the connection is subscribed to channels.
+              // RESP3 connections can tell pushed messages apart, so they get the usual replies.
+              Command::Ping(ping) if subscriber.is_subscribed() && !self.is_resp3() => {
+                  ping.apply_subscribed()
+              }
+              // Only pub/sub commands are allowed if a RESP2 connection is subscribed to channels
+              _ if subscriber.is_subscribed()
+                && !self.is_resp3()
+                && !matches!(
+                    cmd,
+                    Command::Subscribe(_)
+                      | Command::Unsubscribe(_)
+                      | Command::PSubscribe(_)
+                      | Command::PUnsubscribe(_)
+                ) =>
+              {
+                  RespType::SimpleError(String::from(
+                      "only (P)SUBSCRIBE / (P)UNSUBSCRIBE / PING are allowed in this context",
+                  ))
+              }
+              // Subscribe to channels if SUBSCRIBE command is issued. One reply is sent per channel.
+              Command::Subscribe(subscribe) if !multicommand.is_active() => {
+                  let replies = subscribe.apply(&mut subscriber);
+                  if let Err(e) = self.write_responses(replies).await {
+                      log_write_error(&e);
+                      break;
+                  }
+                  continue;
+              }
+              // Unsubscribe from channels if UNSUBSCRIBE command is issued. One reply is sent per channel.
+              Command::Unsubscribe(unsubscribe) if !multicommand.is_active() => {
+                  let replies = unsubscribe.apply(&mut subscriber);
+                  if let Err(e) = self.write_responses(replies).await {
+                      log_write_error(&e);
+                      break;
+                  }
+                  continue;
+              }
+              // Subscribe to patterns if PSUBSCRIBE command is issued. One reply is sent per pattern.
+              Command::PSubscribe(psubscribe) if !multicommand.is_active() => {
+                  let replies = psubscribe.apply(&mut subscriber);
+                  if let Err(e) = self.write_responses(replies).await {
+                      log_write_error(&e);
+                      break;
+                  }
+                  continue;
+              }
+              // Unsubscribe from patterns if PUNSUBSCRIBE command is issued. One reply is sent
+              // per pattern.
+              Command::PUnsubscribe(punsubscribe) if !multicommand.is_active() => {
+                  let replies = punsubscribe.apply(&mut subscriber);
+                  if let Err(e) = self.write_responses(replies).await {
+                      log_write_error(&e);
+                      break;
+                  }
+                  continue;
+              }
               // Initialize pipeline if MULTI command is issued
               Command::Multi => {
                   let init_multicommand = &mut multicommand.init();
                   match init_multicommand {
-                      Ok(_) => cmd.execute(db),
+                      Ok(_) => self.execute_with_timeout(cmd, storage, &client).await,
                       Err(e) => RespType::SimpleError(format!("{}", e)),
                   }
               }
               // Execute all commands in pipeline if EXEC command is issued
               Command::Exec => {
                   if multicommand.is_active() {
-                      multicommand.exec(db).await
+                      multicommand.exec(storage, &client).await
                   } else {
                       RespType::SimpleError(String::from("EXEC without MULTI"))
                   }
               }
+              // Watch the keys for the next transaction if WATCH command is issued. Inside a
+              // transaction, it's rejected and EXEC fails, like with a command which can't be
+              // parsed.
+              Command::Watch(watch) => {
+                  if multicommand.is_active() {
+                      multicommand.fail();
+                      RespType::SimpleError(String::from("WATCH inside MULTI is not allowed"))
+                  } else {
+                      multicommand.watch(&watch.keys());
+                      RespType::SimpleString(String::from("OK"))
+                  }
+              }
+              // Start sending the commands received by the server if MONITOR command is issued.
+              Command::Monitor if !multicommand.is_active() => {
+                  monitor.start();
+                  RespType::SimpleString(String::from("OK"))
+              }
+              Command::Unwatch if !multicommand.is_active() => {
+                  multicommand.unwatch();
+                  RespType::SimpleString(String::from("OK"))
+              }
+              // Drop the queued commands, if any, and end the transaction if DISCARD command
+              // is issued. The watched keys are dropped along with them.
               Command::Discard => {
                   if multicommand.is_active() {
                       multicommand.discard();
-                      cmd.execute(db)
+                      RespType::SimpleString(String::from("OK"))
                   } else {
                       RespType::SimpleError(String::from("DISCARD without MULTI"))
                   }
               }
+              // Stream the dataset to the replica if PSYNC command is issued
+              Command::PSync(_) if !multicommand.is_active() => {
+                  let addr = match self.conn.get_ref().peer_addr() {
+                      Ok(addr) => addr,
+                      Err(e) => {
+                          error!("Error reading the replica address: {}", e);
+                          break;
+                      }
+                  };
+
+                  match storage.replication().full_sync(&storage.db(), addr) {
+                      Ok(sync) => {
+                          let response = RespType::SimpleString(format!(
+                              "FULLRESYNC {} {}",
+                              storage.replication().replid(),
+                              sync.offset
+                          ));
+                          full_sync = Some(sync);
+                          response
+                      }
+                      Err(e) => RespType::SimpleError(format!("{}", e)),
+                  }
+              }
+              // Switch the protocol of the connection if HELLO command is issued.
+              // Client-side caching is turned off along with RESP3, as it relies on pushes.
+              Command::Hello(hello) if !multicommand.is_active() => {
+                  if let Some(protocol) = hello.protocol() {
+                      self.conn.codec_mut().set_protocol(protocol);
+                      if protocol == Protocol::Resp2 {
+                          client.set_tracking(false);
+                          tracker.set_enabled(false);
+                      }
+                  }
+                  hello.apply(storage, self.conn.codec().protocol())
+              }
+              // Update the flags of the connection if CLIENT command is issued
+              Command::Client(client_cmd) if !multicommand.is_active() => {
+                  let protocol = self.conn.codec().protocol();
+                  let res = client_cmd.apply(&mut client, &client_info, protocol);
+                  tracker.set_enabled(client.tracking());
+                  res
+              }
+              // Block until the replicas acknowledge the writes if WAIT command is issued.
+              // The connection is closed without a response if the server shuts down meanwhile.
+              Command::Wait(wait) if !multicommand.is_active() => tokio::select! {
+                  res = wait.apply(storage) => res,
+                  _ = storage.wait_for_shutdown() => break,
+              },
+              // Shut down the server if SHUTDOWN command is issued. The connection is
+              // closed without sending a response.
+              Command::Shutdown(shutdown) if !multicommand.is_active() => {
+                  shutdown.apply(storage);
+                  return Ok(());
+              }
+              // Reject writes from clients if this server is a replica, or in read-only mode
+              _ if cmd.is_write()
+                  && (storage.replication().is_replica() || storage.is_read_only()) =>
+              {
+                  RespType::SimpleError(String::from(
+                      "READONLY You can't write against a read only replica.",
+                  ))
+              }
               _ => {
                   // Queue commands if pipeline is active, else execute the command
                   if multicommand.is_active() {
                       multicommand.add_command(cmd);
                       RespType::SimpleString(String::from("QUEUED"))
                   } else {
-                      cmd.execute(db)
+                      // The keys are tracked before they're read, so that a write
+                      // racing with the read still invalidates them.
+                      if client.tracking() && cmd.is_readonly() {
+                          tracker.track(&cmd.keys());
+                      }
+                      self.execute_with_timeout(cmd, storage, &client).await
                   }
               }
             },
+            // The transaction is kept, so that the following commands are still queued,
+            // but EXEC fails.
             Err(e) => {
                 if multicommand.is_active() {
-                    multicommand.discard();
+                    multicommand.fail();
                 }
                 RespType::SimpleError(format!("{}", e))
             }
           };
 
           // Write the RESP response into the TCP stream.
-          if let Err(e) = self.conn.send(response).await {
-              error!("Error sending response: {}", e);
+          if let Err(e) = self.write_response(response).await {
+              log_write_error(&e);
               break;
           }
+
+          // From now on, the connection belongs to a replica. The state of the client
+          // connection is released first, since serving the replica only returns once it
+          // disconnects. Dropping the transaction removes the keys it watched from storage.
+          if let Some(sync) = full_sync {
+              drop(multicommand);
+              drop(subscriber);
+              drop(tracker);
+              drop(monitor);
+              return self.serve_replica(storage, sync).await;
+          }
+
+          // Let the other connections run if this one has a long pipeline of commands.
+          commands_since_yield += 1;
+          if self.commands_per_yield > 0 && commands_since_yield >= self.commands_per_yield {
+              commands_since_yield = 0;
+              tokio::task::yield_now().await;
+          }
+        }
+        // The malformed command was skipped by the codec, so reply with the error
+        // and carry on with the next command.
+        Ok(Err(e)) => {
+          // Like with a command which can't be parsed, the transaction is kept but EXEC fails.
+          if multicommand.is_active() {
+              multicommand.fail();
+          }
+          // The connection is kept open, so make sure no part of the malformed command
+          // ends up in the next one.
+          self.conn.codec_mut().reset();
+
+          let response = RespType::SimpleError(format!("Protocol error: {}", e));
+          if let Err(e) = self.write_response(response).await {
+              log_write_error(&e);
+              break;
+          }
+        }
+        Err(e) if is_disconnect(&e) => {
+          debug!("Client disconnected while sending a request: {}", e);
+          break;
         }
         Err(e) => {
           error!("Error reading the request: {}", e);
           break;
         }
       };
+    }
+
+    Ok(())
+  }
+
+  /// Checks if the connection switched to RESP3 with the HELLO command.
+  fn is_resp3(&self) -> bool {
+    self.conn.codec().protocol() == Protocol::Resp3
+  }
+
+  /// Writes each of the responses into the TCP stream, in order.
+  ///
+  /// # Errors
+  ///
+  /// This method will return an error if writing any of the responses fails.
+  async fn write_responses(&mut self, responses: Vec<RespType>) -> Result<()> {
+    for response in responses {
+      self.write_response(response).await?;
+    }
+
+    Ok(())
+  }
 
-      // flush the buffer into the TCP stream.
+  /// Executes the command, giving up on it if it runs longer than the configured command timeout.
+  ///
+  /// Commands run synchronously while holding the DB locks, so they can't be interrupted
+  /// midway. Instead, the command is run on tokio's blocking thread pool, and the connection
+  /// stops waiting for it once the timeout elapses. The abandoned command still runs to
+  /// completion in the background, and its effects (if any) are applied. Until then it keeps
+  /// holding the locks it acquired, so other connections touching the same data wait as before.
+  /// Since every command runs on the blocking thread pool once a timeout is set, they also
+  /// wait for these locks there, without blocking the tasks of other connections.
+  ///
+  /// # Returns
+  ///
+  /// The result of the command, or a `SimpleError` if the command timed out.
+  async fn execute_with_timeout(
+    &self,
+    cmd: Command,
+    storage: &Storage,
+    client: &ClientState,
+  ) -> RespType {
+    let timeout = match self.command_timeout {
+      Some(timeout) => timeout,
+      None => return cmd.execute(storage, client),
+    };
+
+    let task_storage = storage.clone();
+    let task_client = client.clone();
+    let task = tokio::task::spawn_blocking(move || cmd.execute(&task_storage, &task_client));
+
+    match tokio::time::timeout(timeout, task).await {
+      Ok(Ok(res)) => res,
+      Ok(Err(e)) => RespType::SimpleError(format!("{}", e)),
+      Err(_) => RespType::SimpleError(String::from("command timed out")),
+    }
+  }
+
+  /// Writes the response into the output buffer and flushes the buffer into the TCP stream,
+  /// while enforcing the output buffer limits.
+  ///
+  /// # Errors
+  ///
+  /// This method will return an error if there's an issue with writing to the connection,
+  /// or if the output buffer goes over the limits, in which case the connection should be closed.
+  async fn write_response(&mut self, response: RespType) -> Result<()> {
+    let limit = self.output_buffer_limit;
+
+    // `feed` only encodes the response into the output buffer, without flushing it.
+    self.conn.feed(response).await?;
+
+    let buffered = self.conn.write_buffer().len();
+    if limit.hard > 0 && buffered > limit.hard {
+      warn!("Client closed for overcoming of output buffer limits: {} bytes buffered", buffered);
+      return Err(anyhow!("Client output buffer hard limit exceeded"));
+    }
+
+    if limit.soft == 0 || buffered <= limit.soft {
       self.conn.flush().await?;
+      return Ok(());
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(limit.soft_seconds);
+    tokio::select! {
+      res = self.conn.flush() => res?,
+      _ = sleep(deadline - Instant::now()) => {
+        let buffered = self.conn.write_buffer().len();
+        if buffered > limit.soft {
+          warn!("Client closed for overcoming of output buffer limits: {} bytes buffered", buffered);
+          return Err(anyhow!("Client output buffer soft limit exceeded"));
+        }
+        self.conn.flush().await?;
+      }
     }
 
     Ok(())
   }
-}
\ No newline at end of file
+
+  /// Sends the dump of the dataset to a replica, and then forwards the write commands
+  /// to it until the replica disconnects.
+  ///
+  /// The dump is sent in the same way as a bulk string, but without the trailing CRLF:
+  /// `$<length>\r\n<commands>`. The acknowledgements sent by the replica are recorded
+  /// in the replication state.
+  ///
+  /// # Arguments
+  ///
+  /// * `storage` - Reference to the shared storage, which holds the replication state.
+  ///
+  /// * `sync` - The full resync started for the replica.
+  ///
+  /// # Errors
+  ///
+  /// This method will return an error if there's an issue with writing to the connection.
+  async fn serve_replica(mut self, storage: &Storage, sync: FullSync) -> Result<()> {
+    let replication = storage.replication();
+    let FullSync {
+      replica_id,
+      dump,
+      mut propagated_cmds,
+      ..
+    } = sync;
+
+    let stream = self.conn.get_mut();
+    stream.write_all(format!("${}\r\n", dump.len()).as_bytes()).await?;
+    stream.write_all(&dump).await?;
+
+    let res = self.forward_to_replica(storage, replica_id, &mut propagated_cmds).await;
+
+    replication.remove_replica(replica_id);
+
+    res
+  }
+
+  /// Forwards the write commands to a replica and records its acknowledgements,
+  /// until the replica disconnects.
+  async fn forward_to_replica(
+    &mut self,
+    storage: &Storage,
+    replica_id: u64,
+    propagated_cmds: &mut UnboundedReceiver<RespType>,
+  ) -> Result<()> {
+    loop {
+      tokio::select! {
+        // The replica notices the master going away, and tries to reconnect.
+        _ = storage.wait_for_shutdown() => break,
+        cmd = propagated_cmds.recv() => match cmd {
+          Some(cmd) => self.conn.send(cmd).await?,
+          None => break,
+        },
+        frame = self.conn.next() => match frame {
+          // Replicas only send acknowledgements after the full resync.
+          Some(Ok(Ok(frame))) => {
+            if let Ok(Command::ReplConf(replconf)) = Command::from_resp_command_frame(frame) {
+              if let ReplConfSubcommand::Ack(offset) = replconf.subcommand() {
+                storage.replication().ack(replica_id, *offset);
+              }
+            }
+          }
+          Some(Ok(Err(e))) => error!("Invalid command from replica: {}", e),
+          Some(Err(e)) => {
+            error!("Error reading from replica: {}", e);
+            break;
+          }
+          None => {
+            info!("Replica disconnected");
+            break;
+          }
+        },
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Checks if the I/O error means that the peer went away, for e.g. by closing the connection
+/// before reading its responses. This is part of the normal life of a connection, unlike the
+/// other I/O errors.
+pub fn is_disconnect(e: &std::io::Error) -> bool {
+  matches!(
+    e.kind(),
+    ErrorKind::BrokenPipe
+      | ErrorKind::ConnectionReset
+      | ErrorKind::ConnectionAborted
+      | ErrorKind::UnexpectedEof
+  )
+}
+
+/// Logs an error from writing to the connection. Clients going away midway through a
+/// response are expected, so they're only logged at debug level.
+fn log_write_error(e: &anyhow::Error) {
+  match e.downcast_ref::<std::io::Error>() {
+    Some(io_err) if is_disconnect(io_err) => {
+      debug!("Client disconnected before the response was sent: {}", e)
+    }
+    _ => error!("Error sending response: {}", e),
+  }
+}
@@ -1,16 +1,46 @@
+mod clients;
 mod command;
+mod config;
 mod server;
 mod resp;
 mod handler;
+mod monitor;
+mod pubsub;
+mod replication;
 mod storage;
+mod tracking;
+mod watch;
 
-use anyhow::Result;
+use std::{
+    fs::OpenOptions,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
 use clap::Parser;
-use log::info;
+use clients::DEFAULT_CLIENT_HISTORY_LEN;
+use config::AppendFsync;
+use log::{error, info};
+use handler::ClientOutputBufferLimit;
 use server::Server;
-use tokio::net::TcpListener;
+use storage::{db::DEFAULT_SET_MAX_INTSET_ENTRIES, eviction::MaxMemoryPolicy};
+use tokio::{
+    net::{lookup_host, TcpListener},
+    runtime::Runtime,
+};
 
 const DEFAULT_PORT: u16 = 6377;
+const DEFAULT_BIND: &str = "127.0.0.1";
+const DEFAULT_COMMANDS_PER_YIELD: usize = 128;
+
+/// How often the expired keys are removed in the background, same as Redis' default `hz`.
+const ACTIVE_EXPIRE_INTERVAL: Duration = Duration::from_millis(100);
+/// The maximum number of expired keys removed at once in the background, so that the DB
+/// isn't locked for long. The rest are removed on the next runs.
+const ACTIVE_EXPIRE_KEYS: usize = 1000;
 
 #[derive(Debug, Parser)]
 #[command(
@@ -23,46 +53,516 @@ struct Cli {
     /// Port to be bound to Nimblecache server
     #[arg(long)]
     port: Option<u16>,
+
+    /// Address the server listens on, either an IP address or a hostname. A hostname may
+    /// resolve to several addresses, in which case the server listens on the first one
+    /// which can be bound. Defaults to 127.0.0.1
+    #[arg(long)]
+    bind: Option<String>,
+
+    /// File to write the process id into. It's removed on graceful shutdown.
+    #[arg(long)]
+    pidfile: Option<PathBuf>,
+
+    /// Output buffer limits for clients, in bytes: <hard> <soft> <soft-seconds>.
+    /// A client is disconnected when its output buffer exceeds the hard limit, or stays
+    /// above the soft limit for soft-seconds. 0 disables a limit.
+    #[arg(long, num_args = 3, value_names = ["HARD", "SOFT", "SOFT_SECONDS"])]
+    client_output_buffer_limit: Option<Vec<u64>>,
+
+    /// Maximum time in milliseconds a single command may run for, before the client
+    /// receives a timeout error. Commands can run indefinitely if unset.
+    #[arg(long)]
+    command_timeout: Option<u64>,
+
+    /// Number of commands a connection runs in a row before letting the other connections
+    /// run, when the client pipelines many commands. Defaults to 128, 0 disables it.
+    #[arg(long)]
+    commands_per_yield: Option<usize>,
+
+    /// Maximum number of connected clients. Defaults to 10000
+    #[arg(long)]
+    maxclients: Option<usize>,
+
+    /// Number of commands remembered per connection and listed by CLIENT INFO. Defaults to
+    /// 1, which only keeps the last command.
+    #[arg(long)]
+    client_history_len: Option<usize>,
+
+    /// File to append the logs to. Logs are written to stderr if unset.
+    #[arg(long)]
+    logfile: Option<PathBuf>,
+
+    /// Number of worker threads handling the connections. Defaults to the number of CPU cores
+    #[arg(long)]
+    io_threads: Option<usize>,
+
+    /// Maximum memory used by the dataset, in bytes. Units like 100mb or 1gb are accepted.
+    #[arg(long, value_parser = config::parse_memory)]
+    maxmemory: Option<u64>,
+
+    /// How memory is freed once the dataset is over maxmemory, either `noeviction` (the
+    /// default) or `volatile-ttl`
+    #[arg(long)]
+    maxmemory_policy: Option<MaxMemoryPolicy>,
+
+    /// Expiry in seconds of the keys written by SET without EX/PX/EXAT/PXAT. Defaults to 0,
+    /// which keeps such keys until they're deleted.
+    #[arg(long)]
+    default_ttl: Option<u64>,
+
+    /// Maximum number of elements a single list may hold. Pushes which would grow a list
+    /// over it are rejected. Defaults to 0, which means there's no limit.
+    #[arg(long)]
+    list_max_size: Option<u64>,
+
+    /// Maximum size in bytes of a single value written by SET, LPUSH or RPUSH. Writes with a
+    /// larger value are rejected. Defaults to 0, which means there's no limit.
+    #[arg(long)]
+    max_value_size: Option<u64>,
+
+    /// Maximum number of members of a set made of integers reported by OBJECT ENCODING as
+    /// `intset`. Larger sets are reported as `hashtable`. Defaults to 512.
+    #[arg(long)]
+    set_max_intset_entries: Option<usize>,
+
+    /// Enables the append-only file. Only `no` is supported, since Nimblecache doesn't
+    /// persist the dataset yet.
+    #[arg(long)]
+    appendonly: Option<String>,
+
+    /// How often the append-only file is synced to disk: `always`, `everysec` (the default)
+    /// or `no`. It has no effect yet, as the append-only file isn't supported.
+    #[arg(long)]
+    appendfsync: Option<AppendFsync>,
+
+    /// Rejects the commands which modify the dataset, while still serving reads.
+    /// It can be turned off at runtime with CONFIG SET read-only no.
+    #[arg(long)]
+    read_only: bool,
+
+    /// Config file with one `directive value` per line, using the same names as CONFIG GET.
+    /// Options given on the command line take precedence over the file.
+    #[arg(long)]
+    configfile: Option<PathBuf>,
+
+    /// Config file, same as --configfile. It's accepted as the first argument, like redis-server.
+    #[arg(value_name = "CONFIGFILE", conflicts_with = "configfile")]
+    config_path: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    // Get port from --port CLI parameter. Defaults to 6377
+    let mut cli = Cli::parse();
+    if cli.configfile.is_none() {
+        cli.configfile = cli.config_path.take();
+    }
+    if let Some(configfile) = cli.configfile.clone() {
+        apply_config_file(&mut cli, &configfile)?;
+    }
+    if cli.appendonly.as_deref().is_some_and(|a| a != "no") {
+        return Err(anyhow!("The append-only file is not supported, appendonly must be 'no'"));
+    }
+
+    let runtime = build_runtime(cli.io_threads)?;
+    runtime.block_on(run(cli))
+}
+
+/// Fills in the options which weren't given on the command line from the config file.
+fn apply_config_file(cli: &mut Cli, path: &Path) -> Result<()> {
+    for (name, value) in config::read_file(path)? {
+        match name.as_str() {
+            "port" => {
+                cli.port = cli.port.or(Some(parse_directive(&name, &value)?));
+            }
+            "bind" => {
+                if value.split_whitespace().count() > 1 {
+                    return Err(anyhow!(
+                        "Only one address can be given for 'bind' in the config file: {}",
+                        value
+                    ));
+                }
+                cli.bind = cli.bind.take().or(Some(value));
+            }
+            "maxmemory" => {
+                let maxmemory = match config::parse_memory(&value) {
+                    Ok(maxmemory) => maxmemory,
+                    Err(e) => {
+                        return Err(anyhow!(
+                            "Invalid value for '{}' in the config file: {}",
+                            name,
+                            e
+                        ))
+                    }
+                };
+                cli.maxmemory = cli.maxmemory.or(Some(maxmemory));
+            }
+            "maxmemory-policy" => {
+                cli.maxmemory_policy =
+                    cli.maxmemory_policy.or(Some(parse_directive(&name, &value)?));
+            }
+            "default-ttl" => {
+                cli.default_ttl = cli.default_ttl.or(Some(parse_directive(&name, &value)?));
+            }
+            "list-max-size" => {
+                cli.list_max_size = cli.list_max_size.or(Some(parse_directive(&name, &value)?));
+            }
+            "max-value-size" => {
+                cli.max_value_size = cli.max_value_size.or(Some(parse_directive(&name, &value)?));
+            }
+            "set-max-intset-entries" => {
+                cli.set_max_intset_entries =
+                    cli.set_max_intset_entries.or(Some(parse_directive(&name, &value)?));
+            }
+            "read-only" => match value.to_lowercase().as_str() {
+                "yes" => cli.read_only = true,
+                "no" => {}
+                _ => {
+                    return Err(anyhow!(
+                        "Invalid value for '{}' in the config file: {}",
+                        name,
+                        value
+                    ))
+                }
+            },
+            "appendonly" => {
+                cli.appendonly = cli.appendonly.take().or(Some(value.to_lowercase()));
+            }
+            "appendfsync" => {
+                cli.appendfsync = cli.appendfsync.or(Some(parse_directive(&name, &value)?));
+            }
+            "pidfile" => {
+                cli.pidfile = cli.pidfile.take().or(Some(PathBuf::from(value)));
+            }
+            "client-output-buffer-limit" => {
+                // The class of clients is optional, since only normal clients have limits.
+                let limits = value.strip_prefix("normal ").unwrap_or(&value);
+                let limits = limits
+                    .split_whitespace()
+                    .map(|limit| parse_directive(&name, limit))
+                    .collect::<Result<Vec<u64>>>()?;
+                if limits.len() != 3 {
+                    return Err(anyhow!(
+                        "Invalid value for '{}' in the config file: {}",
+                        name,
+                        value
+                    ));
+                }
+                cli.client_output_buffer_limit =
+                    cli.client_output_buffer_limit.take().or(Some(limits));
+            }
+            "command-timeout" => {
+                cli.command_timeout = cli.command_timeout.or(Some(parse_directive(&name, &value)?));
+            }
+            "commands-per-yield" => {
+                cli.commands_per_yield =
+                    cli.commands_per_yield.or(Some(parse_directive(&name, &value)?));
+            }
+            "maxclients" => {
+                cli.maxclients = cli.maxclients.or(Some(parse_directive(&name, &value)?));
+            }
+            "client-history-len" => {
+                cli.client_history_len =
+                    cli.client_history_len.or(Some(parse_directive(&name, &value)?));
+            }
+            "logfile" => {
+                cli.logfile = cli.logfile.take().or(Some(PathBuf::from(value)));
+            }
+            "io-threads" => {
+                cli.io_threads = cli.io_threads.or(Some(parse_directive(&name, &value)?));
+            }
+            _ => {
+                return Err(anyhow!(
+                    "Unknown directive '{}' in the config file {}",
+                    name,
+                    path.display()
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses the value of a directive in the config file.
+fn parse_directive<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    match value.parse::<T>() {
+        Ok(v) => Ok(v),
+        Err(_) => Err(anyhow!(
+            "Invalid value for '{}' in the config file: {}",
+            name,
+            value
+        )),
+    }
+}
+
+/// Builds the multi-threaded tokio runtime which runs the server.
+///
+/// # Arguments
+///
+/// * `io_threads` - Number of worker threads. Defaults to the number of CPU cores if `None`.
+fn build_runtime(io_threads: Option<usize>) -> Result<Runtime> {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all();
+
+    if let Some(io_threads) = io_threads {
+        if io_threads == 0 {
+            return Err(anyhow!("The number of IO threads must be greater than 0"));
+        }
+        builder.worker_threads(io_threads);
+    }
+
+    match builder.build() {
+        Ok(runtime) => Ok(runtime),
+        Err(e) => Err(anyhow!("Could not start the runtime. Err: {}", e)),
+    }
 }
 
+/// Starts the server with the given options, and runs it until it's shut down.
+async fn run(cli: Cli) -> Result<()> {
 
-#[tokio::main]
-async fn main() -> Result<()> {
     // Initialize the logger.
     // This sets up logging based on the RUST_LOG environment variable
-    env_logger::init();
-
-    // Get port from --port CLI parameter. Defaults to 6377
-    let cli = Cli::parse();
+    let mut logger = env_logger::Builder::from_default_env();
+    if let Some(logfile) = &cli.logfile {
+        let file = match OpenOptions::new().create(true).append(true).open(logfile) {
+            Ok(file) => file,
+            Err(e) => {
+                return Err(anyhow!(
+                    "Could not open the log file {}. Err: {}",
+                    logfile.display(),
+                    e
+                ))
+            }
+        };
+        logger.target(env_logger::Target::Pipe(Box::new(file)));
+    }
+    logger.init();
     let port = cli.port.unwrap_or(DEFAULT_PORT);
-
-    // Define the address and port for the TCP server to listen on
-    // Here we're using localhost (127.0.0.1) and port 6379 (commonly used for Redis)
-    let addr = format!("127.0.0.1:{}", port);
+    let bind = cli.bind.clone().unwrap_or(String::from(DEFAULT_BIND));
 
     // Attempt to bind the TCP listener to the specified address and port
-    let listener = match TcpListener::bind(&addr).await {
+    // By default we're using localhost (127.0.0.1) and port 6377
+    let listener = match bind_listener(&bind, port).await {
         // if successful, return the TcpListener
-        Ok(tcp_listener) => {
-            info!("TCP Listener started on port port");
-            tcp_listener
-        },
+        Ok(tcp_listener) => tcp_listener,
         // If there is an error, panic and print the error message
         // This could happen if the port is already in use, for example
-        Err(e) => panic!("Could not bind the TCP listener to {}. Err: {}", &addr, e)
+        Err(e) => panic!("Could not bind the TCP listener to {}:{}. Err: {}", &bind, port, e)
+    };
+    // With port 0, the OS picks a free port, so the port actually bound is the one reported.
+    let port = match listener.local_addr() {
+        Ok(local_addr) => local_addr.port(),
+        Err(_) => port,
     };
 
     // initialize shared storage
     let shared_storage = storage::db::Storage::new(storage::db::DB::new());
+    if let Some(maxclients) = cli.maxclients {
+        shared_storage.clients().set_maxclients(maxclients);
+    }
+    let client_history_len = cli.client_history_len.unwrap_or(DEFAULT_CLIENT_HISTORY_LEN);
+    shared_storage.clients().set_history_len(client_history_len);
+    shared_storage.set_read_only(cli.read_only);
+    let maxmemory = cli.maxmemory.unwrap_or(0);
+    shared_storage.set_maxmemory(maxmemory);
+    let maxmemory_policy = cli.maxmemory_policy.unwrap_or_default();
+    shared_storage.set_maxmemory_policy(maxmemory_policy);
+    let default_ttl = cli.default_ttl.unwrap_or(0);
+    shared_storage.set_default_ttl(default_ttl);
+    let list_max_size = cli.list_max_size.unwrap_or(0);
+    shared_storage.set_list_max_size(list_max_size);
+    let max_value_size = cli.max_value_size.unwrap_or(0);
+    shared_storage.set_max_value_size(max_value_size);
+    let set_max_intset_entries =
+        cli.set_max_intset_entries.unwrap_or(DEFAULT_SET_MAX_INTSET_ENTRIES);
+    shared_storage.db().set_set_max_intset_entries(set_max_intset_entries);
+
+    // Output buffer limits for the client connections. Disabled by default.
+    let output_buffer_limit = match cli.client_output_buffer_limit.as_deref() {
+        Some([hard, soft, soft_seconds]) => ClientOutputBufferLimit {
+            hard: *hard as usize,
+            soft: *soft as usize,
+            soft_seconds: *soft_seconds,
+        },
+        _ => ClientOutputBufferLimit::default(),
+    };
 
     // Create a new instance of the Server with the bound TcpListenerlet mut server = Server::new(listener);
-    let mut server = Server::new(listener, shared_storage);
+    let command_timeout = cli.command_timeout.map(Duration::from_millis);
+    let commands_per_yield = cli.commands_per_yield.unwrap_or(DEFAULT_COMMANDS_PER_YIELD);
+
+    // Expose the options the server was started with to CONFIG GET.
+    let config = shared_storage.config();
+    config.set("port", port.to_string());
+    config.set("bind", bind);
+    config.set("read-only", String::from(if cli.read_only { "yes" } else { "no" }));
+    config.set("maxmemory", maxmemory.to_string());
+    config.set("maxmemory-policy", String::from(maxmemory_policy.name()));
+    config.set("default-ttl", default_ttl.to_string());
+    config.set("list-max-size", list_max_size.to_string());
+    config.set("max-value-size", max_value_size.to_string());
+    config.set("set-max-intset-entries", set_max_intset_entries.to_string());
+    if let Some(appendonly) = &cli.appendonly {
+        config.set("appendonly", appendonly.to_string());
+    }
+    let appendfsync = cli.appendfsync.unwrap_or_default();
+    config.set("appendfsync", String::from(appendfsync.name()));
+    if let Some(maxclients) = cli.maxclients {
+        config.set("maxclients", maxclients.to_string());
+    }
+    config.set("client-history-len", client_history_len.to_string());
+    if let Some(limit) = &cli.client_output_buffer_limit {
+        let limit: Vec<String> = limit.iter().map(|v| v.to_string()).collect();
+        config.set("client-output-buffer-limit", format!("normal {}", limit.join(" ")));
+    }
+    config.set("commands-per-yield", commands_per_yield.to_string());
+    if let Some(timeout) = cli.command_timeout {
+        config.set("command-timeout", timeout.to_string());
+    }
+    if let Some(pidfile) = &cli.pidfile {
+        config.set("pidfile", pidfile.display().to_string());
+    }
+    if let Some(logfile) = &cli.logfile {
+        config.set("logfile", logfile.display().to_string());
+    }
+    if let Some(io_threads) = cli.io_threads {
+        config.set("io-threads", io_threads.to_string());
+    }
+    if let Some(configfile) = &cli.configfile {
+        config.set_file(configfile.clone());
+    }
+
+    // Shut the server down gracefully on SIGINT or SIGTERM, the same way as SHUTDOWN does.
+    let signal_storage = shared_storage.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        info!("Shutdown signal received, shutting down");
+        signal_storage.shutdown();
+    });
+
+    // Remove the expired keys in the background, so that the keys which are never accessed
+    // again don't stay in memory. Like a command, it doesn't run in the middle of a
+    // transaction, and it waits for the locks on a blocking thread, not on a worker.
+    let expire_storage = shared_storage.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(ACTIVE_EXPIRE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let storage = expire_storage.clone();
+            let removed = tokio::task::spawn_blocking(move || {
+                let _command_lock = storage.lock_command();
+                storage.db().remove_expired(ACTIVE_EXPIRE_KEYS)
+            })
+            .await;
+            if let Ok(Err(e)) = removed {
+                error!("Could not remove the expired keys. Err: {}", e);
+            }
+        }
+    });
+
+    let mut server = Server::new(
+        listener,
+        shared_storage,
+        output_buffer_limit,
+        command_timeout,
+        commands_per_yield,
+    );
+    match server.local_addr() {
+        Ok(local_addr) => info!("TCP Listener started on {}", local_addr),
+        Err(e) => error!("Could not read the address of the TCP listener. Err: {}", e),
+    }
+
+    // Write the process id, so that process supervisors can track the server.
+    if let Some(pidfile) = &cli.pidfile {
+        if let Err(e) = std::fs::write(pidfile, format!("{}\n", std::process::id())) {
+            error!("Could not write the PID file {}. Err: {}", pidfile.display(), e);
+        }
+    }
 
     // Run the server to start accepting and handling connections
-    // This will run indefinitely until the program is terminated
-    server.run().await?;
+    // This will run indefinitely until the program receives a shutdown signal.
+    let res = server.run().await;
 
-    // This Ok(()) is technically unreachable as server.run() loops infinitely,
-    // but it's needed to satisfy the Result return type of main()
-    Ok(())
+    if let Some(pidfile) = &cli.pidfile {
+        if let Err(e) = std::fs::remove_file(pidfile) {
+            error!("Could not remove the PID file {}. Err: {}", pidfile.display(), e);
+        }
+    }
+
+    res
+}
+
+/// Binds a TCP listener to the given address and port. The address is either an IP address,
+/// with or without the brackets around an IPv6 one, or a hostname.
+///
+/// A hostname is resolved, and the listener is bound to the first of its addresses which
+/// can be bound, in the order the resolver returns them. So `localhost` is usually bound
+/// to its IPv6 or its IPv4 address, not both.
+async fn bind_listener(bind: &str, port: u16) -> std::io::Result<TcpListener> {
+    let host = bind.trim_start_matches('[').trim_end_matches(']');
+
+    let mut last_err = None;
+    for addr in lookup_host((host, port)).await? {
+        match TcpListener::bind(addr).await {
+            Ok(listener) => return Ok(listener),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| {
+        std::io::Error::new(ErrorKind::AddrNotAvailable, "no addresses to bind to")
+    }))
+}
+
+/// Completes when the process receives SIGINT (Ctrl+C), or SIGTERM on unix platforms.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Could not listen for the Ctrl+C signal. Err: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                error!("Could not listen for the SIGTERM signal. Err: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_has_the_given_number_of_worker_threads() {
+        for io_threads in [1, 3] {
+            let runtime = build_runtime(Some(io_threads)).unwrap();
+            assert_eq!(runtime.metrics().num_workers(), io_threads);
+            assert_eq!(runtime.block_on(async { 1 + 1 }), 2);
+        }
+    }
+
+    #[test]
+    fn runtime_needs_at_least_one_worker_thread() {
+        assert!(build_runtime(Some(0)).is_err());
+    }
 }
@@ -0,0 +1,176 @@
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::resp::types::RespType;
+
+/// Holds the connections which issued the MONITOR command. Every command received by the
+/// server is sent to them as a line of text.
+#[derive(Debug)]
+pub struct Monitors {
+    /// The monitoring connections, by monitor id.
+    monitors: Mutex<HashMap<u64, UnboundedSender<RespType>>>,
+    /// Id to be assigned to the next monitor.
+    next_monitor_id: AtomicU64,
+}
+
+/// The monitor state of a single connection.
+///
+/// The commands received by the server are delivered through the receiver held here,
+/// once the connection has issued MONITOR.
+#[derive(Debug)]
+pub struct Monitor {
+    /// Id of the monitor.
+    id: u64,
+    /// The shared monitor state.
+    monitors: Arc<Monitors>,
+    /// Commands are received from this channel. `None` until MONITOR is issued.
+    receiver: Option<UnboundedReceiver<RespType>>,
+}
+
+impl Monitors {
+    /// Create a new instance of `Monitors` with no monitoring connections.
+    pub fn new() -> Monitors {
+        Monitors {
+            monitors: Mutex::new(HashMap::new()),
+            next_monitor_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Sends a command received from a client to the monitoring connections.
+    ///
+    /// The command is sent as a `SimpleString` of the form
+    /// `1339518083.107412 [0 127.0.0.1:60866] "set" "key" "value"`, like Redis does. It's
+    /// sent whether the command succeeds or not.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address of the client which sent the command.
+    /// * `frame` - The command name followed by its arguments, as received.
+    pub fn feed(&self, addr: &str, frame: &[RespType]) {
+        let mut monitors = self.monitors.lock().unwrap_or_else(|e| e.into_inner());
+        if monitors.is_empty() {
+            return;
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let mut line = format!("{}.{:06} [0 {}]", now.as_secs(), now.subsec_micros(), addr);
+        for arg in frame.iter() {
+            line.push(' ');
+            match arg {
+                RespType::BulkString(s) => line.push_str(&quote(s)),
+                _ => line.push_str("\"\""),
+            }
+        }
+
+        // Monitors whose connections are gone are dropped.
+        let line = RespType::SimpleString(line);
+        monitors.retain(|_, sender| sender.send(line.clone()).is_ok());
+    }
+}
+
+impl Monitor {
+    /// Create a new `Monitor`, which doesn't receive any commands until it's started.
+    pub fn new(monitors: Arc<Monitors>) -> Monitor {
+        let id = monitors.next_monitor_id.fetch_add(1, Ordering::Relaxed);
+
+        Monitor {
+            id,
+            monitors,
+            receiver: None,
+        }
+    }
+
+    /// Starts receiving the commands sent to the server (MONITOR command).
+    pub fn start(&mut self) {
+        if self.receiver.is_some() {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut monitors = self.monitors.monitors.lock().unwrap_or_else(|e| e.into_inner());
+        monitors.insert(self.id, sender);
+        self.receiver = Some(receiver);
+    }
+
+    /// Waits for the next command sent to the server. It never returns if the monitor
+    /// wasn't started.
+    pub async fn recv(&mut self) -> Option<RespType> {
+        match &mut self.receiver {
+            Some(receiver) => receiver.recv().await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        if self.receiver.is_some() {
+            let mut monitors = self.monitors.monitors.lock().unwrap_or_else(|e| e.into_inner());
+            monitors.remove(&self.id);
+        }
+    }
+}
+
+/// Quotes a string the way `redis-cli` shows it: within double quotes, with backslashes,
+/// quotes and control characters escaped, and any other non-printable byte written as
+/// `\xHH`.
+///
+/// It's used for the arguments of the commands shown by MONITOR.
+pub fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for b in s.bytes() {
+        match b {
+            b'\\' => quoted.push_str("\\\\"),
+            b'"' => quoted.push_str("\\\""),
+            b'\n' => quoted.push_str("\\n"),
+            b'\r' => quoted.push_str("\\r"),
+            b'\t' => quoted.push_str("\\t"),
+            0x07 => quoted.push_str("\\a"),
+            0x08 => quoted.push_str("\\b"),
+            b' '..=b'~' => quoted.push(b as char),
+            _ => {
+                let _ = write!(quoted, "\\x{:02x}", b);
+            }
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_arguments_are_only_quoted() {
+        assert_eq!(quote("set"), "\"set\"");
+        assert_eq!(quote("hello world"), "\"hello world\"");
+        assert_eq!(quote(""), "\"\"");
+    }
+
+    #[test]
+    fn quotes_backslashes_and_control_characters_are_escaped() {
+        assert_eq!(quote("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote("a\\b"), "\"a\\\\b\"");
+        assert_eq!(quote("a\r\n\t\x07\x08"), "\"a\\r\\n\\t\\a\\b\"");
+    }
+
+    #[test]
+    fn other_non_printable_bytes_are_written_in_hex() {
+        assert_eq!(quote("\0\x1b\x7f"), "\"\\x00\\x1b\\x7f\"");
+        assert_eq!(quote("é"), "\"\\xc3\\xa9\"");
+    }
+}
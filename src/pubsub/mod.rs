@@ -0,0 +1,276 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::{command::glob, resp::types::RespType};
+
+/// The subscribers of each channel or pattern, by subscriber id.
+type Subscriptions = HashMap<String, HashMap<u64, UnboundedSender<RespType>>>;
+
+/// Holds the pub/sub state of the server, i.e. which connections are subscribed to
+/// which channels and patterns.
+#[derive(Debug)]
+pub struct PubSub {
+    /// The subscribers of each channel.
+    channels: Mutex<Subscriptions>,
+    /// The subscribers of each pattern, which receive the messages published to the
+    /// channels matching it.
+    patterns: Mutex<Subscriptions>,
+    /// Id to be assigned to the next subscriber.
+    next_subscriber_id: AtomicU64,
+}
+
+/// The pub/sub state of a single connection.
+///
+/// The messages published to the channels the connection is subscribed to, directly or
+/// through a pattern, are delivered through the receiver held here. When the subscriber
+/// is dropped, it's unsubscribed from all of its channels and patterns.
+#[derive(Debug)]
+pub struct Subscriber {
+    /// Id of the subscriber.
+    id: u64,
+    /// The shared pub/sub state.
+    pubsub: Arc<PubSub>,
+    /// The channels this connection is subscribed to.
+    channels: HashSet<String>,
+    /// The patterns this connection is subscribed to.
+    patterns: HashSet<String>,
+    /// Published messages are sent through this channel. It's cloned for every
+    /// subscribed channel and pattern.
+    sender: UnboundedSender<RespType>,
+    /// Published messages are received from this channel.
+    receiver: UnboundedReceiver<RespType>,
+}
+
+impl PubSub {
+    /// Create a new instance of `PubSub` with no subscriptions.
+    pub fn new() -> PubSub {
+        PubSub {
+            channels: Mutex::new(HashMap::new()),
+            patterns: Mutex::new(HashMap::new()),
+            next_subscriber_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Publishes a message to a channel.
+    ///
+    /// The message is delivered to every subscriber of the channel as a push of the form
+    /// `["message", channel, message]`, and to every subscriber of a pattern matching the
+    /// channel as a push of the form `["pmessage", pattern, channel, message]`.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscribers which received the message. A connection subscribed to
+    /// both the channel and matching patterns receives it once for each, and is counted
+    /// as many times.
+    pub fn publish(&self, channel: &str, message: &str) -> usize {
+        let mut received = 0;
+
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(subscribers) = channels.get_mut(channel) {
+            let message = RespType::Push(vec![
+                RespType::BulkString(String::from("message")),
+                RespType::BulkString(channel.to_string()),
+                RespType::BulkString(message.to_string()),
+            ]);
+            received += Self::deliver(subscribers, &message);
+            if subscribers.is_empty() {
+                channels.remove(channel);
+            }
+        }
+        drop(channels);
+
+        let mut patterns = self.patterns.lock().unwrap_or_else(|e| e.into_inner());
+        for (pattern, subscribers) in patterns.iter_mut() {
+            if !glob::matches(pattern, channel) {
+                continue;
+            }
+
+            let message = RespType::Push(vec![
+                RespType::BulkString(String::from("pmessage")),
+                RespType::BulkString(pattern.to_string()),
+                RespType::BulkString(channel.to_string()),
+                RespType::BulkString(message.to_string()),
+            ]);
+            received += Self::deliver(subscribers, &message);
+        }
+        patterns.retain(|_, subscribers| !subscribers.is_empty());
+
+        received
+    }
+
+    /// Returns the channels with at least one subscriber, or only the ones matching the
+    /// glob-style pattern if given. Pattern subscriptions aren't taken into account.
+    pub fn active_channels(&self, pattern: Option<&str>) -> Vec<String> {
+        let channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        channels
+            .keys()
+            .filter(|channel| pattern.is_none_or(|pattern| glob::matches(pattern, channel)))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the number of subscribers of each of the given channels, in the same order.
+    /// Pattern subscriptions aren't taken into account.
+    pub fn subscriber_counts(&self, channels: &[String]) -> Vec<(String, usize)> {
+        let subscribed = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        channels
+            .iter()
+            .map(|channel| {
+                let count = subscribed.get(channel).map_or(0, |subscribers| subscribers.len());
+                (channel.to_string(), count)
+            })
+            .collect()
+    }
+
+    /// Returns the number of distinct patterns with at least one subscriber.
+    pub fn pattern_count(&self) -> usize {
+        self.patterns.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Sends the message to each of the subscribers. Subscribers whose connections are
+    /// gone are dropped.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscribers which received the message.
+    fn deliver(
+        subscribers: &mut HashMap<u64, UnboundedSender<RespType>>,
+        message: &RespType,
+    ) -> usize {
+        subscribers.retain(|_, sender| sender.send(message.clone()).is_ok());
+        subscribers.len()
+    }
+}
+
+impl Subscriber {
+    /// Create a new `Subscriber` with no subscriptions.
+    pub fn new(pubsub: Arc<PubSub>) -> Subscriber {
+        let id = pubsub.next_subscriber_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        Subscriber {
+            id,
+            pubsub,
+            channels: HashSet::new(),
+            patterns: HashSet::new(),
+            sender,
+            receiver,
+        }
+    }
+
+    /// Checks if the connection is subscribed to at least one channel or pattern.
+    pub fn is_subscribed(&self) -> bool {
+        self.subscription_count() > 0
+    }
+
+    /// Returns the number of channels and patterns this connection is subscribed to.
+    pub fn subscription_count(&self) -> usize {
+        self.channels.len() + self.patterns.len()
+    }
+
+    /// Returns the channels this connection is subscribed to.
+    pub fn channels(&self) -> Vec<String> {
+        self.channels.iter().cloned().collect()
+    }
+
+    /// Returns the patterns this connection is subscribed to.
+    pub fn patterns(&self) -> Vec<String> {
+        self.patterns.iter().cloned().collect()
+    }
+
+    /// Subscribes to a channel. Subscribing to the same channel again has no effect.
+    ///
+    /// # Returns
+    ///
+    /// The number of channels and patterns this connection is subscribed to.
+    pub fn subscribe(&mut self, channel: &str) -> usize {
+        if self.channels.insert(channel.to_string()) {
+            self.register(&self.pubsub.channels, channel);
+        }
+
+        self.subscription_count()
+    }
+
+    /// Unsubscribes from a channel.
+    ///
+    /// # Returns
+    ///
+    /// The number of channels and patterns this connection is still subscribed to.
+    pub fn unsubscribe(&mut self, channel: &str) -> usize {
+        if self.channels.remove(channel) {
+            self.unregister(&self.pubsub.channels, channel);
+        }
+
+        self.subscription_count()
+    }
+
+    /// Subscribes to the channels matching a glob-style pattern. Subscribing to the same
+    /// pattern again has no effect.
+    ///
+    /// # Returns
+    ///
+    /// The number of channels and patterns this connection is subscribed to.
+    pub fn psubscribe(&mut self, pattern: &str) -> usize {
+        if self.patterns.insert(pattern.to_string()) {
+            self.register(&self.pubsub.patterns, pattern);
+        }
+
+        self.subscription_count()
+    }
+
+    /// Unsubscribes from a pattern.
+    ///
+    /// # Returns
+    ///
+    /// The number of channels and patterns this connection is still subscribed to.
+    pub fn punsubscribe(&mut self, pattern: &str) -> usize {
+        if self.patterns.remove(pattern) {
+            self.unregister(&self.pubsub.patterns, pattern);
+        }
+
+        self.subscription_count()
+    }
+
+    /// Adds this connection to the subscribers of a channel or pattern.
+    fn register(&self, subscriptions: &Mutex<Subscriptions>, name: &str) {
+        let mut subscriptions = subscriptions.lock().unwrap_or_else(|e| e.into_inner());
+        subscriptions
+            .entry(name.to_string())
+            .or_default()
+            .insert(self.id, self.sender.clone());
+    }
+
+    /// Removes this connection from the subscribers of a channel or pattern.
+    fn unregister(&self, subscriptions: &Mutex<Subscriptions>, name: &str) {
+        let mut subscriptions = subscriptions.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(subscribers) = subscriptions.get_mut(name) {
+            subscribers.remove(&self.id);
+            if subscribers.is_empty() {
+                subscriptions.remove(name);
+            }
+        }
+    }
+
+    /// Waits for the next message published to one of the subscribed channels.
+    pub async fn recv(&mut self) -> Option<RespType> {
+        self.receiver.recv().await
+    }
+}
+
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        for channel in self.channels() {
+            self.unsubscribe(&channel);
+        }
+        for pattern in self.patterns() {
+            self.punsubscribe(&pattern);
+        }
+    }
+}
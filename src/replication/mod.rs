@@ -0,0 +1,479 @@
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard, RwLock,
+    },
+    time::Duration,
+};
+
+use bytes::{Bytes, BytesMut};
+use log::info;
+use rand::Rng;
+use tokio::{
+    sync::{
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+        Notify,
+    },
+    task::JoinHandle,
+    time::Instant,
+};
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::{Storage, Value, DB},
+        DBError,
+    },
+};
+
+pub mod replica;
+
+/// Length of the replication id, same as in Redis.
+const REPLID_LEN: usize = 40;
+
+/// Holds the replication state of the server.
+///
+/// A Nimblecache server is either a master, which accepts writes from its clients, or a
+/// replica of another server, in which case it rejects writes from its clients and
+/// mirrors the dataset of its master instead.
+#[derive(Debug)]
+pub struct Replication {
+    /// Replication id of this server. It is sent to the replicas during the full resync.
+    replid: String,
+    /// The current role of this server.
+    role: RwLock<Role>,
+    /// The task running the link to the master, if this server is a replica.
+    link: Mutex<Option<JoinHandle<()>>>,
+    /// The state of the link to the master, if this server is a replica.
+    link_state: RwLock<LinkState>,
+    /// The connected replicas.
+    replicas: Mutex<Vec<ReplicaInfo>>,
+    /// Id to be assigned to the next connected replica.
+    next_replica_id: AtomicU64,
+    /// Replication offset of this server. On a master, it's the number of bytes of write
+    /// commands propagated so far. On a replica, it's the number of bytes of the replication
+    /// stream processed so far.
+    offset: AtomicU64,
+    /// Notifies the tasks waiting for acknowledgements from the replicas.
+    acks: Notify,
+    /// Serializes the write commands, so that they are propagated in the same order in
+    /// which they are applied to the DB.
+    write_lock: Mutex<()>,
+}
+
+/// Represents a replica connected to this server.
+#[derive(Debug)]
+struct ReplicaInfo {
+    /// Id of the replica, assigned when it connects.
+    id: u64,
+    /// Address the replica connected from.
+    addr: SocketAddr,
+    /// Write commands are propagated to the replica through this channel.
+    sender: UnboundedSender<RespType>,
+    /// The replication offset up to which the replica has acknowledged the write commands.
+    ack_offset: u64,
+}
+
+/// The result of starting a full resync for a new replica.
+#[derive(Debug)]
+pub struct FullSync {
+    /// Id assigned to the replica.
+    pub replica_id: u64,
+    /// Replication offset of the master, at the time the dataset was copied.
+    pub offset: u64,
+    /// Commands which recreate the dataset, one per key, encoded back to back.
+    pub dump: Bytes,
+    /// Channel through which the write commands to be forwarded to the replica are received.
+    pub propagated_cmds: UnboundedReceiver<RespType>,
+}
+
+/// Represents the role of the server in a replication setup.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Role {
+    /// The server accepts writes from its clients.
+    Master,
+    /// The server replicates the dataset from the master at the given address.
+    Replica { host: String, port: u16 },
+}
+
+/// Represents the state of the link from a replica to its master.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinkState {
+    /// The replica needs to connect to the master.
+    Connect,
+    /// The replica is connecting to the master.
+    Connecting,
+    /// The replica is receiving the dataset from the master.
+    Sync,
+    /// The replica is applying the commands streamed by the master.
+    Connected,
+}
+
+impl Replication {
+    /// Create a new `Replication` instance. The server starts as a master.
+    pub fn new() -> Replication {
+        Replication {
+            replid: generate_replid(),
+            role: RwLock::new(Role::Master),
+            link: Mutex::new(None),
+            link_state: RwLock::new(LinkState::Connect),
+            replicas: Mutex::new(vec![]),
+            next_replica_id: AtomicU64::new(1),
+            offset: AtomicU64::new(0),
+            acks: Notify::new(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Returns the replication id of this server.
+    pub fn replid(&self) -> &str {
+        self.replid.as_str()
+    }
+
+    /// Returns the current role of this server.
+    pub fn role(&self) -> Role {
+        match self.role.read() {
+            Ok(role) => role.clone(),
+            Err(e) => e.into_inner().clone(),
+        }
+    }
+
+    /// Returns the state of the link to the master. It's only meaningful while
+    /// this server is a replica.
+    pub fn link_state(&self) -> LinkState {
+        match self.link_state.read() {
+            Ok(state) => *state,
+            Err(e) => *e.into_inner(),
+        }
+    }
+
+    /// Sets the state of the link to the master. It's updated by the link itself.
+    pub fn set_link_state(&self, state: LinkState) {
+        match self.link_state.write() {
+            Ok(mut s) => *s = state,
+            Err(e) => *e.into_inner() = state,
+        }
+    }
+
+    /// Returns the address and the acknowledged replication offset of each connected replica.
+    pub fn replicas(&self) -> Vec<(SocketAddr, u64)> {
+        self.lock_replicas()
+            .iter()
+            .map(|r| (r.addr, r.ack_offset))
+            .collect()
+    }
+
+    /// Returns the replication offset of this server.
+    pub fn offset(&self) -> u64 {
+        self.offset.load(Ordering::SeqCst)
+    }
+
+    /// Sets the replication offset of this server. It's updated by the link to the master,
+    /// as it processes the replication stream.
+    pub fn set_offset(&self, offset: u64) {
+        self.offset.store(offset, Ordering::SeqCst);
+    }
+
+    /// Checks if this server is currently a replica.
+    pub fn is_replica(&self) -> bool {
+        self.role() != Role::Master
+    }
+
+    /// Turns this server into a replica of the master at the given address.
+    ///
+    /// Any existing link to a master is dropped, and a new link is started in the
+    /// background. The link performs a full resync with the new master and then keeps
+    /// applying the commands streamed by it.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - Host of the master.
+    ///
+    /// * `port` - Port of the master.
+    ///
+    /// * `storage` - The shared storage, which the link writes the replicated data into.
+    pub fn replicate_from(&self, host: String, port: u16, storage: Storage) {
+        let role = Role::Replica {
+            host: host.clone(),
+            port,
+        };
+        if self.role() == role {
+            return;
+        }
+
+        self.set_role(role);
+        self.set_link_state(LinkState::Connect);
+
+        let link = tokio::spawn(replica::run(host, port, storage));
+        if let Some(old_link) = self.replace_link(Some(link)) {
+            old_link.abort();
+        }
+    }
+
+    /// Turns this server back into a master, dropping the link to the current master.
+    /// The dataset replicated so far is kept.
+    pub fn promote(&self) {
+        if let Some(link) = self.replace_link(None) {
+            link.abort();
+            info!("Replication link to master closed");
+        }
+
+        self.set_role(Role::Master);
+    }
+
+    /// Acquires the lock which serializes the write commands. The lock must be held while
+    /// applying a write command and propagating it to the replicas.
+    pub fn lock_writes(&self) -> MutexGuard<'_, ()> {
+        match self.write_lock.lock() {
+            Ok(guard) => guard,
+            Err(e) => e.into_inner(),
+        }
+    }
+
+    /// Sends a write command to all the connected replicas, and advances the replication
+    /// offset by the size of the command. Replicas which are no longer connected are
+    /// dropped from the registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd` - The write command, as an array of bulk strings.
+    pub fn propagate(&self, cmd: RespType) {
+        let mut replicas = self.lock_replicas();
+
+        self.offset
+            .fetch_add(cmd.to_bytes().len() as u64, Ordering::SeqCst);
+
+        replicas.retain(|replica| replica.sender.send(cmd.clone()).is_ok());
+    }
+
+    /// Records the replication offset acknowledged by a replica.
+    ///
+    /// # Arguments
+    ///
+    /// * `replica_id` - Id of the replica.
+    ///
+    /// * `offset` - The replication offset up to which the replica has processed the
+    ///   write commands.
+    pub fn ack(&self, replica_id: u64, offset: u64) {
+        let mut replicas = self.lock_replicas();
+
+        if let Some(replica) = replicas.iter_mut().find(|r| r.id == replica_id) {
+            replica.ack_offset = replica.ack_offset.max(offset);
+        }
+
+        self.acks.notify_waiters();
+    }
+
+    /// Drops a replica from the registry, once it's disconnected.
+    pub fn remove_replica(&self, replica_id: u64) {
+        self.lock_replicas().retain(|r| r.id != replica_id);
+    }
+
+    /// Returns the number of connected replicas which have acknowledged the write commands
+    /// up to the given replication offset.
+    pub fn acked_replicas(&self, offset: u64) -> usize {
+        self.lock_replicas()
+            .iter()
+            .filter(|r| r.ack_offset >= offset)
+            .count()
+    }
+
+    /// Waits until the given number of replicas acknowledge all the write commands
+    /// propagated so far, or until the timeout elapses.
+    ///
+    /// The replicas are asked to acknowledge their offset with a `REPLCONF GETACK`
+    /// command, unless enough replicas have already acknowledged it.
+    ///
+    /// # Arguments
+    ///
+    /// * `numreplicas` - The number of replicas to wait for.
+    ///
+    /// * `timeout` - The maximum time to wait. `None` waits forever.
+    ///
+    /// # Returns
+    ///
+    /// The number of replicas which have acknowledged all the write commands.
+    pub async fn wait_for_acks(&self, numreplicas: usize, timeout: Option<Duration>) -> usize {
+        let offset = self.offset();
+        let deadline = timeout.map(|t| Instant::now() + t);
+
+        let acked = self.acked_replicas(offset);
+        if acked >= numreplicas {
+            return acked;
+        }
+
+        self.propagate(RespType::Array(vec![
+            RespType::BulkString(String::from("REPLCONF")),
+            RespType::BulkString(String::from("GETACK")),
+            RespType::BulkString(String::from("*")),
+        ]));
+
+        loop {
+            // Register for the notification before checking the acks, so that an ack
+            // received in between isn't missed.
+            let notified = self.acks.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let acked = self.acked_replicas(offset);
+            if acked >= numreplicas {
+                return acked;
+            }
+
+            match deadline {
+                Some(deadline) => {
+                    if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                        return self.acked_replicas(offset);
+                    }
+                }
+                None => notified.await,
+            }
+        }
+    }
+
+    /// Starts a full resync for a new replica.
+    ///
+    /// The replica is registered to receive the write commands, and the commands which
+    /// recreate the current dataset are built. Both happen while the writes are blocked,
+    /// so the replica receives every write applied after the dataset was copied, and none
+    /// applied before.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database to be copied to the replica.
+    ///
+    /// * `addr` - Address the replica connected from.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FullSync)` - The dump of the dataset, and the channel through which the write
+    ///   commands are received.
+    /// * `Err(DBError)` - If the dataset couldn't be read.
+    pub fn full_sync(&self, db: &DB, addr: SocketAddr) -> Result<FullSync, DBError> {
+        let _guard = self.lock_writes();
+
+        let dump = dump(db)?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let replica_id = self.next_replica_id.fetch_add(1, Ordering::SeqCst);
+        let offset = self.offset();
+        self.lock_replicas().push(ReplicaInfo {
+            id: replica_id,
+            addr,
+            sender: tx,
+            ack_offset: 0,
+        });
+
+        Ok(FullSync {
+            replica_id,
+            offset,
+            dump: dump.freeze(),
+            propagated_cmds: rx,
+        })
+    }
+
+    fn lock_replicas(&self) -> MutexGuard<'_, Vec<ReplicaInfo>> {
+        match self.replicas.lock() {
+            Ok(replicas) => replicas,
+            Err(e) => e.into_inner(),
+        }
+    }
+
+    fn set_role(&self, role: Role) {
+        match self.role.write() {
+            Ok(mut r) => *r = role,
+            Err(e) => *e.into_inner() = role,
+        }
+    }
+
+    fn replace_link(&self, link: Option<JoinHandle<()>>) -> Option<JoinHandle<()>> {
+        match self.link.lock() {
+            Ok(mut l) => std::mem::replace(&mut *l, link),
+            Err(e) => std::mem::replace(&mut *e.into_inner(), link),
+        }
+    }
+}
+
+/// Serializes the whole dataset of the given DB, as the RESP encoded commands which
+/// recreate it. This is the dump streamed to a replica during a full resync.
+///
+/// # Returns
+///
+/// * `Ok(BytesMut)` - The commands, one after the other.
+/// * `Err(DBError)` - If the dataset couldn't be read.
+pub fn dump(db: &DB) -> Result<BytesMut, DBError> {
+    let mut dump = BytesMut::new();
+    for cmd in full_sync_commands(db)? {
+        dump.extend_from_slice(&cmd.to_bytes());
+    }
+
+    Ok(dump)
+}
+
+/// Builds the commands which recreate the whole dataset of the given DB. These are
+/// streamed to a replica during a full resync.
+///
+/// # Returns
+///
+/// * `Ok(Vec<RespType>)` - One command per key, or per entry for streams, as an array of
+///   bulk strings, followed by a PEXPIREAT command for the keys which expire.
+/// * `Err(DBError)` - If the dataset couldn't be read.
+fn full_sync_commands(db: &DB) -> Result<Vec<RespType>, DBError> {
+    let snapshot = db.snapshot()?;
+
+    let mut cmds = vec![];
+    for (k, v, expires_at) in snapshot {
+        let key_cmds: Vec<(&str, Vec<String>)> = match v {
+            Value::String(s) => vec![("SET", vec![s])],
+            Value::List(l) => vec![("RPUSH", l.into_iter().collect())],
+            Value::Set(s) => vec![("SADD", s.into_iter().collect())],
+            Value::Hash(h) => vec![("HSET", h.into_iter().flat_map(|(f, v)| [f, v]).collect())],
+            Value::SortedSet(z) => vec![(
+                "ZADD",
+                z.iter()
+                    .flat_map(|(m, score)| [score.to_string(), m.to_string()])
+                    .collect(),
+            )],
+            // A stream is recreated one entry at a time, each added with its own ID.
+            Value::Stream(stream) => stream
+                .iter()
+                .map(|(id, fields)| {
+                    let mut values = vec![id.to_string()];
+                    values.extend(fields.iter().flat_map(|(f, v)| [f.clone(), v.clone()]));
+                    ("XADD", values)
+                })
+                .collect(),
+        };
+
+        for (cmd_name, values) in key_cmds {
+            let mut args = vec![
+                RespType::BulkString(String::from(cmd_name)),
+                RespType::BulkString(k.clone()),
+            ];
+            args.extend(values.into_iter().map(RespType::BulkString));
+            cmds.push(RespType::Array(args));
+        }
+
+        // The expiry is sent as an absolute time, so that it isn't pushed back by the
+        // time the dump takes to reach the replica.
+        if let Some(expires_at) = expires_at {
+            cmds.push(RespType::Array(vec![
+                RespType::BulkString(String::from("PEXPIREAT")),
+                RespType::BulkString(k),
+                RespType::BulkString(expires_at.to_string()),
+            ]));
+        }
+    }
+
+    Ok(cmds)
+}
+
+/// Generates a random replication id made of hex characters. It's also used for the run id
+/// of the server, which has the same format.
+pub fn generate_replid() -> String {
+    let mut rng = rand::thread_rng();
+    (0..REPLID_LEN)
+        .map(|_| format!("{:x}", rng.gen_range(0..16)))
+        .collect()
+}
@@ -0,0 +1,197 @@
+// src/replication/replica.rs
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use bytes::{Buf, BytesMut};
+use futures::{SinkExt, StreamExt};
+use log::{error, info};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tokio_util::codec::{Decoder, Framed, FramedParts};
+
+use crate::{
+    clients::ClientState,
+    command::{replconf::ReplConfSubcommand, Command},
+    resp::{frame::RespCommandFrame, types::RespType},
+    storage::db::Storage,
+};
+
+use super::LinkState;
+
+/// Time to wait before reconnecting to the master, after the replication link is broken.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Runs the replication link to the master at the given address.
+///
+/// The link connects to the master, performs a full resync and then applies every
+/// command streamed by the master to the local DB, keeping track of the replication
+/// offset. If the link breaks, it reconnects to the master after a short delay.
+/// The link keeps running until its task is aborted.
+///
+/// # Arguments
+///
+/// * `host` - Host of the master.
+///
+/// * `port` - Port of the master.
+///
+/// * `storage` - The shared storage, which the replicated data is written into.
+pub async fn run(host: String, port: u16, storage: Storage) {
+    loop {
+        if let Err(e) = sync_with_master(host.as_str(), port, &storage).await {
+            error!("Replication link to master {}:{} failed: {}", host, port, e);
+        }
+        storage.replication().set_link_state(LinkState::Connect);
+
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Connects to the master, performs the handshake and applies the command stream
+/// sent by the master, until the connection is closed.
+async fn sync_with_master(host: &str, port: u16, storage: &Storage) -> Result<()> {
+    storage.replication().set_link_state(LinkState::Connecting);
+    let mut stream = TcpStream::connect((host, port)).await?;
+    info!("Connected to master {}:{}", host, port);
+
+    // Bytes read from the master which are not consumed yet.
+    let mut buffer = BytesMut::with_capacity(8 * 1024);
+
+    // The handshake replies are simple strings, which are not handled by the
+    // RespCommandFrame codec. So read them directly from the TCP stream.
+    send_command(&mut stream, &["PING"]).await?;
+    read_simple_string(&mut stream, &mut buffer).await?;
+
+    // The reply is `FULLRESYNC <replid> <offset>`.
+    send_command(&mut stream, &["PSYNC", "?", "-1"]).await?;
+    let reply = read_simple_string(&mut stream, &mut buffer).await?;
+    let mut offset = match reply.split(' ').collect::<Vec<&str>>().as_slice() {
+        ["FULLRESYNC", _, offset] => offset.parse::<u64>()?,
+        _ => return Err(anyhow!("Unexpected reply to PSYNC: {}", reply)),
+    };
+
+    // The master sends the commands which recreate its dataset, so drop whatever
+    // was stored locally before applying them.
+    storage.replication().set_link_state(LinkState::Sync);
+    let mut dump = read_dump(&mut stream, &mut buffer).await?;
+    storage.db().flush()?;
+    info!("Full resync with master {}:{} started", host, port);
+
+    let mut codec = RespCommandFrame::new();
+    while let Some(frame) = codec.decode(&mut dump)? {
+        apply_command(frame?, storage);
+    }
+
+    storage.replication().set_offset(offset);
+    storage.replication().set_link_state(LinkState::Connected);
+    info!("Full resync with master {}:{} completed", host, port);
+
+    // Everything after the dump is a stream of commands, which are
+    // arrays of bulk strings.
+    let mut parts = FramedParts::new::<RespType>(stream, codec);
+    parts.read_buf = buffer;
+    let mut conn = Framed::from_parts(parts);
+
+    while let Some(frame) = conn.next().await {
+        let frame = frame??;
+        let frame_len = RespType::Array(frame.clone()).to_bytes().len() as u64;
+
+        // Acknowledge the offset processed before the GETACK command.
+        if let Ok(Command::ReplConf(replconf)) = Command::from_resp_command_frame(frame.clone()) {
+            if replconf.subcommand() == &ReplConfSubcommand::GetAck {
+                let ack = ["REPLCONF", "ACK", offset.to_string().as_str()]
+                    .iter()
+                    .map(|arg| RespType::BulkString(arg.to_string()))
+                    .collect();
+                conn.send(RespType::Array(ack)).await?;
+            }
+        } else {
+            apply_command(frame, storage);
+        }
+
+        offset += frame_len;
+        storage.replication().set_offset(offset);
+    }
+
+    info!("Connection to master {}:{} closed", host, port);
+
+    Ok(())
+}
+
+/// Applies a command received from the master on the local DB. The reply is not sent
+/// back to the master.
+fn apply_command(frame: Vec<RespType>, storage: &Storage) {
+    let cmd = match Command::from_resp_command_frame(frame) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            error!("Invalid command received from master: {}", e);
+            return;
+        }
+    };
+
+    if let RespType::SimpleError(e) = cmd.execute(storage, &ClientState::new()) {
+        error!("Failed to apply command received from master: {}", e);
+    }
+}
+
+/// Writes a command as an array of bulk strings into the TCP stream.
+async fn send_command(stream: &mut TcpStream, args: &[&str]) -> Result<()> {
+    let cmd = RespType::Array(
+        args.iter()
+            .map(|arg| RespType::BulkString(arg.to_string()))
+            .collect(),
+    );
+
+    stream.write_all(&cmd.to_bytes()).await?;
+
+    Ok(())
+}
+
+/// Reads the dump of the dataset sent by the master, in the form `$<length>\r\n<commands>`.
+/// Any bytes read past the dump are left in the buffer.
+async fn read_dump(stream: &mut TcpStream, buffer: &mut BytesMut) -> Result<BytesMut> {
+    let (dump_len, bytes_read) = loop {
+        match RespType::parse_bulk_string_len(buffer.clone()) {
+            Ok(Some((Some(len), bytes_read))) => break (len, bytes_read),
+            Ok(Some((None, _))) => return Err(anyhow!("Invalid dump from master: null dump")),
+            Ok(None) => {}
+            Err(e) => return Err(anyhow!("Invalid dump from master: {}", e)),
+        }
+
+        if stream.read_buf(buffer).await? == 0 {
+            return Err(anyhow!("Connection closed by master"));
+        }
+    };
+    buffer.advance(bytes_read);
+
+    while buffer.len() < dump_len {
+        if stream.read_buf(buffer).await? == 0 {
+            return Err(anyhow!("Connection closed by master"));
+        }
+    }
+
+    Ok(buffer.split_to(dump_len))
+}
+
+/// Reads a simple string reply from the TCP stream. Any bytes read past the reply
+/// are left in the buffer.
+async fn read_simple_string(stream: &mut TcpStream, buffer: &mut BytesMut) -> Result<String> {
+    while !buffer.windows(2).any(|w| w == b"\r\n") {
+        if stream.read_buf(buffer).await? == 0 {
+            return Err(anyhow!("Connection closed by master"));
+        }
+    }
+
+    let (reply, bytes_read) = match RespType::parse(buffer.clone()) {
+        Ok(reply) => reply,
+        Err(e) => return Err(anyhow!("Invalid reply from master: {}", e)),
+    };
+    buffer.advance(bytes_read);
+
+    match reply {
+        RespType::SimpleString(s) => Ok(s),
+        _ => Err(anyhow!("Unexpected reply from master")),
+    }
+}
@@ -1,12 +1,15 @@
 use core::fmt;
-use std::io::Error;
 
-use bytes::{Buf, BufMut};
+use bytes::Buf;
 use tokio_util::codec::{Decoder, Encoder};
 
-use crate::resp::types::RespType;
+use crate::resp::types::{Protocol, RespType};
 
-use super::RespError;
+/// The maximum number of arguments in a command, as in Redis.
+const MAX_MULTIBULK_LEN: usize = 1024 * 1024;
+
+/// The maximum length of a single argument in bytes, as Redis' default `proto-max-bulk-len`.
+const MAX_BULK_LEN: usize = 512 * 1024 * 1024;
 
 /// This codec handles Nimblecache commands, which are always represented
 /// as array of bulk strings in the RESP (REdis Serialization Protocol) protocol.
@@ -16,6 +19,10 @@ use super::RespError;
 pub struct RespCommandFrame {
   /// Builder for appending the bulk strings inthe command array.
   cmd_builder: Option<CommandBuilder>,
+  /// The rest of a malformed command, which is skipped before decoding the next command.
+  skip: Option<Skip>,
+  /// The protocol version used to encode the replies.
+  protocol: Protocol,
 }
 
 impl RespCommandFrame {
@@ -25,12 +32,35 @@ impl RespCommandFrame {
     ///
     /// A new instance of `RespCommandFrame` with no command builder initialized.
     pub fn new() -> RespCommandFrame {
-      RespCommandFrame { cmd_builder: None }
+      RespCommandFrame {
+        cmd_builder: None,
+        skip: None,
+        protocol: Protocol::Resp2,
+      }
+    }
+
+    /// Returns the protocol version used to encode the replies.
+    pub fn protocol(&self) -> Protocol {
+      self.protocol
+    }
+
+    /// Sets the protocol version used to encode the replies.
+    pub fn set_protocol(&mut self, protocol: Protocol) {
+      self.protocol = protocol;
+    }
+
+    /// Drops the partially decoded command, if any, so that the next command is decoded
+    /// from scratch.
+    ///
+    /// The rest of a malformed command is still skipped, since its bytes may not have
+    /// arrived yet.
+    pub fn reset(&mut self) {
+      self.cmd_builder = None;
     }
 }
 
 impl Decoder for RespCommandFrame {
-    type Item = Vec<RespType>;
+    type Item = Result<Vec<RespType>, FrameError>;
 
     type Error = std::io::Error;
 
@@ -40,53 +70,96 @@ impl Decoder for RespCommandFrame {
     /// arrays of bulk strings which represent Nimblecache commands. It uses a `CommandBuilder`
     /// to accumulate the parts of the command as they are received.
     ///
+    /// A malformed command doesn't end the stream. The rest of the command is skipped, as far
+    /// as its structure can be made out, and the protocol error is returned as an item, so that
+    /// the connection can carry on with the commands that follow.
+    ///
     /// # Arguments
     ///
     /// * `src` - A mutable reference to the input buffer containing bytes to decode.
     ///
     /// # Returns
     ///
-    /// * `Ok(Some(Vec<RespType>))` if a complete command (array of bulk strings) was successfully decoded.
+    /// * `Ok(Some(Ok(Vec<RespType>)))` if a complete command (array of bulk strings) was successfully decoded.
+    /// * `Ok(Some(Err(FrameError)))` if the command was malformed.
     /// * `Ok(None)` if more data is needed to complete the command.
-    /// * `Err(std::io::Error)` if an error occurred during decoding.
     fn decode(&mut self, src: &mut bytes::BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // Skip the rest of a malformed command, which may arrive after the error was returned.
+        if let Some(skip) = self.skip.as_mut() {
+            if !skip.advance(src) {
+                return Ok(None);
+            }
+            self.skip = None;
+        }
+
+        match self.decode_command(src) {
+            Ok(cmd) => Ok(cmd.map(Ok)),
+            Err(e) => {
+                let mut skip = self.rest_of_malformed_command(&e, src);
+                self.reset();
+                if !skip.advance(src) {
+                    self.skip = Some(skip);
+                }
+
+                Ok(Some(Err(e)))
+            }
+        }
+    }
+}
+
+impl RespCommandFrame {
+    /// Decodes the next command from the input buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<RespType>))` if a complete command was decoded.
+    /// * `Ok(None)` if more data is needed to complete the command.
+    /// * `Err(FrameError)` if the command is malformed.
+    fn decode_command(&mut self, src: &mut bytes::BytesMut) -> Result<Option<Vec<RespType>>, FrameError> {
         // A command in RESP protocol should always be an array of Bulk Strings.
         // Check the first 2 bytes to validate if its a RESP array.
-        if self.cmd_builder.is_none() {
+        while self.cmd_builder.is_none() {
           let (cmd_len, bytes_read) = match RespType::parse_array_len(src.clone()) {
-              Ok(arr_len) => match arr_len {
-                Some((len, bytes_read)) => (len, bytes_read),
-                None => return Ok(None),
-              },
-              Err(e) => {
-                  return Err(Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    FrameError::from(e)
-                  ));
-              }
+              Ok(Some((len, bytes_read))) if len <= MAX_MULTIBULK_LEN => (len, bytes_read),
+              Ok(Some(_)) => return Err(FrameError::InvalidMultibulkLength),
+              Ok(None) => return Ok(None),
+              Err(_) => return Err(FrameError::invalid_header(src, b'*')),
           };
 
-          // initialize command builder, if its a valid RESP array.
-          self.cmd_builder = Some(CommandBuilder::new(cmd_len));
-
           // advance buffer
           src.advance(bytes_read);
+
+          // Empty arrays are ignored, like Redis does with empty inline commands.
+          if cmd_len == 0 {
+            continue;
+          }
+
+          // initialize command builder, if its a valid RESP array.
+          self.cmd_builder = Some(CommandBuilder::new(cmd_len));
         }
 
         // Read all bytes in buffer
-        while src.len() != 0 {
+        while !src.is_empty() {
             // Validate and check the length of the next bulk string
             let (bullstr_len, bytes_read) = match RespType::parse_bulk_string_len(src.clone()) {
-                Ok(bulkstr_len) => match bulkstr_len {
-                    Some((len, bytes_read)) => (len, bytes_read),
-                    None => return Ok(None),
-                },
-                Err(e) => {
-                  return Err(Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    FrameError::from(e),
-                  ));
+                Ok(Some((Some(len), bytes_read))) if len <= MAX_BULK_LEN => (len, bytes_read),
+                // A null bulk string is taken as a null argument, which the commands reject
+                // like any argument which isn't a bulk string.
+                Ok(Some((None, bytes_read))) => {
+                    src.advance(bytes_read);
+
+                    let cmd_builder = self.cmd_builder.as_mut().unwrap();
+                    cmd_builder.add_part(RespType::NullBulkString);
+                    if cmd_builder.all_parts_received() {
+                      let cmd = cmd_builder.build();
+                      self.cmd_builder = None;
+                      return Ok(Some(cmd));
+                    }
+                    continue;
                 }
+                Ok(Some(_)) => return Err(FrameError::InvalidBulkLength),
+                Ok(None) => return Ok(None),
+                Err(_) => return Err(FrameError::invalid_header(src, b'$')),
             };
 
             // A bulk string has the below format
@@ -95,20 +168,30 @@ impl Decoder for RespCommandFrame {
             //
             // Check if the buffer contains the required number of bytes to parse
             // the bulk string (including the CRLF at the end)
+            //
+            // The terminator is checked as soon as its bytes arrive, so that a string longer
+            // than its declared length is reported right away, instead of waiting for more data.
             let bulkstr_bytes = bullstr_len + bytes_read + 2;
+            let terminator = &src[(bulkstr_bytes - 2).min(src.len())..bulkstr_bytes.min(src.len())];
+            if !b"\r\n".starts_with(terminator) {
+              return Err(FrameError::UnterminatedBulkString);
+            }
+
             if src.len() < bulkstr_bytes {
-              return Ok(None);;
+              return Ok(None);
             }
 
             // now that its sure the buffer has all the bytes required to parse the bulk string, parse it.
-            let (bulkstr, bytes_read) = match RespType::parse_bulk_string(src.clone()) {
-                Ok((resp_type, bytes_read)) => (resp_type, bytes_read),
-                Err(e) => {
-                    return Err(Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        FrameError::from(e),
-                    ));
-                }
+            // The command name is parsed leniently, so that it can be reported as unknown.
+            // Since the length and CRLF are checked already, parsing can only fail on invalid UTF-8.
+            let parsed = if self.cmd_builder.as_ref().unwrap().parts_parsed == 0 {
+                RespType::parse_bulk_string_lossy(src.clone())
+            } else {
+                RespType::parse_bulk_string(src.clone())
+            };
+            let (bulkstr, bytes_read) = match parsed {
+                Ok(parsed) => parsed,
+                Err(_) => return Err(FrameError::InvalidUtf8),
             };
 
             // append the bulk string to the command builder
@@ -128,6 +211,122 @@ impl Decoder for RespCommandFrame {
 
         Ok(None)
     }
+
+    /// Returns what's left to skip of a malformed command, whose malformed part is at the
+    /// start of the buffer.
+    ///
+    /// The arguments of the command are skipped one by one, along with their data, so that
+    /// data which looks like a command isn't taken as one.
+    fn rest_of_malformed_command(&self, error: &FrameError, src: &bytes::BytesMut) -> Skip {
+        let cmd_builder = match &self.cmd_builder {
+            Some(cmd_builder) => cmd_builder,
+            // The header of the command is malformed. The arguments which follow it are
+            // skipped too, if their number could be read.
+            None => {
+                let args = match RespType::parse_array_len(src.clone()) {
+                    Ok(Some((len, _))) => len,
+                    _ => 0,
+                };
+                return Skip::args(1 + args);
+            }
+        };
+
+        let args = cmd_builder.num_parts - cmd_builder.parts_parsed;
+        match (error, RespType::parse_bulk_string_len(src.clone())) {
+            // The data of the argument is longer than its declared length, so it's taken to
+            // end at the next CRLF.
+            (FrameError::UnterminatedBulkString, Ok(Some((Some(len), bytes_read)))) => Skip {
+                bytes: bytes_read + len,
+                lines: 1,
+                args: args - 1,
+            },
+            _ => Skip::args(args),
+        }
+    }
+}
+
+/// The rest of a malformed command, which is skipped as it arrives.
+///
+/// The parts are skipped in order: the bytes left of the current argument, the lines left
+/// of it, and then the arguments which follow.
+#[derive(Debug)]
+struct Skip {
+  /// Bytes left to skip in the current argument.
+  bytes: usize,
+  /// Lines left to skip in the current argument.
+  lines: usize,
+  /// Arguments left to skip.
+  args: usize,
+}
+
+impl Skip {
+    /// Returns a `Skip` of the given number of arguments.
+    fn args(args: usize) -> Skip {
+      Skip {
+        bytes: 0,
+        lines: 0,
+        args,
+      }
+    }
+
+    /// Skips as much as possible from the input buffer.
+    ///
+    /// A bulk string is skipped along with its data. Its data is assumed to be on the next
+    /// line if its length is invalid. Anything else is skipped up to the end of its line.
+    ///
+    /// # Returns
+    ///
+    /// `true` if everything was skipped, `false` if more data is needed.
+    fn advance(&mut self, src: &mut bytes::BytesMut) -> bool {
+      loop {
+          let bytes = self.bytes.min(src.len());
+          src.advance(bytes);
+          self.bytes -= bytes;
+          if self.bytes > 0 {
+              return false;
+          }
+
+          while self.lines > 0 {
+              match src.windows(2).position(|w| w == b"\r\n") {
+                  Some(pos) => {
+                      src.advance(pos + 2);
+                      self.lines -= 1;
+                  }
+                  None => {
+                      // Keep a trailing CR, which may be followed by the LF ending the line.
+                      let keep = (src.last() == Some(&b'\r')) as usize;
+                      src.advance(src.len() - keep);
+                      return false;
+                  }
+              }
+          }
+
+          if self.args == 0 {
+              return true;
+          }
+
+          match src.first() {
+              None => return false,
+              Some(b'$') => {
+                  // Only the header is parsed, as the buffer may hold many more arguments.
+                  let header = match src.windows(2).position(|w| w == b"\r\n") {
+                      Some(pos) => bytes::BytesMut::from(&src[..pos + 2]),
+                      None => return false,
+                  };
+                  match RespType::parse_bulk_string_len(header) {
+                      Ok(Some((Some(len), bytes_read))) if len <= MAX_BULK_LEN => {
+                          src.advance(bytes_read);
+                          self.bytes = len + 2;
+                      }
+                      Ok(Some((None, bytes_read))) => src.advance(bytes_read),
+                      _ => self.lines = 2,
+                  }
+              }
+              Some(_) => self.lines = 1,
+          }
+          self.args -= 1;
+      }
+    }
 }
 
 impl Encoder<RespType> for RespCommandFrame {
@@ -135,7 +334,8 @@ impl Encoder<RespType> for RespCommandFrame {
 
     /// Encodes a `RespType` into bytes and writes them to the output buffer.
     ///
-    /// It's primarily used for sending responses to redis-clone commands.
+    /// It's primarily used for sending responses to redis-clone commands. The value is encoded
+    /// as per the protocol version negotiated by the client.
     ///
     /// # Arguments
     ///
@@ -147,7 +347,7 @@ impl Encoder<RespType> for RespCommandFrame {
     /// * `Ok(())` if the encoding was successful.
     /// * `Err(std::io::Error)` if an error occurred during encoding.
     fn encode(&mut self, item: RespType, dst: &mut bytes::BytesMut) -> Result<(), Self::Error> {
-        dst.put_slice(&item.to_bytes());
+        item.encode_owned_into(dst, self.protocol);
 
         Ok(())
     }
@@ -202,14 +402,32 @@ impl CommandBuilder {
 }
 
 /// Represents error that can occur during RESP command frame parsing.
-#[derive(Debug)]
-pub struct FrameError {
-  err: RespError
+///
+/// The messages follow the wording of Redis, and are sent to the client prefixed
+/// by `Protocol error: `.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameError {
+  /// The number of arguments of the command isn't a valid length, e.g. `*abc\r\n`.
+  InvalidMultibulkLength,
+  /// The length of an argument isn't a valid length, e.g. `$-5\r\n`.
+  InvalidBulkLength,
+  /// A command doesn't start with `*`, or one of its arguments doesn't start with `$`.
+  UnexpectedByte { expected: u8, got: u8 },
+  /// The data of an argument isn't followed by CRLF, so its length is wrong.
+  UnterminatedBulkString,
+  /// An argument isn't a valid UTF-8 string.
+  InvalidUtf8,
 }
 
 impl FrameError {
-  pub fn from(err: RespError) -> FrameError {
-      FrameError { err }
+  /// Returns the error for a header line which couldn't be parsed, at the start of `src`.
+  /// It's either an unexpected type byte, or an invalid length following the right one.
+  fn invalid_header(src: &[u8], expected: u8) -> FrameError {
+      match src.first() {
+          Some(&got) if got != expected => FrameError::UnexpectedByte { expected, got },
+          _ if expected == b'*' => FrameError::InvalidMultibulkLength,
+          _ => FrameError::InvalidBulkLength,
+      }
   }
 }
 
@@ -217,6 +435,246 @@ impl std::error::Error for FrameError {}
 
 impl fmt::Display for FrameError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-      self.err.fmt(f)
+      match self {
+          FrameError::InvalidMultibulkLength => "invalid multibulk length".fmt(f),
+          FrameError::InvalidBulkLength => "invalid bulk length".fmt(f),
+          FrameError::UnexpectedByte { expected, got } => write!(
+              f,
+              "expected '{}', got '{}'",
+              *expected as char,
+              got.escape_ascii()
+          ),
+          FrameError::UnterminatedBulkString => "bulk string is not terminated by CRLF".fmt(f),
+          FrameError::InvalidUtf8 => "invalid UTF-8 in bulk string".fmt(f),
+      }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+
+    use super::*;
+
+    type Item = Result<Vec<Option<String>>, FrameError>;
+
+    /// Decodes all the items in `bytes`, with each argument as `None` if it's null.
+    fn decode_all(frame: &mut RespCommandFrame, bytes: &[u8]) -> Vec<Item> {
+        let mut src = BytesMut::from(bytes);
+        let mut items = vec![];
+        while let Some(item) = frame.decode(&mut src).unwrap() {
+            items.push(item.map(|cmd| {
+                cmd.into_iter()
+                    .map(|arg| match arg {
+                        RespType::BulkString(s) => Some(s),
+                        RespType::NullBulkString => None,
+                        arg => panic!("unexpected argument: {:?}", arg),
+                    })
+                    .collect()
+            }));
+        }
+        items
+    }
+
+    fn command(args: &[&str]) -> Item {
+        Ok(args.iter().map(|arg| Some(arg.to_string())).collect())
+    }
+
+    #[test]
+    fn commands_are_decoded() {
+        let mut frame = RespCommandFrame::new();
+        let items = decode_all(&mut frame, b"*1\r\n$4\r\nPING\r\n*2\r\n$3\r\nGET\r\n$1\r\nk\r\n");
+        assert_eq!(items, vec![command(&["PING"]), command(&["GET", "k"])]);
+    }
+
+    #[test]
+    fn malformed_commands_are_reported() {
+        let cases: [(&[u8], FrameError, &str); 7] = [
+            (
+                b"*abc\r\n",
+                FrameError::InvalidMultibulkLength,
+                "invalid multibulk length",
+            ),
+            (
+                b"*1048577\r\n",
+                FrameError::InvalidMultibulkLength,
+                "invalid multibulk length",
+            ),
+            (
+                b"*1\r\n$-5\r\n",
+                FrameError::InvalidBulkLength,
+                "invalid bulk length",
+            ),
+            (
+                b"+PING\r\n",
+                FrameError::UnexpectedByte {
+                    expected: b'*',
+                    got: b'+',
+                },
+                "expected '*', got '+'",
+            ),
+            (
+                b"*1\r\n:1\r\n",
+                FrameError::UnexpectedByte {
+                    expected: b'$',
+                    got: b':',
+                },
+                "expected '$', got ':'",
+            ),
+            (
+                b"*1\r\n$2\r\nabc\r\n",
+                FrameError::UnterminatedBulkString,
+                "bulk string is not terminated by CRLF",
+            ),
+            (
+                b"*2\r\n$3\r\nGET\r\n$2\r\n\xff\xfe\r\n",
+                FrameError::InvalidUtf8,
+                "invalid UTF-8 in bulk string",
+            ),
+        ];
+
+        for (bytes, error, message) in cases {
+            let mut frame = RespCommandFrame::new();
+            let items = decode_all(&mut frame, bytes);
+            assert_eq!(items, vec![Err(error.clone())], "{:?}", bytes.escape_ascii());
+            assert_eq!(error.to_string(), message);
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn missing_bulk_string_terminators_are_reported_without_waiting_for_more_data() {
+        // The data is complete, but isn't followed by CRLF.
+        for bytes in [
+            &b"*1\r\n$2\r\nabc"[..],
+            b"*1\r\n$2\r\nab\rx",
+            b"*1\r\n$2\r\nab\n\r",
+        ] {
+            let mut frame = RespCommandFrame::new();
+            let items = decode_all(&mut frame, bytes);
+            assert_eq!(
+                items,
+                vec![Err(FrameError::UnterminatedBulkString)],
+                "{:?}",
+                bytes.escape_ascii()
+            );
+        }
+
+        // The terminator may still arrive.
+        for bytes in [&b"*1\r\n$2\r\nab"[..], b"*1\r\n$2\r\nab\r"] {
+            let mut frame = RespCommandFrame::new();
+            assert_eq!(decode_all(&mut frame, bytes), vec![], "{:?}", bytes.escape_ascii());
+        }
+    }
+
+    #[test]
+    fn empty_commands_are_skipped() {
+        let mut frame = RespCommandFrame::new();
+        let items = decode_all(&mut frame, b"*0\r\n*1\r\n$4\r\nPING\r\n*0\r\n");
+        assert_eq!(items, vec![command(&["PING"])]);
+    }
+
+    #[test]
+    fn null_bulk_string_arguments_are_decoded_as_null() {
+        let mut frame = RespCommandFrame::new();
+        let items = decode_all(&mut frame, b"*3\r\n$3\r\nSET\r\n$-1\r\n$1\r\nv\r\n");
+        assert_eq!(
+            items,
+            vec![Ok(vec![Some(String::from("SET")), None, Some(String::from("v"))])]
+        );
+
+        // A null last argument ends the command too.
+        let items = decode_all(&mut frame, b"*2\r\n$3\r\nGET\r\n$-1\r\n*1\r\n$4\r\nPING\r\n");
+        assert_eq!(
+            items,
+            vec![Ok(vec![Some(String::from("GET")), None]), command(&["PING"])]
+        );
+    }
+
+    #[test]
+    fn decoding_resumes_after_a_malformed_command() {
+        let mut frame = RespCommandFrame::new();
+        let items = decode_all(
+            &mut frame,
+            b"*3\r\n$3\r\nSET\r\n:1\r\n$1\r\nv\r\n*1\r\n$4\r\nPING\r\n",
+        );
+        assert_eq!(
+            items,
+            vec![
+                Err(FrameError::UnexpectedByte {
+                    expected: b'$',
+                    got: b':',
+                }),
+                command(&["PING"]),
+            ]
+        );
+
+        // The rest of the malformed command is skipped when it arrives after the error.
+        let items = decode_all(&mut frame, b"*2\r\n$3\r\nGET\r\n$x\r\n");
+        assert_eq!(items, vec![Err(FrameError::InvalidBulkLength)]);
+        let items = decode_all(&mut frame, b"k\r\n*1\r\n$4\r\nPING\r\n");
+        assert_eq!(items, vec![command(&["PING"])]);
+    }
+
+    #[test]
+    fn arguments_of_a_malformed_command_are_skipped_with_their_data() {
+        let mut frame = RespCommandFrame::new();
+        let error = Err(FrameError::UnexpectedByte {
+            expected: b'$',
+            got: b':',
+        });
+
+        // The data of the last argument looks like a command, but it's skipped as data.
+        let items = decode_all(
+            &mut frame,
+            b"*3\r\n$3\r\nSET\r\n:1\r\n$12\r\n*1\r\n$4\r\nPING\r\n*1\r\n$4\r\nINFO\r\n",
+        );
+        assert_eq!(items, vec![error.clone(), command(&["INFO"])]);
+
+        // Even when it arrives after the error.
+        assert_eq!(
+            decode_all(&mut frame, b"*3\r\n$3\r\nSET\r\n:1\r\n$6\r\n*1\r\n"),
+            vec![error]
+        );
+        let items = decode_all(&mut frame, b"*0\r\n*1\r\n$4\r\nINFO\r\n");
+        assert_eq!(items, vec![command(&["INFO"])]);
+    }
+
+    #[test]
+    fn arguments_are_skipped_after_a_malformed_header_or_length() {
+        let mut frame = RespCommandFrame::new();
+
+        // The number of arguments is over the limit, but they are skipped all the same.
+        let mut bytes = b"*1048577\r\n".to_vec();
+        for _ in 0..1048577 {
+            bytes.extend_from_slice(b"$2\r\n*1\r\n");
+        }
+        bytes.extend_from_slice(b"*1\r\n$4\r\nPING\r\n");
+        let items = decode_all(&mut frame, &bytes);
+        assert_eq!(items, vec![Err(FrameError::InvalidMultibulkLength), command(&["PING"])]);
+
+        // A string longer than its declared length ends at the next CRLF.
+        let items = decode_all(
+            &mut frame,
+            b"*2\r\n$2\r\n*1*1\r\n$1\r\nk\r\n*1\r\n$4\r\nPING\r\n",
+        );
+        assert_eq!(items, vec![Err(FrameError::UnterminatedBulkString), command(&["PING"])]);
+    }
+
+    #[test]
+    fn reset_drops_the_partially_decoded_command() {
+        let mut frame = RespCommandFrame::new();
+        assert_eq!(decode_all(&mut frame, b"*2\r\n$3\r\nGET\r\n"), vec![]);
+
+        frame.reset();
+        let items = decode_all(&mut frame, b"*1\r\n$4\r\nPING\r\n");
+        assert_eq!(items, vec![command(&["PING"])]);
+    }
+
+    #[test]
+    fn unexpected_bytes_are_escaped() {
+        let error = FrameError::UnexpectedByte {
+            expected: b'*',
+            got: b'\n',
+        };
+        assert_eq!(error.to_string(), "expected '*', got '\\n'");
+    }
+}
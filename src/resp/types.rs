@@ -1,7 +1,26 @@
-use bytes::{Bytes, BytesMut};
+use std::fmt::Write;
+
+use bytes::{BufMut, Bytes, BytesMut};
 
 use super::RespError;
 
+/// Error codes which are sent as the first word of error replies, as per Redis conventions.
+/// Clients rely on these to tell the kind of error apart.
+const ERROR_CODES: [&str; 12] = [
+    "ERR",
+    "WRONGTYPE",
+    "READONLY",
+    "NOAUTH",
+    "NOPERM",
+    "EXECABORT",
+    "BUSY",
+    "LOADING",
+    "MASTERDOWN",
+    "NOREPLICAS",
+    "OOM",
+    "NOPROTO",
+];
+
 /// This enum is a wrapper for the different data types in RESP.
 #[derive(Clone, Debug)]
 pub enum RespType {
@@ -16,7 +35,41 @@ pub enum RespType {
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#arrays>
     Array(Vec<RespType>),
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#integers>
-    Integer(i64)
+    Integer(i64),
+    /// Out-of-band data pushed to the client, like pub/sub messages. It's encoded as an Array in RESP2.
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#pushes>
+    Push(Vec<RespType>),
+    /// A string along with its format, for e.g. `txt` for plain text. It's meant for human-readable
+    /// replies, which clients can show as is. It's encoded as a BulkString in RESP2.
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#verbatim-strings>
+    VerbatimString { format: [u8; 3], data: String },
+    /// An ordered collection of key-value pairs. It's encoded as a flat Array of alternating
+    /// keys and values in RESP2.
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#maps>
+    Map(Vec<(RespType, RespType)>),
+    /// An unordered collection of unique elements. It's encoded as an Array in RESP2.
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#sets>
+    Set(Vec<RespType>),
+    /// A reply along with out-of-band metadata about it, like the keys to invalidate for
+    /// client-side caching. The attributes are only sent in RESP3, while RESP2 clients get
+    /// the reply alone.
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#attributes>
+    Attribute {
+        attributes: Vec<(RespType, RespType)>,
+        reply: Box<RespType>,
+    },
+    /// A reply which is already encoded, and is the same in RESP2 and RESP3. It's used for
+    /// replies which are encoded straight from the stored data, like the one of LRANGE.
+    Encoded(Bytes),
+}
+
+/// The version of the RESP protocol used to encode the replies sent to a client.
+/// Connections start with RESP2, and can switch with the HELLO command.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Protocol {
+    #[default]
+    Resp2,
+    Resp3,
 }
 
 impl RespType {
@@ -32,13 +85,13 @@ impl RespType {
     /// - If the parsing fails due to encoding issues etc.
     pub fn parse(buffer: BytesMut) -> Result<(RespType, usize), RespError> {
         let c = buffer[0] as char;
-        return match c {
+        match c {
             '$' => Self::parse_bulk_string(buffer),
             '+' => Self::parse_simple_string(buffer),
             _ => Err(RespError::Other(String::from(
                 "Invalid RESP data type",
             ))),
-        };
+        }
     }
 
     /// Parse the given bytes into a BulkString RESP value. This will return the parsed RESP
@@ -61,6 +114,32 @@ impl RespType {
     /// Note: The first byte in the buffer is skipped since it's just an identifier for the
     /// RESP type and is not the part of the actual value itself.
     pub fn parse_bulk_string(buffer: BytesMut) -> Result<(RespType, usize), RespError> {
+        let (data, bytes_read) = Self::parse_bulk_string_data(&buffer)?;
+
+        // convert raw bytes into UTF-8 string.
+        match String::from_utf8(data.to_vec()) {
+            Ok(bs) => Ok((RespType::BulkString(bs), bytes_read)),
+            Err(_) => Err(RespError::InvalidBulkString(String::from(
+                "Bulk string value is not a valid UTF-8 string",
+            ))),
+        }
+    }
+
+    /// Parse the given bytes into a BulkString RESP value, same as `parse_bulk_string`. But
+    /// invalid UTF-8 sequences are replaced with `U+FFFD`, instead of failing the parsing.
+    ///
+    /// This is used for the command names, so that a binary command name gets an unknown
+    /// command error which can be safely sent back to the client.
+    pub fn parse_bulk_string_lossy(buffer: BytesMut) -> Result<(RespType, usize), RespError> {
+        let (data, bytes_read) = Self::parse_bulk_string_data(&buffer)?;
+        let bs = String::from_utf8_lossy(data).into_owned();
+
+        Ok((RespType::BulkString(bs), bytes_read))
+    }
+
+    /// Reads the raw data of the bulk string at the start of the buffer. This will return the
+    /// data and the number of bytes read from the buffer.
+    fn parse_bulk_string_data(buffer: &[u8]) -> Result<(&[u8], usize), RespError> {
         // read until CRLF and parse length
         let (bulkstr_len, bytes_consumed) =
             if let Some((buf_data, len)) = Self::read_till_crlf(&buffer[1..]) {
@@ -72,24 +151,23 @@ impl RespType {
                 )));
             };
         
-        // validate if buffer contains the complete string data based on
-        // the length parsed in the previous step.
-        let bulkstr_end_idx = bytes_consumed + bulkstr_len as usize;
-        if bulkstr_end_idx >= buffer.len() {
+        // validate if buffer contains the complete string data, along with the CRLF
+        // following it, based on the length parsed in the previous step.
+        let bulkstr_end_idx = bytes_consumed + bulkstr_len;
+        if bulkstr_end_idx + 2 > buffer.len() {
             return Err(RespError::InvalidBulkString(String::from(
                 "Invalid value for bulk string length",
             )));
         }
 
-        // convert raw bytes into UTF-8 string.
-        let bulkstr = String::from_utf8(buffer[bytes_consumed..bulkstr_end_idx].to_vec());
-
-        match bulkstr {
-            Ok(bs) => Ok((RespType::BulkString(bs), bulkstr_end_idx + 2)),
-            Err(_) => Err(RespError::InvalidBulkString(String::from(
-                "Bulk string value is not a valid UTF-8 string",
-            ))),
+        // the string data should be followed by CRLF.
+        if &buffer[bulkstr_end_idx..bulkstr_end_idx + 2] != b"\r\n" {
+            return Err(RespError::InvalidBulkString(String::from(
+                "Bulk string is not terminated by CRLF",
+            )));
         }
+
+        Ok((&buffer[bytes_consumed..bulkstr_end_idx], bulkstr_end_idx + 2))
     }
 
     // Read the bytes till reaching CRLF ("\r\n")
@@ -108,7 +186,7 @@ impl RespType {
     // into usize.
     fn parse_usize_from_buf(buf: &[u8]) -> Result<usize, RespError> {
         let utf8_str = String::from_utf8(buf.to_vec());
-        let parsed_int = match utf8_str {
+        match utf8_str {
             Ok(s) => {
                 let int = s.parse::<usize>();
                 match int {
@@ -119,9 +197,7 @@ impl RespType {
                 }
             }
             Err(_) => Err(RespError::Other(String::from("Invalid UTF-8 string"))),
-        };
-
-        parsed_int
+        }
     }
 
     /// Parse the given bytes into a SimpleString RESP value. This will return the parsed RESP
@@ -137,7 +213,7 @@ impl RespType {
     ///
     /// # Parsing Logic:
     /// - The buffer is read until CRLF characters ("\r\n") are encountered. That slice of bytes are then
-    /// parsed into an UTF-8 string.
+    ///   parsed into an UTF-8 string.
     pub fn parse_simple_string(buffer: BytesMut) -> Result<(RespType, usize), RespError> {
         // read until CRLF and parse the bytes into an UTF-8 string.
         if let Some((buf_data, len)) = Self::read_till_crlf(&buffer[1..]) {
@@ -158,26 +234,172 @@ impl RespType {
         )))
     }
 
-    /// Convert the RESP value into its byte values.
+    /// Convert the RESP value into its byte values, as per RESP2.
     pub fn to_bytes(&self) -> Bytes {
-        return match self {
-            RespType::SimpleString(ss) => Bytes::from_iter(format!("+{}\r\n", ss).into_bytes()),
-            RespType::BulkString(bs) => {
-                let bulkstr_bytes = format!("${}\r\n{}\r\n", bs.chars().count(), bs).into_bytes();
-                Bytes::from_iter(bulkstr_bytes)
+        self.encode(Protocol::Resp2)
+    }
+
+    /// Convert the RESP value into its byte values, as per the given protocol version.
+    /// The types which don't exist in RESP2 are encoded with the closest RESP2 type.
+    pub fn encode(&self, protocol: Protocol) -> Bytes {
+        let mut dst = BytesMut::new();
+        self.encode_into(&mut dst, protocol);
+
+        dst.freeze()
+    }
+
+    /// Encodes the RESP value as per the given protocol version, writing the bytes straight to
+    /// the end of `dst`.
+    ///
+    /// Aggregates are written element by element into the same buffer, so a large reply isn't
+    /// copied once per level of nesting on its way to the connection.
+    pub fn encode_into(&self, dst: &mut BytesMut, protocol: Protocol) {
+        match self {
+            RespType::SimpleString(ss) => Self::encode_line(dst, '+', ss),
+            RespType::BulkString(bs) => Self::encode_blob(dst, '$', &[bs.as_bytes()]),
+            RespType::NullBulkString => dst.put_slice(b"$-1\r\n"),
+            RespType::Array(arr) => Self::encode_aggregate(dst, '*', arr, protocol),
+            RespType::Push(arr) => match protocol {
+                Protocol::Resp2 => Self::encode_aggregate(dst, '*', arr, protocol),
+                Protocol::Resp3 => Self::encode_aggregate(dst, '>', arr, protocol),
+            },
+            RespType::Set(members) => match protocol {
+                Protocol::Resp2 => Self::encode_aggregate(dst, '*', members, protocol),
+                Protocol::Resp3 => Self::encode_aggregate(dst, '~', members, protocol),
+            },
+            RespType::Map(pairs) => {
+                match protocol {
+                    Protocol::Resp2 => Self::encode_line(dst, '*', pairs.len() * 2),
+                    Protocol::Resp3 => Self::encode_line(dst, '%', pairs.len()),
+                };
+
+                for (k, v) in pairs.iter() {
+                    k.encode_into(dst, protocol);
+                    v.encode_into(dst, protocol);
+                }
             }
-            RespType::NullBulkString => Bytes::from("$-1\r\n"),
-            RespType::Array(arr) => {
-                let mut arr_bytes = format!("*{}\r\n", arr.len()).into_bytes();
-                arr.iter()
-                    .map(|v| v.to_bytes())
-                    .for_each(|b| arr_bytes.extend(b));
-
-                Bytes::from_iter(arr_bytes)
+            RespType::Attribute { attributes, reply } => {
+                // The attributes are encoded like a map, and followed by the reply itself.
+                if protocol == Protocol::Resp3 {
+                    Self::encode_line(dst, '|', attributes.len());
+                    for (k, v) in attributes.iter() {
+                        k.encode_into(dst, protocol);
+                        v.encode_into(dst, protocol);
+                    }
+                }
+                reply.encode_into(dst, protocol);
             }
-            RespType::SimpleError(es) => Bytes::from_iter(format!("-{}\r\n", es).into_bytes()),
-            RespType::Integer(i) => Bytes::from_iter(format!(":{}\r\n", i).into_bytes()),
+            RespType::VerbatimString { format, data } => match protocol {
+                Protocol::Resp2 => Self::encode_blob(dst, '$', &[data.as_bytes()]),
+                // The format is separated from the data by a colon.
+                Protocol::Resp3 => Self::encode_blob(dst, '=', &[format, b":", data.as_bytes()]),
+            },
+            RespType::SimpleError(es) => Self::encode_line(dst, '-', Self::with_error_code(es)),
+            RespType::Integer(i) => Self::encode_line(dst, ':', i),
+            RespType::Encoded(bytes) => dst.put_slice(bytes),
+        }
+    }
+
+    /// Encodes an array of bulk strings straight from the given strings, without building
+    /// a `RespType` for each of them.
+    ///
+    /// The length of the reply is computed first, so that its buffer is allocated once at
+    /// the exact size. So encoding a large range of a collection takes about as much memory
+    /// as the encoded reply.
+    pub fn bulk_string_array<'a>(elements: impl Iterator<Item = &'a String> + Clone) -> RespType {
+        let (count, len) = elements.clone().fold((0, 0), |(count, len), element| {
+            (count + 1, len + Self::line_len(element.len()) + element.len() + 2)
+        });
+
+        let mut dst = BytesMut::with_capacity(Self::line_len(count) + len);
+        Self::encode_line(&mut dst, '*', count);
+        for element in elements {
+            // Not `encode_blob`, which would reserve more room than the exact size.
+            Self::encode_line(&mut dst, '$', element.len());
+            dst.put_slice(element.as_bytes());
+            dst.put_slice(b"\r\n");
+        }
+
+        RespType::Encoded(dst.freeze())
+    }
+
+    /// Returns the length of a line written by `encode_line` for a length, including the
+    /// prefix and the CRLF.
+    fn line_len(len: usize) -> usize {
+        let digits = len.checked_ilog10().map_or(1, |log| log as usize + 1);
+        1 + digits + 2
+    }
+
+    /// Encodes the RESP value like `encode_into`, consuming it.
+    ///
+    /// The elements of aggregates are dropped as soon as they're written, so the memory held
+    /// by a large reply is handed back while it's encoded, instead of once it's fully encoded.
+    pub fn encode_owned_into(self, dst: &mut BytesMut, protocol: Protocol) {
+        let (prefix, elements) = match self {
+            RespType::Array(arr) => ('*', arr),
+            RespType::Push(arr) if protocol == Protocol::Resp3 => ('>', arr),
+            RespType::Set(members) if protocol == Protocol::Resp3 => ('~', members),
+            RespType::Push(arr) | RespType::Set(arr) => ('*', arr),
+            _ => return self.encode_into(dst, protocol),
         };
+
+        Self::encode_line(dst, prefix, elements.len());
+        for element in elements {
+            element.encode_owned_into(dst, protocol);
+        }
+    }
+
+    /// Encodes a type which fits on a single line, made of the given prefix and the value.
+    fn encode_line(dst: &mut BytesMut, prefix: char, value: impl std::fmt::Display) {
+        // Writing to a `BytesMut` never fails, it grows as needed.
+        let _ = write!(dst, "{}{}\r\n", prefix, value);
+    }
+
+    /// Encodes a type which is made of the given prefix and the length of the data, followed by
+    /// the data itself. The data is given in parts, which are written one after the other.
+    fn encode_blob(dst: &mut BytesMut, prefix: char, parts: &[&[u8]]) {
+        let len: usize = parts.iter().map(|part| part.len()).sum();
+        dst.reserve(len + 16);
+        Self::encode_line(dst, prefix, len);
+        for part in parts.iter() {
+            dst.put_slice(part);
+        }
+        dst.put_slice(b"\r\n");
+    }
+
+    /// Encodes an aggregate type, which is made of the given prefix and the number of elements,
+    /// followed by each element.
+    fn encode_aggregate(
+        dst: &mut BytesMut,
+        prefix: char,
+        elements: &[RespType],
+        protocol: Protocol,
+    ) {
+        Self::encode_line(dst, prefix, elements.len());
+        for element in elements.iter() {
+            element.encode_into(dst, protocol);
+        }
+    }
+
+    /// Ensures the error message starts with an error code, for e.g. `ERR` or `WRONGTYPE`.
+    ///
+    /// Messages which already start with one of the known error codes are returned as is,
+    /// while the rest are prefixed with the generic `ERR` code.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The error message.
+    ///
+    /// # Returns
+    ///
+    /// The error message prefixed with an error code.
+    pub fn with_error_code(msg: &str) -> String {
+        let code = msg.split(' ').next().unwrap_or_default();
+        if ERROR_CODES.contains(&code) {
+            return msg.to_string();
+        }
+
+        format!("ERR {}", msg)
     }
 
     /// Parses the length of a RESP array from the given byte buffer.
@@ -218,6 +440,7 @@ impl RespType {
     ///
     /// This function attempts to read the first few bytes of a RESP bulk string to determine its length.
     /// It expects the input to start with a '$' character followed by the length and terminated by CRLF.
+    /// A length of `-1` stands for a null bulk string, which has no data following it.
     ///
     /// # Arguments
     ///
@@ -225,13 +448,16 @@ impl RespType {
     ///
     /// # Returns
     ///
-    /// * `Ok(Some((usize, usize)))` - If successful, returns a tuple containing:
-    ///   - The parsed length of the bulk string
+    /// * `Ok(Some((Option<usize>, usize)))` - If successful, returns a tuple containing:
+    ///   - The parsed length of the bulk string, or `None` for a null bulk string
     ///   - The number of bytes read from the input
     /// * `Ok(None)` - If there's not enough data in the buffer to parse the length
-    /// * `Err(RespError)` - If the input is not a valid RESP bulk string prefix or if parsing fails
+    /// * `Err(RespError)` - If the input is not a valid RESP bulk string prefix or if parsing fails,
+    ///   including any other negative length
     ///
-    pub fn parse_bulk_string_len(src: BytesMut) -> Result<Option<(usize, usize)>, RespError> {
+    pub fn parse_bulk_string_len(
+        src: BytesMut,
+    ) -> Result<Option<(Option<usize>, usize)>, RespError> {
         let (bulkstr_prefix_bytes, bytes_read) = match Self::read_till_crlf(&src[..]) {
             Some((b, size)) => (b, size),
             None => return Ok(None),
@@ -243,10 +469,152 @@ impl RespType {
             )));
         }
 
+        if &bulkstr_prefix_bytes[1..] == b"-1" {
+            return Ok(Some((None, bytes_read)));
+        }
+
         match Self::parse_usize_from_buf(&bulkstr_prefix_bytes[1..]) {
-            Ok(len) => Ok(Some((len, bytes_read))),
+            Ok(len) => Ok(Some((Some(len), bytes_read))),
             Err(e) => Err(e),
         }
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_error_code_prefixes_messages_without_a_code() {
+        assert_eq!(RespType::with_error_code("syntax error"), "ERR syntax error");
+        assert_eq!(RespType::with_error_code(""), "ERR ");
+        // Codes are case-sensitive, and must be followed by a space or end the message.
+        assert_eq!(RespType::with_error_code("err oops"), "ERR err oops");
+        assert_eq!(RespType::with_error_code("ERRORS"), "ERR ERRORS");
+    }
+
+    #[test]
+    fn with_error_code_keeps_known_codes() {
+        for msg in [
+            "ERR unknown command",
+            "WRONGTYPE Operation against a key holding the wrong kind of value",
+            "READONLY You can't write against a read only replica.",
+            "EXECABORT Transaction discarded because of previous errors.",
+            "OOM command not allowed when used memory > 'maxmemory'.",
+            "NOPROTO",
+        ] {
+            assert_eq!(RespType::with_error_code(msg), msg);
+        }
+    }
+
+    #[test]
+    fn simple_errors_are_encoded_with_their_code() {
+        let err = RespType::SimpleError(String::from("no such key"));
+        assert_eq!(err.encode(Protocol::Resp2), &b"-ERR no such key\r\n"[..]);
+        assert_eq!(err.encode(Protocol::Resp3), &b"-ERR no such key\r\n"[..]);
+    }
+
+    #[test]
+    fn push_is_an_array_in_resp2_only() {
+        let push = RespType::Push(vec![
+            RespType::BulkString(String::from("message")),
+            RespType::BulkString(String::from("ch")),
+            RespType::BulkString(String::from("hi")),
+        ]);
+        assert_eq!(
+            push.encode(Protocol::Resp2),
+            &b"*3\r\n$7\r\nmessage\r\n$2\r\nch\r\n$2\r\nhi\r\n"[..]
+        );
+        assert_eq!(
+            push.encode(Protocol::Resp3),
+            &b">3\r\n$7\r\nmessage\r\n$2\r\nch\r\n$2\r\nhi\r\n"[..]
+        );
+    }
+
+    #[test]
+    fn verbatim_string_is_a_bulk_string_in_resp2() {
+        let verbatim = RespType::VerbatimString {
+            format: *b"txt",
+            data: String::from("hello"),
+        };
+        assert_eq!(verbatim.encode(Protocol::Resp2), &b"$5\r\nhello\r\n"[..]);
+        // The length covers the format and the colon as well.
+        assert_eq!(verbatim.encode(Protocol::Resp3), &b"=9\r\ntxt:hello\r\n"[..]);
+    }
+
+    #[test]
+    fn map_is_a_flat_array_in_resp2() {
+        let map = RespType::Map(vec![
+            (RespType::BulkString(String::from("a")), RespType::Integer(1)),
+            (RespType::BulkString(String::from("b")), RespType::Integer(2)),
+        ]);
+        assert_eq!(
+            map.encode(Protocol::Resp2),
+            &b"*4\r\n$1\r\na\r\n:1\r\n$1\r\nb\r\n:2\r\n"[..]
+        );
+        assert_eq!(
+            map.encode(Protocol::Resp3),
+            &b"%2\r\n$1\r\na\r\n:1\r\n$1\r\nb\r\n:2\r\n"[..]
+        );
+    }
+
+    #[test]
+    fn set_is_an_array_in_resp2() {
+        let set = RespType::Set(vec![
+            RespType::BulkString(String::from("a")),
+            RespType::BulkString(String::from("b")),
+        ]);
+        assert_eq!(set.encode(Protocol::Resp2), &b"*2\r\n$1\r\na\r\n$1\r\nb\r\n"[..]);
+        assert_eq!(set.encode(Protocol::Resp3), &b"~2\r\n$1\r\na\r\n$1\r\nb\r\n"[..]);
+    }
+
+    #[test]
+    fn attributes_are_only_sent_in_resp3() {
+        let reply = RespType::Attribute {
+            attributes: vec![(
+                RespType::BulkString(String::from("ttl")),
+                RespType::Integer(10),
+            )],
+            reply: Box::new(RespType::SimpleString(String::from("OK"))),
+        };
+        assert_eq!(reply.encode(Protocol::Resp2), &b"+OK\r\n"[..]);
+        assert_eq!(
+            reply.encode(Protocol::Resp3),
+            &b"|1\r\n$3\r\nttl\r\n:10\r\n+OK\r\n"[..]
+        );
+    }
+
+    #[test]
+    fn bulk_string_arrays_are_encoded_like_arrays() {
+        let long = "x".repeat(1000);
+        for elements in [vec![], vec![String::new()], vec![String::from("a"), long]] {
+            let array = RespType::Array(
+                elements.iter().cloned().map(RespType::BulkString).collect(),
+            );
+            for protocol in [Protocol::Resp2, Protocol::Resp3] {
+                let encoded = RespType::bulk_string_array(elements.iter()).encode(protocol);
+                assert_eq!(encoded, array.encode(protocol));
+            }
+        }
+    }
+
+    #[test]
+    fn aggregates_are_encoded_the_same_when_consumed() {
+        let reply = RespType::Array(vec![
+            RespType::Set(vec![RespType::Integer(1)]),
+            RespType::Push(vec![RespType::NullBulkString]),
+            RespType::Map(vec![(RespType::Integer(1), RespType::Integer(2))]),
+            RespType::bulk_string_array([String::from("a")].iter()),
+            RespType::Attribute {
+                attributes: vec![(RespType::Integer(1), RespType::Integer(2))],
+                reply: Box::new(RespType::Integer(3)),
+            },
+        ]);
+        for protocol in [Protocol::Resp2, Protocol::Resp3] {
+            let mut dst = BytesMut::new();
+            reply.clone().encode_owned_into(&mut dst, protocol);
+            assert_eq!(dst.freeze(), reply.encode(protocol));
+        }
+    }
+}
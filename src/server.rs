@@ -1,17 +1,28 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, time::Duration};
 
 use anyhow::{Error, Result};
 // use bytes::BytesMut;
-use log::error;
+use log::{debug, error, info};
 // use tokio::{
 // 	io::{AsyncReadExt, AsyncWriteExt},
 // 	net::{TcpListener, TcpStream}
 // };
-use tokio::net::{TcpListener, TcpStream};
+use tokio::{
+	io::AsyncWriteExt,
+	net::{TcpListener, TcpStream},
+	time::{sleep, Instant},
+};
 use tokio_util::codec::Framed;
 
 // use crate::resp::types::RespType;
-use crate::{handler::FrameHandler, resp::frame::RespCommandFrame, storage::db::Storage};
+use crate::{
+	handler::{is_disconnect, ClientOutputBufferLimit, FrameHandler},
+	resp::{frame::RespCommandFrame, types::RespType},
+	storage::db::Storage,
+};
+
+/// Maximum time the server waits for the client connections to close once it's shutting down.
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// The Server struct holds:
 ///
@@ -19,24 +30,45 @@ use crate::{handler::FrameHandler, resp::frame::RespCommandFrame, storage::db::S
 ///
 /// * Shared storage
 ///
+/// * The output buffer limits and command timeout applied to the client connections.
+///
 #[derive(Debug)]
 pub struct Server {
 	/// The TCP listener for accepting incoming connections.
 	listener: TcpListener,
 	/// Contains the shared storage.
 	storage: Storage,
+	/// The output buffer limits applied to each client connection.
+	output_buffer_limit: ClientOutputBufferLimit,
+	/// Maximum time a single command may run for.
+	command_timeout: Option<Duration>,
+	/// Number of pipelined commands a connection runs before yielding to the other connections.
+	commands_per_yield: usize,
 }
 
 impl Server {
-	/// Creates a new Server instance with the given TcpListener and shared storage.
-	pub fn new(listener:TcpListener, storage: Storage) -> Server {
-		Server { listener, storage }
+	/// Creates a new Server instance with the given TcpListener, shared storage,
+	/// client output buffer limits, command timeout and number of commands per yield.
+	pub fn new(
+		listener: TcpListener,
+		storage: Storage,
+		output_buffer_limit: ClientOutputBufferLimit,
+		command_timeout: Option<Duration>,
+		commands_per_yield: usize,
+	) -> Server {
+		Server { listener, storage, output_buffer_limit, command_timeout, commands_per_yield }
+	}
+
+	/// Returns the address the server is listening on. If it was bound to port 0, this
+	/// holds the port picked by the OS.
+	pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+		self.listener.local_addr()
 	}
 
-	/// Runs the server in an infinite loop, continuously accepting and handling
-    /// incoming connections.
+	/// Runs the server in a loop, continuously accepting and handling
+    /// incoming connections, until the server is signalled to shut down.
 	pub async fn run(&mut self) -> Result<()> {
-		let db = self.storage.db().clone();
+		let storage = self.storage.clone();
 
 		loop {
 				// accept a new TCP connection.
@@ -44,7 +76,16 @@ impl Server {
 				// in the variable `sock`, else a panic will occur.
 				// let mut sock = match self.accept_conn().await {
 				// let mut sock = match self.accept_conn().await {
-				let sock = match self.accept_conn().await {
+				let conn = tokio::select! {
+					conn = self.accept_conn() => conn,
+					// Stop accepting connections once a shutdown is requested.
+					_ = storage.wait_for_shutdown() => {
+						info!("Shutting down the server");
+						self.drain_connections().await;
+						return Ok(());
+					}
+				};
+				let sock = match conn {
 					Ok(stream) => stream,
 					// Log the error and panic if there is an issue accepting a connection.
 					Err(e) => {
@@ -53,12 +94,28 @@ impl Server {
 					}
 			};
 
+			// Register the client, or reject the connection if there are too many clients.
+			let client = match storage.clients().connect() {
+				Some(client) => client,
+				None => {
+					tokio::spawn(async move {
+						let mut sock = sock;
+						let err = RespType::SimpleError(String::from("max number of clients reached"));
+						let _ = sock.write_all(&err.to_bytes()).await;
+					});
+					continue;
+				}
+			};
+
 			// Use RespCommandFrame codec to read incoming TCP messages as Redis command frames,
 			// and to write RespType values into outgoing TCP messages.
 			let resp_command_frame = Framed::with_capacity(sock, RespCommandFrame::new(), 8 * 1024);
 
-			// Clone the Arc of the DB for passing it to the tokio task.
-			let db = Arc::clone(&db);
+			// Clone the shared storage for passing it to the tokio task.
+			let storage = self.storage.clone();
+			let output_buffer_limit = self.output_buffer_limit;
+			let command_timeout = self.command_timeout;
+			let commands_per_yield = self.commands_per_yield;
 
 			// Spawn a new asynchronous task to handle the connection.
       // This allows the server to handle multiple connections concurrently.
@@ -83,28 +140,53 @@ impl Server {
 				// 	error!("{}", e);
 				// 	panic!("Error writing response")
 				// }
-				let handler = FrameHandler::new(resp_command_frame);
-				if let Err(e) = handler.handle(db.as_ref()).await {
-					error!("Failed to handle command: {}", e);
+				let handler = FrameHandler::new(
+					resp_command_frame,
+					output_buffer_limit,
+					command_timeout,
+					commands_per_yield,
+				);
+				match handler.handle(&storage).await {
+					Ok(()) => {}
+					// The peer going away isn't an error on the server's side.
+					Err(e) if e.downcast_ref::<std::io::Error>().is_some_and(is_disconnect) => {
+						debug!("Connection closed by the peer: {}", e);
+					}
+					Err(e) => error!("Failed to handle command: {}", e),
 				}
+				// The client is counted as disconnected once it's dropped.
+				drop(client);
 				// The connection is closed automatically when `sock` goes out of scope.
 			});
 		}
 	}
 
+	/// Waits for the client connections to close after a shutdown was requested, for up to
+	/// `SHUTDOWN_DRAIN_TIMEOUT`. Each connection closes on its own once it notices the shutdown,
+	/// including the ones waiting for pub/sub messages or blocked in WAIT.
+	async fn drain_connections(&self) {
+		let deadline = Instant::now() + SHUTDOWN_DRAIN_TIMEOUT;
+		while self.storage.clients().connected() > 0 && Instant::now() < deadline {
+			sleep(Duration::from_millis(10)).await;
+		}
+
+		let connected = self.storage.clients().connected();
+		if connected > 0 {
+			info!("Shutting down with {} clients still connected", connected);
+		}
+	}
+
 	/// Accepts a new incoming TCP connection and returns the corresponding
     /// tokio TcpStream.
 	async fn accept_conn(&mut self) -> Result<TcpStream> {
-		loop {
-			// Wait for an incoming connection.
-            // The `accept()` method returns a tuple of (TcpStream, SocketAddr),
-            // but we only need the TcpStream.
-			match self.listener.accept().await {
-				// Return the TcpStream if a connection is successfully accepted.
-				Ok((sock, _)) => return Ok(sock),
-				// Return an error if there is an issue accepting a connection.
-				Err(e) => return Err(Error::from(e)),
-			}
+		// Wait for an incoming connection.
+		// The `accept()` method returns a tuple of (TcpStream, SocketAddr),
+		// but we only need the TcpStream.
+		match self.listener.accept().await {
+			// Return the TcpStream if a connection is successfully accepted.
+			Ok((sock, _)) => Ok(sock),
+			// Return an error if there is an issue accepting a connection.
+			Err(e) => Err(Error::from(e)),
 		}
 	}
 }
\ No newline at end of file
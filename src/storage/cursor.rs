@@ -0,0 +1,327 @@
+use std::{
+    collections::{btree_map, BTreeMap},
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+/// Returns the position of an element in the iteration order of the SCAN family of commands.
+///
+/// Elements are visited in the order of their hashes, which doesn't depend on the other
+/// elements of the collection. So the position of an element stays the same however the
+/// collection changes between calls.
+fn position(element: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    element.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A map from strings to values, which keeps its keys ordered by their position in the
+/// iteration order of the SCAN family of commands. So a batch of keys can be returned
+/// starting from any cursor, without visiting the keys before it.
+///
+/// Keys which share a position are ordered lexicographically.
+#[derive(Debug, Clone)]
+pub struct ScanMap<V> {
+    entries: BTreeMap<(u64, String), V>,
+}
+
+impl<V> Default for ScanMap<V> {
+    fn default() -> Self {
+        ScanMap {
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl<V> ScanMap<V> {
+    /// Create a new, empty `ScanMap`.
+    pub fn new() -> ScanMap<V> {
+        ScanMap::default()
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if the map has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the value of a key, or `None` if the key is not in the map.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        let pos = position(key);
+        // The empty string comes before any other key with the same position.
+        self.entries
+            .range((pos, String::new())..)
+            .take_while(|((p, _), _)| *p == pos)
+            .find(|((_, k), _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Set the value of a key. Returns the previous value, if the key was already in the map.
+    pub fn insert(&mut self, key: String, value: V) -> Option<V> {
+        self.entries.insert((position(&key), key), value)
+    }
+
+    /// Remove a key. Returns the key along with its value, if it was in the map.
+    pub fn remove_entry(&mut self, key: &str) -> Option<(String, V)> {
+        self.entries
+            .remove_entry(&(position(key), key.to_string()))
+            .map(|((_, key), value)| (key, value))
+    }
+
+    /// Remove a key. Returns its value, if it was in the map.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the keys and their values, in the order of their positions.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &V)> {
+        self.entries.iter().map(|((_, key), value)| (key, value))
+    }
+
+    /// Returns the next batch of keys for the SCAN family of commands, starting from the
+    /// given cursor. A cursor of `0` starts a new iteration.
+    ///
+    /// Every key which is in the map during the whole iteration is returned at least once.
+    /// Keys added or removed midway may or may not be returned. Only the keys in the batch
+    /// are visited, so the cost of a call doesn't depend on the size of the map.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor` - The cursor returned by the previous call, or `0` to start a new iteration.
+    ///
+    /// * `count` - The number of keys to return. More keys may be returned if they share
+    ///   their position with the last one. Must not be `0`.
+    ///
+    /// # Returns
+    ///
+    /// The cursor to continue the iteration with, which is `0` once all the keys were
+    /// returned, along with the batch of keys and their values.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<(&String, &V)>) {
+        let mut batch = vec![];
+        let mut last = cursor;
+        for ((pos, key), value) in self.entries.range((cursor, String::new())..) {
+            // Keys sharing the last position are returned together, as the next cursor
+            // starts at the next position.
+            if batch.len() >= count && *pos != last {
+                return (*pos, batch);
+            }
+            last = *pos;
+            batch.push((key, value));
+        }
+
+        (0, batch)
+    }
+}
+
+impl<V> FromIterator<(String, V)> for ScanMap<V> {
+    fn from_iter<I: IntoIterator<Item = (String, V)>>(iter: I) -> Self {
+        ScanMap {
+            entries: iter
+                .into_iter()
+                .map(|(key, value)| ((position(&key), key), value))
+                .collect(),
+        }
+    }
+}
+
+impl<V> IntoIterator for ScanMap<V> {
+    type Item = (String, V);
+    type IntoIter = std::iter::Map<
+        btree_map::IntoIter<(u64, String), V>,
+        fn(((u64, String), V)) -> (String, V),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries
+            .into_iter()
+            .map(|((_, key), value)| (key, value))
+    }
+}
+
+/// A set of strings, ordered like the keys of a `ScanMap`.
+#[derive(Debug, Clone, Default)]
+pub struct ScanSet {
+    members: ScanMap<()>,
+}
+
+impl ScanSet {
+    /// Create a new, empty `ScanSet`.
+    pub fn new() -> ScanSet {
+        ScanSet::default()
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Checks if the set has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Checks if the string is a member of the set.
+    pub fn contains(&self, member: &str) -> bool {
+        self.members.get(member).is_some()
+    }
+
+    /// Add a member. Returns `true` if it was newly added.
+    pub fn insert(&mut self, member: String) -> bool {
+        self.members.insert(member, ()).is_none()
+    }
+
+    /// Remove a member. Returns `true` if it was present.
+    pub fn remove(&mut self, member: &str) -> bool {
+        self.members.remove(member).is_some()
+    }
+
+    /// Returns an iterator over the members, in the order of their positions.
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.members.iter().map(|(member, _)| member)
+    }
+
+    /// Returns the next batch of members for the SSCAN command. See `ScanMap::scan`.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<&String>) {
+        let (cursor, batch) = self.members.scan(cursor, count);
+        (
+            cursor,
+            batch.into_iter().map(|(member, _)| member).collect(),
+        )
+    }
+}
+
+impl FromIterator<String> for ScanSet {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        ScanSet {
+            members: iter.into_iter().map(|member| (member, ())).collect(),
+        }
+    }
+}
+
+impl IntoIterator for ScanSet {
+    type Item = String;
+    type IntoIter =
+        std::iter::Map<<ScanMap<()> as IntoIterator>::IntoIter, fn((String, ())) -> String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.into_iter().map(|(member, _)| member)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scans the whole map with the given count, and returns the keys in the order they
+    /// were returned.
+    fn scan_all(map: &ScanMap<usize>, count: usize) -> Vec<String> {
+        let mut keys = vec![];
+        let mut cursor = 0;
+        loop {
+            let (next, batch) = map.scan(cursor, count);
+            assert!(
+                batch.len() <= count,
+                "batch of {} for count {}",
+                batch.len(),
+                count
+            );
+            keys.extend(batch.into_iter().map(|(key, _)| key.to_string()));
+            if next == 0 {
+                return keys;
+            }
+            cursor = next;
+        }
+    }
+
+    fn map_of(n: usize) -> ScanMap<usize> {
+        (0..n).map(|i| (format!("key:{}", i), i)).collect()
+    }
+
+    #[test]
+    fn scan_returns_every_key_exactly_once() {
+        let map = map_of(1000);
+        for count in [1, 7, 10, 999, 1000, 5000] {
+            let mut keys = scan_all(&map, count);
+            keys.sort();
+            let mut expected: Vec<String> = map.iter().map(|(key, _)| key.to_string()).collect();
+            expected.sort();
+            assert_eq!(keys, expected, "count {}", count);
+        }
+    }
+
+    #[test]
+    fn scan_of_an_empty_map_ends_at_once() {
+        let map: ScanMap<usize> = ScanMap::new();
+        assert_eq!(map.scan(0, 10), (0, vec![]));
+    }
+
+    #[test]
+    fn scan_returns_the_keys_present_during_the_whole_iteration() {
+        let mut map = map_of(1000);
+        let mut returned = vec![];
+        let mut cursor = 0;
+        let mut added = 1000;
+        loop {
+            let (next, batch) = map.scan(cursor, 10);
+            returned.extend(batch.into_iter().map(|(key, _)| key.to_string()));
+
+            // Change the map between calls, without touching the first 500 keys.
+            map.remove(&format!("key:{}", 999 - added % 500));
+            map.insert(format!("key:{}", added), added);
+            added += 1;
+
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        for i in 0..500 {
+            assert!(
+                returned.contains(&format!("key:{}", i)),
+                "key:{} is missing",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn keys_are_looked_up_and_removed() {
+        let mut map = map_of(100);
+        assert_eq!(map.len(), 100);
+        assert_eq!(map.get("key:42"), Some(&42));
+        assert_eq!(map.get("key:100"), None);
+
+        assert_eq!(map.insert(String::from("key:42"), 0), Some(42));
+        assert_eq!(map.get("key:42"), Some(&0));
+        assert_eq!(map.len(), 100);
+
+        assert_eq!(map.remove("key:42"), Some(0));
+        assert_eq!(map.remove("key:42"), None);
+        assert_eq!(map.get("key:42"), None);
+        assert_eq!(map.len(), 99);
+    }
+
+    #[test]
+    fn set_scan_returns_every_member() {
+        let set: ScanSet = (0..100).map(|i| i.to_string()).collect();
+        assert!(set.contains("99"));
+        assert!(!set.contains("100"));
+
+        let mut members = vec![];
+        let mut cursor = 0;
+        loop {
+            let (next, batch) = set.scan(cursor, 3);
+            members.extend(batch.into_iter().cloned());
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        members.sort_by_key(|m| m.parse::<usize>().unwrap());
+        assert_eq!(members, (0..100).map(|i| i.to_string()).collect::<Vec<_>>());
+    }
+}
@@ -1,45 +1,278 @@
 use std::{
-  collections::{HashMap, VecDeque},
-  sync::{Arc, RwLock},
+  cell::Cell,
+  collections::{vec_deque, BTreeSet, HashMap, HashSet, VecDeque},
+  marker::PhantomData,
+  sync::{
+      atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+      Arc, RwLock, RwLockReadGuard, RwLockWriteGuard,
+  },
+  time::{SystemTime, UNIX_EPOCH},
 };
 
-use super::DBError;
+use rand::seq::{IteratorRandom, SliceRandom};
+use tokio::sync::watch;
+
+use crate::{
+  clients::Clients,
+  config::Config,
+  monitor::{Monitor, Monitors},
+  pubsub::{PubSub, Subscriber},
+  replication::{generate_replid, Replication},
+  resp::types::RespType,
+  tracking::{Tracker, Tracking},
+  watch::{Watcher, Watches},
+};
+
+use super::{
+  cursor::{ScanMap, ScanSet},
+  eviction::{MaxMemoryPolicy, MAXMEMORY_SAMPLES},
+  hyperloglog::HyperLogLog,
+  sorted_set::{ScoreRange, SortedSet},
+  stream::{Stream, StreamEntry, StreamId},
+  DBError,
+};
 
 /// The Storage struct is designed to act as a wrapper around the core database,
 /// allowing it to be shared across multiple connections. The database is encapsulated within an Arc,
 /// to enable concurrent access.
+///
+/// Along with the database, it also holds the replication, pub/sub, monitor, tracking and watch state of the server,
+/// the connected clients and the configuration, which are shared across all connections in the same way.
+///
+/// It also carries the shutdown signal, so that any connection can ask the server to shut down.
 #[derive(Debug, Clone)]
 pub struct Storage {
   db: Arc<DB>,
+  replication: Arc<Replication>,
+  pubsub: Arc<PubSub>,
+  monitors: Arc<Monitors>,
+  tracking: Arc<Tracking>,
+  watches: Arc<Watches>,
+  clients: Arc<Clients>,
+  config: Arc<Config>,
+  /// Random identifier of this run of the server, so that clients can detect restarts.
+  run_id: Arc<RwLock<String>>,
+  /// When set, clients can't run commands which modify the dataset.
+  read_only: Arc<AtomicBool>,
+  /// The estimated size of the dataset, in bytes, over which memory is freed according to
+  /// the `maxmemory_policy`. `0` means there's no limit.
+  maxmemory: Arc<AtomicU64>,
+  maxmemory_policy: Arc<RwLock<MaxMemoryPolicy>>,
+  /// The expiry, in seconds, of the keys written by SET without an expiry of its own.
+  /// `0` means such keys don't expire.
+  default_ttl: Arc<AtomicU64>,
+  /// The maximum number of elements a list may hold. `0` means there's no limit.
+  list_max_size: Arc<AtomicU64>,
+  /// The maximum size, in bytes, of a value written by SET, LPUSH or RPUSH. `0` means
+  /// there's no limit.
+  max_value_size: Arc<AtomicU64>,
+  /// Taken in shared mode by every command, and in exclusive mode by transactions, so
+  /// that no other command runs while a transaction is being executed.
+  transaction_lock: Arc<RwLock<()>>,
+  shutdown: Arc<watch::Sender<bool>>,
 }
 
 /// The DB struct is the component that houses the actual data,
 /// which is stored in a RwLock wrapped around a HashMap. This ensures thread-safe read and write operations.
 #[derive(Debug)]
 pub struct DB {
-  data: RwLock<HashMap<String, Entry>>,
+  data: RwLock<Keyspace>,
+  /// The maximum number of members of a set reported in the `intset` encoding, if they're
+  /// all integers.
+  set_max_intset_entries: AtomicUsize,
 }
 
 /// The Entry struct represents the value associated with a particular key in the database.
-/// This struct encapsulates the Value enum, which allows for different types of data to be stored.
-#[derive(Debug, Clone)]
+/// This struct encapsulates the Value enum, which allows for different types of data to be stored,
+/// along with the expiry time and the last access time of the key.
+#[derive(Debug)]
 pub struct Entry {
   value: Value,
+  /// The time at which the key expires, as a Unix timestamp in milliseconds.
+  /// `None` if the key doesn't expire.
+  expires_at: Option<u64>,
+  /// The time at which the key was last accessed, as a Unix timestamp in milliseconds.
+  /// It's atomic, so that it can be updated by reads, which only hold the read lock.
+  last_access: AtomicU64,
+  /// Set once a string value is modified in place, for e.g. by APPEND or SETRANGE.
+  /// Redis always stores such strings in the raw encoding.
+  modified_in_place: bool,
+}
+
+/// The keys of the DB along with their entries, and an index of the keys which have an
+/// expiry, ordered by their expiry time. The index lets the expired keys be removed without
+/// going through the whole DB.
+///
+/// Reads go straight to the map. Writes go through the methods below, which keep the index
+/// in sync with the expiry times of the entries.
+#[derive(Debug, Default)]
+struct Keyspace {
+  entries: HashMap<String, Entry>,
+  expiries: BTreeSet<(u64, String)>,
 }
 
 /// The `Value` enum allows for storing various types of data associated with a key.
-/// Currently, it supports only String and List data type. But it can be expanded in the future
-/// to support more data types as needed (like Hash, SortedSet etc).
+/// Currently, it supports String, List, Set, Hash, SortedSet and Stream data types. But it can be
+/// expanded in the future to support more data types as needed.
 #[derive(Debug, Clone)]
 pub enum Value {
   String(String),
   List(VecDeque<String>),
+  Set(ScanSet),
+  Hash(ScanMap<String>),
+  SortedSet(SortedSet),
+  Stream(Stream),
+}
+
+/// Estimated number of bytes used by a key in the DB, besides the key and value data.
+/// This accounts for the hash table slot and the `Entry` struct holding the value.
+const ENTRY_OVERHEAD: usize = 64;
+
+/// The maximum length of a string value, in bytes, same as the maximum length of a bulk string.
+const MAX_STRING_LEN: usize = 512 * 1024 * 1024;
+
+/// Estimated number of bytes used by each element of a collection, besides the element data.
+/// This accounts for the `String` holding the element and the slot in the list or set.
+const ELEMENT_OVERHEAD: usize = 32;
+
+/// The default maximum number of members of a set reported in the `intset` encoding, same
+/// as Redis.
+pub const DEFAULT_SET_MAX_INTSET_ENTRIES: usize = 512;
+
+/// Details on how the value of a key is stored, as reported by the DEBUG OBJECT command.
+#[derive(Debug, Clone)]
+pub struct ObjectInfo {
+  /// The name of the encoding Redis would use for the value.
+  pub encoding: &'static str,
+  /// The number of bytes the value takes once serialized.
+  pub serialized_length: usize,
+  /// The number of elements in the value. Strings count as a single element.
+  pub len: usize,
+  /// The number of seconds since the key was last accessed.
+  pub idle_seconds: u64,
+}
+
+/// The condition under which SET writes the value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetCondition {
+  /// Only set the key if it doesn't exist.
+  Nx,
+  /// Only set the key if it already exists.
+  Xx,
+}
+
+/// The condition under which a member is added to, or updated in, a sorted set by ZADD.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZAddCondition {
+  /// Only add new members, never update the existing ones.
+  Nx,
+  /// Only update the existing members, never add new ones.
+  Xx,
+  /// Only update an existing member when the new score is greater than the current one.
+  /// New members are still added.
+  Gt,
+  /// Only update an existing member when the new score is less than the current one.
+  /// New members are still added.
+  Lt,
+}
+
+impl ZAddCondition {
+  /// Checks if a member with the given current score, or `None` if it's not a member yet,
+  /// may be given the new score.
+  fn holds(&self, current: Option<f64>, score: f64) -> bool {
+      match (self, current) {
+          (ZAddCondition::Nx, current) => current.is_none(),
+          (ZAddCondition::Xx, current) => current.is_some(),
+          (ZAddCondition::Gt, Some(current)) => score > current,
+          (ZAddCondition::Lt, Some(current)) => score < current,
+          (ZAddCondition::Gt | ZAddCondition::Lt, None) => true,
+      }
+  }
+}
+
+/// The condition under which the expiry time of a key is updated by the EXPIRE family of commands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExpireCondition {
+  /// Set the expiry only when the key has no expiry.
+  Nx,
+  /// Set the expiry only when the key has an existing expiry.
+  Xx,
+  /// Set the expiry only when the new expiry is greater than the current one.
+  /// A key without expiry is treated as having an infinite expiry.
+  Gt,
+  /// Set the expiry only when the new expiry is less than the current one.
+  /// A key without expiry is treated as having an infinite expiry.
+  Lt,
+}
+
+thread_local! {
+  /// The time frozen by `freeze_time` on this thread, if any.
+  static FROZEN_TIME: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Returns the current time as a Unix timestamp in milliseconds.
+///
+/// While the time is frozen with `freeze_time`, the time at which it was frozen is returned.
+pub fn current_time_millis() -> u64 {
+  if let Some(now) = FROZEN_TIME.with(|frozen| frozen.get()) {
+      return now;
+  }
+
+  match SystemTime::now().duration_since(UNIX_EPOCH) {
+      Ok(d) => d.as_millis() as u64,
+      Err(_) => 0,
+  }
+}
+
+/// Freezes the time returned by `current_time_millis` on the current thread, until the
+/// returned guard is dropped.
+///
+/// It's used to run all the commands of a transaction at the same point in time, like
+/// Redis does, so that a key can't expire midway through it: the commands either all see
+/// the key, or none of them does. The guard can't be sent to another thread, so it can't be
+/// held across an `await`.
+pub fn freeze_time() -> FrozenTime {
+  let now = current_time_millis();
+  FROZEN_TIME.with(|frozen| frozen.set(Some(now)));
+
+  FrozenTime {
+      _not_send: PhantomData,
+  }
+}
+
+/// Guard returned by `freeze_time`, which unfreezes the time once dropped.
+pub struct FrozenTime {
+  _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for FrozenTime {
+  fn drop(&mut self) {
+      FROZEN_TIME.with(|frozen| frozen.set(None));
+  }
 }
 
 impl Storage {
   /// Create a new instance of `Storage` which contains the DB.
   pub fn new(db: DB) -> Storage {
-      Storage { db: Arc::new(db) }
+      Storage {
+          db: Arc::new(db),
+          replication: Arc::new(Replication::new()),
+          pubsub: Arc::new(PubSub::new()),
+          monitors: Arc::new(Monitors::new()),
+          tracking: Arc::new(Tracking::new()),
+          watches: Arc::new(Watches::new()),
+          clients: Arc::new(Clients::new()),
+          config: Arc::new(Config::new()),
+          run_id: Arc::new(RwLock::new(generate_replid())),
+          read_only: Arc::new(AtomicBool::new(false)),
+          maxmemory: Arc::new(AtomicU64::new(0)),
+          maxmemory_policy: Arc::new(RwLock::new(MaxMemoryPolicy::default())),
+          default_ttl: Arc::new(AtomicU64::new(0)),
+          list_max_size: Arc::new(AtomicU64::new(0)),
+          max_value_size: Arc::new(AtomicU64::new(0)),
+          transaction_lock: Arc::new(RwLock::new(())),
+          shutdown: Arc::new(watch::Sender::new(false)),
+      }
   }
 
   /// Returns a clone of the shared database (`Arc<DB>`).
@@ -49,14 +282,333 @@ impl Storage {
   pub fn db(&self) -> Arc<DB> {
       self.db.clone()
   }
+
+  /// Returns the replication state of the server.
+  pub fn replication(&self) -> &Replication {
+      &self.replication
+  }
+
+  /// Returns the pub/sub state of the server.
+  pub fn pubsub(&self) -> &PubSub {
+      &self.pubsub
+  }
+
+  /// Creates the pub/sub state for a new connection.
+  pub fn subscriber(&self) -> Subscriber {
+      Subscriber::new(self.pubsub.clone())
+  }
+
+  /// Returns the connections which issued the MONITOR command.
+  pub fn monitors(&self) -> &Monitors {
+      &self.monitors
+  }
+
+  /// Creates the monitor state for a new connection.
+  pub fn monitor(&self) -> Monitor {
+      Monitor::new(self.monitors.clone())
+  }
+
+  /// Returns the client-side caching state of the server.
+  pub fn tracking(&self) -> &Tracking {
+      &self.tracking
+  }
+
+  /// Creates the client-side caching state for a new connection.
+  pub fn tracker(&self) -> Tracker {
+      Tracker::new(self.tracking.clone())
+  }
+
+  /// Returns the keys watched by the connections with the WATCH command.
+  pub fn watches(&self) -> &Watches {
+      &self.watches
+  }
+
+  /// Creates the watch state for a new connection.
+  pub fn watcher(&self) -> Watcher {
+      Watcher::new(self.watches.clone())
+  }
+
+  /// Returns the clients connected to the server.
+  pub fn clients(&self) -> &Arc<Clients> {
+      &self.clients
+  }
+
+  /// Returns the configuration of the server.
+  pub fn config(&self) -> &Config {
+      &self.config
+  }
+
+  /// Returns the run id of the server, a 40 characters long hex string generated at startup.
+  pub fn run_id(&self) -> String {
+      match self.run_id.read() {
+          Ok(run_id) => run_id.clone(),
+          Err(e) => e.into_inner().clone(),
+      }
+  }
+
+  /// Replaces the run id with a newly generated one, as if the server was restarted.
+  pub fn change_run_id(&self) {
+      match self.run_id.write() {
+          Ok(mut run_id) => *run_id = generate_replid(),
+          Err(e) => *e.into_inner() = generate_replid(),
+      }
+  }
+
+  /// Checks if the server is in read-only mode, where clients can't modify the dataset.
+  /// Writes from the master still go through on replicas.
+  pub fn is_read_only(&self) -> bool {
+      self.read_only.load(Ordering::Relaxed)
+  }
+
+  /// Turns the read-only mode on or off.
+  pub fn set_read_only(&self, read_only: bool) {
+      self.read_only.store(read_only, Ordering::Relaxed);
+  }
+
+  /// Sets the estimated size of the dataset, in bytes, over which memory is freed. `0`
+  /// removes the limit.
+  pub fn set_maxmemory(&self, maxmemory: u64) {
+      self.maxmemory.store(maxmemory, Ordering::Relaxed);
+  }
+
+  /// Sets the policy which decides how memory is freed once the dataset is over `maxmemory`.
+  pub fn set_maxmemory_policy(&self, policy: MaxMemoryPolicy) {
+      match self.maxmemory_policy.write() {
+          Ok(mut p) => *p = policy,
+          Err(e) => *e.into_inner() = policy,
+      }
+  }
+
+  /// Returns the expiry, in seconds, of the keys written by SET without an expiry of its
+  /// own. `0` means such keys don't expire.
+  pub fn default_ttl(&self) -> u64 {
+      self.default_ttl.load(Ordering::Relaxed)
+  }
+
+  /// Sets the expiry, in seconds, of the keys written by SET without an expiry of its own.
+  /// `0` turns it off.
+  pub fn set_default_ttl(&self, default_ttl: u64) {
+      self.default_ttl.store(default_ttl, Ordering::Relaxed);
+  }
+
+  /// Returns the maximum number of elements a list may hold, or `0` if there's no limit.
+  /// Replicas don't enforce it, so that they apply the same writes as their master.
+  pub fn list_max_size(&self) -> usize {
+      if self.replication.is_replica() {
+          return 0;
+      }
+
+      self.list_max_size.load(Ordering::Relaxed) as usize
+  }
+
+  /// Sets the maximum number of elements a list may hold. `0` removes the limit.
+  pub fn set_list_max_size(&self, list_max_size: u64) {
+      self.list_max_size.store(list_max_size, Ordering::Relaxed);
+  }
+
+  /// Sets the maximum size, in bytes, of a value written by SET, LPUSH or RPUSH. `0` removes
+  /// the limit.
+  pub fn set_max_value_size(&self, max_value_size: u64) {
+      self.max_value_size.store(max_value_size, Ordering::Relaxed);
+  }
+
+  /// Checks that none of the values to be written is larger than `max-value-size`, if
+  /// there's a limit. Replicas don't enforce it, so that they apply the same writes as
+  /// their master.
+  pub fn check_value_size(&self, values: &[String]) -> Result<(), DBError> {
+      let max_value_size = self.max_value_size.load(Ordering::Relaxed) as usize;
+      if max_value_size == 0 || self.replication.is_replica() {
+          return Ok(());
+      }
+
+      if values.iter().any(|v| v.len() > max_value_size) {
+          return Err(DBError::Other(String::from("value too large")));
+      }
+
+      Ok(())
+  }
+
+  /// Makes room for a command which could use more memory, if the dataset is over
+  /// `maxmemory`, by evicting keys according to the `maxmemory` policy. The evicted keys
+  /// are deleted on the replicas too, so this must be called while holding the write lock.
+  ///
+  /// Replicas don't enforce the limit, as their dataset must stay the same as the master's.
+  ///
+  /// # Errors
+  ///
+  /// An `OOM` error is returned if the dataset is still over `maxmemory` once the policy
+  /// runs out of keys to evict, in which case the command must be rejected.
+  pub fn free_memory(&self) -> Result<(), DBError> {
+      let maxmemory = self.maxmemory.load(Ordering::Relaxed) as usize;
+      if maxmemory == 0 || self.replication.is_replica() {
+          return Ok(());
+      }
+
+      let (_, used) = self.db.dataset_usage(MAXMEMORY_SAMPLES)?;
+      if used <= maxmemory {
+          return Ok(());
+      }
+
+      let policy = match self.maxmemory_policy.read() {
+          Ok(policy) => *policy,
+          Err(e) => *e.into_inner(),
+      };
+      let freed = match policy {
+          MaxMemoryPolicy::NoEviction => 0,
+          MaxMemoryPolicy::VolatileTtl => {
+              let (evicted, freed) =
+                  self.db.evict_volatile_ttl(used - maxmemory, MAXMEMORY_SAMPLES)?;
+              self.tracking.invalidate(&evicted);
+              self.watches.touch(&evicted);
+              for k in evicted {
+                  self.replication.propagate(RespType::Array(vec![
+                      RespType::BulkString(String::from("DEL")),
+                      RespType::BulkString(k),
+                  ]));
+              }
+              freed
+          }
+      };
+
+      if used.saturating_sub(freed) > maxmemory {
+          return Err(DBError::Other(String::from(
+              "OOM command not allowed when used memory > 'maxmemory'.",
+          )));
+      }
+
+      Ok(())
+  }
+
+  /// Acquires the transaction lock in shared mode, which must be held while executing
+  /// a command outside of a transaction. Any number of commands can hold it at once.
+  pub fn lock_command(&self) -> RwLockReadGuard<'_, ()> {
+      match self.transaction_lock.read() {
+          Ok(guard) => guard,
+          Err(e) => e.into_inner(),
+      }
+  }
+
+  /// Acquires the transaction lock in exclusive mode, which must be held while executing
+  /// the commands of a transaction. It waits for the running commands to finish, and keeps
+  /// other commands from running until it's released.
+  pub fn lock_transaction(&self) -> RwLockWriteGuard<'_, ()> {
+      match self.transaction_lock.write() {
+          Ok(guard) => guard,
+          Err(e) => e.into_inner(),
+      }
+  }
+
+  /// Signals the server to shut down.
+  pub fn shutdown(&self) {
+      self.shutdown.send_replace(true);
+  }
+
+  /// Waits until the server is signalled to shut down.
+  pub async fn wait_for_shutdown(&self) {
+      let mut rx = self.shutdown.subscribe();
+      // The sender is owned by `self`, so the channel can't be closed while waiting.
+      let _ = rx.wait_for(|shutdown| *shutdown).await;
+  }
 }
 
 impl DB {
   /// Create a new instance of DB.
   pub fn new() -> DB {
       DB {
-          data: RwLock::new(HashMap::new()),
+          data: RwLock::new(Keyspace::default()),
+          set_max_intset_entries: AtomicUsize::new(DEFAULT_SET_MAX_INTSET_ENTRIES),
+      }
+  }
+
+  /// Sets the maximum number of members of a set reported in the `intset` encoding.
+  pub fn set_set_max_intset_entries(&self, set_max_intset_entries: usize) {
+      self
+          .set_max_intset_entries
+          .store(set_max_intset_entries, Ordering::Relaxed);
+  }
+
+  /// Returns a copy of every key-value pair stored in the DB, along with the time at which
+  /// the key expires as a Unix timestamp in milliseconds, if it has one.
+  ///
+  /// This is used to transfer the whole dataset, for e.g. during the full
+  /// resynchronization of a replica.
+  pub fn snapshot(&self) -> Result<Vec<(String, Value, Option<u64>)>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Ok(data
+          .iter()
+          .filter(|(_, entry)| !entry.is_expired())
+          .map(|(k, entry)| (k.clone(), entry.value.clone(), entry.expires_at))
+          .collect())
+  }
+
+  /// Remove all the keys from the DB.
+  ///
+  /// The removed entries are handed back instead of being dropped while holding the lock, as
+  /// freeing a large dataset can take a while.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(HashMap<String, Entry>)` - The removed keys and their entries.
+  pub fn flush(&self) -> Result<HashMap<String, Entry>, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Ok(data.take())
+  }
+
+  /// Returns the number of keys in the DB, not including the expired keys.
+  ///
+  /// The expired keys are removed first, using the expiry index, so that the size of the
+  /// map is the number of live keys. This avoids counting the keys one by one.
+  pub fn len(&self) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      data.remove_expired(usize::MAX);
+
+      Ok(data.len())
+  }
+
+  /// Removes up to `limit` of the keys which have expired. This is run periodically, so
+  /// that the expired keys which are never accessed again are freed too.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The number of keys removed.
+  pub fn remove_expired(&self, limit: usize) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Ok(data.remove_expired(limit))
+  }
+
+  /// Set the keys `<prefix>0` to `<prefix><count - 1>` to the string values `value:0`
+  /// to `value:<count - 1>`. Keys which already exist are left untouched.
+  ///
+  /// This is used to quickly fill the DB for testing.
+  pub fn populate(&self, count: u64, prefix: &str) -> Result<(), DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      for i in 0..count {
+          let k = format!("{}{}", prefix, i);
+          Self::remove_if_expired(&mut data, k.as_str());
+          data.get_or_insert_with(k, || Value::String(format!("value:{}", i)));
       }
+
+      Ok(())
   }
 
   /// Get the string value stored against a key.
@@ -75,7 +627,7 @@ impl DB {
           Err(e) => return Err(DBError::Other(format!("{}", e))),
       };
 
-      let entry = match data.get(k) {
+      let entry = match Self::live_entry(&data, k) {
           Some(entry) => entry,
           None => return Ok(None),
       };
@@ -87,179 +639,2247 @@ impl DB {
       Err(DBError::WrongType)
   }
 
-  /// Set a string value against a key.
+  /// Get the string values stored against the given keys, in a single lookup.
+  ///
+  /// Keys which have expired are treated as missing, the same as with `get`.
   ///
   /// # Arguments
   ///
-  /// * `k` - The key on which value is to be set.
+  /// * `keys` - The keys on which lookup is performed.
   ///
-  /// * `v` - The value to be set against the key.
+  /// # Returns
+  ///
+  /// * `Ok(Vec<Option<String>>)` - The value of each key, in the order of the keys. `None` if
+  ///   the key is not found in DB, or has non-string data.
+  pub fn mget(&self, keys: &[String]) -> Result<Vec<Option<String>>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Ok(keys
+          .iter()
+          .map(|k| match Self::live_entry(&data, k).map(|entry| &entry.value) {
+              Some(Value::String(s)) => Some(s.to_string()),
+              _ => None,
+          })
+          .collect())
+  }
+
+  /// Get the string value stored against a key, and delete the key.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which lookup is performed.
   ///
   /// # Returns
   ///
-  /// * `Ok(())` - If value is successfully added against the key.
+  /// * `Ok(Option<String>)` - `Some(String)` if key was found in DB and deleted, else `None`
   /// * `Err(DBError)` - if key already exists and has non-string data.
-  pub fn set(&self, k: String, v: Value) -> Result<(), DBError> {
+  pub fn getdel(&self, k: &str) -> Result<Option<String>, DBError> {
       let mut data = match self.data.write() {
           Ok(data) => data,
           Err(e) => return Err(DBError::Other(format!("{}", e))),
       };
 
-      let entry = match data.get(k.as_str()) {
-          Some(entry) => Some(entry),
-          None => None,
-      };
+      Self::remove_if_expired(&mut data, k);
 
-      if entry.is_some() {
-          match entry.unwrap().value {
-              Value::String(_) => {}
-              _ => return Err(DBError::WrongType),
-          }
+      match data.get(k).map(|entry| &entry.value) {
+          Some(Value::String(_)) => {}
+          Some(_) => return Err(DBError::WrongType),
+          None => return Ok(None),
       }
 
-      data.insert(k.to_string(), Entry::new(v));
-
-      return Ok(());
+      match data.remove(k).map(|entry| entry.value) {
+          Some(Value::String(s)) => Ok(Some(s)),
+          _ => Ok(None),
+      }
   }
 
-
-  /// Add new elements to the head of a list.
-  /// If the key is not present in the DB, and empty list is initialized
-  /// against the key before adding the elements to the head.
+  /// Get the string value stored against a key, and update the expiry time of the key.
+  /// An expiry time which is already in the past deletes the key.
   ///
   /// # Arguments
   ///
-  /// * `k` - The key on which list is stored.
+  /// * `k` - The key on which lookup is performed.
   ///
-  /// * `v` - The values to be added to the head of the list.
+  /// * `expires_at` - The new expiry time as a Unix timestamp in milliseconds, or `None` to
+  ///   remove the expiry.
   ///
   /// # Returns
   ///
-  /// * `Ok(())` - If values are added successfully to the head of the list.
-  /// * `Err(DBError)` - if key already exists and has non-list data.
-  pub fn lpush(&self, k: String, v: Vec<String>) -> Result<usize, DBError> {
+  /// * `Ok(Option<String>)` - `Some(String)` if key is found in DB, else `None`
+  /// * `Err(DBError)` - if key already exists and has non-string data.
+  pub fn getex(&self, k: &str, expires_at: Option<u64>) -> Result<Option<String>, DBError> {
       let mut data = match self.data.write() {
           Ok(data) => data,
           Err(e) => return Err(DBError::Other(format!("{}", e))),
       };
 
-      let entry = match data.get_mut(k.as_str()) {
-          Some(entry) => Some(entry),
-          None => None,
+      Self::remove_if_expired(&mut data, k);
+
+      let entry = match data.get_mut(k) {
+          Some(entry) => entry,
+          None => return Ok(None),
       };
 
-      match entry {
-          Some(e) => {
-              let val = &mut e.value;
-              match val {
-                  Value::List(l) => {
-                      for each in v.iter().cloned() {
-                          l.push_front(each);
-                      }
-                      Ok(l.len())
-                  }
-                  _ => Err(DBError::WrongType),
-              }
-          }
-          None => {
-              let list = VecDeque::from(v);
-              let l_len = list.len();
-              data.insert(k.to_string(), Entry::new(Value::List(list)));
+      let value = match &entry.value {
+          Value::String(s) => s.to_string(),
+          _ => return Err(DBError::WrongType),
+      };
 
-              Ok(l_len)
+      match expires_at {
+          Some(expires_at) if expires_at <= current_time_millis() => {
+              data.remove(k);
+          }
+          expires_at => {
+              data.set_expiry(k, expires_at);
           }
       }
+
+      Ok(Some(value))
   }
 
-  /// Adds new elements to the tail of a list.
-  /// If the key is not present in the DB, and empty list is initialized
-  /// against the key before adding the elements to the tail.
+  /// Add the given elements to the HyperLogLog stored at key. If the key is not present
+  /// in the DB, an empty HyperLogLog is initialized against the key before adding the elements.
   ///
   /// # Arguments
   ///
-  /// * `k` - The key on which list is stored.
+  /// * `k` - The key on which HyperLogLog is stored.
   ///
-  /// * `v` - The values to be added to the tail of the list.
+  /// * `elements` - The elements to be added.
   ///
   /// # Returns
   ///
-  /// * `Ok(())` - If value are added successfully to the tail of the list.
-  /// * `Err(DBError)` - if key already exists and has non-list data.
-  pub fn rpush(&self, k: String, v: Vec<String>) -> Result<usize, DBError> {
+  /// * `Ok(bool)` - `true` if the key was created or the estimated cardinality changed.
+  /// * `Err(DBError)` - if key already exists and doesn't hold a HyperLogLog.
+  pub fn pfadd(&self, k: &str, elements: &[String]) -> Result<bool, DBError> {
       let mut data = match self.data.write() {
           Ok(data) => data,
           Err(e) => return Err(DBError::Other(format!("{}", e))),
       };
 
-      let entry = match data.get_mut(k.as_str()) {
-          Some(entry) => Some(entry),
-          None => None,
+      Self::remove_if_expired(&mut data, k);
+
+      let (mut hll, mut changed) = match data.get(k).map(|entry| &entry.value) {
+          Some(Value::String(s)) => (Self::parse_hll(s)?, false),
+          Some(_) => return Err(DBError::WrongType),
+          None => (HyperLogLog::new(), true),
       };
 
-      match entry {
-          Some(e) => {
-              let val = &mut e.value;
-              match val {
-                  Value::List(l) => {
-                      for each in v.iter().cloned() {
-                          l.push_back(each);
-                      }
-                      Ok(l.len())
-                  }
-                  _ => Err(DBError::WrongType),
+      for element in elements.iter() {
+          changed |= hll.add(element);
+      }
+
+      if changed {
+          match data.get_mut(k) {
+              Some(entry) => entry.value = Value::String(hll.into_string()),
+              None => {
+                  data.insert(k.to_string(), Entry::new(Value::String(hll.into_string())));
               }
           }
-          None => {
-              let list = VecDeque::from(v);
-              let l_len = list.len();
+      }
+
+      Ok(changed)
+  }
+
+  /// Returns the estimated cardinality of the union of the HyperLogLogs stored at the given keys.
+  /// Keys which are not found are treated as empty HyperLogLogs.
+  ///
+  /// # Arguments
+  ///
+  /// * `keys` - The keys on which the HyperLogLogs are stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(u64)` - The estimated number of distinct elements added to the HyperLogLogs.
+  /// * `Err(DBError)` - if any of the keys doesn't hold a HyperLogLog.
+  pub fn pfcount(&self, keys: &[String]) -> Result<u64, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let mut union = HyperLogLog::new();
+      for k in keys.iter() {
+          match Self::live_entry(&data, k.as_str()).map(|entry| &entry.value) {
+              Some(Value::String(s)) => union.merge(&Self::parse_hll(s)?),
+              Some(_) => return Err(DBError::WrongType),
+              None => {}
+          }
+      }
+
+      Ok(union.count())
+  }
+
+  /// Parse the HyperLogLog stored in a string value.
+  fn parse_hll(s: &str) -> Result<HyperLogLog, DBError> {
+      match HyperLogLog::from_string(s) {
+          Some(hll) => Ok(hll),
+          None => Err(DBError::Other(String::from(
+              "WRONGTYPE Key is not a valid HyperLogLog string value.",
+          ))),
+      }
+  }
+
+  /// Set a string value against a key.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which value is to be set.
+  ///
+  /// * `v` - The value to be set against the key.
+  ///
+  /// * `expires_at` - The time at which the key expires, as a Unix timestamp in milliseconds.
+  ///   `None` if the key doesn't expire. Any previous expiry of the key is discarded.
+  ///
+  /// * `keep_ttl` - If set, the key keeps its previous expiry, and `expires_at` is ignored.
+  ///
+  /// * `condition` - The condition which must hold for the value to be set, if any.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok((bool, Option<String>))` - Whether the value was set, which is only not the case
+  ///   if the condition doesn't hold, and the previous value of the key, if it had one.
+  /// * `Err(DBError)` - if key already exists and has non-string data.
+  pub fn set(
+      &self,
+      k: String,
+      v: Value,
+      expires_at: Option<u64>,
+      keep_ttl: bool,
+      condition: Option<SetCondition>,
+  ) -> Result<(bool, Option<String>), DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let mut expires_at = expires_at;
+      let exists = match Self::live_entry(&data, k.as_str()) {
+          Some(entry) => {
+              match entry.value {
+                  Value::String(_) => {}
+                  _ => return Err(DBError::WrongType),
+              }
+              if keep_ttl {
+                  expires_at = entry.expires_at;
+              }
+              true
+          }
+          None => {
+              if keep_ttl {
+                  expires_at = None;
+              }
+              false
+          }
+      };
+
+      match (condition, exists) {
+          (Some(SetCondition::Nx), true) | (Some(SetCondition::Xx), false) => {
+              let previous = match Self::live_entry(&data, k.as_str()).map(|e| &e.value) {
+                  Some(Value::String(s)) => Some(s.to_string()),
+                  _ => None,
+              };
+              return Ok((false, previous));
+          }
+          _ => {}
+      }
+
+      let mut entry = Entry::new(v);
+      entry.expires_at = expires_at;
+
+      // The entry left behind by an expired key isn't a previous value.
+      let previous = match data.insert(k.to_string(), entry) {
+          Some(Entry {
+              value: Value::String(s),
+              ..
+          }) if exists => Some(s),
+          _ => None,
+      };
+
+      Ok((true, previous))
+  }
+
+  /// Append a value to the string stored against a key.
+  /// If the key is not present in the DB, it's set to the value.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which the string is stored.
+  ///
+  /// * `v` - The value to be appended.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The length of the string after the append.
+  /// * `Err(DBError)` - if key already exists and has non-string data, or if the string
+  ///   would grow over the maximum length.
+  pub fn append(&self, k: &str, v: &str) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let entry = match data.get_mut(k) {
+          Some(entry) => entry,
+          None => {
+              data.insert(k.to_string(), Entry::new(Value::String(v.to_string())));
+              return Ok(v.len());
+          }
+      };
+
+      let s = match &mut entry.value {
+          Value::String(s) => s,
+          _ => return Err(DBError::WrongType),
+      };
+
+      if s.len() + v.len() > MAX_STRING_LEN {
+          return Err(DBError::Other(String::from(
+              "string exceeds maximum allowed size (proto-max-bulk-len)",
+          )));
+      }
+
+      s.push_str(v);
+      let len = s.len();
+      entry.modified_in_place = true;
+
+      Ok(len)
+  }
+
+  /// Increment the integer stored as a string against a key.
+  /// If the key is not present in the DB, it's set to `0` before the increment.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which the integer is stored.
+  ///
+  /// * `delta` - The value added to the integer.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(i64)` - The value of the integer after the increment.
+  /// * `Err(DBError)` - if key already exists and has non-string data, if the string isn't
+  ///   an integer, or if the increment would overflow.
+  pub fn incr_by(&self, k: &str, delta: i64) -> Result<i64, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let entry = match data.get_mut(k) {
+          Some(entry) => entry,
+          None => {
+              data.insert(k.to_string(), Entry::new(Value::String(delta.to_string())));
+              return Ok(delta);
+          }
+      };
+
+      let s = match &mut entry.value {
+          Value::String(s) => s,
+          _ => return Err(DBError::WrongType),
+      };
+
+      let value = match s.parse::<i64>() {
+          Ok(value) => value,
+          Err(_) => {
+              return Err(DBError::Other(String::from(
+                  "value is not an integer or out of range",
+              )))
+          }
+      };
+
+      let value = match value.checked_add(delta) {
+          Some(value) => value,
+          None => {
+              return Err(DBError::Other(String::from(
+                  "increment or decrement would overflow",
+              )))
+          }
+      };
+
+      *s = value.to_string();
+      Ok(value)
+  }
+
+  /// Overwrite part of the string stored against a key, starting at the given byte offset.
+  /// The string is padded with zero bytes if it's shorter than the offset. If the key is
+  /// not present in the DB, it's treated as an empty string.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which the string is stored.
+  ///
+  /// * `offset` - The byte offset at which the value is written.
+  ///
+  /// * `v` - The value to be written.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The length of the string after it's modified.
+  /// * `Err(DBError)` - if key already exists and has non-string data, if the string would
+  ///   grow over the maximum length, or if the offset splits a character of the string.
+  pub fn setrange(&self, k: &str, offset: usize, v: &str) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let current_len = match data.get(k).map(|entry| &entry.value) {
+          Some(Value::String(s)) => s.len(),
+          Some(_) => return Err(DBError::WrongType),
+          None => 0,
+      };
+
+      // Nothing is written, and a missing key isn't created.
+      if v.is_empty() {
+          return Ok(current_len);
+      }
+
+      let end = match offset.checked_add(v.len()) {
+          Some(end) if end <= MAX_STRING_LEN => end,
+          _ => {
+              return Err(DBError::Other(String::from(
+                  "string exceeds maximum allowed size (proto-max-bulk-len)",
+              )));
+          }
+      };
+
+      let entry = data.get_or_insert_with(k.to_string(), || Value::String(String::new()));
+      let s = match &mut entry.value {
+          Value::String(s) => s,
+          _ => return Err(DBError::WrongType),
+      };
+
+      // Values are stored as UTF-8 strings, so a write can't start or end in the middle
+      // of a character.
+      if !s.is_char_boundary(offset.min(s.len())) || !s.is_char_boundary(end.min(s.len())) {
+          return Err(DBError::Other(String::from(
+              "offset splits a multi-byte character of the string",
+          )));
+      }
+
+      if s.len() < offset {
+          s.extend(std::iter::repeat_n('\0', offset - s.len()));
+      }
+      s.replace_range(offset..end.min(s.len()), v);
+      let len = s.len();
+      entry.modified_in_place = true;
+
+      Ok(len)
+  }
+
+
+  /// Add new elements to the head of a list.
+  /// If the key is not present in the DB, and empty list is initialized
+  /// against the key before adding the elements to the head.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which list is stored.
+  ///
+  /// * `v` - The values to be added to the head of the list.
+  ///
+  /// * `max_len` - The maximum number of elements the list may hold. `0` means there's
+  ///   no limit. No value is added if the list would grow over it.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(())` - If values are added successfully to the head of the list.
+  /// * `Err(DBError)` - if key already exists and has non-list data, or if the list would
+  ///   grow over `max_len`.
+  pub fn lpush(&self, k: String, v: Vec<String>, max_len: usize) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k.as_str());
+
+      match data.get_mut(k.as_str()) {
+          Some(e) => {
+              let val = &mut e.value;
+              match val {
+                  Value::List(l) => {
+                      Self::check_list_size(l.len() + v.len(), max_len)?;
+                      for each in v.iter().cloned() {
+                          l.push_front(each);
+                      }
+                      Ok(l.len())
+                  }
+                  _ => Err(DBError::WrongType),
+              }
+          }
+          None => {
+              Self::check_list_size(v.len(), max_len)?;
+              let list = VecDeque::from(v);
+              let l_len = list.len();
+              data.insert(k.to_string(), Entry::new(Value::List(list)));
+
+              Ok(l_len)
+          }
+      }
+  }
+
+  /// Adds new elements to the tail of a list.
+  /// If the key is not present in the DB, and empty list is initialized
+  /// against the key before adding the elements to the tail.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which list is stored.
+  ///
+  /// * `v` - The values to be added to the tail of the list.
+  ///
+  /// * `max_len` - The maximum number of elements the list may hold. `0` means there's
+  ///   no limit. No value is added if the list would grow over it.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(())` - If value are added successfully to the tail of the list.
+  /// * `Err(DBError)` - if key already exists and has non-list data, or if the list would
+  ///   grow over `max_len`.
+  pub fn rpush(&self, k: String, v: Vec<String>, max_len: usize) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k.as_str());
+
+      match data.get_mut(k.as_str()) {
+          Some(e) => {
+              let val = &mut e.value;
+              match val {
+                  Value::List(l) => {
+                      Self::check_list_size(l.len() + v.len(), max_len)?;
+                      for each in v.iter().cloned() {
+                          l.push_back(each);
+                      }
+                      Ok(l.len())
+                  }
+                  _ => Err(DBError::WrongType),
+              }
+          }
+          None => {
+              Self::check_list_size(v.len(), max_len)?;
+              let list = VecDeque::from(v);
+              let l_len = list.len();
               data.insert(k.to_string(), Entry::new(Value::List(list)));
 
-              Ok(l_len)
+              Ok(l_len)
+          }
+      }
+  }
+
+  /// Checks that a list of `len` elements doesn't go over `max_len`, if there's a limit.
+  fn check_list_size(len: usize, max_len: usize) -> Result<(), DBError> {
+      if max_len > 0 && len > max_len {
+          return Err(DBError::Other(String::from("list exceeds configured maximum size")));
+      }
+
+      Ok(())
+  }
+
+  /// Passes the specified elements of the list stored at key, based on the start and stop indices,
+  /// to `f` and returns its result. The elements are borrowed from the list, so that a large range
+  /// can be used without copying it.
+  /// These offsets can also be negative numbers indicating offsets starting at the end of the list.
+  /// For example, -1 is the last element of the list, -2 the penultimate, and so on.
+  /// Please note that the item at stop index is also included in the result.
+  ///
+  /// If the specified key is not found, `f` is given no elements.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which list is stored.
+  ///
+  /// * `start_idx` - The start index.
+  ///
+  /// * `stop_idx` - The end index.
+  ///
+  /// * `f` - Called with the elements, while holding the read lock on the DB.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(R)` - The result of `f`.
+  /// * `Err(DBError)` - if key already exists and has non-list data.
+  pub fn lrange_with<R>(
+      &self,
+      k: &str,
+      start_idx: i64,
+      stop_idx: i64,
+      f: impl FnOnce(vec_deque::Iter<'_, String>) -> R,
+  ) -> Result<R, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let entry = match Self::live_entry(&data, k) {
+          Some(entry) => entry,
+          None => return Ok(f(VecDeque::new().iter())),
+      };
+
+      match &entry.value {
+          Value::List(l) => {
+              let l_len = l.len() as i64;
+              let (rounded_start_idx, rounded_stop_idx) =
+                  Self::round_list_indices(l_len, start_idx, stop_idx);
+              Ok(f(l.range(rounded_start_idx..rounded_stop_idx)))
+          }
+          _ => Err(DBError::WrongType),
+      }
+  }
+
+  /// Returns the indices of the elements of the list stored at key which are equal to
+  /// the given element.
+  ///
+  /// If the specified key is not found, an empty list is returned.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which list is stored.
+  ///
+  /// * `element` - The element to look for.
+  ///
+  /// * `rank` - The match to start from, where `1` is the first match from the head. Negative
+  ///   values scan the list from the tail instead, so `-1` is the last match. Must not be `0`.
+  ///
+  /// * `count` - The maximum number of indices to return, or `0` to return all of them.
+  ///
+  /// * `maxlen` - The maximum number of elements to compare, or `0` to scan the whole list.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<usize>)` - The indices of the matches, counted from the head of the list, in
+  ///   the order they were found.
+  /// * `Err(DBError)` - if key already exists and has non-list data.
+  pub fn lpos(
+      &self,
+      k: &str,
+      element: &str,
+      rank: i64,
+      count: usize,
+      maxlen: usize,
+  ) -> Result<Vec<usize>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let entry = match Self::live_entry(&data, k) {
+          Some(entry) => entry,
+          None => return Ok(vec![]),
+      };
+
+      let l = match &entry.value {
+          Value::List(l) => l,
+          _ => return Err(DBError::WrongType),
+      };
+
+      let maxlen = if maxlen == 0 { l.len() } else { maxlen };
+      let count = if count == 0 { usize::MAX } else { count };
+      let indexed: Box<dyn Iterator<Item = (usize, &String)>> = if rank > 0 {
+          Box::new(l.iter().enumerate())
+      } else {
+          Box::new(l.iter().enumerate().rev())
+      };
+
+      Ok(indexed
+          .take(maxlen)
+          .filter(|(_, e)| e.as_str() == element)
+          .skip(rank.unsigned_abs() as usize - 1)
+          .take(count)
+          .map(|(i, _)| i)
+          .collect())
+  }
+
+  /// Returns all the elements of the list or set stored at key.
+  ///
+  /// If the specified key is not found, an empty list is returned.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which the list or set is stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<String>)` - The elements of the list in order, or the members of the set in no particular order.
+  /// * `Err(DBError)` - if key already exists and has neither list nor set data.
+  pub fn elements(&self, k: &str) -> Result<Vec<String>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let entry = match Self::live_entry(&data, k) {
+          Some(entry) => entry,
+          None => return Ok(vec![]),
+      };
+
+      match &entry.value {
+          Value::List(l) => Ok(l.iter().cloned().collect()),
+          Value::Set(s) => Ok(s.iter().cloned().collect()),
+          _ => Err(DBError::WrongType),
+      }
+  }
+
+  /// Add the given members to the set stored at key.
+  /// If the key is not present in the DB, an empty set is initialized
+  /// against the key before adding the members. Members which are already
+  /// present in the set are ignored.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which set is stored.
+  ///
+  /// * `members` - The members to be added to the set.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The number of members that were newly added to the set.
+  /// * `Err(DBError)` - if key already exists and has non-set data.
+  pub fn sadd(&self, k: String, members: Vec<String>) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k.as_str());
+
+      let entry = data.get_or_insert_with(k, || Value::Set(ScanSet::new()));
+
+      match &mut entry.value {
+          Value::Set(s) => {
+              let mut added = 0;
+              for member in members {
+                  if s.insert(member) {
+                      added += 1;
+                  }
+              }
+              Ok(added)
+          }
+          _ => Err(DBError::WrongType),
+      }
+  }
+
+  /// Remove the given members from the set stored at key. The key is deleted
+  /// once the set is empty.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which set is stored.
+  ///
+  /// * `members` - The members to be removed from the set.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The number of members that were removed from the set.
+  /// * `Err(DBError)` - if key already exists and has non-set data.
+  pub fn srem(&self, k: &str, members: &[String]) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let set = match data.get_mut(k).map(|entry| &mut entry.value) {
+          Some(Value::Set(s)) => s,
+          Some(_) => return Err(DBError::WrongType),
+          None => return Ok(0),
+      };
+
+      let removed = members.iter().filter(|member| set.remove(member)).count();
+      if set.is_empty() {
+          data.remove(k);
+      }
+
+      Ok(removed)
+  }
+
+  /// Move a member from the set stored at `src` to the set stored at `dst`.
+  /// If `dst` is not present in the DB, an empty set is initialized against it.
+  /// `src` is deleted once its set is empty.
+  ///
+  /// # Arguments
+  ///
+  /// * `src` - The key on which the source set is stored.
+  ///
+  /// * `dst` - The key on which the destination set is stored.
+  ///
+  /// * `member` - The member to be moved.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(bool)` - `true` if the member was moved, `false` if it wasn't in the source set.
+  /// * `Err(DBError)` - if either key already exists and has non-set data.
+  pub fn smove(&self, src: &str, dst: &str, member: &str) -> Result<bool, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, src);
+      Self::remove_if_expired(&mut data, dst);
+
+      for k in [src, dst] {
+          if let Some(entry) = data.get(k) {
+              if !matches!(entry.value, Value::Set(_)) {
+                  return Err(DBError::WrongType);
+              }
+          }
+      }
+
+      let removed = match data.get_mut(src).map(|entry| &mut entry.value) {
+          Some(Value::Set(s)) => {
+              // Moving to the same set leaves it unchanged.
+              if src == dst {
+                  return Ok(s.contains(member));
+              }
+              s.remove(member)
+          }
+          _ => false,
+      };
+
+      if !removed {
+          return Ok(false);
+      }
+
+      if data.get(src).is_some_and(|entry| matches!(&entry.value, Value::Set(s) if s.is_empty())) {
+          data.remove(src);
+      }
+
+      let entry = data.get_or_insert_with(dst.to_string(), || Value::Set(ScanSet::new()));
+      if let Value::Set(s) = &mut entry.value {
+          s.insert(member.to_string());
+      }
+
+      Ok(true)
+  }
+
+  /// Remove random members from the set stored at key. The key is deleted
+  /// once the set is empty.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which set is stored.
+  ///
+  /// * `count` - The maximum number of members to be removed.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<String>)` - The removed members. Empty if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-set data.
+  pub fn spop(&self, k: &str, count: usize) -> Result<Vec<String>, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let set = match data.get_mut(k).map(|entry| &mut entry.value) {
+          Some(Value::Set(s)) => s,
+          Some(_) => return Err(DBError::WrongType),
+          None => return Ok(vec![]),
+      };
+
+      let popped: Vec<String> = Self::random_sample(set.iter().cloned(), count as i64);
+      for member in popped.iter() {
+          set.remove(member);
+      }
+      if set.is_empty() {
+          data.remove(k);
+      }
+
+      Ok(popped)
+  }
+
+  /// Returns random members of the set stored at key.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which set is stored.
+  ///
+  /// * `count` - If positive, up to `count` distinct members are returned. If negative,
+  ///   exactly `-count` members are returned, and the same member may be returned more than once.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<String>)` - The random members. Empty if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-set data.
+  pub fn srandmember(&self, k: &str, count: i64) -> Result<Vec<String>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::Set(s)) => Ok(Self::random_sample(s.iter(), count)
+              .into_iter()
+              .cloned()
+              .collect()),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(vec![]),
+      }
+  }
+
+  /// Returns all the members of the set stored at key.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which set is stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<String>)` - The members of the set, in no particular order. Empty if the key
+  ///   doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-set data.
+  pub fn smembers(&self, k: &str) -> Result<Vec<String>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::Set(s)) => Ok(s.iter().cloned().collect()),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(vec![]),
+      }
+  }
+
+  /// Returns the next batch of members of the set stored at key, for the SSCAN command.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which set is stored.
+  ///
+  /// * `cursor` - The cursor returned by the previous call, or `0` to start a new iteration.
+  ///
+  /// * `count` - The number of elements to return. Must not be `0`.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok((u64, Vec<String>))` - The cursor to continue the iteration with, which is `0` once
+  ///   the iteration is complete, and the batch. Empty if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-set data.
+  pub fn sscan(
+      &self,
+      k: &str,
+      cursor: u64,
+      count: usize,
+  ) -> Result<(u64, Vec<String>), DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::Set(s)) => {
+              let (cursor, batch) = s.scan(cursor, count);
+              Ok((cursor, batch.into_iter().map(|member| member.to_string()).collect()))
+          }
+          Some(_) => Err(DBError::WrongType),
+          None => Ok((0, vec![])),
+      }
+  }
+
+  /// Returns the members of the union of the sets stored at the given keys.
+  ///
+  /// Keys which are not found are treated as empty sets.
+  ///
+  /// # Arguments
+  ///
+  /// * `keys` - The keys on which the sets are stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<String>)` - The members of the union, in no particular order.
+  /// * `Err(DBError)` - if any of the keys has non-set data.
+  pub fn sunion(&self, keys: &[String]) -> Result<Vec<String>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let mut union: HashSet<&String> = HashSet::new();
+      for k in keys.iter() {
+          match Self::live_entry(&data, k.as_str()).map(|entry| &entry.value) {
+              Some(Value::Set(s)) => union.extend(s.iter()),
+              Some(_) => return Err(DBError::WrongType),
+              None => {}
+          }
+      }
+
+      Ok(union.into_iter().cloned().collect())
+  }
+
+  /// Returns the cardinality of the intersection of the sets stored at the given keys,
+  /// without building the intersection itself.
+  ///
+  /// If any of the keys is not found, it is treated as an empty set and hence the
+  /// cardinality is zero.
+  ///
+  /// # Arguments
+  ///
+  /// * `keys` - The keys on which the sets are stored.
+  ///
+  /// * `limit` - Stop counting once the cardinality reaches this value. `0` means no limit.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The cardinality of the intersection (capped at `limit`, if given).
+  /// * `Err(DBError)` - if any of the keys has non-set data.
+  pub fn sintercard(&self, keys: &[String], limit: usize) -> Result<usize, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let mut sets: Vec<&ScanSet> = vec![];
+      let mut has_missing_key = false;
+      for k in keys.iter() {
+          match Self::live_entry(&data, k.as_str()) {
+              Some(entry) => match &entry.value {
+                  Value::Set(s) => sets.push(s),
+                  _ => return Err(DBError::WrongType),
+              },
+              None => has_missing_key = true,
+          }
+      }
+
+      if has_missing_key {
+          return Ok(0);
+      }
+
+      // Iterate over the smallest set, so that the number of membership checks is minimal.
+      sets.sort_by_key(|s| s.len());
+      let (smallest, others) = match sets.split_first() {
+          Some(split) => split,
+          None => return Ok(0),
+      };
+
+      let mut card = 0;
+      for member in smallest.iter() {
+          if others.iter().all(|s| s.contains(member)) {
+              card += 1;
+              if card == limit {
+                  break;
+              }
+          }
+      }
+
+      Ok(card)
+  }
+
+  /// Stores the intersection of the sets stored at the given keys in `dest`.
+  ///
+  /// If any of the keys is not found, it is treated as an empty set and hence the
+  /// intersection is empty.
+  ///
+  /// # Arguments
+  ///
+  /// * `dest` - The key to store the intersection at. It's deleted if the intersection is
+  ///   empty, and overwritten otherwise.
+  ///
+  /// * `keys` - The keys on which the sets are stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The cardinality of the intersection.
+  /// * `Err(DBError)` - if any of the keys has non-set data.
+  pub fn sinterstore(&self, dest: &str, keys: &[String]) -> Result<usize, DBError> {
+      self.sstore(dest, keys, |sets| match sets.split_first() {
+          Some((first, others)) => first
+              .iter()
+              .filter(|member| others.iter().all(|s| s.contains(member)))
+              .cloned()
+              .collect(),
+          None => ScanSet::new(),
+      })
+  }
+
+  /// Stores the union of the sets stored at the given keys in `dest`.
+  ///
+  /// Keys which are not found are treated as empty sets.
+  ///
+  /// # Arguments
+  ///
+  /// * `dest` - The key to store the union at. It's deleted if the union is empty, and
+  ///   overwritten otherwise.
+  ///
+  /// * `keys` - The keys on which the sets are stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The cardinality of the union.
+  /// * `Err(DBError)` - if any of the keys has non-set data.
+  pub fn sunionstore(&self, dest: &str, keys: &[String]) -> Result<usize, DBError> {
+      self.sstore(dest, keys, |sets| {
+          sets.iter().flat_map(|s| s.iter()).cloned().collect()
+      })
+  }
+
+  /// Stores the members of the set stored at the first key which are not in any of the sets
+  /// stored at the other keys in `dest`.
+  ///
+  /// Keys which are not found are treated as empty sets.
+  ///
+  /// # Arguments
+  ///
+  /// * `dest` - The key to store the difference at. It's deleted if the difference is empty,
+  ///   and overwritten otherwise.
+  ///
+  /// * `keys` - The keys on which the sets are stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The cardinality of the difference.
+  /// * `Err(DBError)` - if any of the keys has non-set data.
+  pub fn sdiffstore(&self, dest: &str, keys: &[String]) -> Result<usize, DBError> {
+      self.sstore(dest, keys, |sets| match sets.split_first() {
+          Some((first, others)) => first
+              .iter()
+              .filter(|member| others.iter().all(|s| !s.contains(member)))
+              .cloned()
+              .collect(),
+          None => ScanSet::new(),
+      })
+  }
+
+  /// Stores the set computed by `op` from the sets stored at the given keys in `dest`.
+  ///
+  /// Keys which are not found are passed to `op` as empty sets, in the same position.
+  fn sstore(
+      &self,
+      dest: &str,
+      keys: &[String],
+      op: impl FnOnce(&[&ScanSet]) -> ScanSet,
+  ) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let empty = ScanSet::new();
+      let mut sets: Vec<&ScanSet> = vec![];
+      for k in keys.iter() {
+          match Self::live_entry(&data, k.as_str()).map(|entry| &entry.value) {
+              Some(Value::Set(s)) => sets.push(s),
+              Some(_) => return Err(DBError::WrongType),
+              None => sets.push(&empty),
+          }
+      }
+
+      let result = op(&sets);
+      let card = result.len();
+      if result.is_empty() {
+          data.remove(dest);
+      } else {
+          data.insert(dest.to_string(), Entry::new(Value::Set(result)));
+      }
+
+      Ok(card)
+  }
+
+  /// Set the given fields to their values in the hash stored at key.
+  /// If the key is not present in the DB, an empty hash is initialized
+  /// against the key before setting the fields. Fields which are given more
+  /// than once are set to the last value given.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which hash is stored.
+  ///
+  /// * `fields` - The fields to be set, along with their values.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The number of fields that were newly added to the hash. A new field
+  ///   which is given more than once is only counted once.
+  /// * `Err(DBError)` - if key already exists and has non-hash data.
+  pub fn hset(&self, k: String, fields: Vec<(String, String)>) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k.as_str());
+
+      let entry = data.get_or_insert_with(k, || Value::Hash(ScanMap::new()));
+
+      match &mut entry.value {
+          Value::Hash(h) => {
+              let mut added = 0;
+              for (field, value) in fields {
+                  if h.insert(field, value).is_none() {
+                      added += 1;
+                  }
+              }
+              Ok(added)
+          }
+          _ => Err(DBError::WrongType),
+      }
+  }
+
+  /// Returns all the fields of the hash stored at key, along with their values.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which hash is stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<(String, String)>)` - The fields and their values, in no particular order.
+  ///   Empty if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-hash data.
+  pub fn hgetall(&self, k: &str) -> Result<Vec<(String, String)>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::Hash(h)) => Ok(h
+              .iter()
+              .map(|(field, value)| (field.to_string(), value.to_string()))
+              .collect()),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(vec![]),
+      }
+  }
+
+  /// Returns the length in bytes of the value of a field in the hash stored at key.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which hash is stored.
+  ///
+  /// * `field` - The field whose value is measured.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The length of the value, or `0` if the field or the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-hash data.
+  pub fn hstrlen(&self, k: &str, field: &str) -> Result<usize, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::Hash(h)) => Ok(h.get(field).map_or(0, |value| value.len())),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(0),
+      }
+  }
+
+  /// Returns the next batch of fields of the hash stored at key, along with their values,
+  /// for the HSCAN command.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which hash is stored.
+  ///
+  /// * `cursor` - The cursor returned by the previous call, or `0` to start a new iteration.
+  ///
+  /// * `count` - The number of elements to return. Must not be `0`.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok((u64, Vec<(String, String)>))` - The cursor to continue the iteration with, which
+  ///   is `0` once the iteration is complete, and the batch. Empty if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-hash data.
+  pub fn hscan(
+      &self,
+      k: &str,
+      cursor: u64,
+      count: usize,
+  ) -> Result<(u64, Vec<(String, String)>), DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::Hash(h)) => {
+              let (cursor, batch) = h.scan(cursor, count);
+              let batch = batch
+                  .into_iter()
+                  .map(|(field, value)| (field.to_string(), value.to_string()))
+                  .collect();
+              Ok((cursor, batch))
+          }
+          Some(_) => Err(DBError::WrongType),
+          None => Ok((0, vec![])),
+      }
+  }
+
+  /// Add the given members with their scores to the sorted set stored at key, if the given
+  /// conditions hold. If the key is not present in the DB, an empty sorted set is initialized
+  /// against the key before adding the members. The scores of the members which already exist
+  /// are updated.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which sorted set is stored.
+  ///
+  /// * `members` - The members to be added, along with their scores.
+  ///
+  /// * `conditions` - The conditions which must all hold for a member to be added or updated.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok((usize, usize))` - The number of members that were newly added to the sorted set,
+  ///   and the number of existing members whose scores were changed.
+  /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+  pub fn zadd(
+      &self,
+      k: &str,
+      members: Vec<(String, f64)>,
+      conditions: &[ZAddCondition],
+  ) -> Result<(usize, usize), DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let entry = data.get_or_insert_with(k.to_string(), || Value::SortedSet(SortedSet::new()));
+
+      let zset = match &mut entry.value {
+          Value::SortedSet(zset) => zset,
+          _ => return Err(DBError::WrongType),
+      };
+
+      let mut added = 0;
+      let mut updated = 0;
+      for (member, score) in members {
+          let current = zset.score(&member);
+          if !conditions.iter().all(|c| c.holds(current, score)) {
+              continue;
+          }
+
+          match current {
+              None => added += 1,
+              Some(current) if current != score => updated += 1,
+              Some(_) => continue,
+          }
+          zset.insert(member, score);
+      }
+
+      // With XX, a missing key is never created.
+      if zset.len() == 0 {
+          data.remove(k);
+      }
+
+      Ok((added, updated))
+  }
+
+  /// Increment the score of a member of the sorted set stored at key, if the given conditions
+  /// hold. A member which doesn't exist is added with the increment as its score, and a key
+  /// which doesn't exist is initialized with an empty sorted set.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which sorted set is stored.
+  ///
+  /// * `member` - The member whose score is to be incremented.
+  ///
+  /// * `increment` - The amount to increment the score by.
+  ///
+  /// * `conditions` - The conditions which must all hold for the score to be incremented.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Some(f64))` - The new score of the member.
+  /// * `Ok(None)` - If any of the conditions doesn't hold.
+  /// * `Err(DBError)` - if key already exists and has non-sorted set data, or the new score
+  ///   is not a number.
+  pub fn zincrby(
+      &self,
+      k: &str,
+      member: &str,
+      increment: f64,
+      conditions: &[ZAddCondition],
+  ) -> Result<Option<f64>, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let current = match data.get(k).map(|entry| &entry.value) {
+          Some(Value::SortedSet(zset)) => zset.score(member),
+          Some(_) => return Err(DBError::WrongType),
+          None => None,
+      };
+
+      let score = current.unwrap_or(0.0) + increment;
+      if score.is_nan() {
+          return Err(DBError::Other(String::from(
+              "resulting score is not a number (NaN)",
+          )));
+      }
+      if !conditions.iter().all(|c| c.holds(current, score)) {
+          return Ok(None);
+      }
+
+      let entry = data.get_or_insert_with(k.to_string(), || Value::SortedSet(SortedSet::new()));
+      if let Value::SortedSet(zset) = &mut entry.value {
+          zset.insert(member.to_string(), score);
+      }
+
+      Ok(Some(score))
+  }
+
+  /// Remove the given members from the sorted set stored at key. The key is deleted once the
+  /// sorted set is empty.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which sorted set is stored.
+  ///
+  /// * `members` - The members to be removed.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The number of members that were removed from the sorted set.
+  /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+  pub fn zrem(&self, k: &str, members: &[String]) -> Result<usize, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let zset = match data.get_mut(k).map(|entry| &mut entry.value) {
+          Some(Value::SortedSet(zset)) => zset,
+          Some(_) => return Err(DBError::WrongType),
+          None => return Ok(0),
+      };
+
+      let removed = members.iter().filter(|member| zset.remove(member)).count();
+      if zset.len() == 0 {
+          data.remove(k);
+      }
+
+      Ok(removed)
+  }
+
+  /// Remove the members with the lowest scores from the sorted set stored at key, and return
+  /// them. The key is deleted once the sorted set is empty.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which sorted set is stored.
+  ///
+  /// * `count` - The maximum number of members to be removed.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<(String, f64)>)` - The removed members and their scores, lowest score first.
+  ///   Empty if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+  pub fn zpopmin(&self, k: &str, count: usize) -> Result<Vec<(String, f64)>, DBError> {
+      self.zpop(k, count, SortedSet::pop_min)
+  }
+
+  /// Remove the members with the highest scores from the sorted set stored at key, and return
+  /// them. The key is deleted once the sorted set is empty.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which sorted set is stored.
+  ///
+  /// * `count` - The maximum number of members to be removed.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<(String, f64)>)` - The removed members and their scores, highest score first.
+  ///   Empty if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+  pub fn zpopmax(&self, k: &str, count: usize) -> Result<Vec<(String, f64)>, DBError> {
+      self.zpop(k, count, SortedSet::pop_max)
+  }
+
+  /// Remove up to `count` members from the sorted set stored at key, one at a time with
+  /// the given pop function.
+  fn zpop(
+      &self,
+      k: &str,
+      count: usize,
+      pop: fn(&mut SortedSet) -> Option<(String, f64)>,
+  ) -> Result<Vec<(String, f64)>, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let zset = match data.get_mut(k).map(|entry| &mut entry.value) {
+          Some(Value::SortedSet(zset)) => zset,
+          Some(_) => return Err(DBError::WrongType),
+          None => return Ok(vec![]),
+      };
+
+      let popped: Vec<(String, f64)> = (0..count).map_while(|_| pop(zset)).collect();
+      if zset.len() == 0 {
+          data.remove(k);
+      }
+
+      Ok(popped)
+  }
+
+  /// Returns the number of members of the sorted set stored at key.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which sorted set is stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The number of members, or `0` if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+  pub fn zcard(&self, k: &str) -> Result<usize, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::SortedSet(zset)) => Ok(zset.len()),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(0),
+      }
+  }
+
+  /// Returns the number of members of the sorted set stored at key whose scores are within
+  /// the given range.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which sorted set is stored.
+  ///
+  /// * `range` - The range of scores to count the members in.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The number of members in the range, or `0` if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+  pub fn zcount(&self, k: &str, range: &ScoreRange) -> Result<usize, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::SortedSet(zset)) => Ok(zset.count(range)),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(0),
+      }
+  }
+
+  /// Returns all the members of the sorted set stored at key, along with their scores.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which sorted set is stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<(String, f64)>)` - The members and their scores, ordered by score. Empty if
+  ///   the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+  pub fn zmembers(&self, k: &str) -> Result<Vec<(String, f64)>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::SortedSet(zset)) => Ok(zset
+              .iter()
+              .map(|(member, score)| (member.to_string(), score))
+              .collect()),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(vec![]),
+      }
+  }
+
+  /// Returns the next batch of members of the sorted set stored at key, along with their
+  /// scores, for the ZSCAN command.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which sorted set is stored.
+  ///
+  /// * `cursor` - The cursor returned by the previous call, or `0` to start a new iteration.
+  ///
+  /// * `count` - The number of elements to return. Must not be `0`.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok((u64, Vec<(String, f64)>))` - The cursor to continue the iteration with, which
+  ///   is `0` once the iteration is complete, and the batch. Empty if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-sorted set data.
+  pub fn zscan(
+      &self,
+      k: &str,
+      cursor: u64,
+      count: usize,
+  ) -> Result<(u64, Vec<(String, f64)>), DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::SortedSet(zset)) => {
+              let (cursor, batch) = zset.scan(cursor, count);
+              let batch = batch
+                  .into_iter()
+                  .map(|(member, score)| (member.to_string(), score))
+                  .collect();
+              Ok((cursor, batch))
+          }
+          Some(_) => Err(DBError::WrongType),
+          None => Ok((0, vec![])),
+      }
+  }
+
+  /// Append an entry to the stream stored at key. If the key doesn't exist, it's initialized
+  /// with an empty stream.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which stream is stored.
+  ///
+  /// * `id` - The ID of the entry. If `None`, an ID is generated from the current time, which
+  ///   is greater than the ID of the last entry even if it was added in the same millisecond.
+  ///
+  /// * `fields` - The field-value pairs of the entry.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(StreamId)` - The ID of the entry added.
+  /// * `Err(DBError)` - if key already exists and has non-stream data, or the given ID isn't
+  ///   greater than the ID of the last entry.
+  pub fn xadd(
+      &self,
+      k: &str,
+      id: Option<StreamId>,
+      fields: Vec<(String, String)>,
+  ) -> Result<StreamId, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let last_id = match data.get(k).map(|entry| &entry.value) {
+          Some(Value::Stream(stream)) => stream.last_id(),
+          Some(_) => return Err(DBError::WrongType),
+          None => StreamId::MIN,
+      };
+
+      let id = match id {
+          Some(id) if id == StreamId::MIN => {
+              return Err(DBError::Other(String::from(
+                  "The ID specified in XADD must be greater than 0-0",
+              )));
+          }
+          Some(id) if id <= last_id => {
+              return Err(DBError::Other(String::from(
+                  "The ID specified in XADD is equal or smaller than the target stream top item",
+              )));
+          }
+          Some(id) => id,
+          None => {
+              let now = current_time_millis();
+              let next = match data.get(k).map(|entry| &entry.value) {
+                  Some(Value::Stream(stream)) => stream.next_id(now),
+                  _ => Some(StreamId { ms: now, seq: 0 }),
+              };
+              match next {
+                  Some(id) if id > StreamId::MIN => id,
+                  _ => {
+                      return Err(DBError::Other(String::from(
+                          "The stream has exhausted the last possible ID, unable to add more items",
+                      )));
+                  }
+              }
+          }
+      };
+
+      let entry = data.get_or_insert_with(k.to_string(), || Value::Stream(Stream::new()));
+      if let Value::Stream(stream) = &mut entry.value {
+          stream.insert(id, fields);
+      }
+
+      Ok(id)
+  }
+
+  /// Returns the number of entries of the stream stored at key.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which stream is stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The number of entries, or `0` if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-stream data.
+  pub fn xlen(&self, k: &str) -> Result<usize, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::Stream(stream)) => Ok(stream.len()),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(0),
+      }
+  }
+
+  /// Returns the entries of the stream stored at key whose IDs are within the given range.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which stream is stored.
+  ///
+  /// * `start` - The smallest ID of the range, included.
+  ///
+  /// * `end` - The greatest ID of the range, included.
+  ///
+  /// * `count` - The maximum number of entries to return, or `None` for all of them.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<StreamEntry>)` - The entries in ascending order of ID. Empty if the key
+  ///   doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-stream data.
+  pub fn xrange(
+      &self,
+      k: &str,
+      start: StreamId,
+      end: StreamId,
+      count: Option<usize>,
+  ) -> Result<Vec<StreamEntry>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::Stream(stream)) => Ok(stream
+              .range(start, end)
+              .take(count.unwrap_or(usize::MAX))
+              .map(|(id, fields)| (*id, fields.clone()))
+              .collect()),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(vec![]),
+      }
+  }
+
+  /// Returns the entries added to each of the given streams after the given IDs.
+  ///
+  /// # Arguments
+  ///
+  /// * `streams` - The keys on which streams are stored, each with the ID after which
+  ///   entries are returned.
+  ///
+  /// * `count` - The maximum number of entries to return per stream, or `None` for all
+  ///   of them.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<(String, Vec<StreamEntry>)>)` - The entries of each stream, in ascending
+  ///   order of ID. The streams which don't exist or have no such entries are left out.
+  /// * `Err(DBError)` - if any of the keys exists and has non-stream data.
+  pub fn xread(
+      &self,
+      streams: &[(String, StreamId)],
+      count: Option<usize>,
+  ) -> Result<Vec<(String, Vec<StreamEntry>)>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let mut res = vec![];
+      for (k, after) in streams.iter() {
+          let entries: Vec<StreamEntry> = match Self::live_entry(&data, k).map(|e| &e.value) {
+              Some(Value::Stream(stream)) => stream
+                  .after(*after)
+                  .take(count.unwrap_or(usize::MAX))
+                  .map(|(id, fields)| (*id, fields.clone()))
+                  .collect(),
+              Some(_) => return Err(DBError::WrongType),
+              None => continue,
+          };
+
+          if !entries.is_empty() {
+              res.push((k.to_string(), entries));
+          }
+      }
+
+      Ok(res)
+  }
+
+  /// Returns random fields of the hash stored at key, along with their values.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which hash is stored.
+  ///
+  /// * `count` - If positive, up to `count` distinct fields are returned. If negative,
+  ///   exactly `-count` fields are returned, and the same field may be returned more than once.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<(String, String)>)` - The random fields and their values. Empty if the key doesn't exist.
+  /// * `Err(DBError)` - if key already exists and has non-hash data.
+  pub fn hrandfield(&self, k: &str, count: i64) -> Result<Vec<(String, String)>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      match Self::live_entry(&data, k).map(|entry| &entry.value) {
+          Some(Value::Hash(h)) => Ok(Self::random_sample(h.iter(), count)
+              .into_iter()
+              .map(|(field, value)| (field.to_string(), value.to_string()))
+              .collect()),
+          Some(_) => Err(DBError::WrongType),
+          None => Ok(vec![]),
+      }
+  }
+
+  /// Set the expiry time of a key, if the given condition holds.
+  /// An expiry time which is already in the past deletes the key.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key whose expiry is to be set.
+  ///
+  /// * `expires_at` - The expiry time as a Unix timestamp in milliseconds.
+  ///
+  /// * `conditions` - The conditions which must all hold for the expiry to be set.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(true)` - If the expiry was set (or the key was deleted).
+  /// * `Ok(false)` - If the key doesn't exist, or any of the conditions doesn't hold.
+  pub fn expire(
+      &self,
+      k: &str,
+      expires_at: u64,
+      conditions: &[ExpireCondition],
+  ) -> Result<bool, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      let entry = match data.get_mut(k) {
+          Some(entry) => entry,
+          None => return Ok(false),
+      };
+
+      let should_set = conditions
+          .iter()
+          .all(|condition| match (condition, entry.expires_at) {
+              (ExpireCondition::Nx, current) => current.is_none(),
+              (ExpireCondition::Xx, current) => current.is_some(),
+              (ExpireCondition::Gt, Some(current)) => expires_at > current,
+              (ExpireCondition::Gt, None) => false,
+              (ExpireCondition::Lt, Some(current)) => expires_at < current,
+              (ExpireCondition::Lt, None) => true,
+          });
+
+      if !should_set {
+          return Ok(false);
+      }
+
+      if expires_at <= current_time_millis() {
+          data.remove(k);
+      } else {
+          data.set_expiry(k, Some(expires_at));
+      }
+
+      Ok(true)
+  }
+
+  /// Remove the expiry time of a key, so that it never expires.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key whose expiry is to be removed.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(true)` - If the expiry was removed.
+  /// * `Ok(false)` - If the key doesn't exist, or has no expiry.
+  pub fn persist(&self, k: &str) -> Result<bool, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, k);
+
+      Ok(data.set_expiry(k, None).is_some())
+  }
+
+  /// Get the expiry time of a key.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key whose expiry is looked up.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(None)` - If the key doesn't exist.
+  /// * `Ok(Some(None))` - If the key exists, but has no expiry.
+  /// * `Ok(Some(Some(u64)))` - The expiry time of the key as a Unix timestamp in milliseconds.
+  pub fn expiry(&self, k: &str) -> Result<Option<Option<u64>>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Ok(Self::live_entry(&data, k).map(|entry| entry.expires_at))
+  }
+
+  /// Get the remaining time to live of a key.
+  ///
+  /// The liveness of the key and the remaining time are computed from the same reading of
+  /// the clock. So a key is never reported as missing while it still has time to live.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key whose time to live is looked up.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(None)` - If the key doesn't exist.
+  /// * `Ok(Some(None))` - If the key exists, but has no expiry.
+  /// * `Ok(Some(Some(u64)))` - The remaining time to live of the key in milliseconds. It's always
+  ///   greater than zero, since the key expires once its expiry time is reached.
+  pub fn ttl(&self, k: &str) -> Result<Option<Option<u64>>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let now = current_time_millis();
+      let entry = match data.get(k) {
+          Some(entry) if !entry.is_expired_at(now) => entry,
+          _ => return Ok(None),
+      };
+
+      Ok(Some(entry.expires_at.map(|expires_at| expires_at - now)))
+  }
+
+  /// Removes the given keys from the DB, whatever the type of their values. Keys which
+  /// don't exist are ignored.
+  ///
+  /// The removed values are handed back instead of being dropped while holding the lock, as
+  /// freeing a large collection can take a while.
+  ///
+  /// # Arguments
+  ///
+  /// * `keys` - The keys to be removed.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Vec<Value>)` - The values of the keys which were removed, one per key.
+  pub fn remove(&self, keys: &[String]) -> Result<Vec<Value>, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let mut removed = vec![];
+      for k in keys.iter() {
+          Self::remove_if_expired(&mut data, k);
+          if let Some(entry) = data.remove(k) {
+              removed.push(entry.value);
           }
       }
+
+      Ok(removed)
   }
 
-  /// Returns the specified number of elements of the list stored at key, based on the start and stop indices.
-  /// These offsets can also be negative numbers indicating offsets starting at the end of the list.
-  /// For example, -1 is the last element of the list, -2 the penultimate, and so on.
-  /// Please note that the item at stop index is also included in the result.
+  /// Copies the value stored at `src` to `dst`, along with its expiry time.
   ///
-  /// If the specified key is not found, an empty list is returned.
+  /// The destination gets its own copy of the value and of the expiry, so later changes
+  /// to either key, including to its expiry, don't affect the other one.
   ///
   /// # Arguments
   ///
-  /// * `k` - The key on which list is stored.
+  /// * `src` - The key whose value is copied.
   ///
-  /// * `start_idx` - The start index.
+  /// * `dst` - The key the value is copied to.
   ///
-  /// * `stop_idx` - The end index.
+  /// * `replace` - Whether an existing value at `dst` is replaced.
   ///
   /// # Returns
   ///
-  /// * `Ok(Vec<String>)` - If values are retrieved successfully from the list.
-  /// * `Err(DBError)` - if key already exists and has non-list data.
-  pub fn lrange(&self, k: String, start_idx: i64, stop_idx: i64) -> Result<Vec<String>, DBError> {
+  /// * `Ok(bool)` - `true` if the value was copied, `false` if `src` doesn't exist or `dst`
+  ///   already exists and `replace` isn't set.
+  pub fn copy(&self, src: &str, dst: &str, replace: bool) -> Result<bool, DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Self::remove_if_expired(&mut data, src);
+      Self::remove_if_expired(&mut data, dst);
+
+      if data.contains_key(dst) && !replace {
+          return Ok(false);
+      }
+
+      let (value, expires_at) = match data.get(src) {
+          Some(entry) => (entry.value.clone(), entry.expires_at),
+          None => return Ok(false),
+      };
+
+      let mut entry = Entry::new(value);
+      entry.expires_at = expires_at;
+      data.insert(dst.to_string(), entry);
+
+      Ok(true)
+  }
+
+  /// Updates the last access time of the given keys. Keys which don't exist are ignored.
+  ///
+  /// # Arguments
+  ///
+  /// * `keys` - The keys which were accessed.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(usize)` - The number of keys which were touched.
+  pub fn touch(&self, keys: &[String]) -> Result<usize, DBError> {
       let data = match self.data.read() {
           Ok(data) => data,
           Err(e) => return Err(DBError::Other(format!("{}", e))),
       };
 
-      let entry = match data.get(k.as_str()) {
-          Some(entry) => entry,
-          None => return Ok(vec![]),
+      let mut touched = 0;
+      for k in keys.iter() {
+          if let Some(entry) = Self::live_entry(&data, k) {
+              entry.touch();
+              touched += 1;
+          }
+      }
+
+      Ok(touched)
+  }
+
+  /// Returns the number of seconds since the key was last accessed.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Some(u64))` - The idle time of the key in seconds.
+  /// * `Ok(None)` - If the key doesn't exist.
+  pub fn idle_time(&self, k: &str) -> Result<Option<u64>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
       };
 
-      match &entry.value {
-          Value::List(l) => {
-              let l_len = l.len() as i64;
-              let (rounded_start_idx, rounded_stop_idx) =
-                  Self::round_list_indices(l_len, start_idx, stop_idx);
-              Ok(l.range(rounded_start_idx..rounded_stop_idx)
-                  .cloned()
-                  .collect())
+      Ok(Self::live_entry(&data, k).map(|entry| {
+          let last_access = entry.last_access.load(Ordering::Relaxed);
+          current_time_millis().saturating_sub(last_access) / 1000
+      }))
+  }
+
+  /// Returns the name of the encoding Redis would use for the value of the key.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Some(&str))` - The name of the encoding, for e.g. `int` or `embstr` for strings.
+  /// * `Ok(None)` - If the key doesn't exist.
+  pub fn encoding(&self, k: &str) -> Result<Option<&'static str>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let max_intset_entries = self.set_max_intset_entries.load(Ordering::Relaxed);
+      Ok(Self::live_entry(&data, k).map(|entry| entry.encoding(max_intset_entries)))
+  }
+
+  /// Returns the details on how the value of the key is stored.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Some(ObjectInfo))` - The details of the value.
+  /// * `Ok(None)` - If the key doesn't exist.
+  pub fn object_info(&self, k: &str) -> Result<Option<ObjectInfo>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let max_intset_entries = self.set_max_intset_entries.load(Ordering::Relaxed);
+      Ok(Self::live_entry(&data, k).map(|entry| {
+          let last_access = entry.last_access.load(Ordering::Relaxed);
+          ObjectInfo {
+              encoding: entry.encoding(max_intset_entries),
+              serialized_length: entry.value.serialized_length(),
+              len: entry.value.len(),
+              idle_seconds: current_time_millis().saturating_sub(last_access) / 1000,
           }
-          _ => Err(DBError::WrongType),
+      }))
+  }
+
+  /// Returns an estimate of the number of bytes used to store the key and its value.
+  ///
+  /// # Arguments
+  ///
+  /// * `k` - The key on which lookup is performed.
+  ///
+  /// * `samples` - The number of elements sampled to estimate the size of a collection.
+  ///   If it's `0`, all the elements are counted.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Some(usize))` - The estimated number of bytes.
+  /// * `Ok(None)` - If the key doesn't exist.
+  pub fn memory_usage(&self, k: &str, samples: usize) -> Result<Option<usize>, DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      Ok(Self::live_entry(&data, k)
+          .map(|entry| ENTRY_OVERHEAD + k.len() + entry.value.memory_usage(samples)))
+  }
+
+  /// Evicts keys until the estimated size of the dataset drops by the given number of bytes,
+  /// following the volatile-ttl policy: the keys with an expiry are evicted, the ones which
+  /// expire the soonest first. Keys without an expiry are never evicted.
+  ///
+  /// # Arguments
+  ///
+  /// * `bytes` - The number of bytes to free.
+  ///
+  /// * `samples` - The number of elements sampled to estimate the size of each collection.
+  ///   If it's `0`, all the elements are counted.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok((Vec<String>, usize))` - The evicted keys, and the estimated number of bytes freed.
+  ///   It's less than `bytes` if there weren't enough keys with an expiry.
+  pub fn evict_volatile_ttl(
+      &self,
+      bytes: usize,
+      samples: usize,
+  ) -> Result<(Vec<String>, usize), DBError> {
+      let mut data = match self.data.write() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let mut evicted = vec![];
+      let mut freed = 0;
+      while freed < bytes {
+          let candidate = data
+              .iter()
+              .filter_map(|(k, entry)| entry.expires_at.map(|expires_at| (expires_at, k)))
+              .min();
+          let k = match candidate {
+              Some((_, k)) => k.clone(),
+              None => break,
+          };
+
+          if let Some(entry) = data.remove(&k) {
+              // Expired keys weren't counted in the size of the dataset in the first place.
+              if !entry.is_expired() {
+                  freed += ENTRY_OVERHEAD + k.len() + entry.value.memory_usage(samples);
+              }
+              evicted.push(k);
+          }
+      }
+
+      Ok((evicted, freed))
+  }
+
+  /// Returns the number of keys in the DB and an estimate of the number of bytes used to
+  /// store them, not including the expired keys.
+  ///
+  /// # Arguments
+  ///
+  /// * `samples` - The number of elements sampled to estimate the size of each collection.
+  ///   If it's `0`, all the elements are counted.
+  pub fn dataset_usage(&self, samples: usize) -> Result<(usize, usize), DBError> {
+      let data = match self.data.read() {
+          Ok(data) => data,
+          Err(e) => return Err(DBError::Other(format!("{}", e))),
+      };
+
+      let mut keys = 0;
+      let mut bytes = 0;
+      for (k, entry) in data.iter().filter(|(_, entry)| !entry.is_expired()) {
+          keys += 1;
+          bytes += ENTRY_OVERHEAD + k.len() + entry.value.memory_usage(samples);
+      }
+
+      Ok((keys, bytes))
+  }
+
+  /// Returns the entry stored against the key, unless the key has expired.
+  fn live_entry<'a>(data: &'a Keyspace, k: &str) -> Option<&'a Entry> {
+      data.get(k).filter(|entry| !entry.is_expired())
+  }
+
+  /// Removes the key from the DB if it has expired, so that write operations treat it as missing.
+  fn remove_if_expired(data: &mut Keyspace, k: &str) {
+      if data.get(k).is_some_and(|entry| entry.is_expired()) {
+          data.remove(k);
+      }
+  }
+
+  /// Picks random items from the given items.
+  ///
+  /// If `count` is positive, up to `count` distinct items are picked. If it's negative,
+  /// exactly `-count` items are picked, and the same item may be picked more than once.
+  fn random_sample<T: Clone>(items: impl Iterator<Item = T>, count: i64) -> Vec<T> {
+      let mut rng = rand::thread_rng();
+
+      if count >= 0 {
+          return items.choose_multiple(&mut rng, count as usize);
+      }
+
+      let items: Vec<T> = items.collect();
+      if items.is_empty() {
+          return vec![];
       }
+
+      (0..count.unsigned_abs())
+          .filter_map(|_| items.choose(&mut rng).cloned())
+          .collect()
   }
 
   /// Round index to 0, if the given index value is less than zero.
@@ -278,7 +2898,7 @@ impl DB {
           return (list_len - 1) as usize;
       }
 
-      return idx as usize;
+      idx as usize
   }
 
   /// Round the start and stop indices using `Self::round_list_index` method and return them as
@@ -304,6 +2924,457 @@ impl DB {
 
 impl Entry {
   pub fn new(value: Value) -> Entry {
-      Entry { value }
+      Entry {
+          value,
+          expires_at: None,
+          last_access: AtomicU64::new(current_time_millis()),
+          modified_in_place: false,
+      }
+  }
+
+  /// Returns the name of the encoding Redis would use for the value of the entry.
+  /// This is the only place where the encoding is decided, so that it's consistent
+  /// whichever command wrote the value.
+  ///
+  /// Sets of up to `max_intset_entries` integers are reported in the `intset` encoding.
+  fn encoding(&self, max_intset_entries: usize) -> &'static str {
+      match self.value {
+          Value::String(_) if self.modified_in_place => "raw",
+          _ => self.value.encoding(max_intset_entries),
+      }
+  }
+
+  /// Updates the last access time of the entry to the current time.
+  fn touch(&self) {
+      self.last_access.store(current_time_millis(), Ordering::Relaxed);
+  }
+
+  /// Checks if the expiry time of the entry has passed.
+  fn is_expired(&self) -> bool {
+      self.is_expired_at(current_time_millis())
+  }
+
+  /// Checks if the entry is expired at the given time, a Unix timestamp in milliseconds.
+  /// An entry expires as soon as its expiry time is reached, and this is the only place
+  /// where that boundary is decided.
+  fn is_expired_at(&self, now: u64) -> bool {
+      self.expires_at.is_some_and(|expires_at| now >= expires_at)
+  }
+}
+
+impl std::ops::Deref for Keyspace {
+  type Target = HashMap<String, Entry>;
+
+  fn deref(&self) -> &Self::Target {
+      &self.entries
+  }
+}
+
+impl Keyspace {
+  /// Returns the entry stored against the key, for modifying its value. The expiry time
+  /// must be changed with `set_expiry` instead, so that the index is updated.
+  fn get_mut(&mut self, k: &str) -> Option<&mut Entry> {
+      self.entries.get_mut(k)
+  }
+
+  /// Returns the entry stored against the key, after storing a new entry without an expiry
+  /// holding the value returned by `value` if the key doesn't exist.
+  fn get_or_insert_with(&mut self, k: String, value: impl FnOnce() -> Value) -> &mut Entry {
+      self.entries.entry(k).or_insert_with(|| Entry::new(value()))
+  }
+
+  /// Stores the entry against the key, replacing the previous entry, if any.
+  fn insert(&mut self, k: String, entry: Entry) -> Option<Entry> {
+      let expires_at = entry.expires_at;
+      let previous = self.entries.insert(k.clone(), entry);
+      if let Some(previous) = previous.as_ref().and_then(|entry| entry.expires_at) {
+          self.expiries.remove(&(previous, k.clone()));
+      }
+      if let Some(expires_at) = expires_at {
+          self.expiries.insert((expires_at, k));
+      }
+      previous
+  }
+
+  /// Removes the key, returning its entry.
+  fn remove(&mut self, k: &str) -> Option<Entry> {
+      let entry = self.entries.remove(k)?;
+      if let Some(expires_at) = entry.expires_at {
+          self.expiries.remove(&(expires_at, k.to_string()));
+      }
+      Some(entry)
+  }
+
+  /// Sets the expiry time of the key, or removes it if `expires_at` is `None`. Does nothing
+  /// if the key doesn't exist.
+  ///
+  /// # Returns
+  ///
+  /// The previous expiry time of the key, if it had one.
+  fn set_expiry(&mut self, k: &str, expires_at: Option<u64>) -> Option<u64> {
+      let entry = self.entries.get_mut(k)?;
+      let previous = std::mem::replace(&mut entry.expires_at, expires_at);
+      if let Some(previous) = previous {
+          self.expiries.remove(&(previous, k.to_string()));
+      }
+      if let Some(expires_at) = expires_at {
+          self.expiries.insert((expires_at, k.to_string()));
+      }
+      previous
+  }
+
+  /// Removes up to `limit` of the keys which have expired, in the order of their expiry
+  /// times. Only the removed keys are visited.
+  ///
+  /// # Returns
+  ///
+  /// The number of keys removed.
+  fn remove_expired(&mut self, limit: usize) -> usize {
+      let now = current_time_millis();
+      let mut removed = 0;
+      while removed < limit
+          && self.expiries.first().is_some_and(|(expires_at, _)| *expires_at <= now)
+      {
+          if let Some((_, k)) = self.expiries.pop_first() {
+              self.entries.remove(&k);
+              removed += 1;
+          }
+      }
+      removed
+  }
+
+  /// Removes all the keys, returning them along with their entries.
+  fn take(&mut self) -> HashMap<String, Entry> {
+      self.expiries.clear();
+      std::mem::take(&mut self.entries)
+  }
+}
+
+impl Value {
+  /// Returns an estimate of the number of bytes used to store the value.
+  ///
+  /// For collections, the size of the first `samples` elements is extrapolated to the
+  /// whole collection. If `samples` is `0`, all the elements are counted.
+  fn memory_usage(&self, samples: usize) -> usize {
+      match self {
+          Value::String(s) => s.capacity(),
+          Value::List(list) => {
+              let sizes = list.iter().map(|element| element.capacity());
+              Self::collection_memory_usage(sizes, list.len(), samples)
+          }
+          Value::Set(set) => {
+              let sizes = set.iter().map(|member| member.capacity());
+              Self::collection_memory_usage(sizes, set.len(), samples)
+          }
+          Value::Hash(hash) => {
+              let sizes = hash.iter().map(|(field, value)| field.capacity() + value.capacity());
+              Self::collection_memory_usage(sizes, hash.len(), samples)
+          }
+          Value::SortedSet(zset) => {
+              // Each member is stored twice, once in the map and once in the ordered set.
+              let sizes = zset.iter().map(|(member, _)| 2 * member.capacity());
+              Self::collection_memory_usage(sizes, zset.len(), samples)
+          }
+          Value::Stream(stream) => {
+              // Each entry also holds its 16-byte ID.
+              let sizes = stream.iter().map(|(_, fields)| {
+                  16 + fields
+                      .iter()
+                      .map(|(field, value)| field.capacity() + value.capacity())
+                      .sum::<usize>()
+              });
+              Self::collection_memory_usage(sizes, stream.len(), samples)
+          }
+      }
+  }
+
+  /// Returns an estimate of the number of bytes used by the `len` elements of a collection,
+  /// by sampling up to `samples` of them. `sizes` yields the data size of each element.
+  fn collection_memory_usage(
+      sizes: impl Iterator<Item = usize>,
+      len: usize,
+      samples: usize,
+  ) -> usize {
+      let samples = if samples == 0 { len } else { samples.min(len) };
+      if samples == 0 {
+          return 0;
+      }
+
+      let sampled: usize = sizes.take(samples).map(|size| ELEMENT_OVERHEAD + size).sum();
+
+      sampled * len / samples
+  }
+
+  /// Returns the name of the encoding Redis would use for the value. Values are always
+  /// stored in the general purpose encoding of their type, whatever their size. Only
+  /// integer strings, and sets of up to `max_intset_entries` integers, get an encoding
+  /// of their own.
+  fn encoding(&self, max_intset_entries: usize) -> &'static str {
+      match self {
+          Value::String(s) if Self::is_integer(s) => "int",
+          // Redis embeds strings of up to 44 bytes in the object header.
+          Value::String(s) if s.len() <= 44 => "embstr",
+          Value::String(_) => "raw",
+          Value::List(_) => "quicklist",
+          // The encoding is decided by the current members. Unlike Redis, a set goes back
+          // to `intset` once its non-integer members are removed.
+          Value::Set(set)
+              if set.len() <= max_intset_entries && set.iter().all(|m| Self::is_integer(m)) =>
+          {
+              "intset"
+          }
+          Value::Set(_) | Value::Hash(_) => "hashtable",
+          Value::SortedSet(_) => "skiplist",
+          Value::Stream(_) => "stream",
+      }
+  }
+
+  /// Checks if the string is the canonical form of a 64-bit integer, which Redis stores as
+  /// that integer. So e.g. "007" or "+7" aren't integers.
+  fn is_integer(s: &str) -> bool {
+      s.len() <= 20 && s.parse::<i64>().is_ok_and(|n| n.to_string() == s)
+  }
+
+  /// Returns the number of elements in the value. Strings count as a single element.
+  fn len(&self) -> usize {
+      match self {
+          Value::String(_) => 1,
+          Value::List(list) => list.len(),
+          Value::Set(set) => set.len(),
+          Value::Hash(hash) => hash.len(),
+          Value::SortedSet(zset) => zset.len(),
+          Value::Stream(stream) => stream.len(),
+      }
+  }
+
+  /// Returns the number of bytes the value takes once serialized the way Redis dumps
+  /// values, without compression: each string is prefixed with its length, collections
+  /// are prefixed with their number of elements, and scores take 8 bytes.
+  fn serialized_length(&self) -> usize {
+      let string_len = |s: &String| Self::serialized_len_prefix(s.len()) + s.len();
+      match self {
+          Value::String(s) => string_len(s),
+          Value::List(list) => {
+              Self::serialized_len_prefix(list.len()) + list.iter().map(string_len).sum::<usize>()
+          }
+          Value::Set(set) => {
+              Self::serialized_len_prefix(set.len()) + set.iter().map(string_len).sum::<usize>()
+          }
+          Value::Hash(hash) => {
+              let fields: usize = hash
+                  .iter()
+                  .map(|(field, value)| string_len(field) + string_len(value))
+                  .sum();
+              Self::serialized_len_prefix(hash.len()) + fields
+          }
+          Value::SortedSet(zset) => {
+              let members: usize = zset.iter().map(|(member, _)| string_len(member) + 8).sum();
+              Self::serialized_len_prefix(zset.len()) + members
+          }
+          Value::Stream(stream) => {
+              let entries: usize = stream
+                  .iter()
+                  .map(|(_, fields)| {
+                      16 + Self::serialized_len_prefix(fields.len())
+                          + fields
+                              .iter()
+                              .map(|(field, value)| string_len(field) + string_len(value))
+                              .sum::<usize>()
+                  })
+                  .sum();
+              Self::serialized_len_prefix(stream.len()) + entries
+          }
+      }
+  }
+
+  /// Returns the number of bytes used to serialize a length, which grows with the length.
+  fn serialized_len_prefix(len: usize) -> usize {
+      match len {
+          0..64 => 1,
+          64..16384 => 2,
+          16384..=0xFFFF_FFFF => 5,
+          _ => 9,
+      }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Returns an entry holding a string, which expires at the given time.
+  fn entry_expiring_at(expires_at: Option<u64>) -> Entry {
+      let mut entry = Entry::new(Value::String(String::from("v")));
+      entry.expires_at = expires_at;
+      entry
+  }
+
+  fn indexed(keyspace: &Keyspace) -> Vec<(u64, &str)> {
+      keyspace.expiries.iter().map(|(expires_at, k)| (*expires_at, k.as_str())).collect()
+  }
+
+  #[test]
+  fn expiry_index_follows_the_expiry_of_the_keys() {
+      let mut keyspace = Keyspace::default();
+
+      keyspace.insert(String::from("k"), entry_expiring_at(Some(10)));
+      assert_eq!(indexed(&keyspace), vec![(10, "k")]);
+
+      keyspace.set_expiry("k", Some(20));
+      assert_eq!(indexed(&keyspace), vec![(20, "k")]);
+
+      keyspace.set_expiry("k", None);
+      assert_eq!(indexed(&keyspace), vec![]);
+
+      keyspace.set_expiry("k", Some(30));
+      keyspace.insert(String::from("k"), entry_expiring_at(None));
+      assert_eq!(indexed(&keyspace), vec![]);
+
+      keyspace.set_expiry("k", Some(40));
+      keyspace.insert(String::from("k"), entry_expiring_at(Some(40)));
+      assert_eq!(indexed(&keyspace), vec![(40, "k")]);
+
+      keyspace.remove("k");
+      assert_eq!(indexed(&keyspace), vec![]);
+
+      keyspace.set_expiry("missing", Some(50));
+      assert_eq!(indexed(&keyspace), vec![]);
+  }
+
+  #[test]
+  fn expired_keys_are_removed_up_to_the_limit() {
+      let mut keyspace = Keyspace::default();
+      keyspace.insert(String::from("a"), entry_expiring_at(Some(1)));
+      keyspace.insert(String::from("b"), entry_expiring_at(Some(2)));
+      keyspace.insert(String::from("c"), entry_expiring_at(None));
+      let later = current_time_millis() + 60_000;
+      keyspace.insert(String::from("d"), entry_expiring_at(Some(later)));
+
+      assert_eq!(keyspace.remove_expired(1), 1);
+      assert!(!keyspace.contains_key("a"));
+      assert_eq!(keyspace.remove_expired(10), 1);
+      assert_eq!(keyspace.remove_expired(10), 0);
+
+      let mut keys: Vec<&String> = keyspace.keys().collect();
+      keys.sort();
+      assert_eq!(keys, ["c", "d"]);
+      assert_eq!(indexed(&keyspace), vec![(later, "d")]);
+  }
+
+  #[test]
+  fn len_counts_the_live_keys() {
+      let db = DB::new();
+      let later = current_time_millis() + 60_000;
+      let set = |k: &str, expires_at| {
+          db.set(k.to_string(), Value::String(String::from("v")), expires_at, false, None)
+              .unwrap();
+      };
+      set("a", None);
+      set("b", Some(later));
+      set("c", Some(later));
+      assert_eq!(db.len().unwrap(), 3);
+
+      db.data.write().unwrap().remove("c");
+      assert_eq!(db.len().unwrap(), 2);
+
+      // The expiry of `b` is removed, then the key expires after being given another one.
+      db.data.write().unwrap().set_expiry("b", None);
+      db.data.write().unwrap().set_expiry("b", Some(1));
+      assert_eq!(db.len().unwrap(), 1);
+      assert_eq!(db.remove_expired(10).unwrap(), 0);
+      assert!(db.data.read().unwrap().expiries.is_empty());
+  }
+
+  #[test]
+  fn keys_expire_at_the_same_time_for_lookups_and_the_sweep() {
+      let db = DB::new();
+      let _frozen = freeze_time();
+      let now = current_time_millis();
+      let set = |k: &str, expires_at| {
+          db.set(k.to_string(), Value::String(String::from("v")), Some(expires_at), false, None)
+              .unwrap();
+      };
+      set("later", now + 1);
+      set("now", now + 2);
+      db.data.write().unwrap().set_expiry("now", Some(now));
+
+      assert_eq!(db.ttl("later").unwrap(), Some(Some(1)));
+      assert_eq!(db.ttl("now").unwrap(), None);
+
+      assert_eq!(db.remove_expired(10).unwrap(), 1);
+      assert!(db.data.read().unwrap().contains_key("later"));
+      assert!(!db.data.read().unwrap().contains_key("now"));
+      assert_eq!(db.len().unwrap(), 1);
+  }
+
+  fn strings(members: &[&str]) -> Vec<String> {
+      members.iter().map(|member| member.to_string()).collect()
+  }
+
+  #[test]
+  fn sets_of_integers_are_intsets() {
+      let db = DB::new();
+      db.sadd(String::from("s"), strings(&["1", "-2", "9223372036854775807"])).unwrap();
+      assert_eq!(db.encoding("s").unwrap(), Some("intset"));
+
+      // Not in the canonical form of an integer, or out of range.
+      for member in ["007", "+7", "9223372036854775808", "1.5"] {
+          let db = DB::new();
+          db.sadd(String::from("s"), strings(&["1", member])).unwrap();
+          assert_eq!(db.encoding("s").unwrap(), Some("hashtable"), "{}", member);
+      }
+  }
+
+  #[test]
+  fn sets_with_other_members_are_hashtables() {
+      let db = DB::new();
+      db.sadd(String::from("s"), strings(&["1", "a"])).unwrap();
+      assert_eq!(db.encoding("s").unwrap(), Some("hashtable"));
+
+      // The encoding follows the current members.
+      db.srem("s", &strings(&["a"])).unwrap();
+      assert_eq!(db.encoding("s").unwrap(), Some("intset"));
+      assert_eq!(db.encoding("missing").unwrap(), None);
+  }
+
+  #[test]
+  fn sets_over_the_intset_limit_are_hashtables() {
+      let db = DB::new();
+      db.set_set_max_intset_entries(3);
+      db.sadd(String::from("s"), strings(&["1", "2", "3"])).unwrap();
+      assert_eq!(db.encoding("s").unwrap(), Some("intset"));
+
+      db.sadd(String::from("s"), strings(&["4"])).unwrap();
+      assert_eq!(db.encoding("s").unwrap(), Some("hashtable"));
+
+      db.set_set_max_intset_entries(DEFAULT_SET_MAX_INTSET_ENTRIES);
+      assert_eq!(db.encoding("s").unwrap(), Some("intset"));
+  }
+
+  #[test]
+  fn hset_applies_repeated_fields_in_order_and_counts_them_once() {
+      let db = DB::new();
+      let fields = vec![
+          (String::from("a"), String::from("1")),
+          (String::from("a"), String::from("2")),
+      ];
+      assert_eq!(db.hset(String::from("h"), fields).unwrap(), 1);
+      assert_eq!(db.hgetall("h").unwrap(), vec![(String::from("a"), String::from("2"))]);
+
+      let fields = vec![
+          (String::from("a"), String::from("3")),
+          (String::from("b"), String::from("1")),
+          (String::from("b"), String::from("2")),
+      ];
+      assert_eq!(db.hset(String::from("h"), fields).unwrap(), 1);
+      let mut all = db.hgetall("h").unwrap();
+      all.sort();
+      assert_eq!(
+          all,
+          vec![
+              (String::from("a"), String::from("3")),
+              (String::from("b"), String::from("2")),
+          ]
+      );
   }
 }
@@ -0,0 +1,38 @@
+use std::str::FromStr;
+
+/// The number of elements sampled to estimate the size of each collection, when checking
+/// the size of the dataset against `maxmemory`.
+pub const MAXMEMORY_SAMPLES: usize = 5;
+
+/// The policy which decides how memory is freed once the dataset grows over `maxmemory`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MaxMemoryPolicy {
+    /// Nothing is evicted. The commands which could use more memory are rejected instead.
+    #[default]
+    NoEviction,
+    /// The keys with an expiry are evicted, the ones which expire the soonest first.
+    /// Keys without an expiry are never evicted.
+    VolatileTtl,
+}
+
+impl MaxMemoryPolicy {
+    /// Returns the name of the policy, as used in the config.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MaxMemoryPolicy::NoEviction => "noeviction",
+            MaxMemoryPolicy::VolatileTtl => "volatile-ttl",
+        }
+    }
+}
+
+impl FromStr for MaxMemoryPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "noeviction" => Ok(MaxMemoryPolicy::NoEviction),
+            "volatile-ttl" => Ok(MaxMemoryPolicy::VolatileTtl),
+            _ => Err(format!("unsupported maxmemory policy '{}'", s)),
+        }
+    }
+}
@@ -0,0 +1,215 @@
+/// Magic string which starts every HyperLogLog value, so that they can be told apart
+/// from plain strings.
+const MAGIC: &str = "HYLL";
+
+/// Number of bits of the hash used to pick the register.
+const P: u32 = 14;
+
+/// Number of registers.
+const M: usize = 1 << P;
+
+/// A HyperLogLog, used to estimate the number of distinct elements added to it using
+/// a fixed amount of memory. The standard error of the estimate is about 0.81%.
+///
+/// Refer <https://en.wikipedia.org/wiki/HyperLogLog>
+///
+/// It's stored in the DB as a plain string, with one byte per register following a
+/// magic header. Registers never exceed `64 - P + 1`, so the string is always valid UTF-8.
+#[derive(Debug, Clone)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    /// Create a new, empty `HyperLogLog`.
+    pub fn new() -> HyperLogLog {
+        HyperLogLog {
+            registers: vec![0; M],
+        }
+    }
+
+    /// Parse a `HyperLogLog` from the string it's stored as. Returns `None` if the string
+    /// is not a valid HyperLogLog.
+    pub fn from_string(s: &str) -> Option<HyperLogLog> {
+        let registers = s.strip_prefix(MAGIC)?.as_bytes();
+        if registers.len() != M || registers.iter().any(|r| *r as u32 > 64 - P + 1) {
+            return None;
+        }
+
+        Some(HyperLogLog {
+            registers: registers.to_vec(),
+        })
+    }
+
+    /// Convert the `HyperLogLog` into the string it's stored as.
+    pub fn into_string(self) -> String {
+        let mut bytes = MAGIC.as_bytes().to_vec();
+        bytes.extend(self.registers);
+
+        // Registers are always below 128, so they are valid single byte UTF-8 characters.
+        String::from_utf8(bytes).unwrap_or_default()
+    }
+
+    /// Add an element. Returns `true` if a register was updated, which means that the
+    /// estimated cardinality may have changed.
+    pub fn add(&mut self, element: &str) -> bool {
+        let hash = murmur_hash64a(element.as_bytes(), 0xadc83b19);
+        let index = (hash as usize) & (M - 1);
+        // Set a sentinel bit, so that the count of zeros is bounded.
+        let rest = (hash >> P) | (1 << (64 - P));
+        let rank = (rest.trailing_zeros() + 1) as u8;
+
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+            return true;
+        }
+        false
+    }
+
+    /// Merge another `HyperLogLog` into this one, so that it estimates the cardinality
+    /// of the union of both.
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        for (r, o) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *r = (*r).max(*o);
+        }
+    }
+
+    /// Returns the estimated number of distinct elements added.
+    pub fn count(&self) -> u64 {
+        let m = M as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let mut sum = 0.0;
+        let mut zeros = 0;
+        for r in self.registers.iter() {
+            sum += 2f64.powi(-(*r as i32));
+            if *r == 0 {
+                zeros += 1;
+            }
+        }
+
+        let estimate = alpha * m * m / sum;
+
+        // Use linear counting for small cardinalities, where the raw estimate is biased.
+        if estimate <= 2.5 * m && zeros > 0 {
+            return (m * (m / zeros as f64).ln()).round() as u64;
+        }
+        estimate.round() as u64
+    }
+}
+
+/// 64 bit version of MurmurHash2, as used by Redis for HyperLogLogs.
+///
+/// Refer <https://github.com/aappleby/smhasher/blob/master/src/MurmurHash2.cpp>
+fn murmur_hash64a(key: &[u8], seed: u64) -> u64 {
+    const MUL: u64 = 0xc6a4a7935bd1e995;
+    const R: u32 = 47;
+
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(MUL);
+
+    let mut chunks = key.chunks_exact(8);
+    for chunk in chunks.by_ref() {
+        let mut k = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+        k = k.wrapping_mul(MUL);
+        k ^= k >> R;
+        k = k.wrapping_mul(MUL);
+
+        h ^= k;
+        h = h.wrapping_mul(MUL);
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, b) in tail.iter().enumerate() {
+            h ^= (*b as u64) << (8 * i);
+        }
+        h = h.wrapping_mul(MUL);
+    }
+
+    h ^= h >> R;
+    h = h.wrapping_mul(MUL);
+    h ^= h >> R;
+
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the estimate is within 3% of the actual count, which is over three times
+    /// the standard error.
+    fn assert_close(estimate: u64, actual: u64) {
+        let error = (estimate as f64 - actual as f64).abs() / actual as f64;
+        assert!(error < 0.03, "estimate {} for {} elements", estimate, actual);
+    }
+
+    #[test]
+    fn count_is_exact_when_empty_and_close_for_few_elements() {
+        let mut hll = HyperLogLog::new();
+        assert_eq!(hll.count(), 0);
+
+        for i in 0..100 {
+            hll.add(&format!("element:{}", i));
+        }
+        assert_close(hll.count(), 100);
+    }
+
+    #[test]
+    fn count_is_within_tolerance_for_many_elements() {
+        let mut hll = HyperLogLog::new();
+        for i in 0..100_000 {
+            hll.add(&format!("element:{}", i));
+        }
+        assert_close(hll.count(), 100_000);
+
+        // Adding the same elements again changes nothing.
+        let count = hll.count();
+        assert!(!(0..1000).any(|i| hll.add(&format!("element:{}", i))));
+        assert_eq!(hll.count(), count);
+    }
+
+    #[test]
+    fn merge_estimates_the_union() {
+        let mut a = HyperLogLog::new();
+        let mut b = HyperLogLog::new();
+        for i in 0..20_000 {
+            a.add(&i.to_string());
+        }
+        for i in 10_000..30_000 {
+            b.add(&i.to_string());
+        }
+
+        a.merge(&b);
+        assert_close(a.count(), 30_000);
+    }
+
+    #[test]
+    fn round_trips_through_its_string() {
+        let mut hll = HyperLogLog::new();
+        for i in 0..5000 {
+            hll.add(&i.to_string());
+        }
+        let count = hll.count();
+
+        let parsed = HyperLogLog::from_string(&hll.into_string()).unwrap();
+        assert_eq!(parsed.count(), count);
+    }
+
+    #[test]
+    fn from_string_rejects_invalid_values() {
+        // Not a HyperLogLog at all.
+        assert!(HyperLogLog::from_string("hello").is_none());
+        // Missing registers.
+        assert!(HyperLogLog::from_string(MAGIC).is_none());
+        let valid = HyperLogLog::new().into_string();
+        assert!(HyperLogLog::from_string(&valid[..valid.len() - 1]).is_none());
+        // Too many registers.
+        assert!(HyperLogLog::from_string(&format!("{}\0", valid)).is_none());
+        // A register over the greatest possible rank.
+        let mut too_high = valid.clone().into_bytes();
+        too_high[MAGIC.len()] = (64 - P + 2) as u8;
+        let too_high = String::from_utf8(too_high).unwrap();
+        assert!(HyperLogLog::from_string(&too_high).is_none());
+    }
+}
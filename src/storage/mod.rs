@@ -1,4 +1,9 @@
+pub mod cursor;
 pub mod db;
+pub mod eviction;
+pub mod hyperloglog;
+pub mod sorted_set;
+pub mod stream;
 
 /// Represents errors that can occur during DB operations.
 #[derive(Debug)]
@@ -11,6 +16,8 @@ pub enum DBError {
     Other(String),
 }
 
+impl std::error::Error for DBError {}
+
 impl std::fmt::Display for DBError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -0,0 +1,139 @@
+use std::{cmp::Ordering, collections::BTreeSet};
+
+use super::cursor::ScanMap;
+
+/// A set of unique members, each associated with a score. Members are ordered by their
+/// score, and members with the same score are ordered lexicographically.
+///
+/// The scores are kept both in a map, to look them up by member and to scan the members,
+/// and in an ordered set, to iterate the members in order.
+#[derive(Debug, Clone, Default)]
+pub struct SortedSet {
+    scores: ScanMap<f64>,
+    ordered: BTreeSet<(Score, String)>,
+}
+
+/// Wrapper around a score, which orders scores totally so that they can be kept in a
+/// `BTreeSet`. Scores are never NaN.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Score(f64);
+
+/// A range of scores, as given to ZCOUNT. Each bound is either inclusive or exclusive.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreRange {
+    pub min: f64,
+    /// Set if the range is `(min`, i.e. `min` itself is out of the range.
+    pub min_exclusive: bool,
+    pub max: f64,
+    /// Set if the range is `(max`, i.e. `max` itself is out of the range.
+    pub max_exclusive: bool,
+}
+
+impl ScoreRange {
+    /// Checks if the score is not above the upper bound of the range.
+    fn below_max(&self, score: f64) -> bool {
+        if self.max_exclusive {
+            score < self.max
+        } else {
+            score <= self.max
+        }
+    }
+}
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl SortedSet {
+    /// Create a new, empty `SortedSet`.
+    pub fn new() -> SortedSet {
+        SortedSet::default()
+    }
+
+    /// Add a member with the given score, or update the score if the member already exists.
+    /// Returns `true` if the member was newly added.
+    pub fn insert(&mut self, member: String, score: f64) -> bool {
+        match self.scores.insert(member.clone(), score) {
+            Some(old) => {
+                self.ordered.remove(&(Score(old), member.clone()));
+                self.ordered.insert((Score(score), member));
+                false
+            }
+            None => {
+                self.ordered.insert((Score(score), member));
+                true
+            }
+        }
+    }
+
+    /// Returns the score of a member, or `None` if it's not a member.
+    pub fn score(&self, member: &str) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    /// Remove a member. Returns `true` if the member was present.
+    pub fn remove(&mut self, member: &str) -> bool {
+        match self.scores.remove_entry(member) {
+            Some((member, score)) => {
+                self.ordered.remove(&(Score(score), member));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the member with the lowest score, and return it along with its score.
+    pub fn pop_min(&mut self) -> Option<(String, f64)> {
+        let (score, member) = self.ordered.pop_first()?;
+        self.scores.remove(&member);
+        Some((member, score.0))
+    }
+
+    /// Remove the member with the highest score, and return it along with its score.
+    pub fn pop_max(&mut self) -> Option<(String, f64)> {
+        let (score, member) = self.ordered.pop_last()?;
+        self.scores.remove(&member);
+        Some((member, score.0))
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// Returns the number of members whose scores are within the range.
+    pub fn count(&self, range: &ScoreRange) -> usize {
+        // Scores are ordered totally, so -0 comes before 0 even though they compare equal.
+        let min = if range.min == 0.0 { -0.0 } else { range.min };
+
+        // Members with the same score are ordered by member, and the empty string comes
+        // before any other member.
+        self.ordered
+            .range((Score(min), String::new())..)
+            .skip_while(|(score, _)| range.min_exclusive && score.0 <= range.min)
+            .take_while(|(score, _)| range.below_max(score.0))
+            .count()
+    }
+
+    /// Returns an iterator over the members and their scores, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, f64)> {
+        self.ordered.iter().map(|(score, member)| (member, score.0))
+    }
+
+    /// Returns the next batch of members and their scores for the ZSCAN command.
+    /// See `ScanMap::scan`.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<(&String, f64)>) {
+        let (cursor, batch) = self.scores.scan(cursor, count);
+        (cursor, batch.into_iter().map(|(member, score)| (member, *score)).collect())
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    ops::Bound::{Excluded, Unbounded},
+    str::FromStr,
+};
+
+/// An append-only log of entries, each made of field-value pairs and identified by a
+/// unique ID. Entries are kept ordered by their ID, which only ever grows.
+#[derive(Debug, Clone, Default)]
+pub struct Stream {
+    entries: BTreeMap<StreamId, Vec<(String, String)>>,
+    /// The ID of the last entry added. New entries must have a greater ID.
+    last_id: StreamId,
+}
+
+/// An entry of a stream as returned to clients: its ID and its field-value pairs.
+pub type StreamEntry = (StreamId, Vec<(String, String)>);
+
+/// The ID of a stream entry, in the form `ms-seq`. `ms` is the Unix time in milliseconds
+/// at which the entry was added, and `seq` orders the entries added in the same millisecond.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StreamId {
+    pub ms: u64,
+    pub seq: u64,
+}
+
+impl StreamId {
+    /// The smallest ID. No entry can be added with it.
+    pub const MIN: StreamId = StreamId { ms: 0, seq: 0 };
+
+    /// The greatest ID.
+    pub const MAX: StreamId = StreamId {
+        ms: u64::MAX,
+        seq: u64::MAX,
+    };
+
+    /// Parses an ID given as a bound of a range, where `ms` alone stands for `ms-0`, or
+    /// `ms-<max seq>` if `upper` is set so that the whole millisecond is in the range.
+    pub fn parse_bound(s: &str, upper: bool) -> Result<StreamId, String> {
+        let mut id = s.parse::<StreamId>()?;
+        if upper && !s.contains('-') {
+            id.seq = u64::MAX;
+        }
+        Ok(id)
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+impl FromStr for StreamId {
+    type Err = String;
+
+    /// Parses an ID in the form `ms-seq`, or `ms` alone which stands for `ms-0`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || String::from("Invalid stream ID specified as stream command argument");
+        let (ms, seq) = match s.split_once('-') {
+            Some((ms, seq)) => (ms, Some(seq)),
+            None => (s, None),
+        };
+
+        let ms = ms.parse::<u64>().map_err(|_| invalid())?;
+        let seq = match seq {
+            Some(seq) => seq.parse::<u64>().map_err(|_| invalid())?,
+            None => 0,
+        };
+
+        Ok(StreamId { ms, seq })
+    }
+}
+
+impl Stream {
+    /// Create a new, empty `Stream`.
+    pub fn new() -> Stream {
+        Stream::default()
+    }
+
+    /// Returns the ID for an entry added at the given time. It's the time itself, unless
+    /// the last entry was added at or after it, in which case the sequence of the last ID
+    /// is incremented so that IDs keep growing. Returns `None` if the IDs are exhausted.
+    pub fn next_id(&self, now_ms: u64) -> Option<StreamId> {
+        if now_ms > self.last_id.ms {
+            return Some(StreamId { ms: now_ms, seq: 0 });
+        }
+
+        match self.last_id.seq.checked_add(1) {
+            Some(seq) => Some(StreamId {
+                ms: self.last_id.ms,
+                seq,
+            }),
+            None => self.last_id.ms.checked_add(1).map(|ms| StreamId { ms, seq: 0 }),
+        }
+    }
+
+    /// Returns the ID of the last entry added.
+    pub fn last_id(&self) -> StreamId {
+        self.last_id
+    }
+
+    /// Append an entry. The ID must be greater than the ID of the last entry, which is
+    /// left to the caller to check.
+    pub fn insert(&mut self, id: StreamId, fields: Vec<(String, String)>) {
+        self.entries.insert(id, fields);
+        self.last_id = id;
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns an iterator over the entries whose IDs are from `start` to `end`, both
+    /// included, in ascending order of ID.
+    pub fn range(
+        &self,
+        start: StreamId,
+        end: StreamId,
+    ) -> impl Iterator<Item = (&StreamId, &Vec<(String, String)>)> {
+        // `BTreeMap::range` panics on a reversed range, which is simply empty here.
+        let entries = if start <= end {
+            Some(self.entries.range(start..=end))
+        } else {
+            None
+        };
+        entries.into_iter().flatten()
+    }
+
+    /// Returns an iterator over the entries whose IDs are greater than `id`, in ascending
+    /// order of ID.
+    pub fn after(
+        &self,
+        id: StreamId,
+    ) -> impl Iterator<Item = (&StreamId, &Vec<(String, String)>)> {
+        self.entries.range((Excluded(id), Unbounded))
+    }
+
+    /// Returns an iterator over the entries, in ascending order of ID.
+    pub fn iter(&self) -> impl Iterator<Item = (&StreamId, &Vec<(String, String)>)> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(ms: u64, seq: u64) -> StreamId {
+        StreamId { ms, seq }
+    }
+
+    fn fields() -> Vec<(String, String)> {
+        vec![(String::from("f"), String::from("v"))]
+    }
+
+    #[test]
+    fn ids_keep_growing_within_the_same_millisecond() {
+        let mut stream = Stream::new();
+        assert_eq!(stream.next_id(10), Some(id(10, 0)));
+
+        stream.insert(id(10, 0), fields());
+        assert_eq!(stream.next_id(10), Some(id(10, 1)));
+        stream.insert(id(10, 1), fields());
+        assert_eq!(stream.next_id(11), Some(id(11, 0)));
+
+        // The clock went backwards, or the last ID was given explicitly.
+        assert_eq!(stream.next_id(5), Some(id(10, 2)));
+        assert_eq!(stream.len(), 2);
+    }
+
+    #[test]
+    fn ids_roll_over_to_the_next_millisecond() {
+        let mut stream = Stream::new();
+        stream.insert(id(10, u64::MAX), fields());
+        assert_eq!(stream.next_id(10), Some(id(11, 0)));
+
+        stream.insert(StreamId::MAX, fields());
+        assert_eq!(stream.next_id(10), None);
+    }
+
+    #[test]
+    fn ids_are_parsed_with_an_optional_sequence() {
+        assert_eq!("5-3".parse::<StreamId>(), Ok(id(5, 3)));
+        assert_eq!("5".parse::<StreamId>(), Ok(id(5, 0)));
+        assert_eq!(id(5, 3).to_string(), "5-3");
+        for invalid in ["", "-", "5-", "a-1", "5-*", "-1-0"] {
+            assert!(invalid.parse::<StreamId>().is_err(), "{}", invalid);
+        }
+    }
+
+    fn stream_of(ids: &[StreamId]) -> Stream {
+        let mut stream = Stream::new();
+        for id in ids {
+            stream.insert(*id, fields());
+        }
+        stream
+    }
+
+    fn ids<'a>(
+        entries: impl Iterator<Item = (&'a StreamId, &'a Vec<(String, String)>)>,
+    ) -> Vec<StreamId> {
+        entries.map(|(id, _)| *id).collect()
+    }
+
+    #[test]
+    fn range_includes_both_bounds() {
+        let stream = stream_of(&[id(1, 0), id(2, 0), id(2, 1), id(3, 0)]);
+        assert_eq!(ids(stream.range(id(2, 0), id(2, 1))), vec![id(2, 0), id(2, 1)]);
+        assert_eq!(ids(stream.range(StreamId::MIN, StreamId::MAX)).len(), 4);
+        assert_eq!(ids(stream.range(id(3, 0), id(1, 0))), vec![]);
+
+        // A bare millisecond covers the whole millisecond as an upper bound.
+        let start = StreamId::parse_bound("2", false).unwrap();
+        let end = StreamId::parse_bound("2", true).unwrap();
+        assert_eq!(ids(stream.range(start, end)), vec![id(2, 0), id(2, 1)]);
+    }
+
+    #[test]
+    fn after_excludes_the_given_id() {
+        let stream = stream_of(&[id(1, 0), id(2, 0), id(2, 1), id(3, 0)]);
+        assert_eq!(ids(stream.after(id(2, 0))), vec![id(2, 1), id(3, 0)]);
+        assert_eq!(ids(stream.after(id(1, 5))), vec![id(2, 0), id(2, 1), id(3, 0)]);
+        assert_eq!(ids(stream.after(id(3, 0))), vec![]);
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::resp::types::RespType;
+
+/// Holds the client-side caching state of the server, i.e. which connections with
+/// `CLIENT TRACKING` on have read which keys.
+///
+/// A key is tracked until it's modified. The connections which read it are then sent an
+/// invalidation message, and the key is forgotten until it's read again.
+#[derive(Debug)]
+pub struct Tracking {
+    /// The connections which read each key, by tracker id.
+    keys: Mutex<HashMap<String, HashMap<u64, UnboundedSender<RespType>>>>,
+    /// Id to be assigned to the next tracker.
+    next_tracker_id: AtomicU64,
+}
+
+/// The client-side caching state of a single connection.
+///
+/// The invalidation messages for the keys read by the connection are delivered through
+/// the receiver held here.
+#[derive(Debug)]
+pub struct Tracker {
+    /// Id of the tracker.
+    id: u64,
+    /// The shared tracking state.
+    tracking: Arc<Tracking>,
+    /// Set by `CLIENT TRACKING ON`. The keys read by the connection are only tracked if set.
+    enabled: bool,
+    /// Invalidation messages are sent through this channel. It's cloned for every tracked key.
+    sender: UnboundedSender<RespType>,
+    /// Invalidation messages are received from this channel.
+    receiver: UnboundedReceiver<RespType>,
+}
+
+impl Tracking {
+    /// Create a new instance of `Tracking` with no tracked keys.
+    pub fn new() -> Tracking {
+        Tracking {
+            keys: Mutex::new(HashMap::new()),
+            next_tracker_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Sends an invalidation message for the modified keys to the connections which read them,
+    /// and stops tracking the keys.
+    ///
+    /// Each connection receives a single push of the form `["invalidate", [key, ...]]`
+    /// with the keys it read.
+    pub fn invalidate(&self, keys: &[String]) {
+        let mut tracked = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        if tracked.is_empty() {
+            return;
+        }
+
+        let mut invalidated: HashMap<u64, (UnboundedSender<RespType>, Vec<RespType>)> =
+            HashMap::new();
+        for k in keys.iter() {
+            for (id, sender) in tracked.remove(k).unwrap_or_default() {
+                invalidated
+                    .entry(id)
+                    .or_insert_with(|| (sender, vec![]))
+                    .1
+                    .push(RespType::BulkString(k.to_string()));
+            }
+        }
+
+        // Connections which are gone, or turned tracking off, are skipped.
+        for (_, (sender, keys)) in invalidated {
+            let _ = sender.send(RespType::Push(vec![
+                RespType::BulkString(String::from("invalidate")),
+                RespType::Array(keys),
+            ]));
+        }
+    }
+
+    /// Tells every connection which read any key to drop all of them, for when the whole
+    /// dataset is removed, and stops tracking all the keys.
+    ///
+    /// Each connection receives a single push of the form `["invalidate", null]`.
+    pub fn invalidate_all(&self) {
+        let mut tracked = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut invalidated: HashMap<u64, UnboundedSender<RespType>> = HashMap::new();
+        for (id, sender) in tracked.drain().flat_map(|(_, trackers)| trackers) {
+            invalidated.entry(id).or_insert(sender);
+        }
+
+        for (_, sender) in invalidated {
+            let _ = sender.send(RespType::Push(vec![
+                RespType::BulkString(String::from("invalidate")),
+                RespType::NullBulkString,
+            ]));
+        }
+    }
+}
+
+impl Tracker {
+    /// Create a new `Tracker`, with tracking turned off.
+    pub fn new(tracking: Arc<Tracking>) -> Tracker {
+        let id = tracking.next_tracker_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        Tracker {
+            id,
+            tracking,
+            enabled: false,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Turns tracking on or off.
+    ///
+    /// Once tracking is turned off, the connection receives no invalidation messages,
+    /// including the ones for the keys it read before.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled && !enabled {
+            // The senders held for the tracked keys are left behind with the old receiver,
+            // so they fail and are dropped on the next invalidation.
+            let (sender, receiver) = mpsc::unbounded_channel();
+            self.sender = sender;
+            self.receiver = receiver;
+        }
+
+        self.enabled = enabled;
+    }
+
+    /// Tracks the keys read by the connection, if tracking is on.
+    pub fn track(&self, keys: &[String]) {
+        if !self.enabled || keys.is_empty() {
+            return;
+        }
+
+        let mut tracked = self.tracking.keys.lock().unwrap_or_else(|e| e.into_inner());
+        for k in keys.iter() {
+            tracked
+                .entry(k.to_string())
+                .or_default()
+                .insert(self.id, self.sender.clone());
+        }
+    }
+
+    /// Waits for the next invalidation message.
+    pub async fn recv(&mut self) -> Option<RespType> {
+        self.receiver.recv().await
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Holds the keys watched by the connections with the WATCH command, so that their
+/// transactions can be aborted if any of the keys is modified before EXEC.
+#[derive(Debug)]
+pub struct Watches {
+    /// The connections watching each key, by watcher id, along with their dirty flag.
+    keys: Mutex<HashMap<String, HashMap<u64, Arc<AtomicBool>>>>,
+    /// Id to be assigned to the next watcher.
+    next_watcher_id: AtomicU64,
+}
+
+/// The keys watched by a single connection.
+#[derive(Debug)]
+pub struct Watcher {
+    /// Id of the watcher.
+    id: u64,
+    /// The shared watch state.
+    watches: Arc<Watches>,
+    /// The keys watched since the last EXEC, DISCARD or UNWATCH.
+    keys: Vec<String>,
+    /// Set once any of the watched keys is modified. It's shared with every watched key.
+    dirty: Arc<AtomicBool>,
+}
+
+impl Watches {
+    /// Create a new instance of `Watches` with no watched keys.
+    pub fn new() -> Watches {
+        Watches {
+            keys: Mutex::new(HashMap::new()),
+            next_watcher_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Marks the connections watching the modified keys as dirty, so that their next EXEC
+    /// fails. The keys are no longer watched by them.
+    pub fn touch(&self, keys: &[String]) {
+        let mut watched = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        if watched.is_empty() {
+            return;
+        }
+
+        for k in keys.iter() {
+            for (_, dirty) in watched.remove(k).unwrap_or_default() {
+                dirty.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Marks every connection watching any key as dirty, for when the whole dataset is
+    /// removed. No key is watched anymore afterwards.
+    pub fn touch_all(&self) {
+        let mut watched = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        for (_, dirty) in watched.drain().flat_map(|(_, watchers)| watchers) {
+            dirty.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Watcher {
+    /// Create a new `Watcher`, with no watched keys.
+    pub fn new(watches: Arc<Watches>) -> Watcher {
+        let id = watches.next_watcher_id.fetch_add(1, Ordering::Relaxed);
+
+        Watcher {
+            id,
+            watches,
+            keys: vec![],
+            dirty: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Watches the keys, in addition to the ones already watched.
+    pub fn watch(&mut self, keys: &[String]) {
+        let mut watched = self.watches.keys.lock().unwrap_or_else(|e| e.into_inner());
+        for k in keys.iter() {
+            watched
+                .entry(k.to_string())
+                .or_default()
+                .insert(self.id, self.dirty.clone());
+            self.keys.push(k.to_string());
+        }
+    }
+
+    /// Returns the flag set once any of the watched keys is modified since it was watched,
+    /// so that it can be checked from another thread. A new flag is used after `unwatch`.
+    pub fn dirty_flag(&self) -> Arc<AtomicBool> {
+        self.dirty.clone()
+    }
+
+    /// Stops watching all the keys, and clears the dirty flag.
+    pub fn unwatch(&mut self) {
+        if self.keys.is_empty() {
+            return;
+        }
+
+        let mut watched = self.watches.keys.lock().unwrap_or_else(|e| e.into_inner());
+        for k in self.keys.drain(..) {
+            if let Some(watchers) = watched.get_mut(&k) {
+                watchers.remove(&self.id);
+                if watchers.is_empty() {
+                    watched.remove(&k);
+                }
+            }
+        }
+
+        // The keys which were touched are already gone from the shared state, so a new flag
+        // is enough to start clean.
+        self.dirty = Arc::new(AtomicBool::new(false));
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        self.unwatch();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|k| k.to_string()).collect()
+    }
+
+    fn watched(watches: &Watches) -> Vec<String> {
+        let mut keys: Vec<String> = watches.keys.lock().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn watches_are_removed_when_the_watcher_is_dropped() {
+        let watches = Arc::new(Watches::new());
+        let mut watcher = Watcher::new(watches.clone());
+        let mut other = Watcher::new(watches.clone());
+        watcher.watch(&keys(&["a", "b"]));
+        other.watch(&keys(&["b"]));
+        assert_eq!(watched(&watches), keys(&["a", "b"]));
+
+        drop(watcher);
+        assert_eq!(watched(&watches), keys(&["b"]));
+        drop(other);
+        assert_eq!(watched(&watches), keys(&[]));
+    }
+
+    #[test]
+    fn touched_keys_are_no_longer_watched() {
+        let watches = Arc::new(Watches::new());
+        let mut watcher = Watcher::new(watches.clone());
+        watcher.watch(&keys(&["a", "b"]));
+        let dirty = watcher.dirty_flag();
+
+        watches.touch(&keys(&["a", "c"]));
+        assert!(dirty.load(Ordering::Relaxed));
+        assert_eq!(watched(&watches), keys(&["b"]));
+
+        // Unwatching starts clean, with no stale entry left for the touched key.
+        watcher.unwatch();
+        assert!(!watcher.dirty_flag().load(Ordering::Relaxed));
+        assert_eq!(watched(&watches), keys(&[]));
+    }
+}
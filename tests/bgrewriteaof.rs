@@ -0,0 +1,26 @@
+// tests/bgrewriteaof.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn bgrewriteaof_replies_like_a_started_rewrite() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SET", "k", "v"]), Reply::bulk("OK"));
+    assert_eq!(
+        client.call(&["BGREWRITEAOF"]),
+        Reply::Simple(String::from("Background append only file rewriting started"))
+    );
+    // Nothing is rewritten, and the data is left as is.
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("v"));
+
+    assert_eq!(
+        client.call(&["BGREWRITEAOF", "now"]),
+        Reply::Error(String::from(
+            "ERR Wrong number of arguments specified for 'BGREWRITEAOF' command"
+        ))
+    );
+}
@@ -0,0 +1,29 @@
+// tests/bind.rs
+
+mod common;
+
+use std::net::ToSocketAddrs;
+
+use common::{free_port, Client, Reply, Server};
+
+#[test]
+fn bind_resolves_hostnames() {
+    let port = free_port().to_string();
+    let (_server, addr) = Server::start_reporting_addr(&["--bind", "localhost", "--port", &port]);
+
+    let resolved: Vec<_> = ("localhost", addr.port()).to_socket_addrs().unwrap().collect();
+    assert!(resolved.contains(&addr), "{} isn't in {:?}", addr, resolved);
+
+    let mut client = Client::connect_to(addr);
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
+
+#[test]
+fn bind_accepts_ip_addresses() {
+    let port = free_port().to_string();
+    let (_server, addr) = Server::start_reporting_addr(&["--bind", "127.0.0.1", "--port", &port]);
+    assert_eq!(addr.to_string(), format!("127.0.0.1:{}", port));
+
+    let mut client = Client::connect_to(addr);
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
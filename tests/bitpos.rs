@@ -0,0 +1,41 @@
+// tests/bitpos.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn bitpos_finds_the_first_set_bit() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    // 0x00 0x00 0x41 ('A' is 01000001)
+    client.call(&["SET", "k", "\0\0A"]);
+
+    assert_eq!(client.call(&["BITPOS", "k", "1"]), Reply::Integer(17));
+    assert_eq!(client.call(&["BITPOS", "k", "1", "2"]), Reply::Integer(17));
+    assert_eq!(client.call(&["BITPOS", "k", "1", "-1", "-1"]), Reply::Integer(17));
+}
+
+#[test]
+fn bitpos_finds_the_first_clear_bit() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    // 'Ã' is encoded as 0xC3 0xBF, that is 11000011 10111111
+    client.call(&["SET", "k", "Ã"]);
+
+    assert_eq!(client.call(&["BITPOS", "k", "0"]), Reply::Integer(2));
+    assert_eq!(client.call(&["BITPOS", "k", "0", "1"]), Reply::Integer(9));
+    // A missing key is treated as an empty string, padded with zeros.
+    assert_eq!(client.call(&["BITPOS", "missing", "0"]), Reply::Integer(0));
+}
+
+#[test]
+fn bitpos_returns_minus_one_when_no_bit_matches() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SET", "k", "\0\0A"]);
+
+    assert_eq!(client.call(&["BITPOS", "k", "1", "0", "1"]), Reply::Integer(-1));
+    assert_eq!(client.call(&["BITPOS", "k", "1", "3"]), Reply::Integer(-1));
+    assert_eq!(client.call(&["BITPOS", "missing", "1"]), Reply::Integer(-1));
+}
@@ -0,0 +1,79 @@
+// tests/client.rs
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use common::{Reply, Server};
+
+#[test]
+fn reads_under_no_touch_keep_the_idle_time() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    let ok = Reply::Simple(String::from("OK"));
+
+    client.call(&["SET", "k", "v"]);
+    thread::sleep(Duration::from_millis(1100));
+
+    assert_eq!(client.call(&["CLIENT", "NO-TOUCH", "ON"]), ok);
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("v"));
+    match client.call(&["OBJECT", "IDLETIME", "k"]) {
+        Reply::Integer(idle) => assert!(idle >= 1, "{}", idle),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+
+    assert_eq!(client.call(&["CLIENT", "NO-TOUCH", "OFF"]), ok);
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("v"));
+    assert_eq!(client.call(&["OBJECT", "IDLETIME", "k"]), Reply::Integer(0));
+}
+
+#[test]
+fn no_evict_and_no_touch_take_on_or_off() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    let ok = Reply::Simple(String::from("OK"));
+
+    for subcommand in ["NO-EVICT", "NO-TOUCH"] {
+        assert_eq!(client.call(&["CLIENT", subcommand, "ON"]), ok);
+        assert_eq!(client.call(&["CLIENT", subcommand, "off"]), ok);
+        assert!(matches!(
+            client.call(&["CLIENT", subcommand, "maybe"]),
+            Reply::Error(_)
+        ));
+    }
+}
+
+/// Returns the value of a field of the line returned by CLIENT INFO.
+fn info_field(info: &Reply, name: &str) -> String {
+    let Reply::Bulk(line) = info else {
+        panic!("unexpected reply: {:?}", info);
+    };
+    line.split(' ')
+        .find_map(|field| field.strip_prefix(&format!("{}=", name)))
+        .unwrap_or_else(|| panic!("no {} in {}", name, line))
+        .to_string()
+}
+
+#[test]
+fn client_info_reports_the_last_commands() {
+    let server = Server::start(&["--client-history-len", "3"]);
+    let mut client = server.connect();
+
+    client.call(&["SET", "k", "v"]);
+    client.call(&["GET", "k"]);
+    client.call(&["CLIENT", "SETNAME", "worker"]);
+    client.call(&["RPUSH", "l", "a"]);
+
+    let info = client.call(&["CLIENT", "INFO"]);
+    assert_eq!(info_field(&info, "cmd"), "client");
+    assert_eq!(info_field(&info, "history"), "client,rpush,client");
+    assert_eq!(info_field(&info, "name"), "worker");
+    assert_eq!(info_field(&info, "age"), "0");
+
+    // Every connection has its own history and id.
+    let mut other = server.connect();
+    other.call(&["PING"]);
+    let other_info = other.call(&["CLIENT", "INFO"]);
+    assert_eq!(info_field(&other_info, "history"), "ping,client");
+    assert_ne!(info_field(&other_info, "id"), info_field(&info, "id"));
+}
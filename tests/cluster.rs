@@ -0,0 +1,33 @@
+// tests/cluster.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn cluster_info_reports_a_standalone_server() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    match client.call(&["CLUSTER", "INFO"]) {
+        Reply::Bulk(info) => {
+            assert!(info.contains("cluster_enabled:0\r\n"), "{}", info);
+        }
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn cluster_slots_and_myid_are_stubs() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["CLUSTER", "SLOTS"]), Reply::Array(vec![]));
+    match client.call(&["CLUSTER", "MYID"]) {
+        Reply::Bulk(id) => assert_eq!(id.len(), 40),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    // The id is fixed.
+    assert_eq!(client.call(&["CLUSTER", "MYID"]), client.call(&["CLUSTER", "MYID"]));
+    assert!(matches!(client.call(&["CLUSTER", "FOO"]), Reply::Error(_)));
+}
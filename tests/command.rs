@@ -0,0 +1,52 @@
+// tests/command.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+fn keys(keys: &[&str]) -> Reply {
+    Reply::Array(keys.iter().map(|k| Reply::bulk(k)).collect())
+}
+
+#[test]
+fn command_getkeys_returns_the_key_arguments() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["COMMAND", "GETKEYS", "GET", "k"]), keys(&["k"]));
+    assert_eq!(
+        client.call(&["COMMAND", "GETKEYS", "MSET", "a", "1", "b", "2"]),
+        keys(&["a", "b"])
+    );
+    assert_eq!(
+        client.call(&["COMMAND", "GETKEYS", "SET", "k", "v", "EX", "10"]),
+        keys(&["k"])
+    );
+    assert_eq!(
+        client.call(&["COMMAND", "GETKEYS", "SINTERCARD", "2", "a", "b", "LIMIT", "1"]),
+        keys(&["a", "b"])
+    );
+    assert_eq!(
+        client.call(&["COMMAND", "GETKEYS", "XREAD", "COUNT", "1", "STREAMS", "a", "b", "0", "0"]),
+        keys(&["a", "b"])
+    );
+}
+
+#[test]
+fn command_getkeys_rejects_commands_without_keys() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(
+        client.call(&["COMMAND", "GETKEYS", "PING"]),
+        Reply::Error(String::from("ERR The command has no key arguments"))
+    );
+    assert_eq!(
+        client.call(&["COMMAND", "GETKEYS", "NOSUCHCOMMAND", "k"]),
+        Reply::Error(String::from("ERR Invalid command specified"))
+    );
+    assert_eq!(
+        client.call(&["COMMAND", "GETKEYS", "GET"]),
+        Reply::Error(String::from("ERR Invalid arguments specified for command"))
+    );
+}
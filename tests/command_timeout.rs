@@ -0,0 +1,45 @@
+// tests/command_timeout.rs
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{Reply, Server};
+
+#[test]
+fn commands_running_past_the_timeout_get_an_error() {
+    let server = Server::start(&["--command-timeout", "100"]);
+    let mut client = server.connect();
+
+    let started = Instant::now();
+    assert_eq!(
+        client.call(&["DEBUG", "SLEEP", "1"]),
+        Reply::Error(String::from("ERR command timed out"))
+    );
+    assert!(started.elapsed() < Duration::from_millis(900));
+    assert_eq!(client.call(&["DEBUG", "SLEEP", "0"]), Reply::Simple(String::from("OK")));
+}
+
+#[test]
+fn exec_waiting_for_a_timed_out_command_does_not_stall_the_server() {
+    // A single worker thread, so that a blocked worker would stall every connection.
+    let server = Server::start(&["--command-timeout", "100", "--io-threads", "1"]);
+    let mut sleeper = server.connect();
+    let mut transaction = server.connect();
+    let mut other = server.connect();
+
+    // The abandoned command keeps running for a while, holding the transaction lock.
+    assert!(matches!(sleeper.call(&["DEBUG", "SLEEP", "2"]), Reply::Error(_)));
+
+    transaction.call(&["MULTI"]);
+    transaction.call(&["SET", "k", "v"]);
+    transaction.send(&["EXEC"]);
+
+    // Commands of other connections may time out while EXEC waits, but they're replied to.
+    let started = Instant::now();
+    other.call(&["PING"]);
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    assert_eq!(transaction.read(), Reply::Array(vec![Reply::bulk("OK")]));
+    assert_eq!(other.call(&["GET", "k"]), Reply::bulk("v"));
+}
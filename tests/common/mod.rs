@@ -0,0 +1,293 @@
+// tests/common/mod.rs
+
+//! Helpers shared by the integration tests, which run the server binary and talk to it
+//! over TCP.
+
+#![allow(dead_code)]
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    process::{self, Child, Command, ExitStatus, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A server process listening on a free local port. It's killed when dropped.
+pub struct Server {
+    process: Child,
+    pub port: u16,
+}
+
+impl Server {
+    /// Starts a server with the given extra command line arguments, and waits until it
+    /// accepts connections.
+    pub fn start(args: &[&str]) -> Server {
+        Server::start_with_env(args, &[])
+    }
+
+    /// Starts a server like `start`, with the given extra environment variables.
+    pub fn start_with_env(args: &[&str], vars: &[(&str, &str)]) -> Server {
+        let port = free_port();
+        let port_arg = port.to_string();
+        let args = [&["--port", port_arg.as_str()], args].concat();
+        Server::spawn(port, &args, vars)
+    }
+
+    /// Starts a server with exactly the given command line arguments, which are expected
+    /// to make it listen on the given port, and waits until it accepts connections.
+    pub fn start_on(port: u16, args: &[&str]) -> Server {
+        Server::spawn(port, args, &[])
+    }
+
+    /// Starts a server with exactly the given command line arguments, and waits until it
+    /// logs the address it listens on. For when the address isn't known up front, e.g.
+    /// with `--port 0` or a hostname to bind to. The logs go to a temporary file.
+    pub fn start_reporting_addr(args: &[&str]) -> (Server, SocketAddr) {
+        static STARTED: AtomicUsize = AtomicUsize::new(0);
+        let path = env::temp_dir().join(format!(
+            "nimblecache-addr-{}-{}.log",
+            process::id(),
+            STARTED.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_file(&path);
+
+        let args = [args, &["--logfile", path.to_str().unwrap()]].concat();
+        let process = Command::new(env!("CARGO_BIN_EXE_redis-clone"))
+            .args(&args)
+            .env("RUST_LOG", "info")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("the server should start");
+        let mut server = Server { process, port: 0 };
+
+        let mut addr = None;
+        wait_until(|| {
+            let log = fs::read_to_string(&path).unwrap_or_default();
+            addr = log
+                .lines()
+                .find_map(|line| line.split_once("TCP Listener started on "))
+                .map(|(_, addr)| addr.trim().parse::<SocketAddr>().unwrap());
+            addr.is_some()
+        });
+        let _ = fs::remove_file(&path);
+
+        let addr = addr.unwrap();
+        server.port = addr.port();
+        (server, addr)
+    }
+
+    fn spawn(port: u16, args: &[&str], vars: &[(&str, &str)]) -> Server {
+        let process = Command::new(env!("CARGO_BIN_EXE_redis-clone"))
+            .args(args)
+            .envs(vars.iter().copied())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("the server should start");
+        let server = Server { process, port };
+
+        let started = Instant::now();
+        while TcpStream::connect(("127.0.0.1", port)).is_err() {
+            assert!(
+                started.elapsed() < Duration::from_secs(10),
+                "the server should accept connections"
+            );
+            thread::sleep(Duration::from_millis(20));
+        }
+        server
+    }
+
+    /// Opens a new connection to the server.
+    pub fn connect(&self) -> Client {
+        Client::connect(self.port)
+    }
+
+    /// Returns the process ID of the server.
+    pub fn pid(&self) -> u32 {
+        self.process.id()
+    }
+
+    /// Waits until the server process exits, failing the test if it doesn't within a few
+    /// seconds.
+    pub fn wait_for_exit(&mut self) -> ExitStatus {
+        let started = Instant::now();
+        loop {
+            if let Some(status) = self.process.try_wait().unwrap() {
+                return status;
+            }
+            assert!(started.elapsed() < Duration::from_secs(10), "the server should exit");
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// Returns a port which is free at the time of the call.
+pub fn free_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("a free port should be available");
+    listener.local_addr().unwrap().port()
+}
+
+/// A reply as decoded by `Client`. RESP3 types are decoded too, so that tests can check
+/// which type the server used.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Simple(String),
+    Error(String),
+    Integer(i64),
+    Bulk(String),
+    /// `$-1`, `*-1` or the RESP3 `_`.
+    Null,
+    Array(Vec<Reply>),
+    Map(Vec<(Reply, Reply)>),
+    Set(Vec<Reply>),
+    Push(Vec<Reply>),
+    Verbatim(String, String),
+}
+
+impl Reply {
+    /// Shorthand for a bulk string reply.
+    pub fn bulk(s: &str) -> Reply {
+        Reply::Bulk(s.to_string())
+    }
+}
+
+/// A blocking RESP client.
+pub struct Client {
+    writer: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl Client {
+    /// Connects to a server on the given local port.
+    pub fn connect(port: u16) -> Client {
+        Client::connect_to(("127.0.0.1", port))
+    }
+
+    /// Connects to a server on the given address.
+    pub fn connect_to(addr: impl ToSocketAddrs) -> Client {
+        let stream = TcpStream::connect(addr).expect("the server should accept");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        Client {
+            writer: stream.try_clone().unwrap(),
+            reader: BufReader::new(stream),
+        }
+    }
+
+    /// Sends a command and returns its reply.
+    pub fn call(&mut self, args: &[&str]) -> Reply {
+        self.send(args);
+        self.read()
+    }
+
+    /// Sends a command without waiting for its reply.
+    pub fn send(&mut self, args: &[&str]) {
+        self.send_raw(&encode(args));
+    }
+
+    /// Writes raw bytes to the connection.
+    pub fn send_raw(&mut self, bytes: &[u8]) {
+        self.writer.write_all(bytes).unwrap();
+    }
+
+    /// Reads the next reply.
+    pub fn read(&mut self) -> Reply {
+        let line = self.line();
+        let (prefix, rest) = line.split_at(1);
+        match prefix {
+            "+" => Reply::Simple(rest.to_string()),
+            "-" => Reply::Error(rest.to_string()),
+            ":" => Reply::Integer(rest.parse().unwrap()),
+            "_" => Reply::Null,
+            "$" | "=" => {
+                let len: i64 = rest.parse().unwrap();
+                if len < 0 {
+                    return Reply::Null;
+                }
+                let mut data = vec![0; len as usize + 2];
+                self.reader.read_exact(&mut data).unwrap();
+                data.truncate(len as usize);
+                let data = String::from_utf8(data).unwrap();
+                if prefix == "$" {
+                    Reply::Bulk(data)
+                } else {
+                    let (format, text) = data.split_at(3);
+                    Reply::Verbatim(format.to_string(), text[1..].to_string())
+                }
+            }
+            "*" | "~" | ">" => {
+                let len: i64 = rest.parse().unwrap();
+                if len < 0 {
+                    return Reply::Null;
+                }
+                let items = (0..len).map(|_| self.read()).collect();
+                match prefix {
+                    "*" => Reply::Array(items),
+                    "~" => Reply::Set(items),
+                    _ => Reply::Push(items),
+                }
+            }
+            "%" => {
+                let len: usize = rest.parse().unwrap();
+                Reply::Map((0..len).map(|_| (self.read(), self.read())).collect())
+            }
+            _ => panic!("unexpected reply: {}", line),
+        }
+    }
+
+    /// Reads exactly `len` raw bytes from the connection.
+    pub fn read_exact(&mut self, len: usize) -> Vec<u8> {
+        let mut data = vec![0; len];
+        self.reader.read_exact(&mut data).unwrap();
+        data
+    }
+
+    /// Checks if the server closed the connection, without any further data sent on it.
+    pub fn is_closed(&mut self) -> bool {
+        let mut byte = [0];
+        match self.reader.read(&mut byte) {
+            Ok(0) => true,
+            Ok(_) => false,
+            Err(e) => e.kind() == ErrorKind::ConnectionReset,
+        }
+    }
+
+    /// Reads a line, without its CRLF terminator.
+    pub fn line(&mut self) -> String {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        assert!(line.ends_with("\r\n"), "unterminated reply line: {:?}", line);
+        line.truncate(line.len() - 2);
+        line
+    }
+}
+
+/// Encodes a command as an array of bulk strings.
+pub fn encode(args: &[&str]) -> Vec<u8> {
+    let mut out = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        out.extend(format!("${}\r\n{}\r\n", arg.len(), arg).into_bytes());
+    }
+    out
+}
+
+/// Calls `f` until it returns true, failing the test if it doesn't within a few seconds.
+pub fn wait_until(mut f: impl FnMut() -> bool) {
+    let started = Instant::now();
+    while !f() {
+        assert!(started.elapsed() < Duration::from_secs(10), "condition not met in time");
+        thread::sleep(Duration::from_millis(50));
+    }
+}
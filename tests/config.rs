@@ -0,0 +1,137 @@
+// tests/config.rs
+
+mod common;
+
+use std::{env, fs, process};
+
+use common::{free_port, Reply, Server};
+
+fn config_get(server: &Server, name: &str) -> Reply {
+    server.connect().call(&["CONFIG", "GET", name])
+}
+
+#[test]
+fn appendfsync_defaults_to_everysec_and_can_be_changed() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    assert_eq!(
+        config_get(&server, "appendfsync"),
+        Reply::Array(vec![Reply::bulk("appendfsync"), Reply::bulk("everysec")])
+    );
+
+    assert_eq!(
+        client.call(&["CONFIG", "SET", "appendfsync", "always"]),
+        Reply::Simple(String::from("OK"))
+    );
+    assert_eq!(
+        config_get(&server, "appendfsync"),
+        Reply::Array(vec![Reply::bulk("appendfsync"), Reply::bulk("always")])
+    );
+
+    let Reply::Error(e) = client.call(&["CONFIG", "SET", "appendfsync", "sometimes"]) else {
+        panic!("an invalid policy should be rejected");
+    };
+    assert!(e.contains("appendfsync"), "unexpected error: {}", e);
+    assert_eq!(
+        config_get(&server, "appendfsync"),
+        Reply::Array(vec![Reply::bulk("appendfsync"), Reply::bulk("always")])
+    );
+}
+
+#[test]
+fn appendfsync_is_taken_from_the_command_line_and_the_config_file() {
+    let server = Server::start(&["--appendfsync", "no"]);
+    assert_eq!(
+        config_get(&server, "appendfsync"),
+        Reply::Array(vec![Reply::bulk("appendfsync"), Reply::bulk("no")])
+    );
+
+    let path = env::temp_dir().join(format!("nimblecache-test-{}.conf", process::id()));
+    fs::write(&path, "appendonly no\nappendfsync always\n").unwrap();
+    let server = Server::start(&[path.to_str().unwrap()]);
+    assert_eq!(
+        config_get(&server, "appendfsync"),
+        Reply::Array(vec![Reply::bulk("appendfsync"), Reply::bulk("always")])
+    );
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn maxmemory_defaults_to_zero() {
+    let server = Server::start(&[]);
+    assert_eq!(
+        config_get(&server, "maxmemory"),
+        Reply::Array(vec![Reply::bulk("maxmemory"), Reply::bulk("0")])
+    );
+
+    let server = Server::start(&["--maxmemory", "1048576"]);
+    assert_eq!(
+        config_get(&server, "maxmemory"),
+        Reply::Array(vec![Reply::bulk("maxmemory"), Reply::bulk("1048576")])
+    );
+}
+
+#[test]
+fn config_rewrite_persists_the_changes_to_the_config_file() {
+    let path = env::temp_dir().join(format!("nimblecache-test-rewrite-{}.conf", process::id()));
+    fs::write(&path, "# limits\nmaxmemory 1mb\nmaxmemory-policy noeviction\n").unwrap();
+
+    let server = Server::start(&[path.to_str().unwrap()]);
+    let mut client = server.connect();
+    assert_eq!(
+        client.call(&["CONFIG", "SET", "maxmemory", "2097152", "maxmemory-policy", "volatile-ttl"]),
+        Reply::Simple(String::from("OK"))
+    );
+    assert_eq!(client.call(&["CONFIG", "REWRITE"]), Reply::Simple(String::from("OK")));
+    drop(server);
+
+    let contents = fs::read_to_string(&path).unwrap();
+    assert!(contents.starts_with("# limits\n"), "comments are kept: {}", contents);
+
+    let server = Server::start(&[path.to_str().unwrap()]);
+    assert_eq!(
+        config_get(&server, "maxmemory"),
+        Reply::Array(vec![Reply::bulk("maxmemory"), Reply::bulk("2097152")])
+    );
+    assert_eq!(
+        config_get(&server, "maxmemory-policy"),
+        Reply::Array(vec![Reply::bulk("maxmemory-policy"), Reply::bulk("volatile-ttl")])
+    );
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn config_rewrite_fails_without_a_config_file() {
+    let server = Server::start(&[]);
+    assert!(matches!(
+        server.connect().call(&["CONFIG", "REWRITE"]),
+        Reply::Error(_)
+    ));
+}
+
+#[test]
+fn config_file_sets_the_port_and_the_command_line_overrides_it() {
+    let path = env::temp_dir().join(format!("nimblecache-test-port-{}.conf", process::id()));
+    let port = free_port();
+    fs::write(&path, format!("port {}\nbind 127.0.0.1\nmaxmemory 1mb\n", port)).unwrap();
+
+    let server = Server::start_on(port, &[path.to_str().unwrap()]);
+    assert_eq!(server.connect().call(&["PING"]), Reply::Simple(String::from("PONG")));
+    assert_eq!(
+        config_get(&server, "port"),
+        Reply::Array(vec![Reply::bulk("port"), Reply::bulk(&port.to_string())])
+    );
+    assert_eq!(
+        config_get(&server, "maxmemory"),
+        Reply::Array(vec![Reply::bulk("maxmemory"), Reply::bulk("1048576")])
+    );
+    drop(server);
+
+    let other = free_port();
+    let server = Server::start_on(other, &["--port", &other.to_string(), path.to_str().unwrap()]);
+    assert_eq!(
+        config_get(&server, "port"),
+        Reply::Array(vec![Reply::bulk("port"), Reply::bulk(&other.to_string())])
+    );
+    let _ = fs::remove_file(&path);
+}
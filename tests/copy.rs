@@ -0,0 +1,51 @@
+// tests/copy.rs
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use common::{Reply, Server};
+
+#[test]
+fn copy_keeps_the_remaining_ttl() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SET", "src", "v", "EX", "100"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["COPY", "src", "dst"]), Reply::Integer(1));
+    assert_eq!(client.call(&["GET", "dst"]), Reply::bulk("v"));
+    assert_eq!(
+        client.call(&["PEXPIRETIME", "dst"]),
+        client.call(&["PEXPIRETIME", "src"])
+    );
+
+    // The destination isn't copied over unless REPLACE is given.
+    assert_eq!(client.call(&["SET", "other", "w"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["COPY", "other", "dst"]), Reply::Integer(0));
+    assert_eq!(client.call(&["COPY", "other", "dst", "REPLACE"]), Reply::Integer(1));
+    assert_eq!(client.call(&["GET", "dst"]), Reply::bulk("w"));
+    assert_eq!(client.call(&["TTL", "dst"]), Reply::Integer(-1));
+}
+
+#[test]
+fn copied_keys_expire_independently() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SET", "a", "v", "PX", "200"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["COPY", "a", "b"]), Reply::Integer(1));
+    assert_eq!(client.call(&["SET", "c", "v", "PX", "200"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["COPY", "c", "d"]), Reply::Integer(1));
+
+    // Persisting the source doesn't persist the copy, and the other way around.
+    assert_eq!(client.call(&["PERSIST", "a"]), Reply::Integer(1));
+    assert_eq!(client.call(&["PERSIST", "d"]), Reply::Integer(1));
+    assert_eq!(client.call(&["TTL", "a"]), Reply::Integer(-1));
+    assert_eq!(client.call(&["TTL", "d"]), Reply::Integer(-1));
+
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(client.call(&["GET", "a"]), Reply::bulk("v"));
+    assert_eq!(client.call(&["GET", "b"]), Reply::Null);
+    assert_eq!(client.call(&["GET", "c"]), Reply::Null);
+    assert_eq!(client.call(&["GET", "d"]), Reply::bulk("v"));
+}
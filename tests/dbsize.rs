@@ -0,0 +1,35 @@
+// tests/dbsize.rs
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use common::{Reply, Server};
+
+#[test]
+fn dbsize_counts_the_live_keys() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.call(&["SET", "a", "v"]);
+    client.call(&["SET", "b", "v", "PX", "100"]);
+    client.call(&["SET", "c", "v", "EX", "100"]);
+    client.call(&["SET", "d", "v", "PX", "100"]);
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(4));
+
+    assert_eq!(client.call(&["DEL", "c"]), Reply::Integer(1));
+    assert_eq!(client.call(&["PERSIST", "d"]), Reply::Integer(1));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(3));
+
+    // `b` expires, while `d` doesn't anymore.
+    thread::sleep(Duration::from_millis(150));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(2));
+    assert_eq!(client.call(&["GET", "d"]), Reply::bulk("v"));
+
+    // A key given a later expiry is only removed once the later one is reached.
+    client.call(&["SET", "e", "v", "PX", "100"]);
+    assert_eq!(client.call(&["EXPIRE", "e", "100"]), Reply::Integer(1));
+    thread::sleep(Duration::from_millis(150));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(3));
+    assert_eq!(client.call(&["GET", "e"]), Reply::bulk("v"));
+}
@@ -0,0 +1,183 @@
+// tests/debug.rs
+
+mod common;
+
+use std::collections::HashMap;
+
+use common::{Client, Reply, Server};
+
+#[test]
+fn debug_stringmatch_len_exposes_the_glob_matcher() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    for (pattern, string, matched) in [
+        ("user:*", "user:42", 1),
+        ("h?llo", "hello", 1),
+        ("key[a-z]", "keyq", 1),
+        ("key[a-z]", "key1", 0),
+        ("a\\*b", "a*b", 1),
+        ("a\\*b", "axb", 0),
+        ("user:*", "session:1", 0),
+    ] {
+        assert_eq!(
+            client.call(&["DEBUG", "STRINGMATCH-LEN", pattern, string]),
+            Reply::Integer(matched),
+            "{} {}",
+            pattern,
+            string
+        );
+    }
+}
+
+#[test]
+fn debug_stringmatch_len_requires_a_pattern_and_a_string() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert!(matches!(
+        client.call(&["DEBUG", "STRINGMATCH-LEN", "a*"]),
+        Reply::Error(_)
+    ));
+}
+
+#[test]
+fn debug_reload_keeps_the_values_their_types_and_expiry_times() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.call(&["SET", "string", "v"]);
+    client.call(&["SET", "expiring", "v", "PX", "100000"]);
+    client.call(&["RPUSH", "list", "a", "b", "c"]);
+    client.call(&["SADD", "set", "a", "b"]);
+    client.call(&["HSET", "hash", "f", "v"]);
+    client.call(&["ZADD", "zset", "1.5", "a"]);
+    client.call(&["XADD", "stream", "1-1", "f", "v"]);
+    let expires_at = client.call(&["PEXPIRETIME", "expiring"]);
+    let encodings: Vec<Reply> = ["string", "list", "set", "hash", "zset", "stream"]
+        .iter()
+        .map(|key| client.call(&["OBJECT", "ENCODING", key]))
+        .collect();
+
+    assert_eq!(client.call(&["DEBUG", "RELOAD"]), Reply::Simple(String::from("OK")));
+
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(7));
+    assert_eq!(client.call(&["GET", "string"]), Reply::bulk("v"));
+    assert_eq!(client.call(&["GET", "expiring"]), Reply::bulk("v"));
+    assert_eq!(client.call(&["PEXPIRETIME", "expiring"]), expires_at);
+    assert_eq!(client.call(&["PEXPIRETIME", "string"]), Reply::Integer(-1));
+    assert_eq!(
+        client.call(&["LRANGE", "list", "0", "2"]),
+        Reply::Array(vec![Reply::bulk("a"), Reply::bulk("b"), Reply::bulk("c")])
+    );
+    let Reply::Array(mut members) = client.call(&["SMEMBERS", "set"]) else {
+        panic!("SMEMBERS should reply with an array");
+    };
+    members.sort_by_key(|member| format!("{:?}", member));
+    assert_eq!(members, vec![Reply::bulk("a"), Reply::bulk("b")]);
+    assert_eq!(
+        client.call(&["HGETALL", "hash"]),
+        Reply::Array(vec![Reply::bulk("f"), Reply::bulk("v")])
+    );
+    assert_eq!(
+        client.call(&["ZSCAN", "zset", "0"]),
+        Reply::Array(vec![
+            Reply::bulk("0"),
+            Reply::Array(vec![Reply::bulk("a"), Reply::bulk("1.5")])
+        ])
+    );
+    assert_eq!(
+        client.call(&["XRANGE", "stream", "-", "+"]),
+        Reply::Array(vec![Reply::Array(vec![
+            Reply::bulk("1-1"),
+            Reply::Array(vec![Reply::bulk("f"), Reply::bulk("v")])
+        ])])
+    );
+    for (key, encoding) in ["string", "list", "set", "hash", "zset", "stream"]
+        .iter()
+        .zip(encodings)
+    {
+        assert_eq!(client.call(&["OBJECT", "ENCODING", key]), encoding, "{}", key);
+    }
+}
+
+/// Runs DEBUG OBJECT on the key, and returns its fields.
+fn debug_object(client: &mut Client, key: &str) -> HashMap<String, String> {
+    match client.call(&["DEBUG", "OBJECT", key]) {
+        Reply::Simple(fields) => fields
+            .split(' ')
+            .map(|field| {
+                let (name, value) = field.split_once(':').unwrap();
+                (name.to_string(), value.to_string())
+            })
+            .collect(),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn debug_object_describes_how_the_value_is_stored() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["RPUSH", "l", "a", "b", "c"]);
+    client.call(&["SET", "s", "hello"]);
+
+    let list = debug_object(&mut client, "l");
+    assert_eq!(list["encoding"], "quicklist");
+    // A length prefix, followed by each element with its own length prefix.
+    assert_eq!(list["serializedlength"], "7");
+    assert_eq!(list["ql_nodes"], "1");
+    assert_eq!(list["ql_listpack_max"], "3");
+
+    let string = debug_object(&mut client, "s");
+    assert_eq!(string["encoding"], "embstr");
+    assert_eq!(string["serializedlength"], "6");
+    assert!(!string.contains_key("ql_nodes"));
+
+    assert_eq!(
+        client.call(&["DEBUG", "OBJECT", "missing"]),
+        Reply::Error(String::from("ERR no such key"))
+    );
+}
+
+#[test]
+fn debug_protocol_sends_attributes_to_resp3_clients_only() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    let reply = "Some real reply following the attribute";
+
+    assert_eq!(client.call(&["DEBUG", "PROTOCOL", "attrib"]), Reply::bulk(reply));
+
+    client.call(&["HELLO", "3"]);
+    client.send(&["DEBUG", "PROTOCOL", "attrib"]);
+    for line in ["|1", "$14", "key-popularity", "*2", "$7", "key:123", ":90"] {
+        assert_eq!(client.line(), line);
+    }
+    assert_eq!(client.read(), Reply::bulk(reply));
+}
+
+#[test]
+fn debug_protocol_sends_the_reply_types() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["HELLO", "3"]);
+
+    let integers = vec![Reply::Integer(0), Reply::Integer(1), Reply::Integer(2)];
+    for (name, reply) in [
+        ("string", Reply::bulk("Hello World")),
+        ("integer", Reply::Integer(12345)),
+        ("null", Reply::Null),
+        ("array", Reply::Array(integers.clone())),
+        ("set", Reply::Set(integers)),
+        (
+            "verbatim",
+            Reply::Verbatim(String::from("txt"), String::from("This is a verbatim\nstring")),
+        ),
+    ] {
+        assert_eq!(client.call(&["DEBUG", "PROTOCOL", name]), reply, "{}", name);
+    }
+    assert!(matches!(
+        client.call(&["DEBUG", "PROTOCOL", "bignum"]),
+        Reply::Error(e) if e.starts_with("ERR Wrong protocol type name")
+    ));
+}
@@ -0,0 +1,39 @@
+// tests/default_ttl.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn plain_sets_get_the_default_ttl() {
+    let server = Server::start(&["--default-ttl", "100"]);
+    let mut client = server.connect();
+
+    client.call(&["SET", "k", "v"]);
+    assert!(
+        matches!(client.call(&["TTL", "k"]), Reply::Integer(99..=100)),
+        "SET should apply the default TTL"
+    );
+
+    // Explicit options override the default.
+    client.call(&["SET", "ex", "v", "EX", "1000"]);
+    assert!(matches!(client.call(&["TTL", "ex"]), Reply::Integer(999..=1000)));
+    client.call(&["SET", "px", "v", "PX", "5000"]);
+    assert!(matches!(client.call(&["TTL", "px"]), Reply::Integer(4..=5)));
+    client.call(&["SET", "kept", "v", "EX", "500"]);
+    client.call(&["SET", "kept", "other", "KEEPTTL"]);
+    assert!(matches!(client.call(&["TTL", "kept"]), Reply::Integer(499..=500)));
+}
+
+#[test]
+fn keys_are_persistent_without_a_default_ttl() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SET", "k", "v"]);
+    assert_eq!(client.call(&["TTL", "k"]), Reply::Integer(-1));
+
+    // The default can be changed at runtime.
+    client.call(&["CONFIG", "SET", "default-ttl", "60"]);
+    client.call(&["SET", "k", "v"]);
+    assert!(matches!(client.call(&["TTL", "k"]), Reply::Integer(59..=60)));
+}
@@ -0,0 +1,38 @@
+// tests/del.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn del_counts_keys_rather_than_elements() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let elements: Vec<String> = (0..10000).map(|i| i.to_string()).collect();
+    let mut rpush = vec!["RPUSH", "l"];
+    rpush.extend(elements.iter().map(|element| element.as_str()));
+    assert_eq!(client.call(&rpush), Reply::Integer(10000));
+    client.call(&["SET", "s", "v"]);
+
+    assert_eq!(client.call(&["DEL", "l", "s", "missing"]), Reply::Integer(2));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0));
+}
+
+#[test]
+fn unlink_counts_keys_rather_than_elements() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let mut hset = vec![String::from("HSET"), String::from("h")];
+    for i in 0..10000 {
+        hset.extend([format!("field:{}", i), i.to_string()]);
+    }
+    let hset: Vec<&str> = hset.iter().map(|arg| arg.as_str()).collect();
+    assert_eq!(client.call(&hset), Reply::Integer(10000));
+    client.call(&["SADD", "set", "a", "b"]);
+
+    assert_eq!(client.call(&["UNLINK", "h", "set", "h"]), Reply::Integer(2));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0));
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
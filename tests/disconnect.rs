@@ -0,0 +1,41 @@
+// tests/disconnect.rs
+
+mod common;
+
+use std::{env, fs, process};
+
+use common::{wait_until, Reply, Server};
+
+#[test]
+fn clients_going_away_mid_reply_are_not_logged_as_errors() {
+    let path = env::temp_dir().join(format!("nimblecache-test-disconnect-{}.log", process::id()));
+    let _ = fs::remove_file(&path);
+    let server = Server::start_with_env(
+        &["--logfile", path.to_str().unwrap()],
+        &[("RUST_LOG", "debug")],
+    );
+
+    let value = "x".repeat(4 * 1024 * 1024);
+    let mut client = server.connect();
+    assert_eq!(client.call(&["SET", "k", &value]), Reply::bulk("OK"));
+
+    // The client closes the connection without reading the large replies, so the server
+    // fails to write them.
+    let mut leaving = server.connect();
+    for _ in 0..4 {
+        leaving.send(&["GET", "k"]);
+    }
+    drop(leaving);
+
+    wait_until(|| {
+        fs::read_to_string(&path)
+            .unwrap()
+            .contains("Client disconnected before the response was sent")
+    });
+    let logs = fs::read_to_string(&path).unwrap();
+    assert!(!logs.contains("ERROR"), "{}", logs);
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+
+    drop(server);
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,18 @@
+// tests/ephemeral_port.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn port_0_listens_on_a_port_picked_by_the_os_and_reports_it() {
+    let (server, addr) = Server::start_reporting_addr(&["--port", "0"]);
+    assert_ne!(addr.port(), 0);
+    assert!(addr.ip().is_loopback());
+
+    let mut client = server.connect();
+    assert_eq!(
+        client.call(&["CONFIG", "GET", "port"]),
+        Reply::Array(vec![Reply::bulk("port"), Reply::bulk(&addr.port().to_string())])
+    );
+}
@@ -0,0 +1,58 @@
+// tests/eviction.rs
+
+mod common;
+
+use common::{Client, Reply, Server};
+
+/// Returns the estimated size of the dataset, as reported by MEMORY STATS.
+fn dataset_bytes(client: &mut Client) -> i64 {
+    let Reply::Array(stats) = client.call(&["MEMORY", "STATS"]) else {
+        panic!("MEMORY STATS should reply with an array");
+    };
+    match stats.chunks(2).find(|pair| pair[0] == Reply::bulk("dataset.bytes")) {
+        Some([_, Reply::Integer(bytes)]) => *bytes,
+        stat => panic!("unexpected dataset.bytes: {:?}", stat),
+    }
+}
+
+#[test]
+fn volatile_ttl_evicts_the_keys_which_expire_the_soonest_first() {
+    let server = Server::start(&["--maxmemory-policy", "volatile-ttl"]);
+    let mut client = server.connect();
+    let value = "v".repeat(1000);
+
+    client.call(&["SET", "late", &value, "EX", "1000"]);
+    client.call(&["SET", "soon", &value, "EX", "10"]);
+    client.call(&["SET", "forever", &value]);
+    client.call(&["SET", "mid", &value, "EX", "100"]);
+
+    // Memory is freed before each write, so with the dataset over maxmemory by less than
+    // a value, each new key evicts one.
+    let maxmemory = (dataset_bytes(&mut client) - 500).to_string();
+    assert_eq!(
+        client.call(&["CONFIG", "SET", "maxmemory", &maxmemory]),
+        Reply::Simple(String::from("OK"))
+    );
+
+    let live = |client: &mut Client| -> Vec<&str> {
+        ["late", "soon", "forever", "mid"]
+            .into_iter()
+            .filter(|k| client.call(&["GET", k]) != Reply::Null)
+            .collect()
+    };
+
+    assert_eq!(client.call(&["SET", "new:1", &value]), Reply::bulk("OK"));
+    assert_eq!(live(&mut client), ["late", "forever", "mid"]);
+    assert_eq!(client.call(&["SET", "new:2", &value]), Reply::bulk("OK"));
+    assert_eq!(live(&mut client), ["late", "forever"]);
+    assert_eq!(client.call(&["SET", "new:3", &value]), Reply::bulk("OK"));
+    assert_eq!(live(&mut client), ["forever"]);
+
+    // Keys without an expiry are never evicted.
+    match client.call(&["SET", "new:4", &value]) {
+        Reply::Error(e) => assert!(e.starts_with("OOM"), "{}", e),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    assert_eq!(client.call(&["GET", "forever"]), Reply::bulk(&value));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(4));
+}
@@ -0,0 +1,36 @@
+// tests/expiretime.rs
+
+mod common;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use common::{Reply, Server};
+
+#[test]
+fn expiretime_returns_the_timestamp_set_by_expireat() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let at = (now + 1000).to_string();
+    client.call(&["SET", "k", "v"]);
+    assert_eq!(client.call(&["EXPIREAT", "k", &at]), Reply::Integer(1));
+
+    assert_eq!(client.call(&["EXPIRETIME", "k"]), Reply::Integer(now as i64 + 1000));
+    assert_eq!(
+        client.call(&["PEXPIRETIME", "k"]),
+        Reply::Integer((now as i64 + 1000) * 1000)
+    );
+}
+
+#[test]
+fn expiretime_of_keys_without_expiry_or_missing() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.call(&["SET", "k", "v"]);
+    assert_eq!(client.call(&["EXPIRETIME", "k"]), Reply::Integer(-1));
+    assert_eq!(client.call(&["PEXPIRETIME", "k"]), Reply::Integer(-1));
+    assert_eq!(client.call(&["EXPIRETIME", "missing"]), Reply::Integer(-2));
+    assert_eq!(client.call(&["PEXPIRETIME", "missing"]), Reply::Integer(-2));
+}
@@ -0,0 +1,48 @@
+// tests/flushall.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn flushall_removes_every_key() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    for mode in [None, Some("SYNC"), Some("ASYNC")] {
+        assert_eq!(client.call(&["SET", "s", "v", "EX", "100"]), Reply::bulk("OK"));
+        assert_eq!(client.call(&["RPUSH", "l", "a", "b"]), Reply::Integer(2));
+        assert_eq!(client.call(&["SADD", "set", "a"]), Reply::Integer(1));
+        assert_eq!(client.call(&["HSET", "h", "f", "v"]), Reply::Integer(1));
+        assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(4));
+
+        let mut args = vec!["FLUSHALL"];
+        args.extend(mode);
+        assert_eq!(client.call(&args), Reply::Simple(String::from("OK")), "{:?}", mode);
+        // With ASYNC, the keys are gone right away, only freeing the values is deferred.
+        assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0), "{:?}", mode);
+        assert_eq!(client.call(&["GET", "s"]), Reply::Null);
+        assert_eq!(client.call(&["LRANGE", "l", "0", "10"]), Reply::Array(vec![]));
+    }
+
+    assert_eq!(
+        client.call(&["FLUSHALL", "LATER"]),
+        Reply::Error(String::from("ERR syntax error"))
+    );
+}
+
+#[test]
+fn flushall_aborts_the_transactions_watching_keys() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    let mut other = server.connect();
+
+    assert_eq!(client.call(&["SET", "k", "v"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["WATCH", "k"]), Reply::Simple(String::from("OK")));
+    assert_eq!(other.call(&["FLUSHALL"]), Reply::Simple(String::from("OK")));
+
+    assert_eq!(client.call(&["MULTI"]), Reply::Simple(String::from("OK")));
+    assert_eq!(client.call(&["SET", "k", "tx"]), Reply::Simple(String::from("QUEUED")));
+    assert_eq!(client.call(&["EXEC"]), Reply::Null);
+    assert_eq!(client.call(&["GET", "k"]), Reply::Null);
+}
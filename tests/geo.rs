@@ -0,0 +1,43 @@
+// tests/geo.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn geosearch_returns_the_members_within_the_radius() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    assert_eq!(
+        client.call(&[
+            "GEOADD", "sicily", "13.361389", "38.115556", "Palermo", "15.087269", "37.502669",
+            "Catania",
+        ]),
+        Reply::Integer(2)
+    );
+    assert_eq!(
+        client.call(&["GEOADD", "sicily", "12.496366", "41.902782", "Rome"]),
+        Reply::Integer(1)
+    );
+
+    // Catania is about 56 km away, Palermo about 190 km and Rome about 540 km.
+    let search = |client: &mut common::Client, radius: &str, unit: &str| {
+        client.call(&[
+            "GEOSEARCH", "sicily", "FROMLONLAT", "15", "37", "BYRADIUS", radius, unit, "ASC",
+        ])
+    };
+    assert_eq!(search(&mut client, "100", "km"), Reply::Array(vec![Reply::bulk("Catania")]));
+    assert_eq!(
+        search(&mut client, "200", "km"),
+        Reply::Array(vec![Reply::bulk("Catania"), Reply::bulk("Palermo")])
+    );
+    assert_eq!(
+        search(&mut client, "200000", "m"),
+        Reply::Array(vec![Reply::bulk("Catania"), Reply::bulk("Palermo")])
+    );
+    assert_eq!(search(&mut client, "10", "km"), Reply::Array(vec![]));
+    assert_eq!(
+        client.call(&["GEOSEARCH", "missing", "FROMLONLAT", "15", "37", "BYRADIUS", "100", "km"]),
+        Reply::Array(vec![])
+    );
+}
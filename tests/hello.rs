@@ -0,0 +1,59 @@
+// tests/hello.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn hello_3_replies_with_a_map() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    match client.call(&["HELLO", "3"]) {
+        Reply::Map(fields) => {
+            assert!(fields.contains(&(Reply::bulk("server"), Reply::bulk("nimblecache"))));
+            assert!(fields.contains(&(Reply::bulk("proto"), Reply::Integer(3))));
+            assert!(fields.contains(&(Reply::bulk("role"), Reply::bulk("master"))));
+        }
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn hello_2_replies_with_a_flat_array() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    match client.call(&["HELLO", "2"]) {
+        Reply::Array(fields) => {
+            assert_eq!(fields[0], Reply::bulk("server"));
+            assert_eq!(fields[1], Reply::bulk("nimblecache"));
+            let proto = fields.iter().position(|f| *f == Reply::bulk("proto")).unwrap();
+            assert_eq!(fields[proto + 1], Reply::Integer(2));
+        }
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn pubsub_messages_are_pushes_in_resp3_and_arrays_in_resp2() {
+    let server = Server::start(&[]);
+    let mut resp2 = server.connect();
+    let mut resp3 = server.connect();
+    let mut publisher = server.connect();
+
+    resp3.call(&["HELLO", "3"]);
+    assert_eq!(
+        resp2.call(&["SUBSCRIBE", "ch"]),
+        Reply::Array(vec![Reply::bulk("subscribe"), Reply::bulk("ch"), Reply::Integer(1)])
+    );
+    assert_eq!(
+        resp3.call(&["SUBSCRIBE", "ch"]),
+        Reply::Push(vec![Reply::bulk("subscribe"), Reply::bulk("ch"), Reply::Integer(1)])
+    );
+
+    assert_eq!(publisher.call(&["PUBLISH", "ch", "hi"]), Reply::Integer(2));
+    let message = vec![Reply::bulk("message"), Reply::bulk("ch"), Reply::bulk("hi")];
+    assert_eq!(resp2.read(), Reply::Array(message.clone()));
+    assert_eq!(resp3.read(), Reply::Push(message));
+}
@@ -0,0 +1,49 @@
+// tests/help.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+/// Runs the HELP subcommand of the command, and returns the usage lines.
+fn help(server: &Server, command: &str) -> Vec<String> {
+    match server.connect().call(&[command, "HELP"]) {
+        Reply::Array(lines) => lines
+            .into_iter()
+            .map(|line| match line {
+                Reply::Simple(line) => line,
+                line => panic!("unexpected line: {:?}", line),
+            })
+            .collect(),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn client_help_lists_the_subcommands() {
+    let server = Server::start(&[]);
+    let lines = help(&server, "CLIENT");
+    assert!(lines[0].starts_with("CLIENT <subcommand>"), "{:?}", lines);
+    assert!(lines.iter().any(|line| line.starts_with("GETNAME")), "{:?}", lines);
+    assert!(lines.iter().any(|line| line.starts_with("SETNAME")), "{:?}", lines);
+}
+
+#[test]
+fn help_is_available_for_every_command_with_subcommands() {
+    let server = Server::start(&[]);
+    for (command, subcommand) in [
+        ("OBJECT", "ENCODING"),
+        ("CONFIG", "GET"),
+        ("COMMAND", "GETKEYS"),
+        ("MEMORY", "USAGE"),
+    ] {
+        let lines = help(&server, command);
+        assert!(lines[0].starts_with(command), "{:?}", lines);
+        assert!(
+            lines.iter().any(|line| line.starts_with(subcommand)),
+            "{} HELP should mention {}: {:?}",
+            command,
+            subcommand,
+            lines
+        );
+    }
+}
@@ -0,0 +1,45 @@
+// tests/hstrlen.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn hstrlen_returns_the_length_of_the_value() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(
+        client.call(&["HSET", "h", "f", "hello", "empty", "", "utf8", "é"]),
+        Reply::Integer(3)
+    );
+    assert_eq!(client.call(&["HSTRLEN", "h", "f"]), Reply::Integer(5));
+    assert_eq!(client.call(&["HSTRLEN", "h", "empty"]), Reply::Integer(0));
+    // The length is in bytes.
+    assert_eq!(client.call(&["HSTRLEN", "h", "utf8"]), Reply::Integer(2));
+}
+
+#[test]
+fn hstrlen_of_a_missing_field_or_key_is_0() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["HSET", "h", "f", "v"]), Reply::Integer(1));
+    assert_eq!(client.call(&["HSTRLEN", "h", "missing"]), Reply::Integer(0));
+    assert_eq!(client.call(&["HSTRLEN", "missing", "f"]), Reply::Integer(0));
+}
+
+#[test]
+fn hstrlen_of_a_key_holding_another_type_is_an_error() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SET", "s", "v"]), Reply::bulk("OK"));
+    assert_eq!(
+        client.call(&["HSTRLEN", "s", "f"]),
+        Reply::Error(String::from(
+            "WRONGTYPE Operation against a key holding the wrong kind of value"
+        ))
+    );
+    assert!(matches!(client.call(&["HSTRLEN", "h"]), Reply::Error(_)));
+}
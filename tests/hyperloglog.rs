@@ -0,0 +1,47 @@
+// tests/hyperloglog.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn pfcount_is_within_tolerance_of_the_distinct_elements_added() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let elements: Vec<String> = (0..10_000).map(|i| format!("element:{}", i)).collect();
+    for batch in elements.chunks(500) {
+        let mut cmd = vec!["PFADD", "hll"];
+        cmd.extend(batch.iter().map(|e| e.as_str()));
+        assert_eq!(client.call(&cmd), Reply::Integer(1));
+    }
+
+    match client.call(&["PFCOUNT", "hll"]) {
+        Reply::Integer(count) => assert!((9_700..=10_300).contains(&count), "{}", count),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn pfadd_replies_whether_the_estimate_changed() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["PFADD", "hll", "a", "b"]), Reply::Integer(1));
+    assert_eq!(client.call(&["PFADD", "hll", "a"]), Reply::Integer(0));
+    assert_eq!(client.call(&["PFCOUNT", "hll", "missing"]), Reply::Integer(2));
+}
+
+#[test]
+fn hyperloglog_commands_reject_plain_strings() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SET", "s", "x"]);
+
+    for cmd in [&["PFADD", "s", "a"][..], &["PFCOUNT", "s"]] {
+        match client.call(cmd) {
+            Reply::Error(e) => assert!(e.starts_with("WRONGTYPE"), "{}", e),
+            reply => panic!("unexpected reply: {:?}", reply),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// tests/incr.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn incr_increments_the_integer_stored_at_the_key() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["INCR", "n"]), Reply::Integer(1));
+    assert_eq!(client.call(&["INCR", "n"]), Reply::Integer(2));
+    assert_eq!(client.call(&["GET", "n"]), Reply::bulk("2"));
+
+    client.call(&["SET", "n", "-10"]);
+    assert_eq!(client.call(&["INCR", "n"]), Reply::Integer(-9));
+    assert_eq!(
+        client.call(&["OBJECT", "ENCODING", "n"]),
+        Reply::bulk("int")
+    );
+}
+
+#[test]
+fn incr_rejects_values_which_are_not_integers() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    for value in ["a", "1.5", "9223372036854775808"] {
+        client.call(&["SET", "n", value]);
+        assert_eq!(
+            client.call(&["INCR", "n"]),
+            Reply::Error(String::from("ERR value is not an integer or out of range"))
+        );
+        assert_eq!(client.call(&["GET", "n"]), Reply::bulk(value));
+    }
+
+    client.call(&["SET", "n", "9223372036854775807"]);
+    assert_eq!(
+        client.call(&["INCR", "n"]),
+        Reply::Error(String::from("ERR increment or decrement would overflow"))
+    );
+
+    client.call(&["LPUSH", "l", "a"]);
+    assert!(matches!(client.call(&["INCR", "l"]), Reply::Error(e) if e.starts_with("WRONGTYPE")));
+}
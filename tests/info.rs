@@ -0,0 +1,67 @@
+// tests/info.rs
+
+mod common;
+
+use common::{wait_until, Client, Reply, Server};
+
+/// Returns the INFO section with the given name.
+fn info(client: &mut Client, section: &str) -> String {
+    match client.call(&["INFO", section]) {
+        Reply::Bulk(info) => info,
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn info_clients_counts_the_connected_clients() {
+    let server = Server::start(&["--maxclients", "50"]);
+    let mut client = server.connect();
+    let other = server.connect();
+
+    let clients = info(&mut client, "clients");
+    assert!(clients.starts_with("# Clients\r\n"), "{}", clients);
+    assert!(clients.contains("\r\nconnected_clients:2\r\n"), "{}", clients);
+    assert!(clients.contains("\r\nblocked_clients:0\r\n"), "{}", clients);
+    assert!(clients.contains("\r\nmaxclients:50\r\n"), "{}", clients);
+
+    drop(other);
+    wait_until(|| info(&mut client, "clients").contains("\r\nconnected_clients:1\r\n"));
+}
+
+/// Returns the run id reported by INFO server.
+fn run_id(client: &mut Client) -> String {
+    let server = info(client, "server");
+    let run_id = server
+        .split("\r\n")
+        .find_map(|line| line.strip_prefix("run_id:"))
+        .unwrap_or_else(|| panic!("INFO server should report the run id: {}", server));
+    run_id.to_string()
+}
+
+#[test]
+fn info_server_reports_a_stable_run_id() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let id = run_id(&mut client);
+    assert_eq!(id.len(), 40, "{}", id);
+    assert!(
+        id.chars().all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)),
+        "{}",
+        id
+    );
+    assert_eq!(run_id(&mut client), id);
+    assert_eq!(run_id(&mut server.connect()), id);
+
+    assert_eq!(
+        client.call(&["DEBUG", "CHANGE-REPL-ID"]),
+        Reply::Simple(String::from("OK"))
+    );
+    let changed = run_id(&mut client);
+    assert_eq!(changed.len(), 40, "{}", changed);
+    assert_ne!(changed, id);
+
+    // Another server gets its own run id.
+    let other = Server::start(&[]);
+    assert_ne!(run_id(&mut other.connect()), changed);
+}
@@ -0,0 +1,23 @@
+// tests/io_threads.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn server_runs_with_the_given_number_of_io_threads() {
+    let server = Server::start(&["--io-threads", "2"]);
+    let mut clients: Vec<_> = (0..4).map(|_| server.connect()).collect();
+
+    for (i, client) in clients.iter_mut().enumerate() {
+        let value = i.to_string();
+        assert_eq!(client.call(&["SET", &value, &value]), Reply::bulk("OK"));
+    }
+    for (i, client) in clients.iter_mut().enumerate() {
+        assert_eq!(client.call(&["GET", &i.to_string()]), Reply::bulk(&i.to_string()));
+    }
+    assert_eq!(
+        clients[0].call(&["CONFIG", "GET", "io-threads"]),
+        Reply::Array(vec![Reply::bulk("io-threads"), Reply::bulk("2")])
+    );
+}
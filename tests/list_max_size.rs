@@ -0,0 +1,29 @@
+// tests/list_max_size.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn pushes_over_the_list_max_size_are_rejected() {
+    let server = Server::start(&["--list-max-size", "3"]);
+    let mut client = server.connect();
+    let rejected = Reply::Error(String::from("ERR list exceeds configured maximum size"));
+
+    assert_eq!(client.call(&["RPUSH", "l", "a", "b"]), Reply::Integer(2));
+    // The whole push is rejected, even if part of it would fit.
+    assert_eq!(client.call(&["RPUSH", "l", "c", "d"]), rejected);
+    assert_eq!(client.call(&["LPUSH", "l", "c"]), Reply::Integer(3));
+    assert_eq!(client.call(&["LPUSH", "l", "d"]), rejected);
+    assert_eq!(client.call(&["RPUSH", "l", "d"]), rejected);
+    assert_eq!(
+        client.call(&["LRANGE", "l", "0", "10"]),
+        Reply::Array(vec![Reply::bulk("c"), Reply::bulk("a"), Reply::bulk("b")])
+    );
+    assert_eq!(client.call(&["RPUSH", "other", "a", "b", "c", "d"]), rejected);
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(1));
+
+    // 0 lifts the cap.
+    client.call(&["CONFIG", "SET", "list-max-size", "0"]);
+    assert_eq!(client.call(&["RPUSH", "l", "d"]), Reply::Integer(4));
+}
@@ -0,0 +1,24 @@
+// tests/logfile.rs
+
+mod common;
+
+use std::{env, fs, process};
+
+use common::{wait_until, Server};
+
+#[test]
+fn logs_are_appended_to_the_logfile() {
+    let path = env::temp_dir().join(format!("nimblecache-test-{}.log", process::id()));
+    fs::write(&path, "previous run\n").unwrap();
+
+    let server = Server::start_with_env(
+        &["--logfile", path.to_str().unwrap()],
+        &[("RUST_LOG", "info")],
+    );
+    let listening = format!("TCP Listener started on 127.0.0.1:{}", server.port);
+    wait_until(|| fs::read_to_string(&path).unwrap().contains(&listening));
+    assert!(fs::read_to_string(&path).unwrap().starts_with("previous run\n"));
+
+    drop(server);
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,66 @@
+// tests/lpos.rs
+
+mod common;
+
+use common::{Client, Reply, Server};
+
+fn list(server: &Server) -> Client {
+    let mut client = server.connect();
+    assert_eq!(
+        client.call(&["RPUSH", "l", "a", "b", "c", "b", "d", "b"]),
+        Reply::Integer(6)
+    );
+    client
+}
+
+fn indexes(indexes: &[i64]) -> Reply {
+    Reply::Array(indexes.iter().map(|i| Reply::Integer(*i)).collect())
+}
+
+#[test]
+fn lpos_returns_the_index_of_the_first_match() {
+    let server = Server::start(&[]);
+    let mut client = list(&server);
+    assert_eq!(client.call(&["LPOS", "l", "b"]), Reply::Integer(1));
+    assert_eq!(client.call(&["LPOS", "l", "d"]), Reply::Integer(4));
+    assert_eq!(client.call(&["LPOS", "l", "b", "RANK", "2"]), Reply::Integer(3));
+}
+
+#[test]
+fn lpos_with_count_returns_every_match() {
+    let server = Server::start(&[]);
+    let mut client = list(&server);
+    assert_eq!(client.call(&["LPOS", "l", "b", "COUNT", "2"]), indexes(&[1, 3]));
+    assert_eq!(client.call(&["LPOS", "l", "b", "COUNT", "0"]), indexes(&[1, 3, 5]));
+    assert_eq!(
+        client.call(&["LPOS", "l", "b", "COUNT", "0", "MAXLEN", "4"]),
+        indexes(&[1, 3])
+    );
+    assert_eq!(client.call(&["LPOS", "l", "x", "COUNT", "0"]), indexes(&[]));
+}
+
+#[test]
+fn lpos_with_a_negative_rank_scans_from_the_tail() {
+    let server = Server::start(&[]);
+    let mut client = list(&server);
+    assert_eq!(client.call(&["LPOS", "l", "b", "RANK", "-1"]), Reply::Integer(5));
+    assert_eq!(client.call(&["LPOS", "l", "b", "RANK", "-2"]), Reply::Integer(3));
+    assert_eq!(
+        client.call(&["LPOS", "l", "b", "RANK", "-1", "COUNT", "0"]),
+        indexes(&[5, 3, 1])
+    );
+    assert!(matches!(
+        client.call(&["LPOS", "l", "b", "RANK", "0"]),
+        Reply::Error(_)
+    ));
+}
+
+#[test]
+fn lpos_returns_null_without_a_match() {
+    let server = Server::start(&[]);
+    let mut client = list(&server);
+    assert_eq!(client.call(&["LPOS", "l", "x"]), Reply::Null);
+    assert_eq!(client.call(&["LPOS", "l", "b", "RANK", "4"]), Reply::Null);
+    assert_eq!(client.call(&["LPOS", "l", "d", "MAXLEN", "4"]), Reply::Null);
+    assert_eq!(client.call(&["LPOS", "missing", "b"]), Reply::Null);
+}
@@ -0,0 +1,68 @@
+// tests/lrange.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn lrange_returns_the_range() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["RPUSH", "l", "a", "b", "c"]);
+
+    assert_eq!(
+        client.call(&["LRANGE", "l", "1", "10"]),
+        Reply::Array(vec![Reply::bulk("b"), Reply::bulk("c")])
+    );
+    assert_eq!(
+        client.call(&["LRANGE", "missing", "0", "10"]),
+        Reply::Array(vec![])
+    );
+    client.call(&["SET", "s", "v"]);
+    match client.call(&["LRANGE", "s", "0", "10"]) {
+        Reply::Error(e) => assert!(e.starts_with("WRONGTYPE"), "{}", e),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+/// Returns the value of a field of `/proc/<pid>/status`, in bytes.
+#[cfg(target_os = "linux")]
+fn memory_status(pid: u32, field: &str) -> usize {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).unwrap();
+    let line = status.lines().find(|line| line.starts_with(field)).unwrap();
+    let kb: usize = line.split_whitespace().nth(1).unwrap().parse().unwrap();
+    kb * 1024
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn lrange_of_a_large_list_uses_memory_in_proportion_to_the_reply() {
+    const LEN: usize = 500_000;
+
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    let elements: Vec<String> = (0..LEN).map(|i| format!("element:{:08}", i)).collect();
+    for batch in elements.chunks(10_000) {
+        let mut args = vec!["RPUSH", "l"];
+        args.extend(batch.iter().map(|element| element.as_str()));
+        client.call(&args);
+    }
+
+    let before = memory_status(server.pid(), "VmRSS:");
+    let stop = (LEN - 1).to_string();
+    match client.call(&["LRANGE", "l", "0", &stop]) {
+        Reply::Array(reply) => assert_eq!(reply.len(), LEN),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    let peak = memory_status(server.pid(), "VmHWM:");
+
+    // `*500000\r\n`, then `$16\r\n<element>\r\n` for each element.
+    let reply_len = 10 + LEN * 23;
+    let used = peak.saturating_sub(before);
+    assert!(
+        used < 3 * reply_len,
+        "{} bytes used for a reply of {} bytes",
+        used,
+        reply_len
+    );
+}
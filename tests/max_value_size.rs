@@ -0,0 +1,48 @@
+// tests/max_value_size.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn values_over_the_max_value_size_are_rejected() {
+    let server = Server::start(&["--max-value-size", "5"]);
+    let mut client = server.connect();
+    let rejected = Reply::Error(String::from("ERR value too large"));
+
+    assert_eq!(client.call(&["SET", "k", "12345"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["SET", "k", "123456"]), rejected);
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("12345"));
+
+    // The whole push is rejected if any of the values is too large.
+    assert_eq!(client.call(&["LPUSH", "l", "a", "123456"]), rejected);
+    assert_eq!(client.call(&["RPUSH", "l", "123456"]), rejected);
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(1));
+    assert_eq!(client.call(&["RPUSH", "l", "a", "12345"]), Reply::Integer(2));
+
+    // 0 lifts the limit.
+    assert_eq!(
+        client.call(&["CONFIG", "SET", "max-value-size", "0"]),
+        Reply::Simple(String::from("OK"))
+    );
+    assert_eq!(client.call(&["SET", "k", &"v".repeat(1000)]), Reply::bulk("OK"));
+}
+
+#[test]
+fn max_value_size_can_be_set_at_runtime() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(
+        client.call(&["CONFIG", "GET", "max-value-size"]),
+        Reply::Array(vec![Reply::bulk("max-value-size"), Reply::bulk("0")])
+    );
+    assert_eq!(
+        client.call(&["CONFIG", "SET", "max-value-size", "3"]),
+        Reply::Simple(String::from("OK"))
+    );
+    assert_eq!(
+        client.call(&["SET", "k", "1234"]),
+        Reply::Error(String::from("ERR value too large"))
+    );
+}
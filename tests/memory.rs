@@ -0,0 +1,64 @@
+// tests/memory.rs
+
+mod common;
+
+use common::{Client, Reply, Server};
+
+/// Returns the MEMORY USAGE of the key, which must exist.
+fn usage(client: &mut Client, key: &str) -> i64 {
+    match client.call(&["MEMORY", "USAGE", key]) {
+        Reply::Integer(bytes) => bytes,
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn memory_usage_grows_with_the_value() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.call(&["SET", "small", "v"]);
+    client.call(&["SET", "large", &"v".repeat(10000)]);
+    let small = usage(&mut client, "small");
+    let large = usage(&mut client, "large");
+    assert!(small > 0);
+    assert!(large > small + 9000, "{} {}", large, small);
+
+    client.call(&["RPUSH", "short", "a"]);
+    client.call(&["RPUSH", "long", "a", "b", "c", "d", "e", "f", "g", "h"]);
+    assert!(usage(&mut client, "long") > usage(&mut client, "short"));
+
+    assert_eq!(client.call(&["MEMORY", "USAGE", "missing"]), Reply::Null);
+    assert!(matches!(
+        client.call(&["MEMORY", "USAGE", "large", "SAMPLES", "5"]),
+        Reply::Integer(bytes) if bytes == large
+    ));
+}
+
+#[test]
+fn memory_stats_and_doctor_report_on_the_dataset() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let Reply::Bulk(doctor) = client.call(&["MEMORY", "DOCTOR"]) else {
+        panic!("MEMORY DOCTOR should reply with a bulk string");
+    };
+    assert!(!doctor.is_empty());
+
+    client.call(&["SET", "k", &"v".repeat(1000)]);
+    let Reply::Array(stats) = client.call(&["MEMORY", "STATS"]) else {
+        panic!("MEMORY STATS should reply with an array");
+    };
+    assert!(!stats.is_empty() && stats.len() % 2 == 0, "{:?}", stats);
+    let stat = |name: &str| {
+        stats
+            .chunks(2)
+            .find(|pair| pair[0] == Reply::bulk(name))
+            .map(|pair| pair[1].clone())
+    };
+    assert_eq!(stat("keys.count"), Some(Reply::Integer(1)));
+    match stat("dataset.bytes") {
+        Some(Reply::Integer(bytes)) => assert!(bytes >= 1000, "{}", bytes),
+        stat => panic!("unexpected dataset.bytes: {:?}", stat),
+    }
+}
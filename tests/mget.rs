@@ -0,0 +1,36 @@
+// tests/mget.rs
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use common::{Reply, Server};
+
+#[test]
+fn mget_returns_null_for_expired_and_missing_keys() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SET", "short", "a", "PX", "100"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["SET", "persistent", "b"]), Reply::bulk("OK"));
+    thread::sleep(Duration::from_millis(200));
+
+    assert_eq!(
+        client.call(&["MGET", "short", "persistent", "missing"]),
+        Reply::Array(vec![Reply::Null, Reply::bulk("b"), Reply::Null])
+    );
+    assert_eq!(client.call(&["GET", "short"]), Reply::Null);
+}
+
+#[test]
+fn mget_returns_null_for_keys_holding_another_type() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["RPUSH", "l", "a"]), Reply::Integer(1));
+    assert_eq!(client.call(&["SET", "k", "v"]), Reply::bulk("OK"));
+    assert_eq!(
+        client.call(&["MGET", "l", "k"]),
+        Reply::Array(vec![Reply::Null, Reply::bulk("v")])
+    );
+}
@@ -0,0 +1,49 @@
+// tests/monitor.rs
+
+mod common;
+
+use common::{Client, Reply, Server};
+
+/// Reads the next line sent to the monitoring client, without the time and the address
+/// of the client.
+fn monitored(monitor: &mut Client) -> String {
+    match monitor.read() {
+        Reply::Simple(line) => match line.split_once("] ") {
+            Some((_, command)) => command.to_string(),
+            None => panic!("unexpected line: {}", line),
+        },
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn monitor_quotes_the_arguments() {
+    let server = Server::start(&[]);
+    let mut monitor = server.connect();
+    let mut client = server.connect();
+
+    assert_eq!(monitor.call(&["MONITOR"]), Reply::Simple(String::from("OK")));
+
+    client.call(&["SET", "k", "hello world"]);
+    assert_eq!(monitored(&mut monitor), "\"SET\" \"k\" \"hello world\"");
+
+    client.call(&["SET", "k", "say \"hi\"\n"]);
+    assert_eq!(monitored(&mut monitor), "\"SET\" \"k\" \"say \\\"hi\\\"\\n\"");
+}
+
+#[test]
+fn monitor_shows_commands_failing_with_an_error() {
+    let server = Server::start(&[]);
+    let mut monitor = server.connect();
+    let mut client = server.connect();
+
+    assert_eq!(monitor.call(&["MONITOR"]), Reply::Simple(String::from("OK")));
+
+    assert!(matches!(client.call(&["NOSUCHCOMMAND", "a b"]), Reply::Error(_)));
+    assert_eq!(monitored(&mut monitor), "\"NOSUCHCOMMAND\" \"a b\"");
+
+    client.call(&["LPUSH", "l", "a"]);
+    assert!(matches!(client.call(&["GET", "l"]), Reply::Error(_)));
+    assert_eq!(monitored(&mut monitor), "\"LPUSH\" \"l\" \"a\"");
+    assert_eq!(monitored(&mut monitor), "\"GET\" \"l\"");
+}
@@ -0,0 +1,80 @@
+// tests/object.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn object_encoding_reports_intsets_up_to_the_configured_size() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.call(&["SADD", "ints", "1", "2", "3"]);
+    client.call(&["SADD", "mixed", "1", "a"]);
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "ints"]), Reply::bulk("intset"));
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "mixed"]), Reply::bulk("hashtable"));
+
+    assert_eq!(
+        client.call(&["CONFIG", "SET", "set-max-intset-entries", "2"]),
+        Reply::Simple(String::from("OK"))
+    );
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "ints"]), Reply::bulk("hashtable"));
+}
+
+#[test]
+fn object_encoding_reports_int_embstr_and_raw_strings() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    for (value, encoding) in [
+        (String::from("12345"), "int"),
+        (String::from("-42"), "int"),
+        (String::from("9223372036854775807"), "int"),
+        // Not in the canonical form of an integer, or out of range.
+        (String::from("007"), "embstr"),
+        (String::from("9223372036854775808"), "embstr"),
+        (String::from("hello"), "embstr"),
+        ("x".repeat(44), "embstr"),
+        ("x".repeat(45), "raw"),
+    ] {
+        client.call(&["SET", "k", &value]);
+        assert_eq!(
+            client.call(&["OBJECT", "ENCODING", "k"]),
+            Reply::bulk(encoding),
+            "{}",
+            value
+        );
+    }
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "missing"]), Reply::Null);
+}
+
+#[test]
+fn strings_modified_in_place_are_raw() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.call(&["SET", "k", "123"]);
+    assert_eq!(client.call(&["APPEND", "k", "a"]), Reply::Integer(4));
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "k"]), Reply::bulk("raw"));
+
+    // Even if the string is still an integer.
+    client.call(&["SET", "k", "123"]);
+    assert_eq!(client.call(&["APPEND", "k", "4"]), Reply::Integer(4));
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "k"]), Reply::bulk("raw"));
+
+    client.call(&["SET", "k", "123"]);
+    assert_eq!(client.call(&["SETRANGE", "k", "1", "x"]), Reply::Integer(3));
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("1x3"));
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "k"]), Reply::bulk("raw"));
+
+    // A new value gets its encoding from scratch.
+    client.call(&["SET", "k", "123"]);
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "k"]), Reply::bulk("int"));
+
+    // Like in Redis, APPEND to a missing key stores the value as SET does, while SETRANGE
+    // builds the string in place.
+    client.call(&["APPEND", "new", "1"]);
+    client.call(&["SETRANGE", "other", "0", "1"]);
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "new"]), Reply::bulk("int"));
+    assert_eq!(client.call(&["OBJECT", "ENCODING", "other"]), Reply::bulk("raw"));
+}
@@ -0,0 +1,27 @@
+// tests/output_buffer.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn client_is_closed_when_its_output_buffer_exceeds_the_hard_limit() {
+    let server = Server::start(&["--client-output-buffer-limit", "65536", "0", "0"]);
+    let mut client = server.connect();
+
+    let value = "x".repeat(1024 * 1024);
+    assert_eq!(client.call(&["SET", "big", &value]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["SET", "small", "v"]), Reply::bulk("OK"));
+
+    // Responses under the limit are sent as usual.
+    assert_eq!(client.call(&["GET", "small"]), Reply::bulk("v"));
+
+    // The client doesn't read its responses, which pile up over the hard limit.
+    for _ in 0..4 {
+        client.send(&["GET", "big"]);
+    }
+    assert!(client.is_closed());
+
+    // Other clients are unaffected.
+    assert_eq!(server.connect().call(&["GET", "small"]), Reply::bulk("v"));
+}
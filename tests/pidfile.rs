@@ -0,0 +1,23 @@
+// tests/pidfile.rs
+
+mod common;
+
+use std::{env, fs, process};
+
+use common::{wait_until, Server};
+
+#[test]
+fn pidfile_holds_the_pid_and_is_removed_on_shutdown() {
+    let path = env::temp_dir().join(format!("nimblecache-test-{}.pid", process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut server = Server::start(&["--pidfile", path.to_str().unwrap()]);
+    // The listener is bound before the pidfile is written.
+    wait_until(|| fs::read_to_string(&path).is_ok_and(|pid| pid.ends_with('\n')));
+    let pid = fs::read_to_string(&path).unwrap();
+    assert_eq!(pid.trim(), server.pid().to_string());
+
+    server.connect().send(&["SHUTDOWN"]);
+    assert!(server.wait_for_exit().success());
+    assert!(!path.exists(), "the pidfile should be removed on shutdown");
+}
@@ -0,0 +1,33 @@
+// tests/ping.rs
+
+mod common;
+
+use common::{encode, Reply, Server};
+
+#[test]
+fn pipelined_pings_keep_their_reply_types() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let mut pipeline = encode(&["PING"]);
+    pipeline.extend(encode(&["PING", "hello"]));
+    client.send_raw(&pipeline);
+
+    assert_eq!(client.read(), Reply::Simple(String::from("PONG")));
+    assert_eq!(client.read(), Reply::bulk("hello"));
+}
+
+#[test]
+fn pings_in_a_transaction_keep_their_reply_types() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.call(&["MULTI"]);
+    client.call(&["PING"]);
+    client.call(&["PING", "hello"]);
+
+    assert_eq!(
+        client.call(&["EXEC"]),
+        Reply::Array(vec![Reply::Simple(String::from("PONG")), Reply::bulk("hello")])
+    );
+}
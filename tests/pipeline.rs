@@ -0,0 +1,55 @@
+// tests/pipeline.rs
+
+mod common;
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::{encode, Reply, Server};
+
+const COMMANDS: usize = 100_000;
+
+#[test]
+fn long_pipelines_dont_starve_other_connections() {
+    // With a single worker thread, the other connections are only served if the pipelining
+    // connection yields.
+    let server = Server::start(&["--io-threads", "1", "--commands-per-yield", "16"]);
+    let mut other = server.connect();
+
+    let stream = TcpStream::connect(("127.0.0.1", server.port)).unwrap();
+    let mut writer = stream.try_clone().unwrap();
+    let sender = thread::spawn(move || {
+        let incr = encode(&["INCR", "n"]);
+        writer.write_all(&incr.repeat(COMMANDS)).unwrap();
+    });
+    let receiver = thread::spawn(move || {
+        let expected: Vec<u8> = (1..=COMMANDS)
+            .flat_map(|i| format!(":{}\r\n", i).into_bytes())
+            .collect();
+        let mut replies = vec![0; expected.len()];
+        (&stream).read_exact(&mut replies).unwrap();
+        // The replies come in the order of the commands.
+        assert!(replies == expected, "the replies should be in order");
+    });
+
+    let mut pings = 0;
+    while !receiver.is_finished() {
+        let started = Instant::now();
+        assert_eq!(other.call(&["PING"]), Reply::Simple(String::from("PONG")));
+        assert!(
+            started.elapsed() < Duration::from_millis(500),
+            "PING took {:?} during the pipeline",
+            started.elapsed()
+        );
+        pings += 1;
+    }
+    sender.join().unwrap();
+    receiver.join().unwrap();
+
+    assert!(pings > 0);
+    assert_eq!(other.call(&["GET", "n"]), Reply::bulk(&COMMANDS.to_string()));
+}
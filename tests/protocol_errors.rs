@@ -0,0 +1,142 @@
+// tests/protocol_errors.rs
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{Reply, Server};
+
+#[test]
+fn connection_recovers_after_a_malformed_command() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.send_raw(b"*1\r\n+PING\r\n");
+    match client.read() {
+        Reply::Error(e) => assert!(e.starts_with("ERR Protocol error"), "{}", e),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
+
+#[test]
+fn malformed_command_inside_multi_aborts_exec() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.call(&["MULTI"]);
+    client.send_raw(b"*2\r\n$3\r\nGET\r\n:1\r\n");
+    assert!(matches!(client.read(), Reply::Error(_)));
+    // The transaction is still open, so the next commands are queued and not run.
+    assert_eq!(
+        client.call(&["SET", "k", "v"]),
+        Reply::Simple(String::from("QUEUED"))
+    );
+    match client.call(&["EXEC"]) {
+        Reply::Error(e) => assert!(e.starts_with("EXECABORT"), "{}", e),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    assert_eq!(client.call(&["GET", "k"]), Reply::Null);
+
+    // The next transaction starts clean.
+    client.call(&["MULTI"]);
+    client.call(&["SET", "k", "v"]);
+    assert_eq!(client.call(&["EXEC"]), Reply::Array(vec![Reply::bulk("OK")]));
+}
+
+#[test]
+fn unknown_command_inside_multi_aborts_exec() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.call(&["MULTI"]);
+    assert!(matches!(client.call(&["NOSUCHCOMMAND"]), Reply::Error(_)));
+    client.call(&["SET", "k", "v"]);
+    match client.call(&["EXEC"]) {
+        Reply::Error(e) => assert!(e.starts_with("EXECABORT"), "{}", e),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    assert_eq!(client.call(&["GET", "k"]), Reply::Null);
+}
+
+#[test]
+fn empty_commands_are_ignored() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    // No reply is sent for the empty commands, so the first reply is PING's.
+    client.send_raw(b"*0\r\n*0\r\n");
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+    client.send_raw(b"*0\r\n*1\r\n$4\r\nPING\r\n*0\r\n");
+    assert_eq!(client.read(), Reply::Simple(String::from("PONG")));
+    assert_eq!(client.call(&["SET", "k", "v"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("v"));
+}
+
+#[test]
+fn arguments_of_a_malformed_command_are_not_taken_as_commands() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    // The value looks like a PING command, but it's skipped along with the malformed SET.
+    client.send_raw(b"*3\r\n$3\r\nSET\r\n:1\r\n$14\r\n*1\r\n$4\r\nPING\r\n\r\n");
+    match client.read() {
+        Reply::Error(e) => assert!(e.starts_with("ERR Protocol error"), "{}", e),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+
+    // So each reply still matches its request.
+    assert_eq!(client.call(&["SET", "k", "*1"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("*1"));
+}
+
+#[test]
+fn command_names_with_invalid_utf8_get_an_unknown_command_error() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    client.send_raw(b"*2\r\n$3\r\n\xff\xfeX\r\n$3\r\narg\r\n");
+    match client.read() {
+        Reply::Error(e) => assert_eq!(
+            e,
+            "ERR unknown command '\u{fffd}\u{fffd}X', with args beginning with: 'arg' "
+        ),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
+
+#[test]
+fn bulk_strings_without_their_terminator_get_an_error_at_once() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    // Nothing follows the data, so the error can't wait for more of it.
+    client.send_raw(b"*2\r\n$3\r\nGET\r\n$1\r\nkX");
+    let started = Instant::now();
+    assert_eq!(
+        client.read(),
+        Reply::Error(String::from(
+            "ERR Protocol error: bulk string is not terminated by CRLF"
+        ))
+    );
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    client.send_raw(b"\r\n");
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
+
+#[test]
+fn unknown_commands_are_reported_with_their_arguments() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(
+        client.call(&["FOO", "bar", "baz"]),
+        Reply::Error(String::from(
+            "ERR unknown command 'FOO', with args beginning with: 'bar' 'baz' "
+        ))
+    );
+    // The connection is still usable.
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
@@ -0,0 +1,141 @@
+// tests/pubsub.rs
+
+mod common;
+
+use common::{wait_until, Reply, Server};
+
+/// The confirmation sent for each channel or pattern by the (un)subscribe commands.
+fn confirmation(kind: &str, channel: &str, count: i64) -> Reply {
+    Reply::Array(vec![Reply::bulk(kind), Reply::bulk(channel), Reply::Integer(count)])
+}
+
+#[test]
+fn subscription_count_is_running_across_subscribe_calls() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SUBSCRIBE", "a"]), confirmation("subscribe", "a", 1));
+    client.send(&["SUBSCRIBE", "b", "c"]);
+    assert_eq!(client.read(), confirmation("subscribe", "b", 2));
+    assert_eq!(client.read(), confirmation("subscribe", "c", 3));
+
+    // Subscribing to the same channel again doesn't count it twice, while patterns count
+    // along with channels.
+    assert_eq!(client.call(&["SUBSCRIBE", "a"]), confirmation("subscribe", "a", 3));
+    assert_eq!(client.call(&["PSUBSCRIBE", "p*"]), confirmation("psubscribe", "p*", 4));
+    assert_eq!(client.call(&["UNSUBSCRIBE", "b"]), confirmation("unsubscribe", "b", 3));
+}
+
+#[test]
+fn publish_counts_channel_and_pattern_subscribers() {
+    let server = Server::start(&[]);
+    let mut exact = server.connect();
+    let mut pattern = server.connect();
+    let mut publisher = server.connect();
+
+    assert_eq!(exact.call(&["SUBSCRIBE", "news.tech"]), confirmation("subscribe", "news.tech", 1));
+    assert_eq!(pattern.call(&["PSUBSCRIBE", "news.*"]), confirmation("psubscribe", "news.*", 1));
+
+    assert_eq!(publisher.call(&["PUBLISH", "news.tech", "hi"]), Reply::Integer(2));
+    assert_eq!(
+        exact.read(),
+        Reply::Array(vec![Reply::bulk("message"), Reply::bulk("news.tech"), Reply::bulk("hi")])
+    );
+    assert_eq!(
+        pattern.read(),
+        Reply::Array(vec![
+            Reply::bulk("pmessage"),
+            Reply::bulk("news.*"),
+            Reply::bulk("news.tech"),
+            Reply::bulk("hi"),
+        ])
+    );
+
+    // Each matching pattern counts, even without a subscriber to the channel itself.
+    let mut all = server.connect();
+    assert_eq!(all.call(&["PSUBSCRIBE", "*"]), confirmation("psubscribe", "*", 1));
+    assert_eq!(publisher.call(&["PUBLISH", "news.sport", "hi"]), Reply::Integer(2));
+    assert_eq!(publisher.call(&["PUBLISH", "weather", "hi"]), Reply::Integer(1));
+    drop(all);
+    wait_until(|| publisher.call(&["PUBLISH", "weather", "hi"]) == Reply::Integer(0));
+}
+
+/// Returns the channels listed by PUBSUB CHANNELS, sorted.
+fn channels(reply: Reply) -> Vec<String> {
+    let Reply::Array(channels) = reply else {
+        panic!("unexpected reply: {:?}", reply);
+    };
+    let mut channels: Vec<String> = channels
+        .into_iter()
+        .map(|channel| match channel {
+            Reply::Bulk(channel) => channel,
+            channel => panic!("unexpected channel: {:?}", channel),
+        })
+        .collect();
+    channels.sort();
+    channels
+}
+
+#[test]
+fn pubsub_reports_the_subscriptions() {
+    let server = Server::start(&[]);
+    let mut first = server.connect();
+    let mut second = server.connect();
+    let mut client = server.connect();
+
+    first.send(&["SUBSCRIBE", "news.tech", "news.sport"]);
+    first.read();
+    first.read();
+    assert_eq!(second.call(&["SUBSCRIBE", "news.tech"]), confirmation("subscribe", "news.tech", 1));
+    second.send(&["PSUBSCRIBE", "news.*", "weather.*"]);
+    second.read();
+    second.read();
+    assert_eq!(first.call(&["PSUBSCRIBE", "news.*"]), confirmation("psubscribe", "news.*", 3));
+
+    assert_eq!(
+        channels(client.call(&["PUBSUB", "CHANNELS"])),
+        vec![String::from("news.sport"), String::from("news.tech")]
+    );
+    assert_eq!(
+        channels(client.call(&["PUBSUB", "CHANNELS", "*tech"])),
+        vec![String::from("news.tech")]
+    );
+    assert_eq!(
+        client.call(&["PUBSUB", "NUMSUB", "news.tech", "news.sport", "other"]),
+        Reply::Array(vec![
+            Reply::bulk("news.tech"),
+            Reply::Integer(2),
+            Reply::bulk("news.sport"),
+            Reply::Integer(1),
+            Reply::bulk("other"),
+            Reply::Integer(0),
+        ])
+    );
+    assert_eq!(client.call(&["PUBSUB", "NUMSUB"]), Reply::Array(vec![]));
+    // Patterns are counted once, however many clients subscribed to them.
+    assert_eq!(client.call(&["PUBSUB", "NUMPAT"]), Reply::Integer(2));
+
+    // Channels and patterns without subscribers anymore are no longer listed.
+    drop(first);
+    let remaining = vec![String::from("news.tech")];
+    wait_until(|| channels(client.call(&["PUBSUB", "CHANNELS"])) == remaining);
+    assert_eq!(client.call(&["PUBSUB", "NUMPAT"]), Reply::Integer(2));
+    assert_eq!(
+        second.call(&["PUNSUBSCRIBE", "weather.*"]),
+        confirmation("punsubscribe", "weather.*", 2)
+    );
+    assert_eq!(client.call(&["PUBSUB", "NUMPAT"]), Reply::Integer(1));
+}
+
+#[test]
+fn pubsub_rejects_unknown_subcommands() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["PUBSUB", "CHANNELS"]), Reply::Array(vec![]));
+    assert_eq!(client.call(&["PUBSUB", "NUMPAT"]), Reply::Integer(0));
+    assert_eq!(
+        client.call(&["PUBSUB", "FOO"]),
+        Reply::Error(String::from("ERR Unknown subcommand 'FOO' for 'PUBSUB' command"))
+    );
+}
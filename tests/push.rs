@@ -0,0 +1,37 @@
+// tests/push.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn pushing_without_values_is_an_arity_error() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    for command in ["LPUSH", "RPUSH"] {
+        assert_eq!(
+            client.call(&[command, "l"]),
+            Reply::Error(format!(
+                "ERR Wrong number of arguments specified for '{}' command",
+                command
+            ))
+        );
+    }
+
+    // No empty list is created.
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0));
+}
+
+#[test]
+fn pushing_values_returns_the_length_of_the_list() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["RPUSH", "l", "b", "c"]), Reply::Integer(2));
+    assert_eq!(client.call(&["LPUSH", "l", "a"]), Reply::Integer(3));
+    assert_eq!(
+        client.call(&["LRANGE", "l", "0", "10"]),
+        Reply::Array(vec![Reply::bulk("a"), Reply::bulk("b"), Reply::bulk("c")])
+    );
+}
@@ -0,0 +1,97 @@
+// tests/random.rs
+
+mod common;
+
+use std::collections::HashSet;
+
+use common::{Client, Reply, Server};
+
+/// Returns the bulk strings of an array reply.
+fn strings(reply: Reply) -> Vec<String> {
+    match reply {
+        Reply::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Reply::Bulk(s) => s,
+                item => panic!("unexpected item: {:?}", item),
+            })
+            .collect(),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+/// Creates the set `s` with members `a` to `e`, and the hash `h` with fields `a` to `e`,
+/// each holding its name in uppercase.
+fn populate(client: &mut Client) {
+    client.call(&["SADD", "s", "a", "b", "c", "d", "e"]);
+    client.call(&["HSET", "h", "a", "A", "b", "B", "c", "C", "d", "D", "e", "E"]);
+}
+
+#[test]
+fn positive_counts_return_distinct_elements() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    populate(&mut client);
+    let all: HashSet<String> = ["a", "b", "c", "d", "e"].map(String::from).into();
+
+    let members = strings(client.call(&["SRANDMEMBER", "s", "3"]));
+    assert_eq!(members.len(), 3);
+    assert_eq!(members.iter().collect::<HashSet<_>>().len(), 3);
+    assert!(members.iter().all(|m| all.contains(m)));
+
+    // The count is capped by the size of the collection.
+    let members = strings(client.call(&["SRANDMEMBER", "s", "10"]));
+    assert_eq!(members.len(), 5);
+    assert_eq!(members.into_iter().collect::<HashSet<_>>(), all);
+    let fields = strings(client.call(&["HRANDFIELD", "h", "10"]));
+    assert_eq!(fields.len(), 5);
+    assert_eq!(fields.into_iter().collect::<HashSet<_>>(), all);
+
+    let pairs = strings(client.call(&["HRANDFIELD", "h", "2", "WITHVALUES"]));
+    assert_eq!(pairs.len(), 4);
+    assert_ne!(pairs[0], pairs[2]);
+    for pair in pairs.chunks(2) {
+        assert_eq!(pair[1], pair[0].to_uppercase());
+    }
+
+    // The collection is left untouched.
+    assert_eq!(strings(client.call(&["SMEMBERS", "s"])).len(), 5);
+}
+
+#[test]
+fn negative_counts_may_repeat_elements() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    populate(&mut client);
+
+    let members = strings(client.call(&["SRANDMEMBER", "s", "-20"]));
+    assert_eq!(members.len(), 20);
+    assert!(members.iter().all(|m| ["a", "b", "c", "d", "e"].contains(&m.as_str())));
+
+    let pairs = strings(client.call(&["HRANDFIELD", "h", "-20", "WITHVALUES"]));
+    assert_eq!(pairs.len(), 40);
+    for pair in pairs.chunks(2) {
+        assert_eq!(pair[1], pair[0].to_uppercase());
+    }
+}
+
+#[test]
+fn without_a_count_a_single_element_or_null_is_returned() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    populate(&mut client);
+
+    match client.call(&["SRANDMEMBER", "s"]) {
+        Reply::Bulk(member) => assert!(["a", "b", "c", "d", "e"].contains(&member.as_str())),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    match client.call(&["HRANDFIELD", "h"]) {
+        Reply::Bulk(field) => assert!(["a", "b", "c", "d", "e"].contains(&field.as_str())),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+
+    assert_eq!(client.call(&["SRANDMEMBER", "missing"]), Reply::Null);
+    assert_eq!(client.call(&["HRANDFIELD", "missing"]), Reply::Null);
+    assert_eq!(client.call(&["SRANDMEMBER", "missing", "3"]), Reply::Array(vec![]));
+    assert_eq!(client.call(&["HRANDFIELD", "missing", "3"]), Reply::Array(vec![]));
+}
@@ -0,0 +1,71 @@
+// tests/read_only.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn read_only_mode_rejects_writes_but_serves_reads() {
+    let server = Server::start(&["--read-only"]);
+    let mut client = server.connect();
+
+    for cmd in [
+        &["SET", "k", "v"][..],
+        &["LPUSH", "l", "a"],
+        &["DEBUG", "POPULATE", "3"],
+    ] {
+        match client.call(cmd) {
+            Reply::Error(e) => assert!(e.starts_with("READONLY"), "{:?}: {}", cmd, e),
+            reply => panic!("unexpected reply to {:?}: {:?}", cmd, reply),
+        }
+    }
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0));
+    assert_eq!(client.call(&["GET", "k"]), Reply::Null);
+    assert_eq!(
+        client.call(&["DEBUG", "STRINGMATCH-LEN", "k*", "key"]),
+        Reply::Integer(1)
+    );
+}
+
+#[test]
+fn read_only_mode_can_be_toggled_at_runtime() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    let ok = Reply::Simple(String::from("OK"));
+    client.call(&["SET", "k", "v"]);
+
+    assert_eq!(client.call(&["CONFIG", "SET", "read-only", "yes"]), ok);
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("v"));
+    assert_eq!(
+        client.call(&["SET", "k", "other"]),
+        Reply::Error(String::from(
+            "READONLY You can't write against a read only replica."
+        ))
+    );
+    assert_eq!(server.connect().call(&["GET", "k"]), Reply::bulk("v"));
+
+    assert_eq!(client.call(&["CONFIG", "SET", "read-only", "no"]), ok);
+    client.call(&["SET", "k", "other"]);
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("other"));
+}
+
+#[test]
+fn read_only_mode_rejects_getdel_and_getex_with_options() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SET", "k", "v"]);
+    client.call(&["CONFIG", "SET", "read-only", "yes"]);
+
+    for cmd in [
+        &["GETDEL", "k"][..],
+        &["GETEX", "k", "EX", "10"],
+        &["GETEX", "k", "PERSIST"],
+    ] {
+        match client.call(cmd) {
+            Reply::Error(e) => assert!(e.starts_with("READONLY"), "{:?}: {}", cmd, e),
+            reply => panic!("unexpected reply to {:?}: {:?}", cmd, reply),
+        }
+    }
+    assert_eq!(client.call(&["GETEX", "k"]), Reply::bulk("v"));
+    assert_eq!(client.call(&["TTL", "k"]), Reply::Integer(-1));
+}
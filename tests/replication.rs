@@ -0,0 +1,244 @@
+// tests/replication.rs
+
+mod common;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use common::{encode, wait_until, Reply, Server};
+
+#[test]
+fn replica_receives_the_dataset_and_the_writes_of_its_master() {
+    let master = Server::start(&[]);
+    let replica = Server::start(&[]);
+    let mut master_client = master.connect();
+    let mut replica_client = replica.connect();
+
+    // Written before the replica connects, so it's only sent by the full sync.
+    master_client.call(&["SET", "before", "1"]);
+
+    let port = master.port.to_string();
+    assert_eq!(
+        replica_client.call(&["REPLICAOF", "127.0.0.1", &port]),
+        Reply::Simple(String::from("OK"))
+    );
+    wait_until(|| replica_client.call(&["GET", "before"]) == Reply::bulk("1"));
+
+    // Written after, so it's propagated.
+    master_client.call(&["SET", "after", "2"]);
+    master_client.call(&["RPUSH", "list", "a", "b"]);
+    wait_until(|| {
+        replica_client.call(&["LRANGE", "list", "0", "10"])
+            == Reply::Array(vec![Reply::bulk("a"), Reply::bulk("b")])
+    });
+    assert_eq!(replica_client.call(&["GET", "after"]), Reply::bulk("2"));
+}
+
+#[test]
+fn replica_rejects_writes_from_its_clients() {
+    let master = Server::start(&[]);
+    let replica = Server::start(&[]);
+    let mut replica_client = replica.connect();
+
+    let port = master.port.to_string();
+    replica_client.call(&["REPLICAOF", "127.0.0.1", &port]);
+
+    match replica_client.call(&["SET", "k", "v"]) {
+        Reply::Error(e) => assert!(e.starts_with("READONLY"), "{}", e),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    assert_eq!(replica_client.call(&["GET", "k"]), Reply::Null);
+
+    // Writes are accepted again once it's no longer a replica.
+    replica_client.call(&["REPLICAOF", "NO", "ONE"]);
+    assert_eq!(replica_client.call(&["SET", "k", "v"]), Reply::bulk("OK"));
+}
+
+#[test]
+fn replica_gets_the_expiry_times_computed_by_its_master() {
+    let master = Server::start(&[]);
+    let replica = Server::start(&[]);
+    let mut master_client = master.connect();
+    let mut replica_client = replica.connect();
+
+    let port = master.port.to_string();
+    replica_client.call(&["REPLICAOF", "127.0.0.1", &port]);
+    wait_until(|| master_client.call(&["WAIT", "1", "100"]) == Reply::Integer(1));
+
+    master_client.call(&["SET", "set", "v", "PX", "100000"]);
+    master_client.call(&["SET", "expire", "v"]);
+    master_client.call(&["EXPIRE", "expire", "100"]);
+    master_client.call(&["SET", "getex", "v"]);
+    master_client.call(&["GETEX", "getex", "PX", "100000"]);
+    assert_eq!(master_client.call(&["WAIT", "1", "5000"]), Reply::Integer(1));
+
+    for key in ["set", "expire", "getex"] {
+        let expires_at = master_client.call(&["PEXPIRETIME", key]);
+        assert!(matches!(expires_at, Reply::Integer(t) if t > 0), "{:?}", expires_at);
+        assert_eq!(replica_client.call(&["PEXPIRETIME", key]), expires_at, "{}", key);
+    }
+}
+
+#[test]
+fn debug_populate_is_propagated_and_rejected_on_replicas() {
+    let master = Server::start(&[]);
+    let replica = Server::start(&[]);
+    let mut master_client = master.connect();
+    let mut replica_client = replica.connect();
+
+    let port = master.port.to_string();
+    replica_client.call(&["REPLICAOF", "127.0.0.1", &port]);
+    wait_until(|| master_client.call(&["WAIT", "1", "100"]) == Reply::Integer(1));
+
+    master_client.call(&["DEBUG", "POPULATE", "5"]);
+    assert_eq!(master_client.call(&["WAIT", "1", "5000"]), Reply::Integer(1));
+    assert_eq!(replica_client.call(&["DBSIZE"]), Reply::Integer(5));
+    assert_eq!(replica_client.call(&["GET", "key:4"]), Reply::bulk("value:4"));
+
+    match replica_client.call(&["DEBUG", "POPULATE", "3", "local:"]) {
+        Reply::Error(e) => assert!(e.starts_with("READONLY"), "{}", e),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    assert_eq!(replica_client.call(&["DBSIZE"]), Reply::Integer(5));
+}
+
+#[test]
+fn writes_are_forwarded_verbatim_to_a_registered_replica_connection() {
+    let master = Server::start(&[]);
+    let mut master_client = master.connect();
+    let mut replica = master.connect();
+
+    // Register the connection as a replica, and skip the dump of the empty dataset,
+    // which is sent like a bulk string without the trailing CRLF.
+    replica.send(&["PSYNC", "?", "-1"]);
+    assert!(replica.line().starts_with("+FULLRESYNC "));
+    let dump_len: usize = replica.line()[1..].parse().unwrap();
+    replica.read_exact(dump_len);
+
+    // Reads aren't propagated, so the first command received is the SET.
+    assert_eq!(master_client.call(&["GET", "k"]), Reply::Null);
+    assert_eq!(master_client.call(&["SET", "k", "v"]), Reply::bulk("OK"));
+    let set = encode(&["SET", "k", "v"]);
+    assert_eq!(replica.read_exact(set.len()), set);
+}
+
+#[test]
+fn relative_expiry_times_are_forwarded_as_absolute_ones() {
+    let master = Server::start(&[]);
+    let mut master_client = master.connect();
+    let mut replica = master.connect();
+
+    replica.send(&["PSYNC", "?", "-1"]);
+    assert!(replica.line().starts_with("+FULLRESYNC "));
+    let dump_len: usize = replica.line()[1..].parse().unwrap();
+    replica.read_exact(dump_len);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64;
+    let expires_at = |reply: &Reply| match reply {
+        Reply::Bulk(expires_at) => expires_at.parse::<i64>().unwrap(),
+        reply => panic!("unexpected expiry time: {:?}", reply),
+    };
+
+    // SET with EX, the same as SETEX, is forwarded as SET with PXAT, and EXPIRE as PEXPIREAT.
+    master_client.call(&["SET", "k", "v", "EX", "100"]);
+    master_client.call(&["EXPIRE", "k", "200"]);
+    let Reply::Array(set) = replica.read() else {
+        panic!("the SET should be forwarded");
+    };
+    assert_eq!(
+        set[..4],
+        [Reply::bulk("SET"), Reply::bulk("k"), Reply::bulk("v"), Reply::bulk("PXAT")]
+    );
+    assert!((expires_at(&set[4]) - (now + 100_000)).abs() < 5000, "{:?}", set);
+    let Reply::Array(pexpireat) = replica.read() else {
+        panic!("the EXPIRE should be forwarded");
+    };
+    assert_eq!(pexpireat[..2], [Reply::bulk("PEXPIREAT"), Reply::bulk("k")]);
+    assert!((expires_at(&pexpireat[2]) - (now + 200_000)).abs() < 5000, "{:?}", pexpireat);
+
+    // The replicas get the same expiry time as the master.
+    assert_eq!(
+        master_client.call(&["PEXPIRETIME", "k"]),
+        Reply::Integer(expires_at(&pexpireat[2]))
+    );
+}
+
+#[test]
+fn getdel_and_getex_are_forwarded_as_the_writes_they_perform() {
+    let master = Server::start(&[]);
+    let mut master_client = master.connect();
+    let mut replica = master.connect();
+
+    replica.send(&["PSYNC", "?", "-1"]);
+    assert!(replica.line().starts_with("+FULLRESYNC "));
+    let dump_len: usize = replica.line()[1..].parse().unwrap();
+    replica.read_exact(dump_len);
+
+    master_client.call(&["SET", "k", "v"]);
+    let set = encode(&["SET", "k", "v"]);
+    assert_eq!(replica.read_exact(set.len()), set);
+
+    // A bare GETEX and the commands on missing keys don't modify anything.
+    assert_eq!(master_client.call(&["GETEX", "k"]), Reply::bulk("v"));
+    assert_eq!(master_client.call(&["GETEX", "missing", "EX", "10"]), Reply::Null);
+    assert_eq!(master_client.call(&["GETDEL", "missing"]), Reply::Null);
+
+    assert_eq!(master_client.call(&["GETEX", "k", "PX", "100000"]), Reply::bulk("v"));
+    let Reply::Array(pexpireat) = replica.read() else {
+        panic!("GETEX should be forwarded");
+    };
+    assert_eq!(pexpireat[..2], [Reply::bulk("PEXPIREAT"), Reply::bulk("k")]);
+    assert_eq!(
+        master_client.call(&["PEXPIRETIME", "k"]),
+        Reply::Integer(match &pexpireat[2] {
+            Reply::Bulk(expires_at) => expires_at.parse().unwrap(),
+            reply => panic!("unexpected expiry time: {:?}", reply),
+        })
+    );
+
+    assert_eq!(master_client.call(&["GETEX", "k", "PERSIST"]), Reply::bulk("v"));
+    let persist = encode(&["PERSIST", "k"]);
+    assert_eq!(replica.read_exact(persist.len()), persist);
+
+    assert_eq!(master_client.call(&["GETDEL", "k"]), Reply::bulk("v"));
+    let del = encode(&["DEL", "k"]);
+    assert_eq!(replica.read_exact(del.len()), del);
+}
+
+#[test]
+fn role_reports_master_when_standalone_and_slave_when_replicating() {
+    let master = Server::start(&[]);
+    let replica = Server::start(&[]);
+    let mut master_client = master.connect();
+    let mut replica_client = replica.connect();
+
+    assert_eq!(
+        master_client.call(&["ROLE"]),
+        Reply::Array(vec![Reply::bulk("master"), Reply::Integer(0), Reply::Array(vec![])])
+    );
+
+    let port = master.port.to_string();
+    replica_client.call(&["REPLICAOF", "127.0.0.1", &port]);
+    let connected = [
+        Reply::bulk("slave"),
+        Reply::bulk("127.0.0.1"),
+        Reply::Integer(master.port as i64),
+        Reply::bulk("connected"),
+    ];
+    wait_until(|| match replica_client.call(&["ROLE"]) {
+        Reply::Array(role) => role[..4] == connected,
+        reply => panic!("unexpected reply: {:?}", reply),
+    });
+
+    // The master lists its replica.
+    match master_client.call(&["ROLE"]) {
+        Reply::Array(role) => {
+            assert_eq!(role[0], Reply::bulk("master"));
+            assert!(
+                matches!(&role[2], Reply::Array(replicas) if replicas.len() == 1),
+                "{:?}",
+                role
+            );
+        }
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
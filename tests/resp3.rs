@@ -0,0 +1,61 @@
+// tests/resp3.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn hgetall_replies_with_a_map_in_resp3_only() {
+    let server = Server::start(&[]);
+    let mut resp2 = server.connect();
+    let mut resp3 = server.connect();
+    resp3.call(&["HELLO", "3"]);
+    resp2.call(&["HSET", "h", "f", "v"]);
+
+    assert_eq!(
+        resp2.call(&["HGETALL", "h"]),
+        Reply::Array(vec![Reply::bulk("f"), Reply::bulk("v")])
+    );
+    assert_eq!(
+        resp3.call(&["HGETALL", "h"]),
+        Reply::Map(vec![(Reply::bulk("f"), Reply::bulk("v"))])
+    );
+    assert_eq!(resp3.call(&["HGETALL", "missing"]), Reply::Map(vec![]));
+}
+
+#[test]
+fn config_get_replies_with_a_map_in_resp3_only() {
+    let server = Server::start(&["--default-ttl", "0"]);
+    let mut resp2 = server.connect();
+    let mut resp3 = server.connect();
+    resp3.call(&["HELLO", "3"]);
+
+    assert_eq!(
+        resp2.call(&["CONFIG", "GET", "default-ttl"]),
+        Reply::Array(vec![Reply::bulk("default-ttl"), Reply::bulk("0")])
+    );
+    assert_eq!(
+        resp3.call(&["CONFIG", "GET", "default-ttl"]),
+        Reply::Map(vec![(Reply::bulk("default-ttl"), Reply::bulk("0"))])
+    );
+}
+
+#[test]
+fn smembers_and_sunion_reply_with_a_set_in_resp3_only() {
+    let server = Server::start(&[]);
+    let mut resp2 = server.connect();
+    let mut resp3 = server.connect();
+    resp3.call(&["HELLO", "3"]);
+    resp2.call(&["SADD", "s", "a"]);
+
+    assert_eq!(resp2.call(&["SMEMBERS", "s"]), Reply::Array(vec![Reply::bulk("a")]));
+    assert_eq!(resp3.call(&["SMEMBERS", "s"]), Reply::Set(vec![Reply::bulk("a")]));
+    assert_eq!(
+        resp2.call(&["SUNION", "s", "missing"]),
+        Reply::Array(vec![Reply::bulk("a")])
+    );
+    assert_eq!(
+        resp3.call(&["SUNION", "s", "missing"]),
+        Reply::Set(vec![Reply::bulk("a")])
+    );
+}
@@ -0,0 +1,89 @@
+// tests/scan.rs
+
+mod common;
+
+use std::collections::HashSet;
+
+use common::{Client, Reply, Server};
+
+/// Iterates over the collection at key with the given SCAN-like command, and returns the
+/// elements in the order they were returned.
+fn scan_all(client: &mut Client, command: &str, key: &str, count: usize) -> Vec<String> {
+    let mut elements = vec![];
+    let mut cursor = String::from("0");
+    loop {
+        let count = count.to_string();
+        let reply = client.call(&[command, key, &cursor, "COUNT", &count]);
+        let Reply::Array(reply) = reply else {
+            panic!("unexpected reply: {:?}", reply);
+        };
+        match &reply[..] {
+            [Reply::Bulk(next), Reply::Array(batch)] => {
+                elements.extend(batch.iter().map(|element| match element {
+                    Reply::Bulk(element) => element.to_string(),
+                    element => panic!("unexpected element: {:?}", element),
+                }));
+                cursor = next.to_string();
+            }
+            reply => panic!("unexpected reply: {:?}", reply),
+        }
+        if cursor == "0" {
+            return elements;
+        }
+    }
+}
+
+#[test]
+fn hscan_returns_every_field_of_a_large_hash_in_batches() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let mut args = vec![String::from("HSET"), String::from("h")];
+    for i in 0..20000 {
+        args.push(format!("field:{}", i));
+        args.push(i.to_string());
+    }
+    let args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+    assert_eq!(client.call(&args), Reply::Integer(20000));
+
+    let elements = scan_all(&mut client, "HSCAN", "h", 100);
+    let fields: HashSet<(&String, &String)> =
+        elements.chunks(2).map(|pair| (&pair[0], &pair[1])).collect();
+    assert_eq!(fields.len(), 20000);
+    for i in 0..20000 {
+        assert!(fields.contains(&(&format!("field:{}", i), &i.to_string())));
+    }
+}
+
+#[test]
+fn sscan_and_zscan_return_every_member() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let members: Vec<String> = (0..1000).map(|i| format!("member:{}", i)).collect();
+    let mut sadd = vec!["SADD", "s"];
+    sadd.extend(members.iter().map(|member| member.as_str()));
+    assert_eq!(client.call(&sadd), Reply::Integer(1000));
+    let mut zadd = vec!["ZADD", "z"];
+    for member in members.iter() {
+        zadd.extend(["1", member.as_str()]);
+    }
+    assert_eq!(client.call(&zadd), Reply::Integer(1000));
+
+    let mut scanned = scan_all(&mut client, "SSCAN", "s", 10);
+    scanned.sort();
+    let mut expected = members.clone();
+    expected.sort();
+    assert_eq!(scanned, expected);
+
+    let scanned = scan_all(&mut client, "ZSCAN", "z", 10);
+    let mut scanned: Vec<String> = scanned
+        .chunks(2)
+        .map(|pair| {
+            assert_eq!(pair[1], "1");
+            pair[0].to_string()
+        })
+        .collect();
+    scanned.sort();
+    assert_eq!(scanned, expected);
+}
@@ -0,0 +1,81 @@
+// tests/set.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn set_keepttl_keeps_the_ttl_which_a_plain_set_clears() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SET", "k", "v", "EX", "100"]), Reply::bulk("OK"));
+    let expires_at = client.call(&["PEXPIRETIME", "k"]);
+    assert_eq!(client.call(&["SET", "k", "w", "KEEPTTL"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("w"));
+    assert_eq!(client.call(&["PEXPIRETIME", "k"]), expires_at);
+
+    assert_eq!(client.call(&["SET", "k", "x"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["TTL", "k"]), Reply::Integer(-1));
+
+    // A new key has no TTL to keep.
+    assert_eq!(client.call(&["SET", "new", "v", "KEEPTTL"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["TTL", "new"]), Reply::Integer(-1));
+}
+
+#[test]
+fn set_keepttl_conflicts_with_an_expiry() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    let syntax_error = Reply::Error(String::from("ERR syntax error"));
+
+    assert_eq!(client.call(&["SET", "k", "v", "EX", "10", "KEEPTTL"]), syntax_error);
+    assert_eq!(client.call(&["SET", "k", "v", "KEEPTTL", "PX", "10"]), syntax_error);
+    assert_eq!(client.call(&["GET", "k"]), Reply::Null);
+}
+
+#[test]
+fn set_get_returns_the_old_value() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SET", "k", "old"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["SET", "k", "new", "GET"]), Reply::bulk("old"));
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("new"));
+
+    assert_eq!(client.call(&["SET", "missing", "v", "GET"]), Reply::Null);
+    assert_eq!(client.call(&["GET", "missing"]), Reply::bulk("v"));
+}
+
+#[test]
+fn set_get_on_a_key_holding_another_type_is_an_error() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["RPUSH", "l", "a"]), Reply::Integer(1));
+    assert_eq!(
+        client.call(&["SET", "l", "v", "GET"]),
+        Reply::Error(String::from(
+            "WRONGTYPE Operation against a key holding the wrong kind of value"
+        ))
+    );
+    // The key is left as is.
+    assert_eq!(client.call(&["LRANGE", "l", "0", "10"]), Reply::Array(vec![Reply::bulk("a")]));
+}
+
+#[test]
+fn set_get_with_nx_or_xx_returns_the_old_value_whether_it_sets_or_not() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SET", "k", "old"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["SET", "k", "new", "NX", "GET"]), Reply::bulk("old"));
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("old"));
+    assert_eq!(client.call(&["SET", "k", "new", "XX", "GET"]), Reply::bulk("old"));
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("new"));
+
+    assert_eq!(client.call(&["SET", "a", "v", "NX", "GET"]), Reply::Null);
+    assert_eq!(client.call(&["GET", "a"]), Reply::bulk("v"));
+    assert_eq!(client.call(&["SET", "b", "v", "XX", "GET"]), Reply::Null);
+    assert_eq!(client.call(&["GET", "b"]), Reply::Null);
+}
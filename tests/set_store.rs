@@ -0,0 +1,94 @@
+// tests/set_store.rs
+
+mod common;
+
+use common::{Client, Reply, Server};
+
+/// Returns the members of the set at key, sorted.
+fn members(client: &mut Client, key: &str) -> Vec<String> {
+    let reply = client.call(&["SMEMBERS", key]);
+    let Reply::Array(members) = reply else {
+        panic!("unexpected reply: {:?}", reply);
+    };
+    let mut members: Vec<String> = members
+        .into_iter()
+        .map(|member| match member {
+            Reply::Bulk(member) => member,
+            member => panic!("unexpected member: {:?}", member),
+        })
+        .collect();
+    members.sort();
+    members
+}
+
+fn strings(members: &[&str]) -> Vec<String> {
+    members.iter().map(|member| member.to_string()).collect()
+}
+
+/// Starts a server holding the sets `a = {1, 2, 3}` and `b = {2, 3, 4}`, and a destination
+/// set `dst` which is expected to be replaced.
+fn server_with_sets() -> (Server, Client) {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    assert_eq!(client.call(&["SADD", "a", "1", "2", "3"]), Reply::Integer(3));
+    assert_eq!(client.call(&["SADD", "b", "2", "3", "4"]), Reply::Integer(3));
+    assert_eq!(client.call(&["SADD", "dst", "old"]), Reply::Integer(1));
+    (server, client)
+}
+
+#[test]
+fn sinterstore_stores_the_intersection() {
+    let (_server, mut client) = server_with_sets();
+
+    assert_eq!(client.call(&["SINTERSTORE", "dst", "a", "b"]), Reply::Integer(2));
+    assert_eq!(members(&mut client, "dst"), strings(&["2", "3"]));
+
+    assert_eq!(client.call(&["SINTERSTORE", "dst", "a", "missing"]), Reply::Integer(0));
+    assert_eq!(members(&mut client, "dst"), strings(&[]));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(2));
+}
+
+#[test]
+fn sunionstore_stores_the_union() {
+    let (_server, mut client) = server_with_sets();
+
+    assert_eq!(client.call(&["SUNIONSTORE", "dst", "a", "b"]), Reply::Integer(4));
+    assert_eq!(members(&mut client, "dst"), strings(&["1", "2", "3", "4"]));
+
+    // The destination can be one of the sources.
+    assert_eq!(client.call(&["SUNIONSTORE", "a", "a", "b"]), Reply::Integer(4));
+    assert_eq!(members(&mut client, "a"), strings(&["1", "2", "3", "4"]));
+
+    assert_eq!(client.call(&["SUNIONSTORE", "dst", "missing"]), Reply::Integer(0));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(2));
+}
+
+#[test]
+fn sdiffstore_stores_the_difference() {
+    let (_server, mut client) = server_with_sets();
+
+    assert_eq!(client.call(&["SDIFFSTORE", "dst", "a", "b"]), Reply::Integer(1));
+    assert_eq!(members(&mut client, "dst"), strings(&["1"]));
+
+    assert_eq!(client.call(&["SDIFFSTORE", "dst", "a", "a"]), Reply::Integer(0));
+    assert_eq!(members(&mut client, "dst"), strings(&[]));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(2));
+}
+
+#[test]
+fn set_stores_reject_sources_holding_another_type() {
+    let (_server, mut client) = server_with_sets();
+
+    assert_eq!(client.call(&["SET", "s", "v"]), Reply::bulk("OK"));
+    for command in ["SINTERSTORE", "SUNIONSTORE", "SDIFFSTORE"] {
+        assert_eq!(
+            client.call(&[command, "dst", "a", "s"]),
+            Reply::Error(String::from(
+                "WRONGTYPE Operation against a key holding the wrong kind of value"
+            )),
+            "{}",
+            command
+        );
+    }
+    assert_eq!(members(&mut client, "dst"), strings(&["old"]));
+}
@@ -0,0 +1,61 @@
+// tests/shutdown.rs
+
+mod common;
+
+use std::{
+    net::TcpStream,
+    time::{Duration, Instant},
+};
+
+use common::{Reply, Server};
+
+#[test]
+fn shutdown_nosave_terminates_the_server() {
+    let mut server = Server::start(&[]);
+    let mut client = server.connect();
+
+    // No reply is sent, the connection is closed instead.
+    client.send(&["SHUTDOWN", "NOSAVE"]);
+    assert!(server.wait_for_exit().success());
+    assert!(TcpStream::connect(("127.0.0.1", server.port)).is_err());
+}
+
+#[test]
+fn shutdown_rejects_unknown_modifiers() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(
+        client.call(&["SHUTDOWN", "NOW"]),
+        Reply::Error(String::from("ERR syntax error"))
+    );
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
+
+#[test]
+fn shutdown_closes_subscribers_and_blocked_clients_promptly() {
+    let mut server = Server::start(&[]);
+    let mut subscriber = server.connect();
+    assert_eq!(
+        subscriber.call(&["SUBSCRIBE", "ch"]),
+        Reply::Array(vec![Reply::bulk("subscribe"), Reply::bulk("ch"), Reply::Integer(1)])
+    );
+    // WAIT blocks until the timeout, as there are no replicas to acknowledge the writes.
+    let mut blocked = server.connect();
+    blocked.send(&["WAIT", "1", "0"]);
+    let mut idle = server.connect();
+    assert_eq!(idle.call(&["PING"]), Reply::Simple(String::from("PONG")));
+
+    let started = Instant::now();
+    server.connect().send(&["SHUTDOWN"]);
+    assert!(subscriber.is_closed());
+    assert!(blocked.is_closed());
+    assert!(idle.is_closed());
+    assert!(server.wait_for_exit().success());
+    // The server doesn't wait for the connections to time out.
+    assert!(
+        started.elapsed() < Duration::from_secs(2),
+        "shutdown took {:?}",
+        started.elapsed()
+    );
+}
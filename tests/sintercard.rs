@@ -0,0 +1,55 @@
+// tests/sintercard.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+#[test]
+fn sintercard_counts_the_intersection() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "a", "1", "2", "3", "4"]);
+    client.call(&["SADD", "b", "2", "3", "4", "5"]);
+
+    assert_eq!(client.call(&["SINTERCARD", "2", "a", "b"]), Reply::Integer(3));
+    assert_eq!(client.call(&["SINTERCARD", "2", "a", "missing"]), Reply::Integer(0));
+}
+
+#[test]
+fn sintercard_stops_counting_at_the_limit() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "a", "1", "2", "3", "4"]);
+    client.call(&["SADD", "b", "2", "3", "4", "5"]);
+
+    assert_eq!(
+        client.call(&["SINTERCARD", "2", "a", "b", "LIMIT", "2"]),
+        Reply::Integer(2)
+    );
+    assert_eq!(
+        client.call(&["SINTERCARD", "2", "a", "b", "LIMIT", "10"]),
+        Reply::Integer(3)
+    );
+    // LIMIT 0 means no limit.
+    assert_eq!(
+        client.call(&["SINTERCARD", "2", "a", "b", "LIMIT", "0"]),
+        Reply::Integer(3)
+    );
+    assert!(matches!(
+        client.call(&["SINTERCARD", "2", "a", "b", "LIMIT", "-1"]),
+        Reply::Error(_)
+    ));
+}
+
+#[test]
+fn sintercard_rejects_keys_of_another_type() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "a", "1"]);
+    client.call(&["SET", "s", "1"]);
+
+    match client.call(&["SINTERCARD", "2", "a", "s"]) {
+        Reply::Error(e) => assert!(e.starts_with("WRONGTYPE"), "{}", e),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
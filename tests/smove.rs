@@ -0,0 +1,70 @@
+// tests/smove.rs
+
+mod common;
+
+use common::{Client, Reply, Server};
+
+/// Returns the members of the set at key, sorted.
+fn members(client: &mut Client, key: &str) -> Vec<Reply> {
+    match client.call(&["SMEMBERS", key]) {
+        Reply::Array(mut members) => {
+            members.sort_by_key(|member| format!("{:?}", member));
+            members
+        }
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn smove_moves_a_member_between_sets() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "src", "a", "b"]);
+    client.call(&["SADD", "dst", "c"]);
+
+    assert_eq!(client.call(&["SMOVE", "src", "dst", "a"]), Reply::Integer(1));
+    assert_eq!(client.call(&["SMEMBERS", "src"]), Reply::Array(vec![Reply::bulk("b")]));
+    assert_eq!(members(&mut client, "dst"), vec![Reply::bulk("a"), Reply::bulk("c")]);
+
+    // The source is deleted once empty.
+    assert_eq!(client.call(&["SMOVE", "src", "dst", "b"]), Reply::Integer(1));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(1));
+}
+
+#[test]
+fn smove_of_a_missing_member_does_nothing() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "src", "a"]);
+
+    assert_eq!(client.call(&["SMOVE", "src", "dst", "x"]), Reply::Integer(0));
+    assert_eq!(client.call(&["SMOVE", "missing", "dst", "a"]), Reply::Integer(0));
+    assert_eq!(client.call(&["SMEMBERS", "src"]), Reply::Array(vec![Reply::bulk("a")]));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(1));
+}
+
+#[test]
+fn smove_creates_the_destination() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "src", "a", "b"]);
+
+    assert_eq!(client.call(&["SMOVE", "src", "dst", "a"]), Reply::Integer(1));
+    assert_eq!(client.call(&["SMEMBERS", "dst"]), Reply::Array(vec![Reply::bulk("a")]));
+}
+
+#[test]
+fn smove_rejects_keys_which_are_not_sets() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "set", "a"]);
+    client.call(&["SET", "string", "v"]);
+
+    for (src, dst) in [("string", "set"), ("set", "string")] {
+        match client.call(&["SMOVE", src, dst, "a"]) {
+            Reply::Error(e) => assert!(e.starts_with("WRONGTYPE"), "{}", e),
+            reply => panic!("unexpected reply: {:?}", reply),
+        }
+    }
+    assert_eq!(client.call(&["SMEMBERS", "set"]), Reply::Array(vec![Reply::bulk("a")]));
+}
@@ -0,0 +1,70 @@
+// tests/spop.rs
+
+mod common;
+
+use common::{Client, Reply, Server};
+
+/// Returns the members of the set at key, sorted.
+fn members(client: &mut Client, key: &str) -> Vec<Reply> {
+    match client.call(&["SMEMBERS", key]) {
+        Reply::Array(mut members) => {
+            members.sort_by_key(|member| format!("{:?}", member));
+            members
+        }
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+}
+
+#[test]
+fn spop_removes_and_returns_a_single_member() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "s", "a", "b", "c"]);
+
+    let popped = client.call(&["SPOP", "s"]);
+    let mut expected = vec![Reply::bulk("a"), Reply::bulk("b"), Reply::bulk("c")];
+    assert!(expected.contains(&popped), "{:?}", popped);
+    expected.retain(|member| *member != popped);
+    assert_eq!(members(&mut client, "s"), expected);
+
+    assert_eq!(client.call(&["SPOP", "missing"]), Reply::Null);
+}
+
+#[test]
+fn spop_with_a_count_removes_and_returns_distinct_members() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "s", "a", "b", "c", "d"]);
+
+    let Reply::Array(mut popped) = client.call(&["SPOP", "s", "3"]) else {
+        panic!("SPOP with a count should reply with an array");
+    };
+    assert_eq!(popped.len(), 3);
+    let mut left = members(&mut client, "s");
+    assert_eq!(left.len(), 1);
+    popped.append(&mut left);
+    popped.sort_by_key(|member| format!("{:?}", member));
+    assert_eq!(
+        popped,
+        vec![Reply::bulk("a"), Reply::bulk("b"), Reply::bulk("c"), Reply::bulk("d")]
+    );
+
+    assert_eq!(client.call(&["SPOP", "missing", "3"]), Reply::Array(vec![]));
+}
+
+#[test]
+fn spop_deletes_the_set_once_empty() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    client.call(&["SADD", "s", "a", "b"]);
+
+    let Reply::Array(popped) = client.call(&["SPOP", "s", "5"]) else {
+        panic!("SPOP with a count should reply with an array");
+    };
+    assert_eq!(popped.len(), 2);
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0));
+
+    client.call(&["SADD", "t", "a"]);
+    assert_eq!(client.call(&["SPOP", "t"]), Reply::bulk("a"));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0));
+}
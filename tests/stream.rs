@@ -0,0 +1,142 @@
+// tests/stream.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+/// Parses an entry ID in the form `ms-seq`.
+fn parse_id(reply: &Reply) -> (u64, u64) {
+    let Reply::Bulk(id) = reply else {
+        panic!("unexpected reply: {:?}", reply);
+    };
+    let (ms, seq) = id.split_once('-').unwrap();
+    (ms.parse().unwrap(), seq.parse().unwrap())
+}
+
+#[test]
+fn xadd_appends_entries_with_increasing_ids() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    let mut last = (0, 0);
+    for i in 0..100 {
+        let value = i.to_string();
+        let id = parse_id(&client.call(&["XADD", "s", "*", "n", &value]));
+        assert!(id > last, "{:?} after {:?}", id, last);
+        last = id;
+    }
+    assert_eq!(client.call(&["XLEN", "s"]), Reply::Integer(100));
+
+    // Auto IDs keep growing after an explicit ID ahead of the clock.
+    assert_eq!(
+        client.call(&["XADD", "s", "99999999999999-5", "n", "x"]),
+        Reply::bulk("99999999999999-5")
+    );
+    assert_eq!(
+        client.call(&["XADD", "s", "*", "n", "y"]),
+        Reply::bulk("99999999999999-6")
+    );
+    assert_eq!(client.call(&["XLEN", "s"]), Reply::Integer(102));
+}
+
+#[test]
+fn xadd_rejects_ids_which_dont_grow() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["XADD", "s", "5-5", "f", "v"]), Reply::bulk("5-5"));
+    for id in ["5-5", "5-4", "1"] {
+        assert_eq!(
+            client.call(&["XADD", "s", id, "f", "v"]),
+            Reply::Error(String::from(
+                "ERR The ID specified in XADD is equal or smaller than the target stream top item"
+            )),
+            "{}",
+            id
+        );
+    }
+    assert_eq!(client.call(&["XLEN", "s"]), Reply::Integer(1));
+}
+
+#[test]
+fn xlen_of_a_missing_key_is_0() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["XLEN", "missing"]), Reply::Integer(0));
+    assert_eq!(client.call(&["SET", "k", "v"]), Reply::bulk("OK"));
+    assert_eq!(
+        client.call(&["XLEN", "k"]),
+        Reply::Error(String::from(
+            "WRONGTYPE Operation against a key holding the wrong kind of value"
+        ))
+    );
+    assert_eq!(
+        client.call(&["XADD", "s", "*", "f"]),
+        Reply::Error(String::from(
+            "ERR Wrong number of arguments specified for 'XADD' command"
+        ))
+    );
+}
+
+/// Returns the reply of XRANGE or XREAD for a stream entry with a single field.
+fn entry(id: &str, field: &str, value: &str) -> Reply {
+    Reply::Array(vec![
+        Reply::bulk(id),
+        Reply::Array(vec![Reply::bulk(field), Reply::bulk(value)]),
+    ])
+}
+
+#[test]
+fn xrange_returns_the_entries_in_a_range_of_ids() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    for (id, value) in [("1-0", "a"), ("2-0", "b"), ("2-1", "c"), ("3-0", "d")] {
+        assert_eq!(client.call(&["XADD", "s", id, "f", value]), Reply::bulk(id));
+    }
+
+    assert_eq!(
+        client.call(&["XRANGE", "s", "2-0", "3-0"]),
+        Reply::Array(vec![entry("2-0", "f", "b"), entry("2-1", "f", "c"), entry("3-0", "f", "d")])
+    );
+    assert_eq!(
+        client.call(&["XRANGE", "s", "-", "+", "COUNT", "2"]),
+        Reply::Array(vec![entry("1-0", "f", "a"), entry("2-0", "f", "b")])
+    );
+    // A bare millisecond covers every entry of that millisecond.
+    assert_eq!(
+        client.call(&["XRANGE", "s", "2", "2"]),
+        Reply::Array(vec![entry("2-0", "f", "b"), entry("2-1", "f", "c")])
+    );
+    assert_eq!(client.call(&["XRANGE", "s", "3", "1"]), Reply::Array(vec![]));
+    assert_eq!(client.call(&["XRANGE", "missing", "-", "+"]), Reply::Array(vec![]));
+}
+
+#[test]
+fn xread_returns_the_entries_after_an_id() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    for (id, value) in [("1-0", "a"), ("2-0", "b"), ("2-1", "c")] {
+        assert_eq!(client.call(&["XADD", "s", id, "f", value]), Reply::bulk(id));
+    }
+    assert_eq!(client.call(&["XADD", "t", "5-0", "g", "x"]), Reply::bulk("5-0"));
+
+    assert_eq!(
+        client.call(&["XREAD", "STREAMS", "s", "1-0"]),
+        Reply::Array(vec![Reply::Array(vec![
+            Reply::bulk("s"),
+            Reply::Array(vec![entry("2-0", "f", "b"), entry("2-1", "f", "c")]),
+        ])])
+    );
+    assert_eq!(
+        client.call(&["XREAD", "COUNT", "1", "STREAMS", "s", "t", "0", "0"]),
+        Reply::Array(vec![
+            Reply::Array(vec![Reply::bulk("s"), Reply::Array(vec![entry("1-0", "f", "a")])]),
+            Reply::Array(vec![Reply::bulk("t"), Reply::Array(vec![entry("5-0", "g", "x")])]),
+        ])
+    );
+    // Nothing after the last entry.
+    assert_eq!(client.call(&["XREAD", "STREAMS", "s", "2-1"]), Reply::Null);
+}
@@ -0,0 +1,54 @@
+// tests/tracking.rs
+
+mod common;
+
+use common::{Reply, Server};
+
+fn invalidate(keys: &[&str]) -> Reply {
+    Reply::Push(vec![
+        Reply::bulk("invalidate"),
+        Reply::Array(keys.iter().map(|k| Reply::bulk(k)).collect()),
+    ])
+}
+
+#[test]
+fn tracking_clients_are_told_when_the_keys_they_read_are_modified() {
+    let server = Server::start(&[]);
+    let mut tracking = server.connect();
+    let mut other = server.connect();
+
+    tracking.call(&["HELLO", "3"]);
+    assert_eq!(
+        tracking.call(&["CLIENT", "TRACKING", "ON"]),
+        Reply::Simple(String::from("OK"))
+    );
+    other.call(&["SET", "k", "v"]);
+    other.call(&["SET", "unread", "v"]);
+    assert_eq!(tracking.call(&["GET", "k"]), Reply::bulk("v"));
+
+    // Keys which weren't read don't send invalidations.
+    other.call(&["SET", "unread", "other"]);
+    other.call(&["SET", "k", "other"]);
+    assert_eq!(tracking.read(), invalidate(&["k"]));
+
+    // The key has to be read again to be tracked again.
+    other.call(&["SET", "k", "again"]);
+    assert_eq!(tracking.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
+
+#[test]
+fn clients_without_tracking_get_no_invalidations() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    let mut other = server.connect();
+
+    client.call(&["HELLO", "3"]);
+    client.call(&["CLIENT", "TRACKING", "ON"]);
+    assert_eq!(
+        client.call(&["CLIENT", "TRACKING", "OFF"]),
+        Reply::Simple(String::from("OK"))
+    );
+    client.call(&["GET", "k"]);
+    other.call(&["SET", "k", "v"]);
+    assert_eq!(client.call(&["PING"]), Reply::Simple(String::from("PONG")));
+}
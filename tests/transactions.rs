@@ -0,0 +1,158 @@
+// tests/transactions.rs
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use common::{Reply, Server};
+
+const TRANSACTIONS: i64 = 200;
+const WRITES: i64 = 400;
+
+#[test]
+fn exec_is_atomic_with_respect_to_other_connections() {
+    let server = Server::start(&["--io-threads", "4"]);
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            let mut client = server.connect();
+            scope.spawn(move || {
+                for _ in 0..TRANSACTIONS {
+                    client.call(&["MULTI"]);
+                    client.call(&["INCR", "n"]);
+                    client.call(&["INCR", "n"]);
+                    client.call(&["SET", "k", "tx"]);
+                    client.call(&["GET", "k"]);
+                    // No other write can land between the queued commands.
+                    match client.call(&["EXEC"]) {
+                        Reply::Array(replies) => match &replies[..] {
+                            [Reply::Integer(first), Reply::Integer(second), _, k] => {
+                                assert_eq!(*second, first + 1);
+                                assert_eq!(*k, Reply::bulk("tx"));
+                            }
+                            replies => panic!("unexpected replies: {:?}", replies),
+                        },
+                        reply => panic!("unexpected reply: {:?}", reply),
+                    }
+                }
+            });
+        }
+        for _ in 0..2 {
+            let mut client = server.connect();
+            scope.spawn(move || {
+                for _ in 0..WRITES {
+                    client.call(&["INCR", "n"]);
+                    client.call(&["SET", "k", "other"]);
+                }
+            });
+        }
+    });
+
+    let expected = 4 * TRANSACTIONS * 2 + 2 * WRITES;
+    assert_eq!(
+        server.connect().call(&["GET", "n"]),
+        Reply::bulk(&expected.to_string())
+    );
+}
+
+#[test]
+fn discard_ends_an_empty_transaction() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["MULTI"]), Reply::Simple(String::from("OK")));
+    assert_eq!(client.call(&["DISCARD"]), Reply::Simple(String::from("OK")));
+
+    // The connection is back to executing commands right away.
+    assert_eq!(client.call(&["SET", "k", "v"]), Reply::bulk("OK"));
+    assert_eq!(
+        client.call(&["EXEC"]),
+        Reply::Error(String::from("ERR EXEC without MULTI"))
+    );
+    assert_eq!(
+        client.call(&["DISCARD"]),
+        Reply::Error(String::from("ERR DISCARD without MULTI"))
+    );
+    assert_eq!(client.call(&["MULTI"]), Reply::Simple(String::from("OK")));
+}
+
+#[test]
+fn discard_clears_the_watched_keys() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+    let mut other = server.connect();
+
+    assert_eq!(client.call(&["WATCH", "k"]), Reply::Simple(String::from("OK")));
+    assert_eq!(client.call(&["MULTI"]), Reply::Simple(String::from("OK")));
+    assert_eq!(client.call(&["DISCARD"]), Reply::Simple(String::from("OK")));
+
+    // The key is no longer watched, so modifying it doesn't abort the next transaction.
+    assert_eq!(other.call(&["SET", "k", "other"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["MULTI"]), Reply::Simple(String::from("OK")));
+    assert_eq!(client.call(&["SET", "k", "tx"]), Reply::Simple(String::from("QUEUED")));
+    assert_eq!(client.call(&["EXEC"]), Reply::Array(vec![Reply::bulk("OK")]));
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("tx"));
+}
+
+#[test]
+fn watch_inside_multi_aborts_the_transaction() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["MULTI"]), Reply::Simple(String::from("OK")));
+    assert_eq!(
+        client.call(&["WATCH", "k"]),
+        Reply::Error(String::from("ERR WATCH inside MULTI is not allowed"))
+    );
+    assert_eq!(client.call(&["SET", "k", "v"]), Reply::Simple(String::from("QUEUED")));
+    assert_eq!(
+        client.call(&["EXEC"]),
+        Reply::Error(String::from(
+            "EXECABORT Transaction discarded because of previous errors."
+        ))
+    );
+    assert_eq!(client.call(&["GET", "k"]), Reply::Null);
+}
+
+#[test]
+fn keys_expired_before_exec_stay_expired_until_recreated() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["SET", "k", "old", "PX", "100"]), Reply::bulk("OK"));
+    assert_eq!(client.call(&["MULTI"]), Reply::Simple(String::from("OK")));
+    for args in [&["GET", "k"][..], &["SET", "k", "new"], &["GET", "k"], &["TTL", "k"]] {
+        assert_eq!(client.call(args), Reply::Simple(String::from("QUEUED")));
+    }
+    thread::sleep(Duration::from_millis(200));
+
+    // The expired key isn't seen by the GET, and the SET creates a new key without a TTL.
+    assert_eq!(
+        client.call(&["EXEC"]),
+        Reply::Array(vec![
+            Reply::Null,
+            Reply::bulk("OK"),
+            Reply::bulk("new"),
+            Reply::Integer(-1),
+        ])
+    );
+    assert_eq!(client.call(&["GET", "k"]), Reply::bulk("new"));
+}
+
+#[test]
+fn transactions_of_disconnected_clients_are_dropped() {
+    let server = Server::start(&[]);
+    let mut other = server.connect();
+
+    let mut client = server.connect();
+    assert_eq!(client.call(&["WATCH", "k"]), Reply::Simple(String::from("OK")));
+    assert_eq!(client.call(&["MULTI"]), Reply::Simple(String::from("OK")));
+    assert_eq!(client.call(&["SET", "k", "tx"]), Reply::Simple(String::from("QUEUED")));
+    drop(client);
+
+    // None of the queued commands runs, and the watched key can be written freely.
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(other.call(&["GET", "k"]), Reply::Null);
+    assert_eq!(other.call(&["SET", "k", "v"]), Reply::bulk("OK"));
+    assert_eq!(other.call(&["GET", "k"]), Reply::bulk("v"));
+}
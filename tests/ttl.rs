@@ -0,0 +1,35 @@
+// tests/ttl.rs
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{Reply, Server};
+
+#[test]
+fn ttl_never_reports_a_key_as_missing_before_its_deadline() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    // The deadline on the server is a second after the SET was received, minus up to a
+    // millisecond since the server clock has a resolution of a millisecond.
+    let sent = Instant::now();
+    client.call(&["SET", "k", "v", "PX", "1000"]);
+    let deadline = sent + Duration::from_millis(999);
+
+    loop {
+        let reply = client.call(&["TTL", "k"]);
+        let replied = Instant::now();
+        if replied < deadline {
+            assert!(
+                matches!(reply, Reply::Integer(0..=1)),
+                "unexpected TTL {:?} {:?} before the deadline",
+                reply,
+                deadline - replied
+            );
+        } else if reply == Reply::Integer(-2) {
+            break;
+        }
+        assert!(replied < deadline + Duration::from_secs(5), "the key should expire");
+    }
+}
@@ -0,0 +1,32 @@
+// tests/wait.rs
+
+mod common;
+
+use common::{wait_until, Reply, Server};
+
+#[test]
+fn wait_returns_the_replicas_which_acknowledged_the_writes() {
+    let master = Server::start(&[]);
+    let replica = Server::start(&[]);
+    let mut master_client = master.connect();
+    let mut replica_client = replica.connect();
+
+    let port = master.port.to_string();
+    replica_client.call(&["REPLICAOF", "127.0.0.1", &port]);
+    wait_until(|| master_client.call(&["WAIT", "1", "100"]) == Reply::Integer(1));
+
+    master_client.call(&["SET", "k", "v"]);
+    assert_eq!(master_client.call(&["WAIT", "1", "5000"]), Reply::Integer(1));
+    // The write was acknowledged, so it must already be on the replica.
+    assert_eq!(replica_client.call(&["GET", "k"]), Reply::bulk("v"));
+}
+
+#[test]
+fn wait_times_out_without_replicas() {
+    let master = Server::start(&[]);
+    let mut client = master.connect();
+    client.call(&["SET", "k", "v"]);
+
+    assert_eq!(client.call(&["WAIT", "0", "100"]), Reply::Integer(0));
+    assert_eq!(client.call(&["WAIT", "1", "100"]), Reply::Integer(0));
+}
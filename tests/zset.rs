@@ -0,0 +1,237 @@
+// tests/zset.rs
+
+mod common;
+
+use common::{Client, Reply, Server};
+
+/// Returns the members of the sorted set at key along with their scores, sorted by member.
+fn members(client: &mut Client, key: &str) -> Vec<(String, String)> {
+    let reply = client.call(&["ZSCAN", key, "0", "COUNT", "1000"]);
+    let Reply::Array(reply) = reply else {
+        panic!("unexpected reply: {:?}", reply);
+    };
+    let [Reply::Bulk(_), Reply::Array(elements)] = &reply[..] else {
+        panic!("unexpected reply: {:?}", reply);
+    };
+    let mut members: Vec<(String, String)> = elements
+        .chunks(2)
+        .map(|pair| match pair {
+            [Reply::Bulk(member), Reply::Bulk(score)] => (member.to_string(), score.to_string()),
+            pair => panic!("unexpected pair: {:?}", pair),
+        })
+        .collect();
+    members.sort();
+    members
+}
+
+fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(member, score)| (member.to_string(), score.to_string()))
+        .collect()
+}
+
+#[test]
+fn zadd_nx_skips_existing_members() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["ZADD", "z", "1", "a"]), Reply::Integer(1));
+    assert_eq!(client.call(&["ZADD", "z", "NX", "5", "a", "2", "b"]), Reply::Integer(1));
+    assert_eq!(members(&mut client, "z"), pairs(&[("a", "1"), ("b", "2")]));
+
+    // XX is the other way around: only existing members are updated.
+    assert_eq!(client.call(&["ZADD", "z", "XX", "5", "a", "3", "c"]), Reply::Integer(0));
+    assert_eq!(members(&mut client, "z"), pairs(&[("a", "5"), ("b", "2")]));
+}
+
+#[test]
+fn zadd_gt_and_lt_only_move_the_score_one_way() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["ZADD", "z", "5", "a", "5", "b"]), Reply::Integer(2));
+    assert_eq!(client.call(&["ZADD", "z", "GT", "1", "a", "9", "b"]), Reply::Integer(0));
+    assert_eq!(members(&mut client, "z"), pairs(&[("a", "5"), ("b", "9")]));
+
+    assert_eq!(client.call(&["ZADD", "z", "LT", "1", "a", "10", "b"]), Reply::Integer(0));
+    assert_eq!(members(&mut client, "z"), pairs(&[("a", "1"), ("b", "9")]));
+
+    // New members are still added.
+    assert_eq!(client.call(&["ZADD", "z", "GT", "3", "c"]), Reply::Integer(1));
+}
+
+#[test]
+fn zadd_ch_counts_the_updated_members_too() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["ZADD", "z", "1", "a", "2", "b"]), Reply::Integer(2));
+    assert_eq!(
+        client.call(&["ZADD", "z", "CH", "1", "a", "3", "b", "4", "c"]),
+        Reply::Integer(2)
+    );
+    assert_eq!(client.call(&["ZADD", "z", "1", "a", "5", "b"]), Reply::Integer(0));
+}
+
+#[test]
+fn zadd_incr_returns_the_new_score() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["ZADD", "z", "INCR", "2", "a"]), Reply::bulk("2"));
+    assert_eq!(client.call(&["ZADD", "z", "INCR", "0.5", "a"]), Reply::bulk("2.5"));
+    // The increment isn't applied if the options rule it out.
+    assert_eq!(client.call(&["ZADD", "z", "NX", "INCR", "1", "a"]), Reply::Null);
+    assert_eq!(client.call(&["ZADD", "z", "GT", "INCR", "-1", "a"]), Reply::Null);
+    assert_eq!(members(&mut client, "z"), pairs(&[("a", "2.5")]));
+}
+
+#[test]
+fn zadd_rejects_incompatible_options() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(
+        client.call(&["ZADD", "z", "NX", "XX", "1", "a"]),
+        Reply::Error(String::from("ERR XX and NX options at the same time are not compatible"))
+    );
+    for option in ["GT", "LT"] {
+        assert_eq!(
+            client.call(&["ZADD", "z", "NX", option, "1", "a"]),
+            Reply::Error(String::from(
+                "ERR GT, LT, and/or NX options at the same time are not compatible"
+            ))
+        );
+    }
+    assert_eq!(
+        client.call(&["ZADD", "z", "INCR", "1", "a", "2", "b"]),
+        Reply::Error(String::from("ERR INCR option supports a single increment-element pair"))
+    );
+    assert_eq!(client.call(&["ZCARD", "z"]), Reply::Integer(0));
+}
+
+#[test]
+fn zrem_removes_members_and_the_empty_key() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["ZADD", "z", "1", "a", "2", "b", "3", "c"]), Reply::Integer(3));
+    assert_eq!(client.call(&["ZREM", "z", "a", "missing"]), Reply::Integer(1));
+    assert_eq!(client.call(&["ZCARD", "z"]), Reply::Integer(2));
+    assert_eq!(members(&mut client, "z"), pairs(&[("b", "2"), ("c", "3")]));
+
+    assert_eq!(client.call(&["ZREM", "z", "b", "c"]), Reply::Integer(2));
+    assert_eq!(client.call(&["ZCARD", "z"]), Reply::Integer(0));
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0));
+    assert_eq!(client.call(&["ZREM", "z", "a"]), Reply::Integer(0));
+}
+
+#[test]
+fn zcount_supports_exclusive_bounds_and_infinities() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(
+        client.call(&["ZADD", "z", "1", "a", "2", "b", "3", "c", "4", "d"]),
+        Reply::Integer(4)
+    );
+    assert_eq!(client.call(&["ZCOUNT", "z", "2", "3"]), Reply::Integer(2));
+    assert_eq!(client.call(&["ZCOUNT", "z", "(2", "3"]), Reply::Integer(1));
+    assert_eq!(client.call(&["ZCOUNT", "z", "(2", "(3"]), Reply::Integer(0));
+    assert_eq!(client.call(&["ZCOUNT", "z", "-inf", "(3"]), Reply::Integer(2));
+    assert_eq!(client.call(&["ZCOUNT", "z", "-inf", "+inf"]), Reply::Integer(4));
+    assert_eq!(client.call(&["ZCOUNT", "z", "5", "1"]), Reply::Integer(0));
+    assert_eq!(client.call(&["ZCOUNT", "missing", "-inf", "+inf"]), Reply::Integer(0));
+    assert_eq!(
+        client.call(&["ZCOUNT", "z", "x", "1"]),
+        Reply::Error(String::from("ERR min or max is not a float"))
+    );
+}
+
+#[test]
+fn zincrby_increments_or_creates_the_member() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["ZINCRBY", "z", "5", "a"]), Reply::bulk("5"));
+    assert_eq!(client.call(&["ZINCRBY", "z", "1.5", "a"]), Reply::bulk("6.5"));
+    assert_eq!(client.call(&["ZINCRBY", "z", "-10", "a"]), Reply::bulk("-3.5"));
+    assert_eq!(client.call(&["ZINCRBY", "z", "2", "b"]), Reply::bulk("2"));
+    assert_eq!(members(&mut client, "z"), pairs(&[("a", "-3.5"), ("b", "2")]));
+    assert_eq!(
+        client.call(&["ZINCRBY", "z", "x", "a"]),
+        Reply::Error(String::from("ERR value is not a valid float"))
+    );
+}
+
+#[test]
+fn zincrby_keeps_the_members_ordered_by_score() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["ZADD", "z", "1", "a", "2", "b", "3", "c"]), Reply::Integer(3));
+    assert_eq!(client.call(&["ZINCRBY", "z", "10", "a"]), Reply::bulk("11"));
+    assert_eq!(client.call(&["ZINCRBY", "z", "-5", "c"]), Reply::bulk("-2"));
+    assert_eq!(
+        client.call(&["ZPOPMIN", "z", "3"]),
+        Reply::Array(vec![
+            Reply::bulk("c"),
+            Reply::bulk("-2"),
+            Reply::bulk("b"),
+            Reply::bulk("2"),
+            Reply::bulk("a"),
+            Reply::bulk("11"),
+        ])
+    );
+}
+
+#[test]
+fn zpopmin_and_zpopmax_pop_the_lowest_and_highest_scores() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(
+        client.call(&["ZADD", "z", "2", "b", "1", "a", "4", "d", "3", "c"]),
+        Reply::Integer(4)
+    );
+    assert_eq!(
+        client.call(&["ZPOPMIN", "z"]),
+        Reply::Array(vec![Reply::bulk("a"), Reply::bulk("1")])
+    );
+    assert_eq!(
+        client.call(&["ZPOPMAX", "z"]),
+        Reply::Array(vec![Reply::bulk("d"), Reply::bulk("4")])
+    );
+    assert_eq!(
+        client.call(&["ZPOPMAX", "z", "1"]),
+        Reply::Array(vec![Reply::bulk("c"), Reply::bulk("3")])
+    );
+    assert_eq!(members(&mut client, "z"), pairs(&[("b", "2")]));
+}
+
+#[test]
+fn zpop_with_a_count_over_the_cardinality_empties_and_deletes_the_key() {
+    let server = Server::start(&[]);
+    let mut client = server.connect();
+
+    assert_eq!(client.call(&["ZADD", "z", "1", "a", "2", "b"]), Reply::Integer(2));
+    assert_eq!(
+        client.call(&["ZPOPMAX", "z", "10"]),
+        Reply::Array(vec![
+            Reply::bulk("b"),
+            Reply::bulk("2"),
+            Reply::bulk("a"),
+            Reply::bulk("1"),
+        ])
+    );
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0));
+    assert_eq!(client.call(&["ZPOPMIN", "z"]), Reply::Array(vec![]));
+
+    assert_eq!(client.call(&["ZADD", "z", "1", "a"]), Reply::Integer(1));
+    assert_eq!(
+        client.call(&["ZPOPMIN", "z", "5"]),
+        Reply::Array(vec![Reply::bulk("a"), Reply::bulk("1")])
+    );
+    assert_eq!(client.call(&["DBSIZE"]), Reply::Integer(0));
+}